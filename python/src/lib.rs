@@ -0,0 +1,8 @@
+//! `s9_binance_codec` Python extension module, the classes live in the core crate's `python` module.
+
+use pyo3::prelude::*;
+
+#[pymodule]
+fn s9_binance_codec(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    codec::python::register(module)
+}
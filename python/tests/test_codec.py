@@ -0,0 +1,75 @@
+"""Parses the fixtures shared with the Rust golden tests and compares fields with the raw payloads."""
+
+import json
+from pathlib import Path
+
+import pytest
+
+import s9_binance_codec as codec
+
+FIXTURES = Path(__file__).resolve().parents[2] / "fixtures" / "json" / "spot"
+
+
+def fixture(name):
+    text = (FIXTURES / name).read_text()
+    return text, json.loads(text)
+
+
+def test_trade():
+    text, wire = fixture("trade.json")
+    trade = codec.parse_event(text)
+    assert isinstance(trade, codec.Trade)
+    assert trade.symbol == wire["s"]
+    assert trade.trade_id == wire["t"]
+    assert trade.price == float(wire["p"])
+    assert trade.quantity == float(wire["q"])
+    assert trade.is_buyer_market_maker == wire["m"]
+    assert repr(trade).startswith("TRADE " + wire["s"])
+
+
+def test_agg_trade():
+    text, wire = fixture("agg_trade.json")
+    trade = codec.parse_event(text)
+    assert isinstance(trade, codec.AggTrade)
+    assert (trade.aggregate_trade_id, trade.first_trade_id, trade.last_trade_id) == (wire["a"], wire["f"], wire["l"])
+    assert trade.price == float(wire["p"])
+
+
+def test_kline():
+    text, wire = fixture("kline.json")
+    event = codec.parse_event(text)
+    assert isinstance(event, codec.KlineEvent)
+    kline = event.kline
+    assert kline.interval == wire["k"]["i"]
+    assert kline.open_price == float(wire["k"]["o"])
+    assert kline.close_price == float(wire["k"]["c"])
+    assert kline.is_closed == wire["k"]["x"]
+    assert repr(event) == repr(kline)
+
+
+def test_depth_update():
+    text, wire = fixture("depth_update.json")
+    depth = codec.DepthUpdate.from_json(text)
+    assert (depth.first_update_id, depth.final_update_id) == (wire["U"], wire["u"])
+    assert depth.bids == [(float(price), float(quantity)) for price, quantity in wire["b"]]
+    assert depth.asks == [(float(price), float(quantity)) for price, quantity in wire["a"]]
+
+
+def test_user_data_events_are_dicts():
+    text, wire = fixture("balance_update.json")
+    assert codec.parse_event(text) == wire
+
+
+def test_parse_error_has_context():
+    with pytest.raises(ValueError, match="trade"):
+        codec.parse_event('{"e":"trade","E":1,"s":"BNBBTC","t":"12345"}')
+
+
+def test_subscription_request_builder():
+    request = codec.SubscriptionRequestBuilder().id(3).trade("btcusdt").depth("bnbbtc", 10, 100).build()
+    assert request.method == "SUBSCRIBE"
+    assert request.params == ["btcusdt@trade", "bnbbtc@depth10@100ms"]
+    assert json.loads(request.to_json()) == {"method": "SUBSCRIBE", "params": request.params, "id": 3}
+
+    with pytest.raises(ValueError):
+        codec.SubscriptionRequestBuilder().stream("")
@@ -0,0 +1,118 @@
+//! Parsing a batch of websocket frames in one call, collecting every failure instead of stopping at the first.
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{instrumented, CodecError};
+use crate::websocket::frame::{classify, FrameKind};
+
+// Frames are partitioned into parsed values, failures and request responses, indices are 0-based
+// positions in the batch
+#[derive(Debug)]
+pub struct ParseBatch<T> {
+    pub values: Vec<T>,                  // Parsed values in frame order
+    pub errors: Vec<(usize, CodecError)>, // Index and error of every frame that failed to parse
+    pub controls: Vec<(usize, FrameKind)>, // Index and kind of acks, responses and errors to requests
+    pub frames: usize,                   // Frames in the batch
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub frames: usize,                   // Frames in the batch
+    pub parsed: usize,                   // Frames parsed into values
+    pub failed: usize,                   // Frames that failed to parse
+    pub controls: usize,                 // Request responses, not parsed
+}
+
+impl<T> ParseBatch<T> {
+    fn new() -> Self {
+        ParseBatch { values: Vec::new(), errors: Vec::new(), controls: Vec::new(), frames: 0 }
+    }
+
+    pub fn summary(&self) -> BatchSummary {
+        BatchSummary {
+            frames: self.frames,
+            parsed: self.values.len(),
+            failed: self.errors.len(),
+            controls: self.controls.len(),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// Acks, responses and errors to requests are recorded in `controls` instead of being parsed as `T`
+pub fn parse_many<'a, T: DeserializeOwned>(frames: impl IntoIterator<Item = &'a str>) -> ParseBatch<T> {
+    parse_many_with(frames, |json| serde_json::from_str(json))
+}
+
+// For entry points that dispatch on the event type, e.g. `parse_many_with(frames, SpotEvent::from_json)`
+pub fn parse_many_with<'a, T>(
+    frames: impl IntoIterator<Item = &'a str>,
+    mut from_json: impl FnMut(&str) -> Result<T, serde_json::Error>,
+) -> ParseBatch<T> {
+    let frames = frames.into_iter();
+    let mut batch = ParseBatch::new();
+    batch.values.reserve(frames.size_hint().0);
+    for (index, frame) in frames.enumerate() {
+        batch.frames += 1;
+        match classify(frame) {
+            kind @ (FrameKind::Ack { .. } | FrameKind::Response { .. } | FrameKind::Error { .. }) => {
+                batch.controls.push((index, kind));
+            }
+            _ => match instrumented(frame.as_bytes(), || from_json(frame).map_err(|error| CodecError::new(error, frame))) {
+                Ok(value) => batch.values.push(value),
+                Err(error) => batch.errors.push((index, error)),
+            },
+        }
+    }
+    batch
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+    use crate::websocket::event::BinanceEvent;
+    use crate::testutil::{sample_balance_update_json, SampleTrade};
+    use crate::websocket::{SpotEvent, Trade, UserDataEvent};
+
+    const ACK: &str = r#"{"result":null,"id":1}"#;
+    const CORRUPT: &str = r#"{"e":"trade","E":1672515782138,"s":"BNBBTC","t":"12347","p":"0.001""#;
+
+    fn trade(trade_id: u64) -> String {
+        SampleTrade { trade_id, ..SampleTrade::default() }.to_json()
+    }
+
+    #[test]
+    fn test_parse_many_partitions_batch() {
+        let (trade_1, trade_2) = (trade(12345), trade(12346));
+        let batch = parse_many::<Trade>([trade_1.as_str(), ACK, trade_2.as_str(), CORRUPT]);
+
+        assert_eq!(batch.summary(), BatchSummary { frames: 4, parsed: 2, failed: 1, controls: 1 });
+        assert!(!batch.is_ok());
+        assert_eq!(batch.values.iter().map(|trade| trade.trade_id).collect::<Vec<_>>(), vec![12345, 12346]);
+        assert_eq!(batch.controls, vec![(1, FrameKind::Ack { id: Some(1) })]);
+        let (index, error) = &batch.errors[0];
+        assert_eq!(*index, 3);
+        assert_eq!(error.event_type.as_deref(), Some("trade"));
+        assert!(error.is_data());
+
+        let empty = parse_many::<Trade>(Vec::<&str>::new());
+        assert_eq!(empty.summary(), BatchSummary::default());
+        assert!(empty.is_ok());
+    }
+
+    #[test]
+    fn test_parse_many_spot_events() {
+        let unknown = r#"{"e":"newEvent","E":1}"#;
+        let batch = SpotEvent::parse_many([&trade(12345), sample_balance_update_json(), ACK, unknown, CORRUPT]);
+
+        assert_eq!(batch.summary(), BatchSummary { frames: 5, parsed: 3, failed: 1, controls: 1 });
+        assert!(matches!(&batch.values[0], SpotEvent::Trade(trade) if trade.trade_id == 12345));
+        assert!(matches!(&batch.values[1], SpotEvent::UserData(UserDataEvent::BalanceUpdate(_))));
+        assert_eq!(batch.values[2], SpotEvent::Unknown(unknown.to_string()));
+        assert_eq!(batch.values.iter().map(|event| event.event_type()).collect::<Vec<_>>(), vec!["trade", "balanceUpdate", "unknown"]);
+        assert_eq!(batch.errors[0].0, 4);
+    }
+}
@@ -0,0 +1,224 @@
+//! Best bid/offer cache fed by spot and futures bookTicker streams.
+
+use std::collections::HashMap;
+
+cfg_spot! {
+    use crate::websocket::BookTicker;
+}
+cfg_futures! {
+    use crate::websocket::futures;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bbo {
+    pub symbol: String,                  // Symbol
+    pub update_id: Option<u64>,          // Order book update ID, absent for REST seeded quotes
+    pub bid_price: f64,                  // Best bid price
+    pub bid_quantity: f64,               // Best bid quantity
+    pub ask_price: f64,                  // Best ask price
+    pub ask_quantity: f64,               // Best ask quantity
+    pub event_time: Option<u64>,         // Event time, futures only
+    pub transaction_time: Option<u64>,   // Transaction time, futures only
+}
+
+impl Bbo {
+    // An empty side, price 0, is never crossed
+    pub fn is_crossed(&self) -> bool {
+        self.bid_price > 0.0 && self.ask_price > 0.0 && self.bid_price >= self.ask_price
+    }
+
+    pub fn mid_price(&self) -> f64 {
+        (self.bid_price + self.ask_price) / 2.0
+    }
+
+    // Spread relative to the mid price in basis points, None when either side is empty
+    pub fn spread_bps(&self) -> Option<f64> {
+        if self.bid_price <= 0.0 || self.ask_price <= 0.0 {
+            return None;
+        }
+        Some((self.ask_price - self.bid_price) / self.mid_price() * 10_000.0)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl From<&BookTicker> for Bbo {
+    fn from(ticker: &BookTicker) -> Self {
+        Bbo {
+            symbol: ticker.symbol.clone(),
+            update_id: ticker.update_id,
+            bid_price: ticker.best_bid_price,
+            bid_quantity: ticker.best_bid_quantity,
+            ask_price: ticker.best_ask_price,
+            ask_quantity: ticker.best_ask_quantity,
+            event_time: None,
+            transaction_time: None,
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl From<&futures::BookTicker> for Bbo {
+    fn from(ticker: &futures::BookTicker) -> Self {
+        Bbo {
+            symbol: ticker.symbol.clone(),
+            update_id: Some(ticker.update_id),
+            bid_price: ticker.best_bid_price,
+            bid_quantity: ticker.best_bid_quantity,
+            ask_price: ticker.best_ask_price,
+            ask_quantity: ticker.best_ask_quantity,
+            event_time: Some(ticker.event_time),
+            transaction_time: Some(ticker.transaction_time),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BboUpdate {
+    Updated,                             // Stored as the current quote
+    Stale,                               // Update ID not newer than the stored one, dropped
+    Crossed,                             // Bid at or above ask, kept apart, the last good quote stays current
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct BboEntry {
+    valid: Option<Bbo>,                  // Last quote that was not crossed
+    crossed: Option<Bbo>,                // Crossed quote received after it, if any
+}
+
+impl BboEntry {
+    fn latest(&self) -> Option<&Bbo> {
+        self.crossed.as_ref().or(self.valid.as_ref())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BboCache {
+    entries: HashMap<String, BboEntry>,
+}
+
+impl BboCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Quotes without an update ID (REST seeds) only land while the symbol has no sequenced quote yet
+    pub fn ingest(&mut self, quote: impl Into<Bbo>) -> BboUpdate {
+        let quote = quote.into();
+        if let Some(latest) = self.entries.get(&quote.symbol).and_then(BboEntry::latest) {
+            let is_newer = match (quote.update_id, latest.update_id) {
+                (Some(new), Some(old)) => new > old,
+                (Some(_), None) | (None, None) => true,
+                (None, Some(_)) => false,
+            };
+            if !is_newer {
+                return BboUpdate::Stale;
+            }
+        }
+        let entry = self.entries.entry(quote.symbol.clone()).or_insert(BboEntry { valid: None, crossed: None });
+        if quote.is_crossed() {
+            entry.crossed = Some(quote);
+            BboUpdate::Crossed
+        } else {
+            *entry = BboEntry { valid: Some(quote), crossed: None };
+            BboUpdate::Updated
+        }
+    }
+
+    // Last quote that was not crossed, kept while newer quotes are crossed
+    pub fn get(&self, symbol: &str) -> Option<&Bbo> {
+        self.entries.get(symbol)?.valid.as_ref()
+    }
+
+    // Latest quote if it is crossed
+    pub fn crossed(&self, symbol: &str) -> Option<&Bbo> {
+        self.entries.get(symbol)?.crossed.as_ref()
+    }
+
+    pub fn spread_bps(&self, symbol: &str) -> Option<f64> {
+        self.get(symbol).and_then(Bbo::spread_bps)
+    }
+
+    pub fn remove(&mut self, symbol: &str) {
+        self.entries.remove(symbol);
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+
+    fn spot(update_id: u64, bid: f64, ask: f64) -> BookTicker {
+        BookTicker {
+            update_id: Some(update_id),
+            symbol: "BNBUSDT".to_string(),
+            best_bid_price: bid,
+            best_bid_quantity: 1.0,
+            best_ask_price: ask,
+            best_ask_quantity: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_bbo_cache_in_order_updates() {
+        let mut cache = BboCache::new();
+        assert_eq!(cache.ingest(&spot(10, 99.0, 101.0)), BboUpdate::Updated);
+        assert_eq!(cache.ingest(&spot(11, 99.5, 100.5)), BboUpdate::Updated);
+
+        let bbo = cache.get("BNBUSDT").unwrap();
+        assert_eq!(bbo.update_id, Some(11));
+        assert_eq!(bbo.bid_price, 99.5);
+        assert_eq!(bbo.ask_price, 100.5);
+        assert_eq!(cache.spread_bps("BNBUSDT"), Some(100.0));
+        assert_eq!(cache.get("BTCUSDT"), None);
+
+        #[cfg(feature = "futures")]
+        {
+            let json = r#"{"e":"bookTicker","u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000","T":1568014460891,"E":1568014460893}"#;
+            let ticker = futures::BookTicker::from_json(json).unwrap();
+            assert_eq!(cache.ingest(&ticker), BboUpdate::Updated);
+            assert_eq!(cache.get("BNBUSDT").unwrap().event_time, Some(1568014460893));
+        }
+    }
+
+    #[test]
+    fn test_bbo_cache_drops_stale_updates() {
+        let mut cache = BboCache::new();
+        cache.ingest(&spot(10, 99.0, 101.0));
+        assert_eq!(cache.ingest(&spot(10, 98.0, 102.0)), BboUpdate::Stale);
+        assert_eq!(cache.ingest(&spot(7, 98.0, 102.0)), BboUpdate::Stale);
+        assert_eq!(cache.get("BNBUSDT").unwrap().bid_price, 99.0);
+
+        // A REST seed cannot overwrite a sequenced quote
+        let mut seed = spot(0, 50.0, 150.0);
+        seed.update_id = None;
+        assert_eq!(cache.ingest(&seed), BboUpdate::Stale);
+
+        cache.remove("BNBUSDT");
+        assert_eq!(cache.ingest(&seed), BboUpdate::Updated);
+        assert_eq!(cache.ingest(&spot(3, 99.0, 101.0)), BboUpdate::Updated);
+    }
+
+    #[test]
+    fn test_bbo_cache_flags_crossed_quotes() {
+        let mut cache = BboCache::new();
+        cache.ingest(&spot(10, 99.0, 101.0));
+        assert_eq!(cache.ingest(&spot(11, 101.0, 100.0)), BboUpdate::Crossed);
+
+        assert_eq!(cache.get("BNBUSDT").unwrap().update_id, Some(10));
+        assert_eq!(cache.crossed("BNBUSDT").unwrap().update_id, Some(11));
+        assert_eq!(cache.ingest(&spot(11, 99.0, 101.0)), BboUpdate::Stale);
+        assert_eq!(cache.spread_bps("BNBUSDT"), Some(200.0));
+
+        assert_eq!(cache.ingest(&spot(12, 100.0, 100.5)), BboUpdate::Updated);
+        assert_eq!(cache.crossed("BNBUSDT"), None);
+        assert_eq!(cache.get("BNBUSDT").unwrap().update_id, Some(12));
+
+        // A crossed first quote leaves nothing to fall back to, an empty side is not crossed
+        cache.remove("BNBUSDT");
+        assert_eq!(cache.ingest(&spot(13, 101.0, 100.0)), BboUpdate::Crossed);
+        assert_eq!(cache.get("BNBUSDT"), None);
+        assert_eq!(cache.ingest(&spot(14, 101.0, 0.0)), BboUpdate::Updated);
+        assert_eq!(cache.ingest(&spot(15, 0.0, 0.0)), BboUpdate::Updated);
+        assert_eq!((cache.crossed("BNBUSDT"), cache.spread_bps("BNBUSDT")), (None, None));
+    }
+}
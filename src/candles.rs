@@ -0,0 +1,563 @@
+//! OHLCV bars built locally from trade streams, and continuity checks and close filtering for received klines.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::rest::{KlineRow, KlinesRequest};
+use crate::websocket::{AggTrade, Kline, KlineEvent, KlineInterval, Trade};
+
+// The part of a trade or aggregated trade a bar needs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TradeTick {
+    pub first_trade_id: i64,             // First trade ID covered
+    pub last_trade_id: i64,              // Last trade ID covered
+    pub price: f64,                      // Price
+    pub quantity: f64,                   // Quantity
+    pub trade_time: u64,                 // Trade time
+    pub is_buyer_market_maker: bool,     // Is the buyer the market maker?
+}
+
+impl From<&Trade> for TradeTick {
+    fn from(trade: &Trade) -> Self {
+        TradeTick {
+            first_trade_id: trade.trade_id as i64,
+            last_trade_id: trade.trade_id as i64,
+            price: trade.price,
+            quantity: trade.quantity,
+            trade_time: trade.trade_time,
+            is_buyer_market_maker: trade.is_buyer_market_maker,
+        }
+    }
+}
+
+impl From<&AggTrade> for TradeTick {
+    fn from(trade: &AggTrade) -> Self {
+        TradeTick {
+            first_trade_id: trade.first_trade_id as i64,
+            last_trade_id: trade.last_trade_id as i64,
+            price: trade.price,
+            quantity: trade.quantity,
+            trade_time: trade.trade_time,
+            is_buyer_market_maker: trade.is_buyer_market_maker,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmptyIntervals {
+    Skip,                                // Emit nothing for intervals without trades
+    CarryForward,                        // Emit a flat zero-volume bar at the previous close
+}
+
+#[derive(Clone, Debug)]
+struct OpenBar {
+    open: (u64, f64),
+    close: (u64, f64),
+    high: f64,
+    low: f64,
+    volume: f64,
+    quote_volume: f64,
+    taker_buy_base_volume: f64,
+    taker_buy_quote_volume: f64,
+    number_of_trades: u64,
+    first_trade_id: i64,
+    last_trade_id: i64,
+}
+
+impl OpenBar {
+    fn new(tick: &TradeTick) -> Self {
+        OpenBar {
+            open: (tick.trade_time, tick.price),
+            close: (tick.trade_time, tick.price),
+            high: tick.price,
+            low: tick.price,
+            volume: 0.0,
+            quote_volume: 0.0,
+            taker_buy_base_volume: 0.0,
+            taker_buy_quote_volume: 0.0,
+            number_of_trades: 0,
+            first_trade_id: tick.first_trade_id,
+            last_trade_id: tick.last_trade_id,
+        }
+    }
+
+    // Open and close follow trade time, so late trades inside the tolerance land where they belong
+    fn add(&mut self, tick: &TradeTick) {
+        if tick.trade_time < self.open.0 {
+            self.open = (tick.trade_time, tick.price);
+        }
+        if tick.trade_time >= self.close.0 {
+            self.close = (tick.trade_time, tick.price);
+        }
+        self.high = self.high.max(tick.price);
+        self.low = self.low.min(tick.price);
+        self.volume += tick.quantity;
+        self.quote_volume += tick.quantity * tick.price;
+        if !tick.is_buyer_market_maker {
+            self.taker_buy_base_volume += tick.quantity;
+            self.taker_buy_quote_volume += tick.quantity * tick.price;
+        }
+        self.number_of_trades += (tick.last_trade_id - tick.first_trade_id + 1).max(1) as u64;
+        self.first_trade_id = self.first_trade_id.min(tick.first_trade_id);
+        self.last_trade_id = self.last_trade_id.max(tick.last_trade_id);
+    }
+}
+
+// Bars close once a trade at least `tolerance_ms` past their end arrives; trades for bars that
+// were already emitted are counted as late and dropped
+#[derive(Clone, Debug)]
+pub struct CandleAggregator {
+    symbol: String,
+    interval: KlineInterval,
+    interval_ms: u64,
+    empty_intervals: EmptyIntervals,
+    tolerance_ms: u64,
+    bars: BTreeMap<u64, OpenBar>,
+    next_start: Option<u64>,
+    last_close: Option<f64>,
+    watermark: u64,
+    late_trades: u64,
+}
+
+impl CandleAggregator {
+    // Month bars have no fixed length and are rejected
+    pub fn new(symbol: &str, interval: KlineInterval, empty_intervals: EmptyIntervals, tolerance_ms: u64) -> Result<Self, String> {
+        let interval_ms = interval
+            .duration_ms()
+            .ok_or_else(|| format!("Interval {} has no fixed duration", interval))?;
+        Ok(CandleAggregator {
+            symbol: symbol.to_string(),
+            interval,
+            interval_ms,
+            empty_intervals,
+            tolerance_ms,
+            bars: BTreeMap::new(),
+            next_start: None,
+            last_close: None,
+            watermark: 0,
+            late_trades: 0,
+        })
+    }
+
+    pub fn late_trades(&self) -> u64 {
+        self.late_trades
+    }
+
+    pub fn ingest(&mut self, tick: impl Into<TradeTick>) -> Vec<Kline> {
+        let tick = tick.into();
+        let start = tick.trade_time - tick.trade_time % self.interval_ms;
+        let next_start = *self.next_start.get_or_insert(start);
+        if start < next_start {
+            self.late_trades += 1;
+            return Vec::new();
+        }
+        self.bars.entry(start).or_insert_with(|| OpenBar::new(&tick)).add(&tick);
+        self.watermark = self.watermark.max(tick.trade_time);
+
+        let mut completed = Vec::new();
+        while let Some(next_start) = self.next_start {
+            if next_start + self.interval_ms + self.tolerance_ms > self.watermark {
+                break;
+            }
+            self.emit_next(next_start, &mut completed);
+        }
+        completed
+    }
+
+    // Emits every bar still open, e.g. on shutdown
+    pub fn flush(&mut self) -> Vec<Kline> {
+        let mut completed = Vec::new();
+        while let (Some(next_start), false) = (self.next_start, self.bars.is_empty()) {
+            self.emit_next(next_start, &mut completed);
+        }
+        completed
+    }
+
+    fn emit_next(&mut self, start: u64, completed: &mut Vec<Kline>) {
+        match self.bars.remove(&start) {
+            Some(bar) => {
+                self.last_close = Some(bar.close.1);
+                completed.push(self.to_kline(start, &bar));
+                self.next_start = Some(start + self.interval_ms);
+            }
+            None => match (self.empty_intervals, self.last_close) {
+                (EmptyIntervals::CarryForward, Some(close)) => {
+                    completed.push(self.carry_forward_kline(start, close));
+                    self.next_start = Some(start + self.interval_ms);
+                }
+                _ => {
+                    self.next_start = self.bars.keys().next().copied().or(Some(start + self.interval_ms));
+                }
+            },
+        }
+    }
+
+    fn to_kline(&self, start: u64, bar: &OpenBar) -> Kline {
+        Kline {
+            start_time: start,
+            close_time: start + self.interval_ms - 1,
+            symbol: self.symbol.clone(),
+            interval: self.interval,
+            first_trade_id: bar.first_trade_id,
+            last_trade_id: bar.last_trade_id,
+            open_price: bar.open.1,
+            close_price: bar.close.1,
+            high_price: bar.high,
+            low_price: bar.low,
+            volume: bar.volume,
+            number_of_trades: bar.number_of_trades,
+            is_closed: true,
+            quote_asset_volume: bar.quote_volume,
+            taker_buy_base_asset_volume: bar.taker_buy_base_volume,
+            taker_buy_quote_asset_volume: bar.taker_buy_quote_volume,
+            ignore: "0".to_string(),
+        }
+    }
+
+    fn carry_forward_kline(&self, start: u64, close: f64) -> Kline {
+        Kline {
+            start_time: start,
+            close_time: start + self.interval_ms - 1,
+            symbol: self.symbol.clone(),
+            interval: self.interval,
+            first_trade_id: -1,
+            last_trade_id: -1,
+            open_price: close,
+            close_price: close,
+            high_price: close,
+            low_price: close,
+            volume: 0.0,
+            number_of_trades: 0,
+            is_closed: true,
+            quote_asset_volume: 0.0,
+            taker_buy_base_asset_volume: 0.0,
+            taker_buy_quote_asset_volume: 0.0,
+            ignore: "0".to_string(),
+        }
+    }
+}
+
+// Open time range of missing closed klines, inclusive; usable as a REST klines start and end time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MissingKlines {
+    pub start_time: u64,                 // Open time of the first missing kline
+    pub end_time: u64,                   // Just before the open time of the next received kline
+}
+
+impl MissingKlines {
+    pub fn to_request(&self, symbol: &str, interval: KlineInterval) -> KlinesRequest {
+        KlinesRequest::new(symbol, interval).start_time(self.start_time).end_time(self.end_time)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KlineCheck {
+    Continuous,                          // Directly follows the previous kline, or is the first one
+    Missing(MissingKlines),              // Follows a gap
+    Duplicate,                           // Same open time as the previous kline
+    Overlap { previous_start_time: u64 }, // Starts before the previous kline ends, or before it
+    NotClosed,                           // Still open, ignored
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KlineContinuityReport {
+    pub missing: Vec<MissingKlines>,     // Gaps seen so far, oldest first
+    pub duplicates: u64,                 // Klines received twice
+    pub overlaps: u64,                   // Klines overlapping or preceding the previous one
+}
+
+#[derive(Clone, Debug, Default)]
+struct ContinuityState {
+    last: Option<(u64, u64)>,
+    report: KlineContinuityReport,
+}
+
+// Checks closed klines per symbol and interval; the next kline is expected at the previous open time
+// plus the interval, or right after the previous close time for the calendar month interval
+#[derive(Clone, Debug, Default)]
+pub struct KlineContinuity {
+    series: HashMap<(String, KlineInterval), ContinuityState>,
+}
+
+impl KlineContinuity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ingest(&mut self, kline: &Kline) -> KlineCheck {
+        if !kline.is_closed {
+            return KlineCheck::NotClosed;
+        }
+        self.record(&kline.symbol, kline.interval, kline.start_time, kline.close_time)
+    }
+
+    // REST rows are always treated as closed, drop the last row of a live request first
+    pub fn ingest_row(&mut self, symbol: &str, interval: KlineInterval, row: &KlineRow) -> KlineCheck {
+        self.record(symbol, interval, row.open_time, row.close_time)
+    }
+
+    pub fn record(&mut self, symbol: &str, interval: KlineInterval, start_time: u64, close_time: u64) -> KlineCheck {
+        let state = self.series.entry((symbol.to_string(), interval)).or_default();
+        let Some((previous_start_time, previous_close_time)) = state.last else {
+            state.last = Some((start_time, close_time));
+            return KlineCheck::Continuous;
+        };
+        let expected = interval.duration_ms().map_or(previous_close_time + 1, |duration| previous_start_time + duration);
+        let check = if start_time == previous_start_time {
+            state.report.duplicates += 1;
+            KlineCheck::Duplicate
+        } else if start_time < expected {
+            state.report.overlaps += 1;
+            KlineCheck::Overlap { previous_start_time }
+        } else if start_time > expected {
+            let missing = MissingKlines { start_time: expected, end_time: start_time - 1 };
+            state.report.missing.push(missing);
+            KlineCheck::Missing(missing)
+        } else {
+            KlineCheck::Continuous
+        };
+        if start_time > previous_start_time {
+            state.last = Some((start_time, close_time));
+        }
+        check
+    }
+
+    pub fn report(&self, symbol: &str, interval: KlineInterval) -> KlineContinuityReport {
+        self.series.get(&(symbol.to_string(), interval)).map(|state| state.report.clone()).unwrap_or_default()
+    }
+
+    // Hands over the gaps to backfill, leaving the duplicate and overlap counts in place
+    pub fn take_missing(&mut self, symbol: &str, interval: KlineInterval) -> Vec<MissingKlines> {
+        self.series
+            .get_mut(&(symbol.to_string(), interval))
+            .map(|state| std::mem::take(&mut state.report.missing))
+            .unwrap_or_default()
+    }
+
+    pub fn reset(&mut self, symbol: &str, interval: KlineInterval) {
+        self.series.remove(&(symbol.to_string(), interval));
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct FilterState {
+    last_closed_start_time: Option<u64>,
+    forming: Option<Kline>,
+}
+
+// Passes on the final kline of each interval once per symbol and interval. Binance can resend the closed
+// kline, and updates of an interval can arrive around the first update of the next one; anything opening
+// at or before the last emitted kline is dropped
+#[derive(Clone, Debug, Default)]
+pub struct ClosedKlineFilter {
+    series: HashMap<(String, KlineInterval), FilterState>,
+    duplicates: u64,
+}
+
+impl ClosedKlineFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ingest(&mut self, event: KlineEvent) -> Option<Kline> {
+        self.ingest_kline(event.kline)
+    }
+
+    pub fn ingest_kline(&mut self, kline: Kline) -> Option<Kline> {
+        let state = self.series.entry((kline.symbol.clone(), kline.interval)).or_default();
+        if state.last_closed_start_time.is_some_and(|start_time| kline.start_time <= start_time) {
+            if kline.is_final() {
+                self.duplicates += 1;
+            }
+            return None;
+        }
+        if !kline.is_final() {
+            if state.forming.as_ref().is_none_or(|forming| kline.start_time >= forming.start_time) {
+                state.forming = Some(kline);
+            }
+            return None;
+        }
+        state.last_closed_start_time = Some(kline.start_time);
+        if state.forming.as_ref().is_some_and(|forming| forming.start_time <= kline.start_time) {
+            state.forming = None;
+        }
+        Some(kline)
+    }
+
+    // Latest update of the kline still open, None right after a close until the next interval's first update
+    pub fn forming(&self, symbol: &str, interval: KlineInterval) -> Option<&Kline> {
+        self.series.get(&(symbol.to_string(), interval))?.forming.as_ref()
+    }
+
+    pub fn last_closed_start_time(&self, symbol: &str, interval: KlineInterval) -> Option<u64> {
+        self.series.get(&(symbol.to_string(), interval))?.last_closed_start_time
+    }
+
+    // Closed klines received again after they were passed on
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    pub fn reset(&mut self, symbol: &str, interval: KlineInterval) {
+        self.series.remove(&(symbol.to_string(), interval));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T0: u64 = 1_700_000_040_000; // Start of a minute
+
+    fn tick(trade_id: i64, price: f64, quantity: f64, trade_time: u64, is_buyer_market_maker: bool) -> TradeTick {
+        TradeTick { first_trade_id: trade_id, last_trade_id: trade_id, price, quantity, trade_time, is_buyer_market_maker }
+    }
+
+    fn ohlcv(kline: &Kline) -> (u64, f64, f64, f64, f64, f64, u64) {
+        (kline.start_time, kline.open_price, kline.high_price, kline.low_price, kline.close_price, kline.volume, kline.number_of_trades)
+    }
+
+    fn script(aggregator: &mut CandleAggregator) -> Vec<Kline> {
+        let mut klines = Vec::new();
+        klines.extend(aggregator.ingest(tick(1, 100.0, 1.0, T0 + 1_000, false)));
+        klines.extend(aggregator.ingest(tick(2, 102.0, 0.5, T0 + 20_000, true)));
+        klines.extend(aggregator.ingest(tick(3, 99.0, 2.0, T0 + 59_000, false)));
+        // Nothing in the second minute
+        klines.extend(aggregator.ingest(tick(4, 101.0, 1.0, T0 + 120_500, true)));
+        // Belongs to the first minute, which was already emitted
+        klines.extend(aggregator.ingest(tick(5, 98.0, 1.0, T0 + 58_000, false)));
+        klines.extend(aggregator.ingest(tick(6, 103.0, 1.0, T0 + 150_000, false)));
+        klines.extend(aggregator.ingest(tick(7, 104.0, 1.0, T0 + 185_000, false)));
+        klines
+    }
+
+    #[test]
+    fn test_candle_aggregator_carry_forward() {
+        let mut aggregator = CandleAggregator::new("BTCUSDT", KlineInterval::M1, EmptyIntervals::CarryForward, 0).unwrap();
+        let klines = script(&mut aggregator);
+
+        assert_eq!(klines.len(), 3);
+        assert_eq!(ohlcv(&klines[0]), (T0, 100.0, 102.0, 99.0, 99.0, 3.5, 3));
+        assert_eq!(klines[0].close_time, T0 + 59_999);
+        assert_eq!(klines[0].taker_buy_base_asset_volume, 3.0);
+        assert_eq!(klines[0].quote_asset_volume, 100.0 + 51.0 + 198.0);
+        assert_eq!((klines[0].first_trade_id, klines[0].last_trade_id), (1, 3));
+        assert_eq!(ohlcv(&klines[1]), (T0 + 60_000, 99.0, 99.0, 99.0, 99.0, 0.0, 0));
+        assert_eq!(klines[1].first_trade_id, -1);
+        assert_eq!(ohlcv(&klines[2]), (T0 + 120_000, 101.0, 103.0, 101.0, 103.0, 2.0, 2));
+        assert!(klines.iter().all(|kline| kline.is_closed && kline.interval == KlineInterval::M1));
+        assert_eq!(aggregator.late_trades(), 1);
+
+        let rest = aggregator.flush();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(ohlcv(&rest[0]), (T0 + 180_000, 104.0, 104.0, 104.0, 104.0, 1.0, 1));
+    }
+
+    #[test]
+    fn test_candle_aggregator_skip_empty_and_tolerance() {
+        let mut aggregator = CandleAggregator::new("BTCUSDT", KlineInterval::M1, EmptyIntervals::Skip, 5_000).unwrap();
+        let mut klines = Vec::new();
+        klines.extend(aggregator.ingest(tick(1, 100.0, 1.0, T0 + 1_000, false)));
+        klines.extend(aggregator.ingest(tick(3, 99.0, 2.0, T0 + 59_000, false)));
+        klines.extend(aggregator.ingest(tick(4, 101.0, 1.0, T0 + 63_000, true)));
+        // Out of order, but the first minute stays open until 5 s past its end
+        klines.extend(aggregator.ingest(tick(2, 102.0, 0.5, T0 + 20_000, true)));
+        assert!(klines.is_empty());
+
+        // Nothing in the third minute
+        klines.extend(aggregator.ingest(tick(5, 105.0, 1.0, T0 + 185_000, false)));
+        assert_eq!(klines.len(), 2);
+        assert_eq!(ohlcv(&klines[0]), (T0, 100.0, 102.0, 99.0, 99.0, 3.5, 3));
+        assert_eq!(ohlcv(&klines[1]), (T0 + 60_000, 101.0, 101.0, 101.0, 101.0, 1.0, 1));
+        assert_eq!(aggregator.late_trades(), 0);
+
+        let rest = aggregator.flush();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].start_time, T0 + 180_000);
+        assert!(CandleAggregator::new("BTCUSDT", KlineInterval::Mo1, EmptyIntervals::Skip, 0).is_err());
+    }
+
+    #[test]
+    fn test_kline_continuity_missing_and_duplicate() {
+        const MINUTE: u64 = 60_000;
+        let row = |open_time: u64| KlineRow {
+            open_time,
+            open_price: 1.0,
+            high_price: 1.0,
+            low_price: 1.0,
+            close_price: 1.0,
+            volume: 1.0,
+            close_time: open_time + MINUTE - 1,
+            quote_asset_volume: 1.0,
+            number_of_trades: 1,
+            taker_buy_base_asset_volume: 0.0,
+            taker_buy_quote_asset_volume: 0.0,
+            ignore: "0".to_string(),
+        };
+        let mut continuity = KlineContinuity::new();
+        let checks: Vec<KlineCheck> = [0, 1, 3, 3, 4]
+            .iter()
+            .map(|minute| continuity.ingest_row("BTCUSDT", KlineInterval::M1, &row(T0 + minute * MINUTE)))
+            .collect();
+        let missing = MissingKlines { start_time: T0 + 2 * MINUTE, end_time: T0 + 3 * MINUTE - 1 };
+        assert_eq!(checks, vec![
+            KlineCheck::Continuous,
+            KlineCheck::Continuous,
+            KlineCheck::Missing(missing),
+            KlineCheck::Duplicate,
+            KlineCheck::Continuous,
+        ]);
+
+        // Streamed klines share the series; an open kline is skipped and a misaligned one overlaps
+        let mut kline = row(T0 + 4 * MINUTE + 30_000).to_kline("BTCUSDT", KlineInterval::M1, false);
+        assert_eq!(continuity.ingest(&kline), KlineCheck::NotClosed);
+        kline.is_closed = true;
+        assert_eq!(continuity.ingest(&kline), KlineCheck::Overlap { previous_start_time: T0 + 4 * MINUTE });
+
+        assert_eq!(continuity.report("BTCUSDT", KlineInterval::M1), KlineContinuityReport { missing: vec![missing], duplicates: 1, overlaps: 1 });
+        assert_eq!(continuity.report("BTCUSDT", KlineInterval::M5), KlineContinuityReport::default());
+        assert_eq!(missing.to_request("BTCUSDT", KlineInterval::M1).to_query_string(), format!("symbol=BTCUSDT&interval=1m&startTime={}&endTime={}", T0 + 120_000, T0 + 179_999));
+        assert_eq!(continuity.take_missing("BTCUSDT", KlineInterval::M1), vec![missing]);
+        assert_eq!(continuity.report("BTCUSDT", KlineInterval::M1).missing, Vec::new());
+    }
+
+    #[test]
+    fn test_closed_kline_filter_emits_each_interval_once() {
+        const MINUTE: u64 = 60_000;
+        let update = |start_time: u64, close_price: f64, is_closed: bool| {
+            let mut event = KlineEvent::from_json(crate::testutil::sample_kline_json()).unwrap();
+            event.event_time = start_time + 1_000;
+            event.kline = Kline { start_time, close_time: start_time + MINUTE - 1, close_price, is_closed, ..event.kline };
+            event
+        };
+        let sequence = [
+            update(T0, 0.0011, false),
+            update(T0, 0.0012, false),
+            update(T0, 0.0013, true),
+            // Resent close and the first update of the next minute, then a straggler of the closed minute
+            update(T0, 0.0013, true),
+            update(T0 + MINUTE, 0.0014, false),
+            update(T0, 0.0012, false),
+            update(T0 + MINUTE, 0.0015, false),
+            update(T0 + MINUTE, 0.0016, true),
+        ];
+
+        let mut filter = ClosedKlineFilter::new();
+        assert!(filter.forming("BNBBTC", KlineInterval::M1).is_none());
+        let mut emitted = Vec::new();
+        for (index, event) in sequence.into_iter().enumerate() {
+            emitted.extend(filter.ingest(event));
+            match index {
+                1 => assert_eq!(filter.forming("BNBBTC", KlineInterval::M1).map(|kline| kline.close_price), Some(0.0012)),
+                2 | 3 => assert!(filter.forming("BNBBTC", KlineInterval::M1).is_none()),
+                5 => assert_eq!(filter.forming("BNBBTC", KlineInterval::M1).map(|kline| kline.start_time), Some(T0 + MINUTE)),
+                _ => {}
+            }
+        }
+
+        assert_eq!(emitted.iter().map(|kline| (kline.start_time, kline.close_price)).collect::<Vec<_>>(), [(T0, 0.0013), (T0 + MINUTE, 0.0016)]);
+        assert!(emitted.iter().all(Kline::is_final));
+        assert_eq!(filter.duplicates(), 1);
+        assert_eq!(filter.last_closed_start_time("BNBBTC", KlineInterval::M1), Some(T0 + MINUTE));
+        assert_eq!(filter.last_closed_start_time("BNBBTC", KlineInterval::M5), None);
+    }
+}
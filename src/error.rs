@@ -0,0 +1,336 @@
+//! Parse errors carrying the context of the offending message.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+const SNIPPET_CONTEXT: usize = 40;
+
+// A serde_json error together with where and in which message it happened. `Display` and `Error` are
+// written by hand like the crate's other error types rather than derived with thiserror, which would be
+// the only non-optional dependency besides serde and serde_json
+#[derive(Debug)]
+pub struct CodecError {
+    pub event_type: Option<String>,      // Value of the `e` field, if it could be found
+    pub element_index: Option<usize>,    // 0-based index of the failing element when parsing an array
+    pub line: usize,                     // 1-based line of the error, 0 if unknown
+    pub column: usize,                   // 1-based column of the error, 0 if unknown
+    pub offset: Option<usize>,           // Byte offset of the error in the input
+    pub snippet: String,                 // Input around the error, truncated to a few dozen bytes each side
+    pub source: serde_json::Error,       // Underlying error
+}
+
+impl CodecError {
+    pub fn new(source: serde_json::Error, input: &str) -> Self {
+        let offset = byte_offset(input, source.line(), source.column());
+        CodecError {
+            event_type: event_type(input).map(str::to_string),
+            element_index: None,
+            line: source.line(),
+            column: source.column(),
+            offset,
+            snippet: snippet(input, offset.unwrap_or(0)),
+            source,
+        }
+    }
+
+    pub fn from_slice(source: serde_json::Error, input: &[u8]) -> Self {
+        CodecError::new(source, &String::from_utf8_lossy(input))
+    }
+
+    // Locates the failing element of a top-level array and takes the event type from that element
+    pub fn array(source: serde_json::Error, input: &str) -> Self {
+        let mut error = CodecError::new(source, input);
+        if let Some((index, element_start)) = error.offset.and_then(|offset| array_element(input, offset)) {
+            error.element_index = Some(index);
+            error.event_type = event_type(&input[element_start..]).map(str::to_string);
+        }
+        error
+    }
+
+    pub fn array_from_slice(source: serde_json::Error, input: &[u8]) -> Self {
+        CodecError::array(source, &String::from_utf8_lossy(input))
+    }
+
+    pub fn is_syntax(&self) -> bool {
+        self.source.is_syntax() || self.source.is_eof()
+    }
+
+    pub fn is_data(&self) -> bool {
+        self.source.is_data()
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = self.source.to_string();
+        let suffix = format!(" at line {} column {}", self.line, self.column);
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+        write!(f, "failed to parse {} message", self.event_type.as_deref().unwrap_or("unknown"))?;
+        if let Some(index) = self.element_index {
+            write!(f, " in array element {}", index)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at byte {} (line {}, column {})", offset, self.line, self.column)?;
+        }
+        write!(f, ": {}; near `{}`", message, self.snippet)
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+pub fn parse<T: DeserializeOwned>(json: &str) -> Result<T, CodecError> {
+    instrumented(json.as_bytes(), || serde_json::from_str(json).map_err(|error| CodecError::new(error, json)))
+}
+
+pub fn parse_slice<T: DeserializeOwned>(json: &[u8]) -> Result<T, CodecError> {
+    instrumented(json, || serde_json::from_slice(json).map_err(|error| CodecError::from_slice(error, json)))
+}
+
+// For array streams such as `!markPrice@arr`, errors carry the index of the failing element
+pub fn parse_array<T: DeserializeOwned>(json: &str) -> Result<Vec<T>, CodecError> {
+    instrumented(json.as_bytes(), || serde_json::from_str(json).map_err(|error| CodecError::array(error, json)))
+}
+
+pub fn parse_array_slice<T: DeserializeOwned>(json: &[u8]) -> Result<Vec<T>, CodecError> {
+    instrumented(json, || serde_json::from_slice(json).map_err(|error| CodecError::array_from_slice(error, json)))
+}
+
+// Backs the `parse` entry points of the event enums: the enum's `from_json` with the failing message's
+// context in the error. For entry points that dispatch on the event type, e.g.
+// `parse_with(json, UserDataEvent::from_json)`
+pub fn parse_with<T>(json: &str, from_json: impl FnOnce(&str) -> Result<T, serde_json::Error>) -> Result<T, CodecError> {
+    instrumented(json.as_bytes(), || from_json(json).map_err(|error| CodecError::new(error, json)))
+}
+
+// Records count, errors and duration of one parse with the `metrics` feature and logs failures at debug
+// level with the `tracing` feature. Without either it is a plain call
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn instrumented<T>(json: &[u8], parse: impl FnOnce() -> Result<T, CodecError>) -> Result<T, CodecError> {
+    // There is no clock on wasm32-unknown-unknown, `Instant::now` panics there
+    #[cfg(all(feature = "metrics", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    let started = Some(std::time::Instant::now());
+    #[cfg(all(feature = "metrics", target_arch = "wasm32", target_os = "unknown"))]
+    let started: Option<std::time::Instant> = None;
+    let result = parse();
+    #[cfg(feature = "metrics")]
+    crate::metrics::emit::parse(json, &result, started.map(|started| started.elapsed()));
+    #[cfg(feature = "tracing")]
+    if let Err(error) = &result {
+        tracing::debug!(
+            event_type = error.event_type.as_deref().unwrap_or("unknown"),
+            offset = error.offset,
+            element_index = error.element_index,
+            error = %error.source,
+            "failed to parse message",
+        );
+    }
+    result
+}
+
+#[cfg(not(any(feature = "metrics", feature = "tracing")))]
+#[inline(always)]
+pub(crate) fn instrumented<T>(_json: &[u8], parse: impl FnOnce() -> Result<T, CodecError>) -> Result<T, CodecError> {
+    parse()
+}
+
+// serde_json reports the column of the last byte it consumed
+fn byte_offset(input: &str, line: usize, column: usize) -> Option<usize> {
+    if line == 0 {
+        return None;
+    }
+    let line_start = if line == 1 {
+        0
+    } else {
+        input.match_indices('\n').nth(line - 2)?.0 + 1
+    };
+    Some((line_start + column.saturating_sub(1)).min(input.len()))
+}
+
+fn snippet(input: &str, offset: usize) -> String {
+    let mut start = offset.saturating_sub(SNIPPET_CONTEXT);
+    while !input.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (offset + SNIPPET_CONTEXT).min(input.len());
+    while !input.is_char_boundary(end) {
+        end += 1;
+    }
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    snippet.extend(input[start..end].chars().map(|c| if c.is_control() { ' ' } else { c }));
+    if end < input.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+// Index of the top-level array element containing `offset` and the byte where that element starts,
+// counting commas outside of strings and nested values
+fn array_element(input: &str, offset: usize) -> Option<(usize, usize)> {
+    let bytes = input.as_bytes();
+    let open = bytes.iter().position(|byte| !byte.is_ascii_whitespace())?;
+    if bytes[open] != b'[' {
+        return None;
+    }
+    let (mut index, mut element_start, mut depth) = (0, open + 1, 0usize);
+    let (mut in_string, mut escaped) = (false, false);
+    for (position, byte) in bytes.iter().enumerate().take(offset.min(bytes.len())).skip(open + 1) {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => {
+                index += 1;
+                element_start = position + 1;
+            }
+            _ => {}
+        }
+    }
+    Some((index, element_start))
+}
+
+// Finds the first `"e"` key and its string value without parsing the message
+pub(crate) fn event_type(input: &str) -> Option<&str> {
+    let mut rest = input;
+    while let Some(index) = rest.find("\"e\"") {
+        rest = rest[index + 3..].trim_start();
+        if let Some(value) = rest.strip_prefix(':') {
+            let value = value.trim_start().strip_prefix('"')?;
+            return value.find('"').map(|end| &value[..end]);
+        }
+    }
+    None
+}
+
+#[cfg(all(test, any(feature = "spot", feature = "futures")))]
+mod tests {
+    use super::*;
+    #[cfg(feature = "futures")]
+    use crate::websocket::futures::MarkPriceUpdate;
+    #[cfg(feature = "spot")]
+    use crate::websocket::{Trade, UserDataEvent};
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_codec_error_context() {
+        let json = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":"not a number","p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#;
+        let error = parse::<Trade>(json).unwrap_err();
+        assert_eq!(error.event_type.as_deref(), Some("trade"));
+        assert_eq!((error.line, error.column), (1, 62));
+        assert_eq!(error.offset, Some(61));
+        assert!(error.is_data());
+        assert_eq!(error.snippet, r#"...515782136,"s":"BNBBTC","t":"not a number","p":"0.001","q":"100","T":167251578213..."#);
+        assert_eq!(
+            error.to_string(),
+            r#"failed to parse trade message at byte 61 (line 1, column 62): invalid type: string "not a number", expected u64; near `...515782136,"s":"BNBBTC","t":"not a number","p":"0.001","q":"100","T":167251578213...`"#,
+        );
+
+        let error = parse_slice::<Trade>(b"{\"e\": \"trade\", \"E\":").unwrap_err();
+        assert!(error.is_syntax());
+        assert_eq!(error.event_type.as_deref(), Some("trade"));
+        assert_eq!(error.snippet, r#"{"e": "trade", "E":"#);
+    }
+
+    #[cfg(all(feature = "tracing", feature = "spot"))]
+    #[test]
+    fn test_parse_failure_traced_once() {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Captured(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Captured {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || writer.clone())
+            .without_time()
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let json = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":"not a number","p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#;
+            assert!(parse::<Trade>(json).is_err());
+            assert!(parse::<Trade>(r#"{"e":"trade","E":1,"s":"BNBBTC","t":1,"p":"0.001","q":"100","T":1,"m":true,"M":true}"#).is_ok());
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "{}", output);
+        assert!(lines[0].contains("DEBUG"));
+        assert!(lines[0].contains("failed to parse message"));
+        assert!(lines[0].contains(r#"event_type="trade""#));
+        assert!(lines[0].contains("offset=61"));
+        assert!(!lines[0].contains("element_index"));
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_parse_with_dispatching_entry_point() {
+        let json = r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":100,"T":1573200697068}"#;
+        let error = parse_with(json, UserDataEvent::from_json).unwrap_err();
+        assert_eq!(error.event_type.as_deref(), Some("balanceUpdate"));
+        assert!(error.to_string().starts_with("failed to parse balanceUpdate message at byte "));
+        assert_eq!(UserDataEvent::parse(json).unwrap_err().offset, error.offset);
+
+        let error = parse::<Trade>("[1, 2]").unwrap_err();
+        assert_eq!(error.event_type, None);
+        assert!(error.to_string().starts_with("failed to parse unknown message"));
+        assert!(parse_with(r#"{"e":"balanceUpdate","E":1,"a":"BTC","d":"1","T":1}"#, UserDataEvent::from_json).is_ok());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_parse_array_reports_element_index() {
+        let element = |symbol: &str, price: &str| {
+            format!(r#"{{"e":"markPriceUpdate","E":1562305380000,"s":"{}","p":"{}","i":"1.0","P":"1.0","r":"0.0001","T":1562306400000}}"#, symbol, price)
+        };
+        let elements: Vec<String> = (0..500).map(|index| element(&format!("SYM{},\\\"X", index), "1.5")).collect();
+        let json = format!("[{}]", elements.join(", "));
+        assert_eq!(parse_array::<MarkPriceUpdate>(&json).unwrap().len(), 500);
+
+        let mut corrupted = elements.clone();
+        corrupted[412] = element("SYM412", "not a price");
+        let json = format!("[{}]", corrupted.join(", "));
+        let error = parse_array::<MarkPriceUpdate>(&json).unwrap_err();
+        assert_eq!(error.element_index, Some(412));
+        assert_eq!(error.event_type.as_deref(), Some("markPriceUpdate"));
+        assert!(error.is_data());
+        assert!(error.to_string().starts_with("failed to parse markPriceUpdate message in array element 412 at byte "));
+
+        // Truncated in the middle of the last element, commas inside strings do not count
+        let json = format!("[{}, {}, {{\"e\":\"markPriceUpdate\",\"s\":\"BTC,USDT\",\"p\":\"1.", elements[0], elements[1]);
+        let error = parse_array_slice::<MarkPriceUpdate>(json.as_bytes()).unwrap_err();
+        assert_eq!((error.element_index, error.is_syntax()), (Some(2), true));
+
+        let error = parse_array::<MarkPriceUpdate>(&elements[0]).unwrap_err();
+        assert_eq!(error.element_index, None);
+    }
+}
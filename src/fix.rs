@@ -0,0 +1,369 @@
+//! FIX 4.4 tag=value codec for the Binance FIX order entry and market data sessions.
+
+pub mod messages;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+pub const SOH: u8 = 0x01;
+pub const BEGIN_STRING: &str = "FIX.4.4";
+
+pub const TAG_BEGIN_STRING: u32 = 8;
+pub const TAG_BODY_LENGTH: u32 = 9;
+pub const TAG_CHECKSUM: u32 = 10;
+pub const TAG_MSG_SEQ_NUM: u32 = 34;
+pub const TAG_MSG_TYPE: u32 = 35;
+pub const TAG_SENDER_COMP_ID: u32 = 49;
+pub const TAG_SENDING_TIME: u32 = 52;
+pub const TAG_TARGET_COMP_ID: u32 = 56;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixError {
+    Incomplete,                          // More bytes are needed for a whole message
+    Malformed { offset: usize },         // Not `tag=value<SOH>` at this byte
+    UnsupportedBeginString(String),      // BeginString other than FIX.4.4
+    BodyLength { declared: usize, actual: Option<usize> }, // BodyLength does not end where the checksum starts
+    Checksum { declared: u8, computed: u8 }, // CheckSum does not match the message
+    MissingField(u32),                   // Required tag is absent
+    DuplicateField(u32),                 // Tag repeated outside of a repeating group
+    InvalidValue { tag: u32, value: String }, // Value that does not parse or has no FIX encoding
+    GroupCount { tag: u32, declared: usize, found: usize }, // NoXXX count does not match the entries
+    UnexpectedMsgType { expected: &'static str, actual: String }, // Decoding into the wrong message type
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FixError::Incomplete => write!(f, "incomplete message"),
+            FixError::Malformed { offset } => write!(f, "malformed field at byte {}", offset),
+            FixError::UnsupportedBeginString(begin_string) => write!(f, "unsupported BeginString {:?}", begin_string),
+            FixError::BodyLength { declared, actual: Some(actual) } => write!(f, "BodyLength {} but body is {} bytes", declared, actual),
+            FixError::BodyLength { declared, actual: None } => write!(f, "BodyLength {} but no CheckSum follows", declared),
+            FixError::Checksum { declared, computed } => write!(f, "CheckSum {:03} but computed {:03}", declared, computed),
+            FixError::MissingField(tag) => write!(f, "missing tag {}", tag),
+            FixError::DuplicateField(tag) => write!(f, "duplicate tag {}", tag),
+            FixError::InvalidValue { tag, value } => write!(f, "invalid value {:?} for tag {}", value, tag),
+            FixError::GroupCount { tag, declared, found } => write!(f, "tag {} declares {} entries but {} found", tag, declared, found),
+            FixError::UnexpectedMsgType { expected, actual } => write!(f, "expected MsgType {} but got {}", expected, actual),
+        }
+    }
+}
+
+impl std::error::Error for FixError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub tag: u32,                        // Tag number
+    pub value: String,                   // Value, without the delimiter
+}
+
+impl Field {
+    pub fn new(tag: u32, value: impl ToString) -> Self {
+        Field { tag, value: value.to_string() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub sender_comp_id: String,          // SenderCompID (49)
+    pub target_comp_id: String,          // TargetCompID (56), SPOT for the Binance gateways
+    pub msg_seq_num: u64,                // MsgSeqNum (34)
+    pub sending_time: String,            // SendingTime (52), `YYYYMMDD-HH:MM:SS.sss`
+}
+
+// A message with the standard header split out; `body` keeps every other field in wire order,
+// repeating groups included
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixMessage {
+    pub msg_type: String,                // MsgType (35)
+    pub header: Header,                  // Standard header
+    pub body: Vec<Field>,                // Body fields
+}
+
+// A FIX message body with a fixed MsgType
+pub trait FixBody: Sized {
+    const MSG_TYPE: &'static str;
+    const GROUPS: &'static [GroupSpec];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError>;
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError>;
+}
+
+impl FixMessage {
+    pub fn from_body<T: FixBody>(header: Header, body: &T) -> Result<Self, FixError> {
+        let mut fields = Vec::new();
+        body.write_fields(&mut fields)?;
+        Ok(FixMessage { msg_type: T::MSG_TYPE.to_string(), header, body: fields })
+    }
+
+    pub fn to_body<T: FixBody>(&self) -> Result<T, FixError> {
+        if self.msg_type != T::MSG_TYPE {
+            return Err(FixError::UnexpectedMsgType { expected: T::MSG_TYPE, actual: self.msg_type.clone() });
+        }
+        T::read_fields(&FieldMap::new(&self.body, T::GROUPS)?)
+    }
+
+    // BodyLength and CheckSum are computed here
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        let header = [
+            (TAG_MSG_TYPE, self.msg_type.as_str()),
+            (TAG_SENDER_COMP_ID, self.header.sender_comp_id.as_str()),
+            (TAG_TARGET_COMP_ID, self.header.target_comp_id.as_str()),
+        ];
+        for (tag, value) in header {
+            push_field(&mut body, tag, value);
+        }
+        push_field(&mut body, TAG_MSG_SEQ_NUM, &self.header.msg_seq_num.to_string());
+        push_field(&mut body, TAG_SENDING_TIME, &self.header.sending_time);
+        for field in &self.body {
+            push_field(&mut body, field.tag, &field.value);
+        }
+
+        let mut message = Vec::with_capacity(body.len() + 32);
+        push_field(&mut message, TAG_BEGIN_STRING, BEGIN_STRING);
+        push_field(&mut message, TAG_BODY_LENGTH, &body.len().to_string());
+        message.extend_from_slice(&body);
+        let checksum = checksum(&message);
+        push_field(&mut message, TAG_CHECKSUM, &format!("{:03}", checksum));
+        message
+    }
+
+    // Decodes the first message in `bytes` and returns it with the number of bytes it took, so a
+    // buffer holding several messages can be drained. Incomplete means more bytes are needed
+    pub fn decode(bytes: &[u8]) -> Result<(Self, usize), FixError> {
+        let (tag, begin_string, mut position) = read_field(bytes, 0)?;
+        if tag != TAG_BEGIN_STRING {
+            return Err(FixError::MissingField(TAG_BEGIN_STRING));
+        }
+        if begin_string != BEGIN_STRING {
+            return Err(FixError::UnsupportedBeginString(begin_string.to_string()));
+        }
+        let (tag, body_length, body_start) = read_field(bytes, position)?;
+        if tag != TAG_BODY_LENGTH {
+            return Err(FixError::MissingField(TAG_BODY_LENGTH));
+        }
+        let declared: usize = parse_value(TAG_BODY_LENGTH, body_length)?;
+
+        let body_length_error = || {
+            let actual = bytes[body_start..].windows(4).position(|window| window == b"\x0110=").map(|index| index + 1);
+            FixError::BodyLength { declared, actual }
+        };
+        // A declared length no buffer can hold is an error, not a reason to wait for more bytes
+        let Some(body_end) = body_start.checked_add(declared).filter(|body_end| body_end.checked_add(7).is_some()) else {
+            return Err(body_length_error());
+        };
+        if bytes.len() < body_end + 7 {
+            return Err(FixError::Incomplete);
+        }
+        if !bytes[..body_end].ends_with(&[SOH]) || !bytes[body_end..].starts_with(b"10=") {
+            return Err(body_length_error());
+        }
+        let (_, declared_checksum, end) = read_field(bytes, body_end)?;
+        let declared_checksum = parse_value(TAG_CHECKSUM, declared_checksum)?;
+        let computed = checksum(&bytes[..body_end]);
+        if declared_checksum != computed {
+            return Err(FixError::Checksum { declared: declared_checksum, computed });
+        }
+
+        position = body_start;
+        let mut msg_type = None;
+        let (mut sender_comp_id, mut target_comp_id, mut msg_seq_num, mut sending_time) = (None, None, None, None);
+        let mut body = Vec::new();
+        while position < body_end {
+            let is_first = position == body_start;
+            let (tag, value, next) = read_field(bytes, position)?;
+            position = next;
+            let slot = match tag {
+                TAG_MSG_TYPE if is_first => &mut msg_type,
+                TAG_SENDER_COMP_ID if sender_comp_id.is_none() => &mut sender_comp_id,
+                TAG_TARGET_COMP_ID if target_comp_id.is_none() => &mut target_comp_id,
+                TAG_MSG_SEQ_NUM if msg_seq_num.is_none() => &mut msg_seq_num,
+                TAG_SENDING_TIME if sending_time.is_none() => &mut sending_time,
+                _ => {
+                    body.push(Field::new(tag, value));
+                    continue;
+                }
+            };
+            *slot = Some(value.to_string());
+        }
+        let msg_type = msg_type.ok_or(FixError::MissingField(TAG_MSG_TYPE))?;
+        let header = Header {
+            sender_comp_id: sender_comp_id.ok_or(FixError::MissingField(TAG_SENDER_COMP_ID))?,
+            target_comp_id: target_comp_id.ok_or(FixError::MissingField(TAG_TARGET_COMP_ID))?,
+            msg_seq_num: parse_value(TAG_MSG_SEQ_NUM, &msg_seq_num.ok_or(FixError::MissingField(TAG_MSG_SEQ_NUM))?)?,
+            sending_time: sending_time.ok_or(FixError::MissingField(TAG_SENDING_TIME))?,
+        };
+        Ok((FixMessage { msg_type, header, body }, end))
+    }
+}
+
+fn push_field(buffer: &mut Vec<u8>, tag: u32, value: &str) {
+    buffer.extend_from_slice(tag.to_string().as_bytes());
+    buffer.push(b'=');
+    buffer.extend_from_slice(value.as_bytes());
+    buffer.push(SOH);
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+// One `tag=value<SOH>` starting at `position`, returns the position after the delimiter
+fn read_field(bytes: &[u8], position: usize) -> Result<(u32, &str, usize), FixError> {
+    let rest = bytes.get(position..).filter(|rest| !rest.is_empty()).ok_or(FixError::Incomplete)?;
+    let end = rest.iter().position(|byte| *byte == SOH).ok_or(FixError::Incomplete)?;
+    let field = std::str::from_utf8(&rest[..end]).map_err(|_| FixError::Malformed { offset: position })?;
+    let (tag, value) = field.split_once('=').ok_or(FixError::Malformed { offset: position })?;
+    let tag = tag.parse().map_err(|_| FixError::Malformed { offset: position })?;
+    Ok((tag, value, position + end + 1))
+}
+
+fn parse_value<T: FromStr>(tag: u32, value: &str) -> Result<T, FixError> {
+    value.parse().map_err(|_| FixError::InvalidValue { tag, value: value.to_string() })
+}
+
+// Repeating group layout: the NoXXX counter tag and the member tags, the first of which opens each entry
+pub struct GroupSpec {
+    pub count_tag: u32,
+    pub members: &'static [u32],
+}
+
+// Body fields by tag, with repeating groups collected into entries. Groups are not nested in any
+// of the supported messages
+#[derive(Debug, Default)]
+pub struct FieldMap<'a> {
+    values: HashMap<u32, &'a str>,
+    groups: HashMap<u32, Vec<FieldMap<'a>>>,
+}
+
+impl<'a> FieldMap<'a> {
+    pub fn new(fields: &'a [Field], specs: &[GroupSpec]) -> Result<Self, FixError> {
+        let mut map = FieldMap::default();
+        let mut position = 0;
+        while position < fields.len() {
+            let field = &fields[position];
+            position += 1;
+            if map.values.insert(field.tag, &field.value).is_some() {
+                return Err(FixError::DuplicateField(field.tag));
+            }
+            let Some(spec) = specs.iter().find(|spec| spec.count_tag == field.tag) else {
+                continue;
+            };
+            let declared: usize = parse_value(field.tag, &field.value)?;
+            let mut entries = Vec::with_capacity(declared.min(fields.len()));
+            while entries.len() < declared && fields.get(position).is_some_and(|field| field.tag == spec.members[0]) {
+                let mut entry = FieldMap::default();
+                entry.values.insert(fields[position].tag, &fields[position].value);
+                position += 1;
+                while let Some(member) = fields.get(position).filter(|field| spec.members[1..].contains(&field.tag)) {
+                    if entry.values.insert(member.tag, &member.value).is_some() {
+                        return Err(FixError::DuplicateField(member.tag));
+                    }
+                    position += 1;
+                }
+                entries.push(entry);
+            }
+            if entries.len() != declared {
+                return Err(FixError::GroupCount { tag: field.tag, declared, found: entries.len() });
+            }
+            map.groups.insert(field.tag, entries);
+        }
+        Ok(map)
+    }
+
+    pub fn get(&self, tag: u32) -> Option<&'a str> {
+        self.values.get(&tag).copied()
+    }
+
+    pub fn required(&self, tag: u32) -> Result<&'a str, FixError> {
+        self.get(tag).ok_or(FixError::MissingField(tag))
+    }
+
+    pub fn parse<T: FromStr>(&self, tag: u32) -> Result<T, FixError> {
+        parse_value(tag, self.required(tag)?)
+    }
+
+    pub fn parse_optional<T: FromStr>(&self, tag: u32) -> Result<Option<T>, FixError> {
+        self.get(tag).map(|value| parse_value(tag, value)).transpose()
+    }
+
+    // Entries of a repeating group, empty when the counter is absent
+    pub fn group(&self, count_tag: u32) -> &[FieldMap<'a>] {
+        self.groups.get(&count_tag).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> Header {
+        Header {
+            sender_comp_id: "EXAMPLE".to_string(),
+            target_comp_id: "SPOT".to_string(),
+            msg_seq_num: 1,
+            sending_time: "20240627-11:17:25.223".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_encode_computes_length_and_checksum() {
+        let message = FixMessage { msg_type: "0".to_string(), header: header(), body: vec![] };
+        let encoded = message.encode();
+        assert_eq!(encoded, b"8=FIX.4.4\x019=54\x0135=0\x0149=EXAMPLE\x0156=SPOT\x0134=1\x0152=20240627-11:17:25.223\x0110=038\x01");
+        assert_eq!(FixMessage::decode(&encoded), Ok((message, encoded.len())));
+    }
+
+    #[test]
+    fn test_decode_framing_errors() {
+        let encoded = FixMessage { msg_type: "0".to_string(), header: header(), body: vec![Field::new(112, "ping")] }.encode();
+        for length in [0, 5, 20, encoded.len() - 1] {
+            assert_eq!(FixMessage::decode(&encoded[..length]), Err(FixError::Incomplete), "{length}");
+        }
+
+        // Two messages back to back
+        let mut buffer = encoded.clone();
+        buffer.extend_from_slice(&encoded);
+        let (_, used) = FixMessage::decode(&buffer).unwrap();
+        assert_eq!(FixMessage::decode(&buffer[used..]).unwrap().1, encoded.len());
+
+        let mut corrupted = encoded.clone();
+        let index = corrupted.len() - 10;
+        corrupted[index] = b'P';
+        assert!(matches!(FixMessage::decode(&corrupted), Err(FixError::Checksum { .. })));
+
+        let text = String::from_utf8(encoded.clone()).unwrap();
+        let wrong_length = text.replace("9=63\x01", "9=60\x01");
+        assert_eq!(FixMessage::decode(wrong_length.as_bytes()), Err(FixError::BodyLength { declared: 60, actual: Some(63) }));
+        let huge_length = text.replace("9=63\x01", &format!("9={}\x01", usize::MAX));
+        assert_eq!(FixMessage::decode(huge_length.as_bytes()), Err(FixError::BodyLength { declared: usize::MAX, actual: Some(63) }));
+        let fix42 = text.replace("FIX.4.4", "FIX.4.2");
+        assert_eq!(FixMessage::decode(fix42.as_bytes()), Err(FixError::UnsupportedBeginString("FIX.4.2".to_string())));
+    }
+
+    #[test]
+    fn test_field_map_groups() {
+        const SPECS: [GroupSpec; 1] = [GroupSpec { count_tag: 268, members: &[269, 270, 271] }];
+        let fields: Vec<Field> = [(262, "1"), (268, "2"), (269, "0"), (270, "1.5"), (271, "3"), (269, "1"), (270, "1.6"), (55, "BTCUSDT")]
+            .into_iter()
+            .map(|(tag, value)| Field::new(tag, value))
+            .collect();
+        let map = FieldMap::new(&fields, &SPECS).unwrap();
+        assert_eq!((map.get(262), map.get(55)), (Some("1"), Some("BTCUSDT")));
+        let entries = map.group(268);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].parse::<f64>(271), Ok(3.0));
+        assert_eq!(entries[1].get(271), None);
+        assert_eq!(map.group(453).len(), 0);
+
+        let mut short = fields.clone();
+        short[1].value = "3".to_string();
+        assert_eq!(FieldMap::new(&short, &SPECS).unwrap_err(), FixError::GroupCount { tag: 268, declared: 3, found: 2 });
+        let mut duplicate = fields;
+        duplicate.push(Field::new(262, "2"));
+        assert_eq!(FieldMap::new(&duplicate, &SPECS).unwrap_err(), FixError::DuplicateField(262));
+    }
+}
@@ -0,0 +1,1063 @@
+//! Binance FIX session, order entry and market data messages.
+
+use super::{Field, FieldMap, FixBody, FixError, FixMessage, GroupSpec, Header, SOH};
+use crate::orders::{OrderUpdate, Venue};
+use crate::rest::{OrderType, TimeInForce, TradeFill};
+use crate::websocket::{ExecutionType, OrderStatus, Side};
+
+const TAG_CL_ORD_ID: u32 = 11;
+const TAG_CUM_QTY: u32 = 14;
+const TAG_EXEC_ID: u32 = 17;
+const TAG_EXEC_INST: u32 = 18;
+const TAG_LAST_PX: u32 = 31;
+const TAG_LAST_QTY: u32 = 32;
+const TAG_ORDER_ID: u32 = 37;
+const TAG_ORDER_QTY: u32 = 38;
+const TAG_ORD_STATUS: u32 = 39;
+const TAG_ORD_TYPE: u32 = 40;
+const TAG_ORIG_CL_ORD_ID: u32 = 41;
+const TAG_PRICE: u32 = 44;
+const TAG_SIDE: u32 = 54;
+const TAG_SYMBOL: u32 = 55;
+const TAG_TEXT: u32 = 58;
+const TAG_TIME_IN_FORCE: u32 = 59;
+const TAG_TRANSACT_TIME: u32 = 60;
+const TAG_RAW_DATA_LENGTH: u32 = 95;
+const TAG_RAW_DATA: u32 = 96;
+const TAG_ENCRYPT_METHOD: u32 = 98;
+const TAG_HEART_BT_INT: u32 = 108;
+const TAG_NO_MISC_FEES: u32 = 136;
+const TAG_MISC_FEE_AMT: u32 = 137;
+const TAG_MISC_FEE_CURR: u32 = 138;
+const TAG_MISC_FEE_TYPE: u32 = 139;
+const TAG_RESET_SEQ_NUM_FLAG: u32 = 141;
+const TAG_NO_RELATED_SYM: u32 = 146;
+const TAG_EXEC_TYPE: u32 = 150;
+const TAG_LEAVES_QTY: u32 = 151;
+const TAG_CASH_ORDER_QTY: u32 = 152;
+const TAG_MD_REQ_ID: u32 = 262;
+const TAG_SUBSCRIPTION_REQUEST_TYPE: u32 = 263;
+const TAG_MARKET_DEPTH: u32 = 264;
+const TAG_NO_MD_ENTRY_TYPES: u32 = 267;
+const TAG_NO_MD_ENTRIES: u32 = 268;
+const TAG_MD_ENTRY_TYPE: u32 = 269;
+const TAG_MD_ENTRY_PX: u32 = 270;
+const TAG_MD_ENTRY_SIZE: u32 = 271;
+const TAG_MD_UPDATE_ACTION: u32 = 279;
+const TAG_PARTY_ID_SOURCE: u32 = 447;
+const TAG_PARTY_ID: u32 = 448;
+const TAG_PARTY_ROLE: u32 = 452;
+const TAG_NO_PARTY_IDS: u32 = 453;
+const TAG_USERNAME: u32 = 553;
+const TAG_TRADE_ID: u32 = 1003;
+const TAG_AGGRESSOR_INDICATOR: u32 = 1057;
+const TAG_TRIGGER_PRICE: u32 = 1102;
+const TAG_AGGRESSOR_SIDE: u32 = 2446;
+const TAG_DROP_COPY_FLAG: u32 = 9406;
+const TAG_ERROR_CODE: u32 = 25016;
+const TAG_CUM_QUOTE_QTY: u32 = 25017;
+const TAG_MESSAGE_HANDLING: u32 = 25035;
+const TAG_RESPONSE_MODE: u32 = 25036;
+const TAG_FIRST_BOOK_UPDATE_ID: u32 = 25043;
+const TAG_LAST_BOOK_UPDATE_ID: u32 = 25044;
+
+const PARTIES: GroupSpec = GroupSpec { count_tag: TAG_NO_PARTY_IDS, members: &[TAG_PARTY_ID, TAG_PARTY_ID_SOURCE, TAG_PARTY_ROLE] };
+const MISC_FEES: GroupSpec = GroupSpec { count_tag: TAG_NO_MISC_FEES, members: &[TAG_MISC_FEE_AMT, TAG_MISC_FEE_CURR, TAG_MISC_FEE_TYPE] };
+
+// ExecInst value for orders that must not take liquidity
+const PARTICIPATE_DONT_INITIATE: &str = "6";
+
+fn invalid(tag: u32, value: &str) -> FixError {
+    FixError::InvalidValue { tag, value: value.to_string() }
+}
+
+fn push(fields: &mut Vec<Field>, tag: u32, value: impl ToString) {
+    fields.push(Field::new(tag, value));
+}
+
+fn push_optional(fields: &mut Vec<Field>, tag: u32, value: Option<impl ToString>) {
+    if let Some(value) = value {
+        fields.push(Field::new(tag, value));
+    }
+}
+
+fn push_bool(fields: &mut Vec<Field>, tag: u32, value: bool) {
+    push(fields, tag, if value { "Y" } else { "N" });
+}
+
+fn parse_bool(fields: &FieldMap, tag: u32) -> Result<Option<bool>, FixError> {
+    match fields.get(tag) {
+        None => Ok(None),
+        Some("Y") => Ok(Some(true)),
+        Some("N") => Ok(Some(false)),
+        Some(value) => Err(invalid(tag, value)),
+    }
+}
+
+// Maps a FIX code to a value for one tag, where `codes` lists both directions
+fn from_code<T: Copy>(fields: &FieldMap, tag: u32, codes: &[(&str, T)]) -> Result<Option<T>, FixError> {
+    let Some(value) = fields.get(tag) else {
+        return Ok(None);
+    };
+    codes.iter().find(|(code, _)| *code == value).map(|(_, item)| Some(*item)).ok_or_else(|| invalid(tag, value))
+}
+
+fn required_code<T: Copy>(fields: &FieldMap, tag: u32, codes: &[(&str, T)]) -> Result<T, FixError> {
+    from_code(fields, tag, codes)?.ok_or(FixError::MissingField(tag))
+}
+
+// For tables that cover every variant
+fn code<T: PartialEq>(item: T, codes: &[(&'static str, T)]) -> &'static str {
+    codes.iter().find(|(_, code_item)| *code_item == item).map(|(code, _)| *code).unwrap()
+}
+
+fn to_code<T: PartialEq>(tag: u32, item: &T, codes: &[(&'static str, T)], name: impl Fn(&T) -> &'static str) -> Result<&'static str, FixError> {
+    codes.iter().find(|(_, code_item)| code_item == item).map(|(code, _)| *code).ok_or_else(|| invalid(tag, name(item)))
+}
+
+const SIDES: [(&str, Side); 2] = [("1", Side::Buy), ("2", Side::Sell)];
+
+const TIME_IN_FORCE: [(&str, TimeInForce); 3] = [("1", TimeInForce::Gtc), ("3", TimeInForce::Ioc), ("4", TimeInForce::Fok)];
+
+// LIMIT_MAKER shares code 2 with LIMIT and is told apart by ExecInst; take profit orders are not supported
+const ORDER_TYPES: [(&str, OrderType); 4] = [
+    ("1", OrderType::Market),
+    ("2", OrderType::Limit),
+    ("3", OrderType::StopLoss),
+    ("4", OrderType::StopLossLimit),
+];
+
+const EXECUTION_TYPES: [(&str, ExecutionType); 6] = [
+    ("0", ExecutionType::New),
+    ("4", ExecutionType::Canceled),
+    ("5", ExecutionType::Replaced),
+    ("8", ExecutionType::Rejected),
+    ("F", ExecutionType::Trade),
+    ("C", ExecutionType::Expired),
+];
+
+const ORDER_STATUSES: [(&str, OrderStatus); 7] = [
+    ("0", OrderStatus::New),
+    ("1", OrderStatus::PartiallyFilled),
+    ("2", OrderStatus::Filled),
+    ("4", OrderStatus::Canceled),
+    ("6", OrderStatus::PendingCancel),
+    ("8", OrderStatus::Rejected),
+    ("C", OrderStatus::Expired),
+];
+
+fn write_order_type(fields: &mut Vec<Field>, order_type: OrderType) -> Result<(), FixError> {
+    if order_type == OrderType::LimitMaker {
+        push(fields, TAG_ORD_TYPE, "2");
+        push(fields, TAG_EXEC_INST, PARTICIPATE_DONT_INITIATE);
+    } else {
+        push(fields, TAG_ORD_TYPE, to_code(TAG_ORD_TYPE, &order_type, &ORDER_TYPES, OrderType::as_str)?);
+    }
+    Ok(())
+}
+
+fn read_order_type(fields: &FieldMap) -> Result<Option<OrderType>, FixError> {
+    let order_type = from_code(fields, TAG_ORD_TYPE, &ORDER_TYPES)?;
+    if order_type == Some(OrderType::Limit) && fields.get(TAG_EXEC_INST) == Some(PARTICIPATE_DONT_INITIATE) {
+        return Ok(Some(OrderType::LimitMaker));
+    }
+    Ok(order_type)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Party {
+    pub id: String,                      // PartyID (448)
+    pub source: Option<String>,          // PartyIDSource (447)
+    pub role: Option<u32>,               // PartyRole (452)
+}
+
+fn write_parties(fields: &mut Vec<Field>, parties: &[Party]) {
+    if parties.is_empty() {
+        return;
+    }
+    push(fields, TAG_NO_PARTY_IDS, parties.len());
+    for party in parties {
+        push(fields, TAG_PARTY_ID, &party.id);
+        push_optional(fields, TAG_PARTY_ID_SOURCE, party.source.as_ref());
+        push_optional(fields, TAG_PARTY_ROLE, party.role);
+    }
+}
+
+fn read_parties(fields: &FieldMap) -> Result<Vec<Party>, FixError> {
+    fields
+        .group(TAG_NO_PARTY_IDS)
+        .iter()
+        .map(|entry| {
+            Ok(Party {
+                id: entry.required(TAG_PARTY_ID)?.to_string(),
+                source: entry.get(TAG_PARTY_ID_SOURCE).map(str::to_string),
+                role: entry.parse_optional(TAG_PARTY_ROLE)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageHandling {
+    Unordered,                           // Messages may be processed in parallel
+    Sequential,                          // Messages are processed in MsgSeqNum order
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseMode {
+    Everything,                          // All execution reports
+    OnlyAcks,                            // Only acknowledgements of own requests
+}
+
+const MESSAGE_HANDLING: [(&str, MessageHandling); 2] = [("1", MessageHandling::Unordered), ("2", MessageHandling::Sequential)];
+const RESPONSE_MODES: [(&str, ResponseMode); 2] = [("1", ResponseMode::Everything), ("2", ResponseMode::OnlyAcks)];
+
+// Logon <A>. Binance authenticates the session with an Ed25519 signature over `signature_payload`,
+// base64 encoded into RawData; signing is left to the caller's key handling
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Logon {
+    pub heartbeat_interval: u32,         // HeartBtInt (108), seconds
+    pub signature: String,               // RawData (96), its length goes into RawDataLength (95)
+    pub username: String,                // Username (553), the API key
+    pub reset_seq_num: bool,             // ResetSeqNumFlag (141), required to be Y by Binance
+    pub message_handling: Option<MessageHandling>, // MessageHandling (25035)
+    pub response_mode: Option<ResponseMode>, // ResponseMode (25036)
+    pub drop_copy: Option<bool>,         // DropCopyFlag (9406)
+}
+
+impl Logon {
+    // MsgType, SenderCompID, TargetCompID, MsgSeqNum and SendingTime joined by SOH
+    pub fn signature_payload(header: &Header) -> String {
+        let soh = char::from(SOH).to_string();
+        ["A", &header.sender_comp_id, &header.target_comp_id, &header.msg_seq_num.to_string(), &header.sending_time].join(&soh)
+    }
+}
+
+impl FixBody for Logon {
+    const MSG_TYPE: &'static str = "A";
+    const GROUPS: &'static [GroupSpec] = &[];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push(fields, TAG_ENCRYPT_METHOD, 0);
+        push(fields, TAG_HEART_BT_INT, self.heartbeat_interval);
+        push(fields, TAG_RAW_DATA_LENGTH, self.signature.len());
+        push(fields, TAG_RAW_DATA, &self.signature);
+        push_bool(fields, TAG_RESET_SEQ_NUM_FLAG, self.reset_seq_num);
+        push(fields, TAG_USERNAME, &self.username);
+        push_optional(fields, TAG_MESSAGE_HANDLING, self.message_handling.map(|handling| code(handling, &MESSAGE_HANDLING)));
+        push_optional(fields, TAG_RESPONSE_MODE, self.response_mode.map(|mode| code(mode, &RESPONSE_MODES)));
+        if let Some(drop_copy) = self.drop_copy {
+            push_bool(fields, TAG_DROP_COPY_FLAG, drop_copy);
+        }
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        let signature = fields.required(TAG_RAW_DATA)?;
+        if let Some(length) = fields.parse_optional::<usize>(TAG_RAW_DATA_LENGTH)? {
+            if length != signature.len() {
+                return Err(invalid(TAG_RAW_DATA_LENGTH, &length.to_string()));
+            }
+        }
+        Ok(Logon {
+            heartbeat_interval: fields.parse(TAG_HEART_BT_INT)?,
+            signature: signature.to_string(),
+            username: fields.required(TAG_USERNAME)?.to_string(),
+            reset_seq_num: parse_bool(fields, TAG_RESET_SEQ_NUM_FLAG)?.unwrap_or(false),
+            message_handling: from_code(fields, TAG_MESSAGE_HANDLING, &MESSAGE_HANDLING)?,
+            response_mode: from_code(fields, TAG_RESPONSE_MODE, &RESPONSE_MODES)?,
+            drop_copy: parse_bool(fields, TAG_DROP_COPY_FLAG)?,
+        })
+    }
+}
+
+// NewOrderSingle <D>
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewOrderSingle {
+    pub client_order_id: String,         // ClOrdID (11)
+    pub symbol: String,                  // Symbol (55)
+    pub side: Side,                      // Side (54)
+    pub order_type: OrderType,           // OrdType (40), with ExecInst (18) for LIMIT_MAKER
+    pub quantity: Option<f64>,           // OrderQty (38)
+    pub quote_quantity: Option<f64>,     // CashOrderQty (152), for market orders sized in the quote asset
+    pub price: Option<f64>,              // Price (44)
+    pub trigger_price: Option<f64>,      // TriggerPrice (1102), for stop orders
+    pub time_in_force: Option<TimeInForce>, // TimeInForce (59)
+    pub parties: Vec<Party>,             // NoPartyIDs (453) group
+}
+
+impl FixBody for NewOrderSingle {
+    const MSG_TYPE: &'static str = "D";
+    const GROUPS: &'static [GroupSpec] = &[PARTIES];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push(fields, TAG_CL_ORD_ID, &self.client_order_id);
+        push(fields, TAG_SYMBOL, &self.symbol);
+        push(fields, TAG_SIDE, to_code(TAG_SIDE, &self.side, &SIDES, Side::as_str)?);
+        write_order_type(fields, self.order_type)?;
+        push_optional(fields, TAG_ORDER_QTY, self.quantity);
+        push_optional(fields, TAG_CASH_ORDER_QTY, self.quote_quantity);
+        push_optional(fields, TAG_PRICE, self.price);
+        push_optional(fields, TAG_TRIGGER_PRICE, self.trigger_price);
+        if let Some(time_in_force) = self.time_in_force {
+            push(fields, TAG_TIME_IN_FORCE, to_code(TAG_TIME_IN_FORCE, &time_in_force, &TIME_IN_FORCE, TimeInForce::as_str)?);
+        }
+        write_parties(fields, &self.parties);
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        Ok(NewOrderSingle {
+            client_order_id: fields.required(TAG_CL_ORD_ID)?.to_string(),
+            symbol: fields.required(TAG_SYMBOL)?.to_string(),
+            side: required_code(fields, TAG_SIDE, &SIDES)?,
+            order_type: read_order_type(fields)?.ok_or(FixError::MissingField(TAG_ORD_TYPE))?,
+            quantity: fields.parse_optional(TAG_ORDER_QTY)?,
+            quote_quantity: fields.parse_optional(TAG_CASH_ORDER_QTY)?,
+            price: fields.parse_optional(TAG_PRICE)?,
+            trigger_price: fields.parse_optional(TAG_TRIGGER_PRICE)?,
+            time_in_force: from_code(fields, TAG_TIME_IN_FORCE, &TIME_IN_FORCE)?,
+            parties: read_parties(fields)?,
+        })
+    }
+}
+
+// OrderCancelRequest <F>, identifies the order by OrigClOrdID or OrderID
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderCancelRequest {
+    pub client_order_id: String,         // ClOrdID (11) of the cancel request itself
+    pub symbol: String,                  // Symbol (55)
+    pub original_client_order_id: Option<String>, // OrigClOrdID (41)
+    pub order_id: Option<u64>,           // OrderID (37)
+}
+
+impl FixBody for OrderCancelRequest {
+    const MSG_TYPE: &'static str = "F";
+    const GROUPS: &'static [GroupSpec] = &[];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push(fields, TAG_CL_ORD_ID, &self.client_order_id);
+        push_optional(fields, TAG_ORIG_CL_ORD_ID, self.original_client_order_id.as_ref());
+        push_optional(fields, TAG_ORDER_ID, self.order_id);
+        push(fields, TAG_SYMBOL, &self.symbol);
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        Ok(OrderCancelRequest {
+            client_order_id: fields.required(TAG_CL_ORD_ID)?.to_string(),
+            symbol: fields.required(TAG_SYMBOL)?.to_string(),
+            original_client_order_id: fields.get(TAG_ORIG_CL_ORD_ID).map(str::to_string),
+            order_id: fields.parse_optional(TAG_ORDER_ID)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MiscFee {
+    pub amount: f64,                     // MiscFeeAmt (137)
+    pub currency: String,                // MiscFeeCurr (138)
+    pub fee_type: Option<u32>,           // MiscFeeType (139), 4 for exchange fees
+}
+
+// ExecutionReport <8>. Times are epoch milliseconds, finer digits of the UTCTimestamp are dropped
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionReport {
+    pub order_id: Option<u64>,           // OrderID (37), absent on rejects of unknown orders
+    pub client_order_id: String,         // ClOrdID (11)
+    pub original_client_order_id: Option<String>, // OrigClOrdID (41), the order's own ID on cancels
+    pub exec_id: Option<String>,         // ExecID (17)
+    pub execution_type: ExecutionType,   // ExecType (150)
+    pub order_status: OrderStatus,       // OrdStatus (39)
+    pub symbol: String,                  // Symbol (55)
+    pub side: Side,                      // Side (54)
+    pub order_type: Option<OrderType>,   // OrdType (40)
+    pub time_in_force: Option<TimeInForce>, // TimeInForce (59)
+    pub order_quantity: Option<f64>,     // OrderQty (38)
+    pub price: Option<f64>,              // Price (44)
+    pub cumulative_quantity: f64,        // CumQty (14)
+    pub leaves_quantity: Option<f64>,    // LeavesQty (151)
+    pub cumulative_quote_quantity: Option<f64>, // CumQuoteQty (25017)
+    pub last_price: Option<f64>,         // LastPx (31)
+    pub last_quantity: Option<f64>,      // LastQty (32)
+    pub trade_id: Option<i64>,           // TradeID (1003)
+    pub is_aggressor: Option<bool>,      // AggressorIndicator (1057)
+    pub transact_time: u64,              // TransactTime (60)
+    pub error_code: Option<i64>,         // ErrorCode (25016), on rejects
+    pub text: Option<String>,            // Text (58), on rejects
+    pub misc_fees: Vec<MiscFee>,         // NoMiscFees (136) group, the commission of the fill
+    pub parties: Vec<Party>,             // NoPartyIDs (453) group
+}
+
+impl FixBody for ExecutionReport {
+    const MSG_TYPE: &'static str = "8";
+    const GROUPS: &'static [GroupSpec] = &[MISC_FEES, PARTIES];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push_optional(fields, TAG_ORDER_ID, self.order_id);
+        push(fields, TAG_CL_ORD_ID, &self.client_order_id);
+        push_optional(fields, TAG_ORIG_CL_ORD_ID, self.original_client_order_id.as_ref());
+        push_optional(fields, TAG_EXEC_ID, self.exec_id.as_ref());
+        push(fields, TAG_EXEC_TYPE, to_code(TAG_EXEC_TYPE, &self.execution_type, &EXECUTION_TYPES, ExecutionType::as_str)?);
+        push(fields, TAG_ORD_STATUS, to_code(TAG_ORD_STATUS, &self.order_status, &ORDER_STATUSES, OrderStatus::as_str)?);
+        push(fields, TAG_SYMBOL, &self.symbol);
+        push(fields, TAG_SIDE, to_code(TAG_SIDE, &self.side, &SIDES, Side::as_str)?);
+        if let Some(order_type) = self.order_type {
+            write_order_type(fields, order_type)?;
+        }
+        if let Some(time_in_force) = self.time_in_force {
+            push(fields, TAG_TIME_IN_FORCE, to_code(TAG_TIME_IN_FORCE, &time_in_force, &TIME_IN_FORCE, TimeInForce::as_str)?);
+        }
+        push_optional(fields, TAG_ORDER_QTY, self.order_quantity);
+        push_optional(fields, TAG_PRICE, self.price);
+        push(fields, TAG_CUM_QTY, self.cumulative_quantity);
+        push_optional(fields, TAG_LEAVES_QTY, self.leaves_quantity);
+        push_optional(fields, TAG_CUM_QUOTE_QTY, self.cumulative_quote_quantity);
+        push_optional(fields, TAG_LAST_PX, self.last_price);
+        push_optional(fields, TAG_LAST_QTY, self.last_quantity);
+        push_optional(fields, TAG_TRADE_ID, self.trade_id);
+        if let Some(is_aggressor) = self.is_aggressor {
+            push_bool(fields, TAG_AGGRESSOR_INDICATOR, is_aggressor);
+        }
+        push(fields, TAG_TRANSACT_TIME, format_utc_timestamp(self.transact_time));
+        push_optional(fields, TAG_ERROR_CODE, self.error_code);
+        push_optional(fields, TAG_TEXT, self.text.as_ref());
+        if !self.misc_fees.is_empty() {
+            push(fields, TAG_NO_MISC_FEES, self.misc_fees.len());
+            for fee in &self.misc_fees {
+                push(fields, TAG_MISC_FEE_AMT, fee.amount);
+                push(fields, TAG_MISC_FEE_CURR, &fee.currency);
+                push_optional(fields, TAG_MISC_FEE_TYPE, fee.fee_type);
+            }
+        }
+        write_parties(fields, &self.parties);
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        let transact_time = fields.required(TAG_TRANSACT_TIME)?;
+        let misc_fees = fields
+            .group(TAG_NO_MISC_FEES)
+            .iter()
+            .map(|entry| {
+                Ok(MiscFee {
+                    amount: entry.parse(TAG_MISC_FEE_AMT)?,
+                    currency: entry.required(TAG_MISC_FEE_CURR)?.to_string(),
+                    fee_type: entry.parse_optional(TAG_MISC_FEE_TYPE)?,
+                })
+            })
+            .collect::<Result<_, FixError>>()?;
+        Ok(ExecutionReport {
+            order_id: fields.parse_optional(TAG_ORDER_ID)?,
+            client_order_id: fields.required(TAG_CL_ORD_ID)?.to_string(),
+            original_client_order_id: fields.get(TAG_ORIG_CL_ORD_ID).map(str::to_string),
+            exec_id: fields.get(TAG_EXEC_ID).map(str::to_string),
+            execution_type: from_code(fields, TAG_EXEC_TYPE, &EXECUTION_TYPES)?.unwrap_or(ExecutionType::Unknown),
+            order_status: from_code(fields, TAG_ORD_STATUS, &ORDER_STATUSES)?.unwrap_or(OrderStatus::Unknown),
+            symbol: fields.required(TAG_SYMBOL)?.to_string(),
+            side: required_code(fields, TAG_SIDE, &SIDES)?,
+            order_type: read_order_type(fields)?,
+            time_in_force: from_code(fields, TAG_TIME_IN_FORCE, &TIME_IN_FORCE)?,
+            order_quantity: fields.parse_optional(TAG_ORDER_QTY)?,
+            price: fields.parse_optional(TAG_PRICE)?,
+            cumulative_quantity: fields.parse_optional(TAG_CUM_QTY)?.unwrap_or(0.0),
+            leaves_quantity: fields.parse_optional(TAG_LEAVES_QTY)?,
+            cumulative_quote_quantity: fields.parse_optional(TAG_CUM_QUOTE_QTY)?,
+            last_price: fields.parse_optional(TAG_LAST_PX)?,
+            last_quantity: fields.parse_optional(TAG_LAST_QTY)?,
+            trade_id: fields.parse_optional(TAG_TRADE_ID)?,
+            is_aggressor: parse_bool(fields, TAG_AGGRESSOR_INDICATOR)?,
+            transact_time: parse_utc_timestamp(transact_time).ok_or_else(|| invalid(TAG_TRANSACT_TIME, transact_time))?,
+            error_code: fields.parse_optional(TAG_ERROR_CODE)?,
+            text: fields.get(TAG_TEXT).map(str::to_string),
+            misc_fees,
+            parties: read_parties(fields)?,
+        })
+    }
+}
+
+// TransactTime is both the event and the transaction time; the first misc fee is the commission. A fill
+// needs LastPx, LastQty and TradeID
+impl From<ExecutionReport> for OrderUpdate {
+    fn from(report: ExecutionReport) -> Self {
+        let fee = report.misc_fees.into_iter().next();
+        let trade_id = report.trade_id.and_then(|trade_id| u64::try_from(trade_id).ok());
+        let last_fill = match (report.last_price, report.last_quantity, trade_id) {
+            (Some(price), Some(quantity), Some(trade_id)) if quantity > 0.0 => Some(TradeFill {
+                symbol: report.symbol.clone(),
+                trade_id,
+                order_id: report.order_id.unwrap_or(0),
+                side: report.side,
+                price,
+                quantity,
+                quote_quantity: price * quantity,
+                commission: fee.as_ref().map_or(0.0, |fee| fee.amount),
+                commission_asset: fee.map(|fee| fee.currency),
+                time: report.transact_time,
+                is_maker: report.is_aggressor == Some(false),
+            }),
+            _ => None,
+        };
+        let average_price = report
+            .cumulative_quote_quantity
+            .filter(|_| report.cumulative_quantity > 0.0)
+            .map(|quote_quantity| quote_quantity / report.cumulative_quantity);
+        OrderUpdate {
+            venue: Venue::Spot,
+            symbol: report.symbol,
+            order_id: report.order_id.unwrap_or(0),
+            client_order_id: report.original_client_order_id.unwrap_or(report.client_order_id),
+            side: report.side,
+            order_type: report.order_type.map_or("", |order_type| order_type.as_str()).to_string(),
+            execution_type: report.execution_type,
+            status: report.order_status,
+            quantity: report.order_quantity.unwrap_or(0.0),
+            price: report.price.unwrap_or(0.0),
+            last_fill,
+            cumulative_quantity: report.cumulative_quantity,
+            average_price,
+            event_time: report.transact_time,
+            transaction_time: report.transact_time,
+        }
+    }
+}
+
+// For replaying unified updates to FIX clients. Fails on order types and statuses without a FIX code
+impl TryFrom<&OrderUpdate> for ExecutionReport {
+    type Error = FixError;
+
+    fn try_from(update: &OrderUpdate) -> Result<Self, Self::Error> {
+        let order_type = [OrderType::Limit, OrderType::Market, OrderType::StopLoss, OrderType::StopLossLimit, OrderType::LimitMaker]
+            .into_iter()
+            .find(|order_type| order_type.as_str() == update.order_type)
+            .ok_or_else(|| invalid(TAG_ORD_TYPE, &update.order_type))?;
+        to_code(TAG_EXEC_TYPE, &update.execution_type, &EXECUTION_TYPES, ExecutionType::as_str)?;
+        to_code(TAG_ORD_STATUS, &update.status, &ORDER_STATUSES, OrderStatus::as_str)?;
+        let fill = update.last_fill.as_ref();
+        Ok(ExecutionReport {
+            order_id: Some(update.order_id),
+            client_order_id: update.client_order_id.clone(),
+            original_client_order_id: None,
+            exec_id: None,
+            execution_type: update.execution_type,
+            order_status: update.status,
+            symbol: update.symbol.clone(),
+            side: update.side,
+            order_type: Some(order_type),
+            time_in_force: None,
+            order_quantity: Some(update.quantity),
+            price: (update.price > 0.0).then_some(update.price),
+            cumulative_quantity: update.cumulative_quantity,
+            leaves_quantity: Some(update.remaining_quantity()),
+            cumulative_quote_quantity: update.average_price.map(|price| price * update.cumulative_quantity),
+            last_price: fill.map(|fill| fill.price),
+            last_quantity: fill.map(|fill| fill.quantity),
+            trade_id: fill.and_then(|fill| i64::try_from(fill.trade_id).ok()),
+            is_aggressor: fill.map(|fill| !fill.is_maker),
+            transact_time: update.transaction_time,
+            error_code: None,
+            text: None,
+            misc_fees: fill
+                .and_then(|fill| Some(MiscFee { amount: fill.commission, currency: fill.commission_asset.clone()?, fee_type: Some(4) }))
+                .into_iter()
+                .collect(),
+            parties: vec![],
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionRequestType {
+    Subscribe,
+    Unsubscribe,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MdEntryType {
+    Bid,
+    Offer,
+    Trade,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MdUpdateAction {
+    New,
+    Change,
+    Delete,
+}
+
+const SUBSCRIPTION_REQUEST_TYPES: [(&str, SubscriptionRequestType); 2] =
+    [("1", SubscriptionRequestType::Subscribe), ("2", SubscriptionRequestType::Unsubscribe)];
+const MD_ENTRY_TYPES: [(&str, MdEntryType); 3] = [("0", MdEntryType::Bid), ("1", MdEntryType::Offer), ("2", MdEntryType::Trade)];
+const MD_UPDATE_ACTIONS: [(&str, MdUpdateAction); 3] =
+    [("0", MdUpdateAction::New), ("1", MdUpdateAction::Change), ("2", MdUpdateAction::Delete)];
+
+// MarketDataRequest <V>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarketDataRequest {
+    pub md_req_id: String,               // MDReqID (262)
+    pub subscription_request_type: SubscriptionRequestType, // SubscriptionRequestType (263)
+    pub market_depth: Option<u32>,       // MarketDepth (264), 1 for the best bid and offer only
+    pub entry_types: Vec<MdEntryType>,   // NoMDEntryTypes (267) group
+    pub symbols: Vec<String>,            // NoRelatedSym (146) group
+}
+
+impl FixBody for MarketDataRequest {
+    const MSG_TYPE: &'static str = "V";
+    const GROUPS: &'static [GroupSpec] = &[
+        GroupSpec { count_tag: TAG_NO_RELATED_SYM, members: &[TAG_SYMBOL] },
+        GroupSpec { count_tag: TAG_NO_MD_ENTRY_TYPES, members: &[TAG_MD_ENTRY_TYPE] },
+    ];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push(fields, TAG_MD_REQ_ID, &self.md_req_id);
+        push(fields, TAG_SUBSCRIPTION_REQUEST_TYPE, code(self.subscription_request_type, &SUBSCRIPTION_REQUEST_TYPES));
+        push_optional(fields, TAG_MARKET_DEPTH, self.market_depth);
+        push(fields, TAG_NO_RELATED_SYM, self.symbols.len());
+        for symbol in &self.symbols {
+            push(fields, TAG_SYMBOL, symbol);
+        }
+        push(fields, TAG_NO_MD_ENTRY_TYPES, self.entry_types.len());
+        for entry_type in &self.entry_types {
+            push(fields, TAG_MD_ENTRY_TYPE, code(*entry_type, &MD_ENTRY_TYPES));
+        }
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        Ok(MarketDataRequest {
+            md_req_id: fields.required(TAG_MD_REQ_ID)?.to_string(),
+            subscription_request_type: required_code(fields, TAG_SUBSCRIPTION_REQUEST_TYPE, &SUBSCRIPTION_REQUEST_TYPES)?,
+            market_depth: fields.parse_optional(TAG_MARKET_DEPTH)?,
+            entry_types: fields
+                .group(TAG_NO_MD_ENTRY_TYPES)
+                .iter()
+                .map(|entry| required_code(entry, TAG_MD_ENTRY_TYPE, &MD_ENTRY_TYPES))
+                .collect::<Result<_, _>>()?,
+            symbols: fields
+                .group(TAG_NO_RELATED_SYM)
+                .iter()
+                .map(|entry| entry.required(TAG_SYMBOL).map(str::to_string))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdEntry {
+    pub entry_type: MdEntryType,         // MDEntryType (269)
+    pub price: f64,                      // MDEntryPx (270)
+    pub size: f64,                       // MDEntrySize (271)
+}
+
+// MarketDataSnapshot <W>
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketDataSnapshot {
+    pub md_req_id: Option<String>,       // MDReqID (262)
+    pub symbol: String,                  // Symbol (55)
+    pub last_book_update_id: Option<u64>, // LastBookUpdateID (25044)
+    pub entries: Vec<MdEntry>,           // NoMDEntries (268) group
+}
+
+impl FixBody for MarketDataSnapshot {
+    const MSG_TYPE: &'static str = "W";
+    const GROUPS: &'static [GroupSpec] =
+        &[GroupSpec { count_tag: TAG_NO_MD_ENTRIES, members: &[TAG_MD_ENTRY_TYPE, TAG_MD_ENTRY_PX, TAG_MD_ENTRY_SIZE] }];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push_optional(fields, TAG_MD_REQ_ID, self.md_req_id.as_ref());
+        push(fields, TAG_SYMBOL, &self.symbol);
+        push_optional(fields, TAG_LAST_BOOK_UPDATE_ID, self.last_book_update_id);
+        push(fields, TAG_NO_MD_ENTRIES, self.entries.len());
+        for entry in &self.entries {
+            push(fields, TAG_MD_ENTRY_TYPE, code(entry.entry_type, &MD_ENTRY_TYPES));
+            push(fields, TAG_MD_ENTRY_PX, entry.price);
+            push(fields, TAG_MD_ENTRY_SIZE, entry.size);
+        }
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        Ok(MarketDataSnapshot {
+            md_req_id: fields.get(TAG_MD_REQ_ID).map(str::to_string),
+            symbol: fields.required(TAG_SYMBOL)?.to_string(),
+            last_book_update_id: fields.parse_optional(TAG_LAST_BOOK_UPDATE_ID)?,
+            entries: fields
+                .group(TAG_NO_MD_ENTRIES)
+                .iter()
+                .map(|entry| {
+                    Ok(MdEntry {
+                        entry_type: required_code(entry, TAG_MD_ENTRY_TYPE, &MD_ENTRY_TYPES)?,
+                        price: entry.parse(TAG_MD_ENTRY_PX)?,
+                        size: entry.parse(TAG_MD_ENTRY_SIZE)?,
+                    })
+                })
+                .collect::<Result<_, FixError>>()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MdIncrementalEntry {
+    pub update_action: MdUpdateAction,   // MDUpdateAction (279)
+    pub entry_type: MdEntryType,         // MDEntryType (269)
+    pub symbol: Option<String>,          // Symbol (55), None repeats the previous entry's symbol
+    pub price: Option<f64>,              // MDEntryPx (270)
+    pub size: Option<f64>,               // MDEntrySize (271), absent on deletes
+    pub first_book_update_id: Option<u64>, // FirstBookUpdateID (25043)
+    pub last_book_update_id: Option<u64>, // LastBookUpdateID (25044)
+    pub trade_id: Option<u64>,           // TradeID (1003), on trades
+    pub aggressor_side: Option<Side>,    // AggressorSide (2446), on trades
+}
+
+// MarketDataIncrementalRefresh <X>
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketDataIncrementalRefresh {
+    pub md_req_id: Option<String>,       // MDReqID (262)
+    pub entries: Vec<MdIncrementalEntry>, // NoMDEntries (268) group
+}
+
+impl FixBody for MarketDataIncrementalRefresh {
+    const MSG_TYPE: &'static str = "X";
+    const GROUPS: &'static [GroupSpec] = &[GroupSpec {
+        count_tag: TAG_NO_MD_ENTRIES,
+        members: &[
+            TAG_MD_UPDATE_ACTION,
+            TAG_MD_ENTRY_TYPE,
+            TAG_SYMBOL,
+            TAG_MD_ENTRY_PX,
+            TAG_MD_ENTRY_SIZE,
+            TAG_FIRST_BOOK_UPDATE_ID,
+            TAG_LAST_BOOK_UPDATE_ID,
+            TAG_TRADE_ID,
+            TAG_AGGRESSOR_SIDE,
+        ],
+    }];
+
+    fn write_fields(&self, fields: &mut Vec<Field>) -> Result<(), FixError> {
+        push_optional(fields, TAG_MD_REQ_ID, self.md_req_id.as_ref());
+        push(fields, TAG_NO_MD_ENTRIES, self.entries.len());
+        for entry in &self.entries {
+            push(fields, TAG_MD_UPDATE_ACTION, code(entry.update_action, &MD_UPDATE_ACTIONS));
+            push(fields, TAG_MD_ENTRY_TYPE, code(entry.entry_type, &MD_ENTRY_TYPES));
+            push_optional(fields, TAG_SYMBOL, entry.symbol.as_ref());
+            push_optional(fields, TAG_MD_ENTRY_PX, entry.price);
+            push_optional(fields, TAG_MD_ENTRY_SIZE, entry.size);
+            push_optional(fields, TAG_FIRST_BOOK_UPDATE_ID, entry.first_book_update_id);
+            push_optional(fields, TAG_LAST_BOOK_UPDATE_ID, entry.last_book_update_id);
+            push_optional(fields, TAG_TRADE_ID, entry.trade_id);
+            if let Some(side) = entry.aggressor_side {
+                push(fields, TAG_AGGRESSOR_SIDE, to_code(TAG_AGGRESSOR_SIDE, &side, &SIDES, Side::as_str)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_fields(fields: &FieldMap) -> Result<Self, FixError> {
+        Ok(MarketDataIncrementalRefresh {
+            md_req_id: fields.get(TAG_MD_REQ_ID).map(str::to_string),
+            entries: fields
+                .group(TAG_NO_MD_ENTRIES)
+                .iter()
+                .map(|entry| {
+                    Ok(MdIncrementalEntry {
+                        update_action: required_code(entry, TAG_MD_UPDATE_ACTION, &MD_UPDATE_ACTIONS)?,
+                        entry_type: required_code(entry, TAG_MD_ENTRY_TYPE, &MD_ENTRY_TYPES)?,
+                        symbol: entry.get(TAG_SYMBOL).map(str::to_string),
+                        price: entry.parse_optional(TAG_MD_ENTRY_PX)?,
+                        size: entry.parse_optional(TAG_MD_ENTRY_SIZE)?,
+                        first_book_update_id: entry.parse_optional(TAG_FIRST_BOOK_UPDATE_ID)?,
+                        last_book_update_id: entry.parse_optional(TAG_LAST_BOOK_UPDATE_ID)?,
+                        trade_id: entry.parse_optional(TAG_TRADE_ID)?,
+                        aggressor_side: from_code(entry, TAG_AGGRESSOR_SIDE, &SIDES)?,
+                    })
+                })
+                .collect::<Result<_, FixError>>()?,
+        })
+    }
+}
+
+// Application messages by MsgType, anything else (heartbeats, rejects, ...) is kept as it is
+#[derive(Clone, Debug, PartialEq)]
+pub enum BinanceFixMessage {
+    Logon(Logon),
+    NewOrderSingle(NewOrderSingle),
+    ExecutionReport(ExecutionReport),
+    OrderCancelRequest(OrderCancelRequest),
+    MarketDataRequest(MarketDataRequest),
+    MarketDataSnapshot(MarketDataSnapshot),
+    MarketDataIncrementalRefresh(MarketDataIncrementalRefresh),
+    Other(FixMessage),
+}
+
+impl BinanceFixMessage {
+    pub fn from_message(message: FixMessage) -> Result<Self, FixError> {
+        let typed = match message.msg_type.as_str() {
+            Logon::MSG_TYPE => BinanceFixMessage::Logon(message.to_body()?),
+            NewOrderSingle::MSG_TYPE => BinanceFixMessage::NewOrderSingle(message.to_body()?),
+            ExecutionReport::MSG_TYPE => BinanceFixMessage::ExecutionReport(message.to_body()?),
+            OrderCancelRequest::MSG_TYPE => BinanceFixMessage::OrderCancelRequest(message.to_body()?),
+            MarketDataRequest::MSG_TYPE => BinanceFixMessage::MarketDataRequest(message.to_body()?),
+            MarketDataSnapshot::MSG_TYPE => BinanceFixMessage::MarketDataSnapshot(message.to_body()?),
+            MarketDataIncrementalRefresh::MSG_TYPE => BinanceFixMessage::MarketDataIncrementalRefresh(message.to_body()?),
+            _ => BinanceFixMessage::Other(message),
+        };
+        Ok(typed)
+    }
+}
+
+// `YYYYMMDD-HH:MM:SS[.fraction]` in UTC to epoch milliseconds
+pub fn parse_utc_timestamp(value: &str) -> Option<u64> {
+    let (date, time) = value.split_once('-')?;
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    if date.len() != 8 || time.len() != 8 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let number = |digits: &str| {
+        if digits.bytes().all(|byte| byte.is_ascii_digit()) { digits.parse::<i64>().ok() } else { None }
+    };
+    let (year, month, day) = (number(&date[..4])?, number(&date[4..6])?, number(&date[6..])?);
+    let mut parts = time.split(':');
+    let (hour, minute, second) = (number(parts.next()?)?, number(parts.next()?)?, number(parts.next()?)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let millis = fraction.get(..3).map_or_else(|| format!("{:0<3}", fraction), str::to_string).parse::<i64>().ok()?;
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    u64::try_from(seconds * 1_000 + millis).ok()
+}
+
+pub fn format_utc_timestamp(millis: u64) -> String {
+    let seconds = (millis / 1_000) as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+    format!("{:04}{:02}{:02}-{:02}:{:02}:{:02}.{:03}", year, month, day, time / 3_600, time % 3_600 / 60, time % 60, millis % 1_000)
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGON: &[u8] = include_bytes!("../../fixtures/fix/logon.fix");
+    const EXECUTION_REPORT: &[u8] = include_bytes!("../../fixtures/fix/execution_report.fix");
+    const MARKET_DATA_SNAPSHOT: &[u8] = include_bytes!("../../fixtures/fix/market_data_snapshot.fix");
+    const MARKET_DATA_INCREMENTAL: &[u8] = include_bytes!("../../fixtures/fix/market_data_incremental.fix");
+
+    fn header(msg_seq_num: u64) -> Header {
+        Header {
+            sender_comp_id: "EXAMPLE".to_string(),
+            target_comp_id: "SPOT".to_string(),
+            msg_seq_num,
+            sending_time: "20240627-11:17:25.223".to_string(),
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> BinanceFixMessage {
+        let (message, used) = FixMessage::decode(bytes).unwrap();
+        assert_eq!(used, bytes.len());
+        BinanceFixMessage::from_message(message).unwrap()
+    }
+
+    fn round_trip<T: FixBody + PartialEq + std::fmt::Debug>(body: T) {
+        let encoded = FixMessage::from_body(header(7), &body).unwrap().encode();
+        let (message, _) = FixMessage::decode(&encoded).unwrap();
+        assert_eq!(message.header, header(7));
+        assert_eq!(message.to_body::<T>().unwrap(), body);
+    }
+
+    #[test]
+    fn test_utc_timestamps() {
+        assert_eq!(parse_utc_timestamp("20240627-11:17:25.223"), Some(1719487045223));
+        assert_eq!(parse_utc_timestamp("20240627-11:17:25.223456"), Some(1719487045223));
+        assert_eq!(parse_utc_timestamp("20240627-11:17:25"), Some(1719487045000));
+        assert_eq!(parse_utc_timestamp("19700101-00:00:00.5"), Some(500));
+        assert_eq!(parse_utc_timestamp("20240627 11:17:25"), None);
+        assert_eq!(parse_utc_timestamp("20241327-11:17:25"), None);
+        assert_eq!(format_utc_timestamp(1719487045223), "20240627-11:17:25.223");
+        assert_eq!(format_utc_timestamp(951782400000), "20000229-00:00:00.000");
+    }
+
+    #[test]
+    fn test_decode_logon_fixture() {
+        let BinanceFixMessage::Logon(logon) = decode(LOGON) else {
+            panic!("expected logon");
+        };
+        assert_eq!(logon, Logon {
+            heartbeat_interval: 30,
+            signature: "4MHXelVVcpkdwuLbl6n73HQUXUf1dse2PCgT1DYqW9w8AVZ1RACFGM+5UdlGPrQHrgtS3CvsRURC1oj73j8gCA==".to_string(),
+            username: "sBRXrJx2DsOraMXOaUovEhgVRcjOvCtQwnWj8VxkOh1xqboS02SPGfKi2h8spZJb".to_string(),
+            reset_seq_num: true,
+            message_handling: Some(MessageHandling::Sequential),
+            response_mode: Some(ResponseMode::Everything),
+            drop_copy: None,
+        });
+        assert_eq!(Logon::signature_payload(&header(1)), "A\x01EXAMPLE\x01SPOT\x011\x0120240627-11:17:25.223");
+        // Field order is preserved, so re-encoding gives back the captured bytes
+        assert_eq!(FixMessage::from_body(header(1), &logon).unwrap().encode(), LOGON);
+    }
+
+    #[test]
+    fn test_decode_execution_report_fixture() {
+        let BinanceFixMessage::ExecutionReport(report) = decode(EXECUTION_REPORT) else {
+            panic!("expected execution report");
+        };
+        assert_eq!((report.execution_type, report.order_status), (ExecutionType::Trade, OrderStatus::PartiallyFilled));
+        assert_eq!((report.order_type, report.time_in_force), (Some(OrderType::Limit), Some(TimeInForce::Gtc)));
+        assert_eq!(report.transact_time, 1719487045223);
+        assert_eq!(report.misc_fees, vec![MiscFee { amount: 0.00001, currency: "BNB".to_string(), fee_type: Some(4) }]);
+        assert_eq!(report.parties, vec![
+            Party { id: "alice".to_string(), source: Some("D".to_string()), role: Some(3) },
+            Party { id: "desk-1".to_string(), source: Some("D".to_string()), role: Some(24) },
+        ]);
+        // TradeID follows the fee group and is not swallowed by it
+        assert_eq!(report.trade_id, Some(77));
+
+        let update = OrderUpdate::from(report.clone());
+        assert_eq!((update.venue, update.symbol.as_str(), update.order_id, update.client_order_id.as_str()), (Venue::Spot, "ETHBTC", 4293153, "myOrder"));
+        assert_eq!(update.order_type, "LIMIT");
+        assert_eq!(update.last_fill, Some(TradeFill {
+            symbol: "ETHBTC".to_string(),
+            trade_id: 77,
+            order_id: 4293153,
+            side: Side::Buy,
+            price: 0.1026,
+            quantity: 0.5,
+            quote_quantity: 0.1026 * 0.5,
+            commission: 0.00001,
+            commission_asset: Some("BNB".to_string()),
+            time: 1719487045223,
+            is_maker: true,
+        }));
+        assert_eq!((update.quantity, update.cumulative_quantity, update.average_price), (2.0, 1.0, Some(0.10262)));
+        assert_eq!(update.transaction_time, 1719487045223);
+
+        let replayed = ExecutionReport::try_from(&update).unwrap();
+        assert_eq!(OrderUpdate::from(replayed.clone()), update);
+        round_trip(replayed);
+        round_trip(report);
+
+        let mut unsupported = update;
+        unsupported.order_type = "TAKE_PROFIT".to_string();
+        assert_eq!(ExecutionReport::try_from(&unsupported), Err(FixError::InvalidValue { tag: 40, value: "TAKE_PROFIT".to_string() }));
+    }
+
+    #[test]
+    fn test_decode_market_data_fixtures() {
+        let BinanceFixMessage::MarketDataSnapshot(snapshot) = decode(MARKET_DATA_SNAPSHOT) else {
+            panic!("expected snapshot");
+        };
+        assert_eq!((snapshot.md_req_id.as_deref(), snapshot.symbol.as_str(), snapshot.last_book_update_id), (Some("BOOK_1"), "BNBBTC", Some(160)));
+        assert_eq!(snapshot.entries, vec![
+            MdEntry { entry_type: MdEntryType::Bid, price: 0.0024, size: 10.0 },
+            MdEntry { entry_type: MdEntryType::Bid, price: 0.0023, size: 0.5 },
+            MdEntry { entry_type: MdEntryType::Offer, price: 0.0026, size: 100.0 },
+        ]);
+        round_trip(snapshot);
+
+        let BinanceFixMessage::MarketDataIncrementalRefresh(refresh) = decode(MARKET_DATA_INCREMENTAL) else {
+            panic!("expected incremental refresh");
+        };
+        assert_eq!(refresh.entries.len(), 2);
+        assert_eq!(refresh.entries[0].symbol.as_deref(), Some("BTCUSDT"));
+        assert_eq!(refresh.entries[1], MdIncrementalEntry {
+            update_action: MdUpdateAction::New,
+            entry_type: MdEntryType::Trade,
+            symbol: None,
+            price: Some(65432.11),
+            size: Some(0.01),
+            first_book_update_id: None,
+            last_book_update_id: None,
+            trade_id: Some(400000002),
+            aggressor_side: Some(Side::Sell),
+        });
+        round_trip(refresh);
+    }
+
+    #[test]
+    fn test_order_entry_round_trips() {
+        round_trip(NewOrderSingle {
+            client_order_id: "order-1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::LimitMaker,
+            quantity: Some(0.001),
+            quote_quantity: None,
+            price: Some(65000.5),
+            trigger_price: None,
+            time_in_force: None,
+            parties: vec![Party { id: "desk-1".to_string(), source: None, role: Some(24) }],
+        });
+        round_trip(NewOrderSingle {
+            client_order_id: "order-2".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Market,
+            quantity: None,
+            quote_quantity: Some(100.0),
+            price: None,
+            trigger_price: None,
+            time_in_force: None,
+            parties: vec![],
+        });
+        round_trip(OrderCancelRequest {
+            client_order_id: "cancel-1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            original_client_order_id: Some("order-1".to_string()),
+            order_id: None,
+        });
+        round_trip(MarketDataRequest {
+            md_req_id: "BOOK_1".to_string(),
+            subscription_request_type: SubscriptionRequestType::Subscribe,
+            market_depth: Some(10),
+            entry_types: vec![MdEntryType::Bid, MdEntryType::Offer],
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+        });
+
+        let message = FixMessage::from_body(header(1), &OrderCancelRequest {
+            client_order_id: "cancel-1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            original_client_order_id: None,
+            order_id: Some(12),
+        })
+        .unwrap();
+        assert_eq!(
+            message.to_body::<NewOrderSingle>(),
+            Err(FixError::UnexpectedMsgType { expected: "D", actual: "F".to_string() }),
+        );
+        let take_profit = NewOrderSingle {
+            client_order_id: "order-3".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::TakeProfit,
+            quantity: Some(1.0),
+            quote_quantity: None,
+            price: None,
+            trigger_price: Some(70000.0),
+            time_in_force: None,
+            parties: vec![],
+        };
+        assert_eq!(FixMessage::from_body(header(2), &take_profit), Err(FixError::InvalidValue { tag: 40, value: "TAKE_PROFIT".to_string() }));
+    }
+}
@@ -0,0 +1,203 @@
+//! Decimal formatting of prices and quantities for wire serialization and query strings.
+
+use std::cell::Cell;
+use std::fmt::{self, Write};
+
+use serde::{Serialize, Serializer};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PriceFormat {
+    #[default]
+    Shortest,                            // Shortest round-trip digits, e.g. "4532.56"
+    Fixed(u8),                           // Exactly N decimals, e.g. "4532.56000000" for 8
+    Trimmed(u8),                         // Rounded to N decimals, trailing zeros removed
+}
+
+impl PriceFormat {
+    // Binance's wire format for prices and quantities
+    pub const WIRE: PriceFormat = PriceFormat::Fixed(8);
+
+    // Fixed decimals matching a tick or step size from exchange info, e.g. 0.01000000 => Fixed(2)
+    pub fn from_step(step: f64) -> Self {
+        let mut decimals = 0;
+        let mut scaled = step;
+        while decimals < 16 && (scaled - scaled.round()).abs() > 1e-9 * scaled.abs().max(1.0) {
+            scaled *= 10.0;
+            decimals += 1;
+        }
+        PriceFormat::Fixed(decimals)
+    }
+
+    // Format used by `Serialize` impls on this thread, see `with_price_format`
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+
+    // Lazily formatted value, implements `Display` and serializes as a string
+    pub fn display(self, value: f64) -> FormattedPrice {
+        FormattedPrice { format: self, value }
+    }
+
+    pub fn write_to<W: Write>(self, out: &mut W, value: f64) -> fmt::Result {
+        match self {
+            PriceFormat::Shortest => write!(out, "{}", value),
+            PriceFormat::Fixed(decimals) => write!(out, "{:.*}", decimals as usize, value),
+            PriceFormat::Trimmed(decimals) => {
+                let mut trim = TrimZeros { out, in_fraction: false, pending_point: false, pending_zeros: 0 };
+                write!(trim, "{:.*}", decimals as usize, value)
+            }
+        }
+    }
+
+    // Appends to an existing buffer, e.g. a query string being built
+    pub fn push_to(self, out: &mut String, value: f64) {
+        // Writing into a String never fails
+        let _ = self.write_to(out, value);
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<PriceFormat> = const { Cell::new(PriceFormat::Shortest) };
+}
+
+// Runs `f` with `format` used by price serialization on this thread, restoring the previous format
+// afterwards, also on panic
+pub fn with_price_format<T>(format: PriceFormat, f: impl FnOnce() -> T) -> T {
+    struct Restore(PriceFormat);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(format)));
+    f()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FormattedPrice {
+    format: PriceFormat,
+    value: f64,
+}
+
+impl fmt::Display for FormattedPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format.write_to(f, self.value)
+    }
+}
+
+// `collect_str` lets serde_json write the digits straight into its output
+impl Serialize for FormattedPrice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Holds back zeros after the decimal point until a non-zero digit follows, so trimming needs no buffer
+struct TrimZeros<'a, W: Write> {
+    out: &'a mut W,
+    in_fraction: bool,
+    pending_point: bool,
+    pending_zeros: usize,
+}
+
+impl<W: Write> Write for TrimZeros<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        if !self.in_fraction {
+            match rest.find('.') {
+                Some(point) => {
+                    self.out.write_str(&rest[..point])?;
+                    self.in_fraction = true;
+                    self.pending_point = true;
+                    rest = &rest[point + 1..];
+                }
+                None => return self.out.write_str(rest),
+            }
+        }
+        while !rest.is_empty() {
+            let zeros = rest.len() - rest.trim_start_matches('0').len();
+            self.pending_zeros += zeros;
+            rest = &rest[zeros..];
+            let digits = rest.find('0').unwrap_or(rest.len());
+            if digits == 0 {
+                continue;
+            }
+            if self.pending_point {
+                self.out.write_char('.')?;
+                self.pending_point = false;
+            }
+            for _ in 0..self.pending_zeros {
+                self.out.write_char('0')?;
+            }
+            self.pending_zeros = 0;
+            self.out.write_str(&rest[..digits])?;
+            rest = &rest[digits..];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(format: PriceFormat, value: f64) -> String {
+        format.display(value).to_string()
+    }
+
+    #[test]
+    fn test_fixed_decimals() {
+        assert_eq!(format(PriceFormat::WIRE, 4532.56), "4532.56000000");
+        assert_eq!(format(PriceFormat::WIRE, 0.00000001), "0.00000001");
+        assert_eq!(format(PriceFormat::WIRE, 123456.0), "123456.00000000");
+        assert_eq!(format(PriceFormat::Fixed(2), 123456.0), "123456.00");
+        assert_eq!(format(PriceFormat::Fixed(2), 0.00000001), "0.00");
+        assert_eq!(format(PriceFormat::Fixed(0), 123456.0), "123456");
+        assert_eq!(format(PriceFormat::Fixed(4), 1.23456), "1.2346");
+    }
+
+    #[test]
+    fn test_trimmed_decimals() {
+        assert_eq!(format(PriceFormat::Trimmed(8), 4532.56), "4532.56");
+        assert_eq!(format(PriceFormat::Trimmed(8), 0.00000001), "0.00000001");
+        assert_eq!(format(PriceFormat::Trimmed(8), 123456.0), "123456");
+        assert_eq!(format(PriceFormat::Trimmed(8), 100.10203), "100.10203");
+        assert_eq!(format(PriceFormat::Trimmed(2), 0.00000001), "0");
+        assert_eq!(format(PriceFormat::Trimmed(3), 1.0005), "1");
+        assert_eq!(format(PriceFormat::Trimmed(0), 123456.0), "123456");
+    }
+
+    #[test]
+    fn test_shortest_matches_display() {
+        assert_eq!(format(PriceFormat::Shortest, 4532.56), "4532.56");
+        assert_eq!(format(PriceFormat::Shortest, 0.00000001), "0.00000001");
+        assert_eq!(format(PriceFormat::Shortest, 123456.0), "123456");
+    }
+
+    #[test]
+    fn test_from_step() {
+        assert_eq!(PriceFormat::from_step(0.01), PriceFormat::Fixed(2));
+        assert_eq!(PriceFormat::from_step(0.00001), PriceFormat::Fixed(5));
+        assert_eq!(PriceFormat::from_step(0.00000001), PriceFormat::Fixed(8));
+        assert_eq!(PriceFormat::from_step(0.5), PriceFormat::Fixed(1));
+        assert_eq!(PriceFormat::from_step(1.0), PriceFormat::Fixed(0));
+        assert_eq!(PriceFormat::from_step(10.0), PriceFormat::Fixed(0));
+    }
+
+    #[test]
+    fn test_with_price_format_restores_previous() {
+        assert_eq!(PriceFormat::current(), PriceFormat::Shortest);
+        let json = with_price_format(PriceFormat::WIRE, || {
+            assert_eq!(with_price_format(PriceFormat::Fixed(2), PriceFormat::current), PriceFormat::Fixed(2));
+            serde_json::to_string(&PriceFormat::current().display(0.5)).unwrap()
+        });
+        assert_eq!(json, r#""0.50000000""#);
+        assert_eq!(PriceFormat::current(), PriceFormat::Shortest);
+
+        let mut query = String::from("price=");
+        PriceFormat::Trimmed(8).push_to(&mut query, 25.3519);
+        assert_eq!(query, "price=25.3519");
+    }
+}
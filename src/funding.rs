@@ -0,0 +1,178 @@
+//! Expected futures funding payments accrued from mark price updates.
+
+use std::collections::HashMap;
+
+use crate::websocket::futures::MarkPriceUpdate;
+
+pub const DEFAULT_FUNDING_INTERVAL_MS: u64 = 8 * 60 * 60 * 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FundingPayment {
+    pub symbol: String,                  // Symbol
+    pub funding_time: u64,               // Funding boundary the payment belongs to
+    pub funding_rate: f64,               // Last funding rate seen before the boundary
+    pub mark_price: f64,                 // Last mark price seen before the boundary
+    pub position: f64,                   // Signed position size, negative when short
+    pub amount: f64,                     // Received when positive, paid when negative
+}
+
+#[derive(Clone, Debug, Default)]
+struct SymbolFunding {
+    position: f64,
+    accrued: f64,
+    funding_rate: Option<f64>,
+    mark_price: f64,
+    next_funding_time: Option<u64>,
+    interval_ms: Option<u64>,
+}
+
+// Longs pay shorts when the funding rate is positive and receive when it is negative: a position of
+// `size` pays `size * mark price * rate` at each boundary. A boundary is detected when `T` moves on,
+// and settled with the rate and mark price of the last update before it
+#[derive(Clone, Debug, Default)]
+pub struct FundingTracker {
+    symbols: HashMap<String, SymbolFunding>,
+}
+
+impl FundingTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Signed position size, positive for long and negative for short
+    pub fn set_position(&mut self, symbol: &str, size: f64) {
+        self.symbols.entry(symbol.to_string()).or_default().position = size;
+    }
+
+    pub fn position(&self, symbol: &str) -> f64 {
+        self.symbols.get(symbol).map_or(0.0, |funding| funding.position)
+    }
+
+    // The payment settled by this update, if it is the first one past a funding boundary
+    pub fn ingest(&mut self, update: &MarkPriceUpdate) -> Option<FundingPayment> {
+        let funding = self.symbols.entry(update.symbol.clone()).or_default();
+        let mut payment = None;
+        if let Some(previous_time) = funding.next_funding_time {
+            if update.next_funding_time > previous_time {
+                funding.interval_ms = Some(update.next_funding_time - previous_time);
+                if let Some(funding_rate) = funding.funding_rate {
+                    let amount = -funding.position * funding.mark_price * funding_rate;
+                    funding.accrued += amount;
+                    payment = Some(FundingPayment {
+                        symbol: update.symbol.clone(),
+                        funding_time: previous_time,
+                        funding_rate,
+                        mark_price: funding.mark_price,
+                        position: funding.position,
+                        amount,
+                    });
+                }
+            } else if update.next_funding_time < previous_time {
+                // Out of order update from before the last boundary
+                return None;
+            }
+        }
+        funding.next_funding_time = Some(update.next_funding_time);
+        funding.mark_price = update.mark_price;
+        if update.funding_rate.is_some() {
+            funding.funding_rate = update.funding_rate;
+        }
+        payment
+    }
+
+    // Sum of the payments settled so far
+    pub fn accrued(&self, symbol: &str) -> f64 {
+        self.symbols.get(symbol).map_or(0.0, |funding| funding.accrued)
+    }
+
+    pub fn total_accrued(&self) -> f64 {
+        self.symbols.values().map(|funding| funding.accrued).sum()
+    }
+
+    // Estimate for the upcoming boundary from the latest rate and mark price
+    pub fn next_payment(&self, symbol: &str) -> Option<FundingPayment> {
+        let funding = self.symbols.get(symbol)?;
+        let funding_rate = funding.funding_rate?;
+        Some(FundingPayment {
+            symbol: symbol.to_string(),
+            funding_time: funding.next_funding_time?,
+            funding_rate,
+            mark_price: funding.mark_price,
+            position: funding.position,
+            amount: -funding.position * funding.mark_price * funding_rate,
+        })
+    }
+
+    // Inferred from consecutive `T` values, 8h until two of them have been seen
+    pub fn interval_ms(&self, symbol: &str) -> u64 {
+        self.symbols.get(symbol).and_then(|funding| funding.interval_ms).unwrap_or(DEFAULT_FUNDING_INTERVAL_MS)
+    }
+
+    pub fn reset(&mut self, symbol: &str) {
+        self.symbols.remove(symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 60 * 60 * 1000;
+
+    fn mark_price(symbol: &str, event_time: u64, mark_price: f64, funding_rate: f64, next_funding_time: u64) -> MarkPriceUpdate {
+        MarkPriceUpdate {
+            event_type: "markPriceUpdate".to_string(),
+            event_time,
+            symbol: symbol.to_string(),
+            mark_price,
+            index_price: mark_price,
+            estimated_settle_price: None,
+            funding_rate: Some(funding_rate),
+            next_funding_time,
+        }
+    }
+
+    #[test]
+    fn test_funding_tracker_two_boundaries_long_and_short() {
+        let mut tracker = FundingTracker::new();
+        tracker.set_position("BTCUSDT", 2.0);
+        tracker.set_position("ETHUSDT", -10.0);
+        assert_eq!(tracker.interval_ms("BTCUSDT"), DEFAULT_FUNDING_INTERVAL_MS);
+
+        // Before the first boundary at 8h: BTC rate positive, ETH rate negative
+        assert_eq!(tracker.ingest(&mark_price("BTCUSDT", 7 * HOUR, 30_000.0, 0.0001, 8 * HOUR)), None);
+        assert_eq!(tracker.ingest(&mark_price("ETHUSDT", 7 * HOUR, 2_000.0, -0.0002, 8 * HOUR)), None);
+        let estimate = tracker.next_payment("BTCUSDT").unwrap();
+        assert_eq!((estimate.funding_time, estimate.amount), (8 * HOUR, -6.0));
+
+        // Boundary crossed: the long pays 2 * 30000 * 0.0001, the short pays 10 * 2000 * 0.0002
+        let btc = tracker.ingest(&mark_price("BTCUSDT", 8 * HOUR + 1, 30_100.0, 0.0003, 16 * HOUR)).unwrap();
+        assert_eq!((btc.funding_time, btc.funding_rate, btc.mark_price, btc.amount), (8 * HOUR, 0.0001, 30_000.0, -6.0));
+        let eth = tracker.ingest(&mark_price("ETHUSDT", 8 * HOUR + 1, 2_010.0, 0.0001, 16 * HOUR)).unwrap();
+        assert!((eth.amount - -4.0).abs() < 1e-9);
+        assert_eq!(tracker.interval_ms("BTCUSDT"), 8 * HOUR);
+
+        // Second boundary: BTC 2 * 31000 * 0.0003 paid, ETH short receives 10 * 1900 * 0.0001
+        assert_eq!(tracker.ingest(&mark_price("BTCUSDT", 15 * HOUR, 31_000.0, 0.0003, 16 * HOUR)), None);
+        assert_eq!(tracker.ingest(&mark_price("ETHUSDT", 15 * HOUR, 1_900.0, 0.0001, 16 * HOUR)), None);
+        tracker.ingest(&mark_price("BTCUSDT", 16 * HOUR, 31_000.0, 0.0001, 24 * HOUR)).unwrap();
+        tracker.ingest(&mark_price("ETHUSDT", 16 * HOUR, 1_900.0, 0.0001, 24 * HOUR)).unwrap();
+
+        assert!((tracker.accrued("BTCUSDT") - (-6.0 - 18.6)).abs() < 1e-9);
+        assert!((tracker.accrued("ETHUSDT") - (-4.0 + 1.9)).abs() < 1e-9);
+        assert!((tracker.total_accrued() - (-24.6 - 2.1)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_funding_tracker_infers_symbol_interval() {
+        let mut tracker = FundingTracker::new();
+        tracker.set_position("XYZUSDT", 1.0);
+        tracker.ingest(&mark_price("XYZUSDT", 3 * HOUR, 10.0, 0.001, 4 * HOUR));
+        // An update from before the last boundary is ignored
+        assert_eq!(tracker.ingest(&mark_price("XYZUSDT", 0, 10.0, 0.001, 0)), None);
+        let payment = tracker.ingest(&mark_price("XYZUSDT", 4 * HOUR, 10.0, 0.001, 8 * HOUR)).unwrap();
+        assert!((payment.amount - -0.01).abs() < 1e-12);
+        assert_eq!(tracker.interval_ms("XYZUSDT"), 4 * HOUR);
+        assert_eq!(tracker.next_payment("XYZUSDT").unwrap().funding_time, 8 * HOUR);
+    }
+}
@@ -0,0 +1,331 @@
+//! Paths and CSV row parsers for the data.binance.vision kline, trade and aggregate trade dumps.
+
+use std::fmt;
+
+use crate::metrics::TimeUnit;
+use crate::orders::Venue;
+use crate::rest::KlineRow;
+use crate::websocket::{AggTrade, KlineInterval, Trade};
+
+pub const BASE_URL: &str = "https://data.binance.vision";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dataset {
+    Klines(KlineInterval),
+    Trades,
+    AggTrades,
+}
+
+// Daily files are published the day after, monthly ones early in the following month
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    Daily { year: u16, month: u8, day: u8 },
+    Monthly { year: u16, month: u8 },
+}
+
+impl Period {
+    fn directory(&self) -> &'static str {
+        match self {
+            Period::Daily { .. } => "daily",
+            Period::Monthly { .. } => "monthly",
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Period::Daily { year, month, day } => write!(f, "{:04}-{:02}-{:02}", year, month, day),
+            Period::Monthly { year, month } => write!(f, "{:04}-{:02}", year, month),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoricalFile {
+    pub venue: Venue,                    // Spot or USDⓈ-M futures
+    pub dataset: Dataset,                // Klines of an interval, trades or aggregate trades
+    pub symbol: String,                  // Symbol, uppercased in the path
+    pub period: Period,                  // Day or month covered by the file
+}
+
+impl HistoricalFile {
+    pub fn new(venue: Venue, dataset: Dataset, symbol: &str, period: Period) -> Self {
+        HistoricalFile { venue, dataset, symbol: symbol.to_ascii_uppercase(), period }
+    }
+
+    // e.g. `spot/daily/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01-15.zip`
+    pub fn path(&self) -> String {
+        let market = match self.venue {
+            Venue::Spot => "spot",
+            Venue::UsdMFutures => "futures/um",
+        };
+        let period = self.period.directory();
+        match self.dataset {
+            Dataset::Klines(interval) => {
+                format!("{}/{}/klines/{}/{}/{}", market, period, self.symbol, interval, self.file_name())
+            }
+            Dataset::Trades => format!("{}/{}/trades/{}/{}", market, period, self.symbol, self.file_name()),
+            Dataset::AggTrades => format!("{}/{}/aggTrades/{}/{}", market, period, self.symbol, self.file_name()),
+        }
+    }
+
+    // e.g. `BTCUSDT-aggTrades-2024-01.zip`, the archive holds the same name ending in `.csv`
+    pub fn file_name(&self) -> String {
+        let kind = match self.dataset {
+            Dataset::Klines(interval) => interval.as_str(),
+            Dataset::Trades => "trades",
+            Dataset::AggTrades => "aggTrades",
+        };
+        format!("{}-{}-{}.zip", self.symbol, kind, self.period)
+    }
+
+    pub fn url(&self) -> String {
+        format!("{}/data/{}", BASE_URL, self.path())
+    }
+
+    // SHA-256 of the zip, as `<hex>  <file name>`
+    pub fn checksum_url(&self) -> String {
+        format!("{}.CHECKSUM", self.url())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvError {
+    ColumnCount { line: usize, expected: usize, found: usize }, // Row with the wrong number of columns
+    InvalidField { line: usize, column: &'static str, value: String }, // Value that does not parse
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::ColumnCount { line, expected, found } => write!(f, "line {}: expected {} columns, found {}", line, expected, found),
+            CsvError::InvalidField { line, column, value } => write!(f, "line {}: invalid {} {:?}", line, column, value),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+struct Row<'a> {
+    line: usize,
+    columns: Vec<&'a str>,
+}
+
+impl<'a> Row<'a> {
+    fn new(line: usize, text: &'a str, expected: &[usize]) -> Result<Self, CsvError> {
+        let columns: Vec<&str> = text.trim_end_matches(['\r', '\n']).split(',').collect();
+        if !expected.contains(&columns.len()) {
+            return Err(CsvError::ColumnCount { line, expected: expected[0], found: columns.len() });
+        }
+        Ok(Row { line, columns })
+    }
+
+    fn invalid(&self, index: usize, column: &'static str) -> CsvError {
+        CsvError::InvalidField { line: self.line, column, value: self.columns[index].to_string() }
+    }
+
+    fn u64(&self, index: usize, column: &'static str) -> Result<u64, CsvError> {
+        self.columns[index].parse().map_err(|_| self.invalid(index, column))
+    }
+
+    fn f64(&self, index: usize, column: &'static str) -> Result<f64, CsvError> {
+        self.columns[index].parse().map_err(|_| self.invalid(index, column))
+    }
+
+    // Older dumps write Python-style `True`/`False`
+    fn bool(&self, index: usize, column: &'static str) -> Result<bool, CsvError> {
+        match self.columns[index] {
+            "True" | "true" => Ok(true),
+            "False" | "false" => Ok(false),
+            _ => Err(self.invalid(index, column)),
+        }
+    }
+
+    // Spot dumps switched from milliseconds to microseconds on 2025-01-01, the unit is detected per value
+    fn timestamp(&self, index: usize, column: &'static str) -> Result<u64, CsvError> {
+        let timestamp = self.u64(index, column)?;
+        Ok(TimeUnit::detect(timestamp).to_millis(timestamp))
+    }
+}
+
+// Futures dumps start with a header row, spot dumps do not
+fn is_header(line: &str) -> bool {
+    !line.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn parse_lines<T>(csv: &str, parse: impl Fn(usize, &str) -> Result<T, CsvError>) -> Result<Vec<T>, CsvError> {
+    csv.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !is_header(line))
+        .map(|(index, line)| parse(index + 1, line))
+        .collect()
+}
+
+fn kline_row(line: usize, text: &str) -> Result<KlineRow, CsvError> {
+    let row = Row::new(line, text, &[12])?;
+    Ok(KlineRow {
+        open_time: row.timestamp(0, "open time")?,
+        open_price: row.f64(1, "open price")?,
+        high_price: row.f64(2, "high price")?,
+        low_price: row.f64(3, "low price")?,
+        close_price: row.f64(4, "close price")?,
+        volume: row.f64(5, "volume")?,
+        close_time: row.timestamp(6, "close time")?,
+        quote_asset_volume: row.f64(7, "quote asset volume")?,
+        number_of_trades: row.u64(8, "number of trades")?,
+        taker_buy_base_asset_volume: row.f64(9, "taker buy base asset volume")?,
+        taker_buy_quote_asset_volume: row.f64(10, "taker buy quote asset volume")?,
+        ignore: row.columns[11].to_string(),
+    })
+}
+
+// id, price, qty, quote_qty, time, is_buyer_maker[, is_best_match]; futures dumps have no best match column
+fn trade(symbol: &str, line: usize, text: &str) -> Result<Trade, CsvError> {
+    let row = Row::new(line, text, &[7, 6])?;
+    Ok(Trade {
+        event_type: None,
+        event_time: None,
+        symbol: symbol.to_string(),
+        trade_id: row.u64(0, "trade id")?,
+        price: row.f64(1, "price")?,
+        quantity: row.f64(2, "quantity")?,
+        trade_time: row.timestamp(4, "time")?,
+        is_buyer_market_maker: row.bool(5, "is buyer maker")?,
+        ignore: if row.columns.len() == 7 { row.bool(6, "is best match")? } else { true },
+    })
+}
+
+// agg_trade_id, price, quantity, first_trade_id, last_trade_id, transact_time, is_buyer_maker[, is_best_match]
+fn agg_trade(symbol: &str, line: usize, text: &str) -> Result<AggTrade, CsvError> {
+    let row = Row::new(line, text, &[8, 7])?;
+    let trade_time = row.timestamp(5, "transact time")?;
+    Ok(AggTrade {
+        event_type: "aggTrade".to_string(),
+        event_time: trade_time,
+        symbol: symbol.to_string(),
+        aggregate_trade_id: row.u64(0, "aggregate trade id")?,
+        price: row.f64(1, "price")?,
+        quantity: row.f64(2, "quantity")?,
+        first_trade_id: row.u64(3, "first trade id")?,
+        last_trade_id: row.u64(4, "last trade id")?,
+        trade_time,
+        is_buyer_market_maker: row.bool(6, "is buyer maker")?,
+        ignore: if row.columns.len() == 8 { row.bool(7, "is best match")? } else { true },
+    })
+}
+
+// Timestamps are returned in milliseconds whatever the vintage of the file
+pub fn parse_kline_line(line: &str) -> Result<KlineRow, CsvError> {
+    kline_row(1, line)
+}
+
+pub fn parse_klines_csv(csv: &str) -> Result<Vec<KlineRow>, CsvError> {
+    parse_lines(csv, kline_row)
+}
+
+// The dumps carry no symbol, so it has to be supplied from the file name
+pub fn parse_trade_line(symbol: &str, line: &str) -> Result<Trade, CsvError> {
+    trade(symbol, 1, line)
+}
+
+pub fn parse_trades_csv(symbol: &str, csv: &str) -> Result<Vec<Trade>, CsvError> {
+    parse_lines(csv, |line, text| trade(symbol, line, text))
+}
+
+// The trade time stands in for the event time, as for REST aggregate trades
+pub fn parse_agg_trade_line(symbol: &str, line: &str) -> Result<AggTrade, CsvError> {
+    agg_trade(symbol, 1, line)
+}
+
+pub fn parse_agg_trades_csv(symbol: &str, csv: &str) -> Result<Vec<AggTrade>, CsvError> {
+    parse_lines(csv, |line, text| agg_trade(symbol, line, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_historical_file_paths() {
+        let file = HistoricalFile::new(Venue::Spot, Dataset::Klines(KlineInterval::M1), "btcusdt", Period::Daily { year: 2024, month: 1, day: 15 });
+        assert_eq!(file.path(), "spot/daily/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01-15.zip");
+        assert_eq!(file.url(), "https://data.binance.vision/data/spot/daily/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01-15.zip");
+        assert_eq!(file.checksum_url(), "https://data.binance.vision/data/spot/daily/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01-15.zip.CHECKSUM");
+
+        let file = HistoricalFile::new(Venue::UsdMFutures, Dataset::AggTrades, "ETHUSDT", Period::Monthly { year: 2023, month: 9 });
+        assert_eq!(file.path(), "futures/um/monthly/aggTrades/ETHUSDT/ETHUSDT-aggTrades-2023-09.zip");
+        let file = HistoricalFile::new(Venue::Spot, Dataset::Trades, "BNBBTC", Period::Daily { year: 2025, month: 3, day: 2 });
+        assert_eq!(file.file_name(), "BNBBTC-trades-2025-03-02.zip");
+    }
+
+    #[test]
+    fn test_parse_klines_both_vintages() {
+        let millis = "1705276800000,42499.99000000,42500.00000000,42460.01000000,42475.52000000,20.55105000,1705276859999,873089.99659030,1018,9.63165000,409175.46377080,0";
+        let micros = "1735689600000000,93576.00000000,93610.93000000,93537.50000000,93610.93000000,8.21827000,1735689659999999,768978.11443600,3042,4.58277000,428845.19759270,0";
+        let row = parse_kline_line(millis).unwrap();
+        assert_eq!((row.open_time, row.close_time, row.number_of_trades), (1705276800000, 1705276859999, 1018));
+        assert_eq!((row.open_price, row.close_price, row.taker_buy_quote_asset_volume), (42499.99, 42475.52, 409175.4637708));
+        let row = parse_kline_line(micros).unwrap();
+        assert_eq!((row.open_time, row.close_time), (1735689600000, 1735689659999));
+
+        // Futures files have a header row
+        let csv = format!("open_time,open,high,low,close,volume,close_time,quote_volume,count,taker_buy_volume,taker_buy_quote_volume,ignore\n{}\n{}\n", millis, micros);
+        assert_eq!(parse_klines_csv(&csv).unwrap().len(), 2);
+        assert_eq!(
+            parse_klines_csv(&format!("{}\n1705276860000,x", millis)),
+            Err(CsvError::ColumnCount { line: 2, expected: 12, found: 2 }),
+        );
+    }
+
+    #[test]
+    fn test_parse_trades_both_vintages() {
+        let trade = parse_trade_line("BTCUSDT", "3365678044,42499.99000000,0.00045000,19.12499550,1705276800011,True,True").unwrap();
+        assert_eq!(trade, Trade {
+            event_type: None,
+            event_time: None,
+            symbol: "BTCUSDT".to_string(),
+            trade_id: 3365678044,
+            price: 42499.99,
+            quantity: 0.00045,
+            trade_time: 1705276800011,
+            is_buyer_market_maker: true,
+            ignore: true,
+        });
+        let trade = parse_trade_line("BTCUSDT", "4370655371,93576.00000000,0.00009000,8.42184000,1735689600010542,false,true").unwrap();
+        assert_eq!((trade.trade_time, trade.is_buyer_market_maker), (1735689600010, false));
+        // Futures, without the best match column and with a header
+        let trades = parse_trades_csv("BTCUSDT", "id,price,qty,quote_qty,time,is_buyer_maker\r\n4555771140,42480.10,0.004,169.92040,1705276800053,false\r\n").unwrap();
+        assert_eq!(trades[0].quantity, 0.004);
+
+        assert_eq!(
+            parse_trade_line("BTCUSDT", "3365678044,42499.99000000,0.00045000,19.12499550,1705276800011,Yes,True"),
+            Err(CsvError::InvalidField { line: 1, column: "is buyer maker", value: "Yes".to_string() }),
+        );
+    }
+
+    #[test]
+    fn test_parse_agg_trades_both_vintages() {
+        let millis = "2996714227,42499.99000000,0.00239000,3365678044,3365678045,1705276800011,True,True";
+        let micros = "3381581794,93576.00000000,0.00018000,4370655371,4370655372,1735689600010542,False,True";
+        let trades = parse_agg_trades_csv("BTCUSDT", &format!("{}\n\n{}\n", millis, micros)).unwrap();
+        assert_eq!(trades[0], AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: 1705276800011,
+            symbol: "BTCUSDT".to_string(),
+            aggregate_trade_id: 2996714227,
+            price: 42499.99,
+            quantity: 0.00239,
+            first_trade_id: 3365678044,
+            last_trade_id: 3365678045,
+            trade_time: 1705276800011,
+            is_buyer_market_maker: true,
+            ignore: true,
+        });
+        assert_eq!((trades[1].trade_time, trades[1].is_buyer_market_maker), (1735689600010, false));
+        assert_eq!(
+            parse_agg_trades_csv("BTCUSDT", &format!("{}\n3381581794,abc,1,1,1,1,True,True", millis)),
+            Err(CsvError::InvalidField { line: 2, column: "price", value: "abc".to_string() }),
+        );
+    }
+}
@@ -1 +1,58 @@
-pub mod websocket;
\ No newline at end of file
+// Gate every item on a market feature, for the per-market impls in modules shared by all markets
+macro_rules! cfg_spot {
+    ($($item:item)*) => { $(#[cfg(feature = "spot")] $item)* };
+}
+
+macro_rules! cfg_futures {
+    ($($item:item)*) => { $(#[cfg(feature = "futures")] $item)* };
+}
+
+macro_rules! cfg_options {
+    ($($item:item)*) => { $(#[cfg(feature = "options")] $item)* };
+}
+
+pub mod batch;
+pub mod bbo;
+#[cfg(feature = "spot")]
+pub mod candles;
+pub mod error;
+#[cfg(feature = "fix")]
+pub mod fix;
+pub mod format;
+#[cfg(feature = "futures")]
+pub mod funding;
+#[cfg(feature = "spot")]
+pub mod historical;
+pub mod limits;
+pub mod metrics;
+pub mod orderbook;
+pub mod orders;
+#[cfg(feature = "futures")]
+pub mod positions;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod reorder;
+pub mod rest;
+#[cfg(test)]
+mod roundtrip;
+#[cfg(feature = "schemars")]
+pub mod schema;
+#[cfg(feature = "sbe")]
+pub mod sbe;
+#[cfg(feature = "spot")]
+pub mod sequence;
+#[cfg(feature = "spot")]
+pub mod stats;
+#[cfg(feature = "async")]
+pub mod stream;
+pub mod symbol;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testutil;
+pub mod trades;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod websocket;
+pub mod ws_api;
+
+#[cfg(feature = "smallvec")]
+pub use smallvec::SmallVec;
@@ -0,0 +1,230 @@
+//! Bounds on input size, array lengths and string lengths, checked before a message is parsed.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{parse_with, CodecError};
+
+// Nesting levels tracked by the scan, serde_json already rejects input nested deeper than 128
+const MAX_TRACKED_NESTING: usize = 128;
+
+// Limits applied by the `*_limited` entry points. The defaults accept every payload Binance sends,
+// including 5000 level REST depth snapshots and full exchange info, while bounding what a corrupted or
+// malicious frame can make the parser allocate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+    pub max_input_bytes: usize,          // Whole message, 32 MiB by default
+    pub max_array_elements: usize,       // Elements of any one array, e.g. depth levels, balances or fills, 100_000 by default
+    pub max_string_bytes: usize,         // Any one string including keys, escapes counted as sent, 64 KiB by default
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits { max_input_bytes: 32 << 20, max_array_elements: 100_000, max_string_bytes: 64 << 10 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    InputBytes,
+    ArrayElements,
+    StringBytes,
+}
+
+impl Limit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Limit::InputBytes => "max_input_bytes",
+            Limit::ArrayElements => "max_array_elements",
+            Limit::StringBytes => "max_string_bytes",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitExceeded {
+    pub limit: Limit,                    // Limit that tripped
+    pub max: usize,                      // Its configured value
+    pub offset: usize,                   // Byte offset where it tripped, 0 for the input size
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} of {} exceeded at byte {}", self.limit.as_str(), self.max, self.offset)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+#[derive(Debug)]
+pub enum LimitedParseError {
+    LimitExceeded(LimitExceeded),        // Rejected before parsing
+    Codec(CodecError),                   // Within the limits but failed to parse
+}
+
+impl fmt::Display for LimitedParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitedParseError::LimitExceeded(error) => write!(f, "message rejected: {}", error),
+            LimitedParseError::Codec(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LimitedParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LimitedParseError::LimitExceeded(error) => Some(error),
+            LimitedParseError::Codec(error) => Some(error),
+        }
+    }
+}
+
+impl From<LimitExceeded> for LimitedParseError {
+    fn from(error: LimitExceeded) -> Self {
+        LimitedParseError::LimitExceeded(error)
+    }
+}
+
+impl From<CodecError> for LimitedParseError {
+    fn from(error: CodecError) -> Self {
+        LimitedParseError::Codec(error)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Nesting {
+    array: bool,
+    elements: usize,
+    expect_element: bool,
+}
+
+impl ParseLimits {
+    // One pass over the bytes without allocating. Malformed input is not rejected here, only measured,
+    // the parser reports it afterwards
+    pub fn check(&self, json: &[u8]) -> Result<(), LimitExceeded> {
+        if json.len() > self.max_input_bytes {
+            return Err(LimitExceeded { limit: Limit::InputBytes, max: self.max_input_bytes, offset: 0 });
+        }
+        let mut stack = [Nesting::default(); MAX_TRACKED_NESTING];
+        let mut depth = 0usize;
+        let mut position = 0;
+        while let Some(&byte) = json.get(position) {
+            if byte.is_ascii_whitespace() {
+                position += 1;
+                continue;
+            }
+            let start = position;
+            if let Some(top) = depth.checked_sub(1).and_then(|top| stack.get_mut(top)) {
+                if top.array && top.expect_element && byte != b']' {
+                    top.elements += 1;
+                    top.expect_element = false;
+                    if top.elements > self.max_array_elements {
+                        return Err(LimitExceeded { limit: Limit::ArrayElements, max: self.max_array_elements, offset: start });
+                    }
+                }
+                if top.array && byte == b',' {
+                    top.expect_element = true;
+                }
+            }
+            match byte {
+                b'"' => {
+                    position += 1;
+                    while let Some(&byte) = json.get(position) {
+                        match byte {
+                            b'\\' => position += 2,
+                            b'"' => break,
+                            _ => position += 1,
+                        }
+                    }
+                    let length = position.min(json.len()) - start - 1;
+                    if length > self.max_string_bytes {
+                        return Err(LimitExceeded { limit: Limit::StringBytes, max: self.max_string_bytes, offset: start });
+                    }
+                }
+                b'[' | b'{' => {
+                    if let Some(nesting) = stack.get_mut(depth) {
+                        *nesting = Nesting { array: byte == b'[', elements: 0, expect_element: true };
+                    }
+                    depth += 1;
+                }
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            position += 1;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_limited<T: DeserializeOwned>(json: &str, limits: &ParseLimits) -> Result<T, LimitedParseError> {
+    parse_with_limited(json, limits, |json| serde_json::from_str(json))
+}
+
+// Every `*_limited` entry point goes through here. Messages over the limits are rejected by the scan
+// before anything is deserialized, so they allocate nothing. For entry points that dispatch on the event
+// type, e.g. `parse_with_limited(json, &limits, SpotEvent::from_json)`
+pub fn parse_with_limited<T>(
+    json: &str,
+    limits: &ParseLimits,
+    from_json: impl FnOnce(&str) -> Result<T, serde_json::Error>,
+) -> Result<T, LimitedParseError> {
+    limits.check(json.as_bytes())?;
+    Ok(parse_with(json, from_json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "spot")]
+    fn depth_update(levels: usize) -> String {
+        let mut json = String::from(r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":["#);
+        for index in 0..levels {
+            json.push_str(if index == 0 { r#"["0.0024","10"]"# } else { r#",["0.0024","10"]"# });
+        }
+        json.push_str(r#"],"a":[]}"#);
+        json
+    }
+
+    #[test]
+    fn test_input_and_string_limits() {
+        let trade = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#;
+        let limits = ParseLimits { max_input_bytes: 64, ..ParseLimits::default() };
+        let error = limits.check(trade.as_bytes()).unwrap_err();
+        assert_eq!(error, LimitExceeded { limit: Limit::InputBytes, max: 64, offset: 0 });
+        assert_eq!(error.to_string(), "max_input_bytes of 64 exceeded at byte 0");
+
+        let limits = ParseLimits { max_string_bytes: 5, ..ParseLimits::default() };
+        let error = limits.check(trade.as_bytes()).unwrap_err();
+        assert_eq!((error.limit, error.offset), (Limit::StringBytes, trade.find("\"BNBBTC\"").unwrap()));
+        assert!(ParseLimits { max_string_bytes: 6, ..limits }.check(trade.as_bytes()).is_ok());
+
+        // Escaped quotes do not end the string, nested and empty arrays are counted on their own
+        assert!(limits.check(br#"{"m":"ab\"c"}"#).is_ok());
+        assert!(limits.check(br#"{"m":"ab\"cd"}"#).is_err());
+        let limits = ParseLimits { max_array_elements: 2, ..ParseLimits::default() };
+        assert!(limits.check(br#"[[1,2],[3,4],[]]"#).is_err());
+        assert!(limits.check(br#"[[1,2,3]]"#).is_err());
+        assert!(limits.check(br#"[[1,2],[[],[]]]"#).is_ok());
+        assert!(limits.check(br#"{"a":[1,2],"b":[3,4],"c":"[,,,]"}"#).is_ok());
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_spot_event_limited() {
+        use crate::websocket::{DepthUpdate, SpotEvent};
+
+        let limits = ParseLimits { max_array_elements: 100, ..ParseLimits::default() };
+        assert!(matches!(SpotEvent::from_json_limited(&depth_update(100), &limits), Ok(SpotEvent::DepthUpdate(depth)) if depth.bids.len() == 100));
+        match DepthUpdate::from_json_limited(&depth_update(101), &limits) {
+            Err(error @ LimitedParseError::LimitExceeded(_)) => assert!(error.to_string().starts_with("message rejected: max_array_elements of 100")),
+            other => panic!("expected the array limit to trip, got {:?}", other),
+        }
+        match SpotEvent::from_json_limited(r#"{"e":"depthUpdate","E":1,"s":"BNBBTC","U":"157"}"#, &limits) {
+            Err(LimitedParseError::Codec(error)) => assert_eq!(error.event_type.as_deref(), Some("depthUpdate")),
+            other => panic!("expected a codec error, got {:?}", other),
+        }
+    }
+}
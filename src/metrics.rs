@@ -0,0 +1,706 @@
+//! Feed health metrics: latency between exchange event time and local receipt, message counters and
+//! stream staleness.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::websocket::{DepthSpeed, StreamName};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeUnit {
+    Milliseconds,
+    Microseconds,
+}
+
+impl TimeUnit {
+    // Epoch milliseconds stay below 10^14 until the year 5138, epoch microseconds are above it
+    pub fn detect(timestamp: u64) -> Self {
+        if timestamp < 100_000_000_000_000 {
+            TimeUnit::Milliseconds
+        } else {
+            TimeUnit::Microseconds
+        }
+    }
+
+    pub fn to_micros(self, timestamp: u64) -> u64 {
+        match self {
+            TimeUnit::Milliseconds => timestamp * 1_000,
+            TimeUnit::Microseconds => timestamp,
+        }
+    }
+
+    pub fn to_millis(self, timestamp: u64) -> u64 {
+        match self {
+            TimeUnit::Milliseconds => timestamp,
+            TimeUnit::Microseconds => timestamp / 1_000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatencySnapshot {
+    pub samples: usize,                  // Samples in the reservoir
+    pub p50_us: u64,                     // Median latency in microseconds
+    pub p95_us: u64,                     // 95th percentile latency in microseconds
+    pub p99_us: u64,                     // 99th percentile latency in microseconds
+    pub negative: u64,                   // Samples where the local clock was behind the event time
+}
+
+#[derive(Clone, Debug, Default)]
+struct StreamLatency {
+    reservoir: VecDeque<u64>,
+    negative: u64,
+}
+
+// Keeps the latest `capacity` non-negative latencies per stream; negative ones only bump a counter
+#[derive(Clone, Debug)]
+pub struct LatencyTracker {
+    capacity: usize,
+    streams: HashMap<String, StreamLatency>,
+}
+
+impl LatencyTracker {
+    pub fn new(capacity: usize) -> Self {
+        LatencyTracker { capacity: capacity.max(1), streams: HashMap::new() }
+    }
+
+    // Either timestamp may be in milliseconds or microseconds, the unit is detected per value
+    pub fn record(&mut self, stream: &str, event_time: u64, local_receive_time: u64) -> i64 {
+        let event_us = TimeUnit::detect(event_time).to_micros(event_time) as i64;
+        let receive_us = TimeUnit::detect(local_receive_time).to_micros(local_receive_time) as i64;
+        let latency_us = receive_us - event_us;
+
+        let entry = self.streams.entry(stream.to_string()).or_default();
+        if latency_us < 0 {
+            entry.negative += 1;
+        } else {
+            if entry.reservoir.len() == self.capacity {
+                entry.reservoir.pop_front();
+            }
+            entry.reservoir.push_back(latency_us as u64);
+        }
+        latency_us
+    }
+
+    pub fn negative_count(&self, stream: &str) -> u64 {
+        self.streams.get(stream).map_or(0, |entry| entry.negative)
+    }
+
+    // None until the stream has a non-negative sample
+    pub fn snapshot(&self, stream: &str) -> Option<LatencySnapshot> {
+        let entry = self.streams.get(stream)?;
+        if entry.reservoir.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = entry.reservoir.iter().copied().collect();
+        sorted.sort_unstable();
+        Some(LatencySnapshot {
+            samples: sorted.len(),
+            p50_us: percentile(&sorted, 50.0),
+            p95_us: percentile(&sorted, 95.0),
+            p99_us: percentile(&sorted, 99.0),
+            negative: entry.negative,
+        })
+    }
+
+    pub fn streams(&self) -> impl Iterator<Item = &str> {
+        self.streams.keys().map(String::as_str)
+    }
+
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+}
+
+// Nearest-rank percentile of an ascending, non-empty slice
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MessageCounters {
+    pub messages: u64,                   // Messages parsed
+    pub bytes: u64,                      // Payload bytes, including those that failed to parse
+    pub parse_errors: u64,               // Messages that failed to parse
+}
+
+impl MessageCounters {
+    fn diff(&self, previous: &MessageCounters) -> MessageCounters {
+        MessageCounters {
+            messages: self.messages.saturating_sub(previous.messages),
+            bytes: self.bytes.saturating_sub(previous.bytes),
+            parse_errors: self.parse_errors.saturating_sub(previous.parse_errors),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MessageStatsSnapshot {
+    pub total: MessageCounters,          // All messages
+    pub by_event_type: HashMap<String, MessageCounters>, // Per event type, `unknown` when a failed parse has none
+    pub by_symbol: HashMap<String, MessageCounters>, // Per symbol, for events that carry one
+}
+
+impl MessageStatsSnapshot {
+    // Counts accumulated since `previous`, for rates over the interval between two snapshots
+    pub fn diff(&self, previous: &MessageStatsSnapshot) -> MessageStatsSnapshot {
+        let diff_map = |current: &HashMap<String, MessageCounters>, previous: &HashMap<String, MessageCounters>| {
+            current
+                .iter()
+                .map(|(key, counters)| (key.clone(), counters.diff(&previous.get(key).copied().unwrap_or_default())))
+                .collect()
+        };
+        MessageStatsSnapshot {
+            total: self.total.diff(&previous.total),
+            by_event_type: diff_map(&self.by_event_type, &previous.by_event_type),
+            by_symbol: diff_map(&self.by_symbol, &previous.by_symbol),
+        }
+    }
+}
+
+const UNKNOWN_EVENT_TYPE: &str = "unknown";
+
+// Single-threaded collector, see AtomicMessageStats for a shared one
+#[derive(Clone, Debug, Default)]
+pub struct MessageStats {
+    stats: MessageStatsSnapshot,
+}
+
+impl MessageStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, event_type: &str, symbol: Option<&str>, bytes: usize) {
+        let bump = |counters: &mut MessageCounters| {
+            counters.messages += 1;
+            counters.bytes += bytes as u64;
+        };
+        bump(&mut self.stats.total);
+        bump(counter_entry(&mut self.stats.by_event_type, event_type));
+        if let Some(symbol) = symbol {
+            bump(counter_entry(&mut self.stats.by_symbol, symbol));
+        }
+    }
+
+    pub fn record_parse_error(&mut self, event_type: Option<&str>, bytes: usize) {
+        let bump = |counters: &mut MessageCounters| {
+            counters.parse_errors += 1;
+            counters.bytes += bytes as u64;
+        };
+        bump(&mut self.stats.total);
+        bump(counter_entry(&mut self.stats.by_event_type, event_type.unwrap_or(UNKNOWN_EVENT_TYPE)));
+    }
+
+    pub fn snapshot(&self) -> MessageStatsSnapshot {
+        self.stats.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.stats = MessageStatsSnapshot::default();
+    }
+}
+
+fn counter_entry<'a>(map: &'a mut HashMap<String, MessageCounters>, key: &str) -> &'a mut MessageCounters {
+    if !map.contains_key(key) {
+        map.insert(key.to_string(), MessageCounters::default());
+    }
+    map.get_mut(key).unwrap()
+}
+
+#[derive(Debug, Default)]
+struct AtomicCounters {
+    messages: AtomicU64,
+    bytes: AtomicU64,
+    parse_errors: AtomicU64,
+}
+
+impl AtomicCounters {
+    fn load(&self) -> MessageCounters {
+        MessageCounters {
+            messages: self.messages.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            parse_errors: self.parse_errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// Shared collector: counters are atomics, and the per-key maps only take their write lock the first
+// time a key is seen
+#[derive(Debug, Default)]
+pub struct AtomicMessageStats {
+    total: AtomicCounters,
+    by_event_type: RwLock<HashMap<String, Arc<AtomicCounters>>>,
+    by_symbol: RwLock<HashMap<String, Arc<AtomicCounters>>>,
+}
+
+impl AtomicMessageStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, event_type: &str, symbol: Option<&str>, bytes: usize) {
+        let bump = |counters: &AtomicCounters| {
+            counters.messages.fetch_add(1, Ordering::Relaxed);
+            counters.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        };
+        bump(&self.total);
+        bump(&atomic_entry(&self.by_event_type, event_type));
+        if let Some(symbol) = symbol {
+            bump(&atomic_entry(&self.by_symbol, symbol));
+        }
+    }
+
+    pub fn record_parse_error(&self, event_type: Option<&str>, bytes: usize) {
+        let bump = |counters: &AtomicCounters| {
+            counters.parse_errors.fetch_add(1, Ordering::Relaxed);
+            counters.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        };
+        bump(&self.total);
+        bump(&atomic_entry(&self.by_event_type, event_type.unwrap_or(UNKNOWN_EVENT_TYPE)));
+    }
+
+    pub fn snapshot(&self) -> MessageStatsSnapshot {
+        let load_map = |map: &RwLock<HashMap<String, Arc<AtomicCounters>>>| {
+            map.read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .map(|(key, counters)| (key.clone(), counters.load()))
+                .collect()
+        };
+        MessageStatsSnapshot {
+            total: self.total.load(),
+            by_event_type: load_map(&self.by_event_type),
+            by_symbol: load_map(&self.by_symbol),
+        }
+    }
+}
+
+fn atomic_entry(map: &RwLock<HashMap<String, Arc<AtomicCounters>>>, key: &str) -> Arc<AtomicCounters> {
+    if let Some(counters) = map.read().unwrap_or_else(PoisonError::into_inner).get(key) {
+        return Arc::clone(counters);
+    }
+    let mut map = map.write().unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(map.entry(key.to_string()).or_default())
+}
+
+// Expected time between two events of a spot stream, None for raw or unparseable stream names.
+// Streams pushed in real time have a cadence of 0, klines are expected at least once per interval
+pub fn default_cadence_ms(stream: &str) -> Option<u64> {
+    let cadence = match stream.parse::<StreamName>().ok()? {
+        StreamName::Trade(_) | StreamName::AggTrade(_) | StreamName::BookTicker(_) | StreamName::TokenNav(_) => 0,
+        StreamName::PartialDepth(_, _, DepthSpeed::Ms100) | StreamName::DiffDepth(_, DepthSpeed::Ms100) => 100,
+        StreamName::PartialDepth(_, _, DepthSpeed::Ms1000) | StreamName::DiffDepth(_, DepthSpeed::Ms1000) => 1_000,
+        StreamName::Kline(_, interval) | StreamName::NavKline(_, interval) => interval.duration_ms().unwrap_or(31 * 24 * 60 * 60 * 1_000),
+        StreamName::MiniTicker(_)
+        | StreamName::Ticker(_)
+        | StreamName::RollingTicker(_, _)
+        | StreamName::AvgPrice(_)
+        | StreamName::AllMiniTickers
+        | StreamName::AllTickers
+        | StreamName::AllRollingTickers(_) => 1_000,
+        StreamName::Raw(_) => return None,
+    };
+    Some(cadence)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleStream {
+    pub stream: String,                  // Stream name as passed to watch or record
+    pub last_event_time: Option<u64>,    // Event time of the last event, None if none arrived yet
+    pub last_receive_time: u64,          // Local time of the last event, or of watch if none arrived yet
+    pub silent_ms: u64,                  // Time since last_receive_time at the poll
+    pub threshold_ms: u64,               // Threshold that was exceeded
+}
+
+#[derive(Clone, Copy, Debug)]
+struct StreamActivity {
+    last_event_time: Option<u64>,
+    last_receive_time: u64,
+}
+
+// Reports streams that went quiet for longer than their threshold, by default the stream kind's cadence
+// plus `grace_ms`. All times are in milliseconds and silence is measured on the local receive clock
+#[derive(Clone, Debug)]
+pub struct StalenessWatchdog {
+    grace_ms: u64,
+    overrides: HashMap<String, Option<u64>>,
+    streams: HashMap<String, StreamActivity>,
+}
+
+impl StalenessWatchdog {
+    pub fn new(grace_ms: u64) -> Self {
+        StalenessWatchdog { grace_ms, overrides: HashMap::new(), streams: HashMap::new() }
+    }
+
+    // Starts the clock for a stream that has not delivered anything yet, e.g. right after subscribing
+    pub fn watch(&mut self, stream: &str, now: u64) {
+        self.streams
+            .entry(stream.to_string())
+            .or_insert(StreamActivity { last_event_time: None, last_receive_time: now });
+    }
+
+    pub fn unwatch(&mut self, stream: &str) {
+        self.streams.remove(stream);
+    }
+
+    // Fixed staleness threshold for a stream, None never reports it, e.g. trades on an illiquid symbol.
+    // Overrides also apply to streams that are watched later
+    pub fn set_threshold(&mut self, stream: &str, threshold_ms: Option<u64>) {
+        self.overrides.insert(stream.to_string(), threshold_ms);
+    }
+
+    pub fn clear_threshold(&mut self, stream: &str) {
+        self.overrides.remove(stream);
+    }
+
+    // None when the stream is never reported stale, either by override or for lack of a default cadence
+    pub fn threshold_ms(&self, stream: &str) -> Option<u64> {
+        match self.overrides.get(stream) {
+            Some(threshold_ms) => *threshold_ms,
+            None => default_cadence_ms(stream).map(|cadence| cadence + self.grace_ms),
+        }
+    }
+
+    pub fn record(&mut self, stream: &str, event_time: u64, local_receive_time: u64) {
+        let activity = StreamActivity { last_event_time: Some(event_time), last_receive_time: local_receive_time };
+        match self.streams.get_mut(stream) {
+            // Out of order receive times must not move the clock back
+            Some(entry) => {
+                if local_receive_time >= entry.last_receive_time {
+                    *entry = activity;
+                }
+            }
+            None => {
+                self.streams.insert(stream.to_string(), activity);
+            }
+        }
+    }
+
+    // Streams silent for longer than their threshold at `now`, sorted by stream name
+    pub fn poll(&self, now: u64) -> Vec<StaleStream> {
+        let mut stale: Vec<StaleStream> = self
+            .streams
+            .iter()
+            .filter_map(|(stream, activity)| {
+                let threshold_ms = self.threshold_ms(stream)?;
+                let silent_ms = now.saturating_sub(activity.last_receive_time);
+                (silent_ms > threshold_ms).then(|| StaleStream {
+                    stream: stream.clone(),
+                    last_event_time: activity.last_event_time,
+                    last_receive_time: activity.last_receive_time,
+                    silent_ms,
+                    threshold_ms,
+                })
+            })
+            .collect();
+        stale.sort_by(|a, b| a.stream.cmp(&b.stream));
+        stale
+    }
+
+    pub fn streams(&self) -> impl Iterator<Item = &str> {
+        self.streams.keys().map(String::as_str)
+    }
+
+    pub fn reset(&mut self) {
+        self.streams.clear();
+    }
+}
+
+// Metrics emitted through the `metrics` facade with the `metrics` feature. The names are stable, label
+// keys are in brackets. Without the feature no instrumentation is compiled in
+pub const MESSAGES_PARSED: &str = "binance_codec_messages_parsed_total"; // Counter [event_type]
+pub const PARSE_ERRORS: &str = "binance_codec_parse_errors_total"; // Counter [event_type]
+pub const PARSE_DURATION: &str = "binance_codec_parse_duration_seconds"; // Histogram, successful and failed parses, not on wasm32-unknown-unknown
+pub const DEPTH_DESYNCS: &str = "binance_codec_depth_desyncs_total"; // Counter [market], broken diff depth sequences
+pub const TRADE_GAPS: &str = "binance_codec_trade_gaps_total"; // Counter [symbol], aggregate id jumps
+pub const TRADES_MISSING: &str = "binance_codec_trades_missing_total"; // Counter [symbol], aggregate ids skipped by gaps
+
+pub const EVENT_TYPE_LABEL: &str = "event_type";
+pub const MARKET_LABEL: &str = "market";
+pub const SYMBOL_LABEL: &str = "symbol";
+
+#[cfg(feature = "metrics")]
+pub(crate) mod emit {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::{event_type, CodecError};
+
+    // Frames without an `e` field, e.g. book tickers and array streams, are labelled "unknown"
+    pub(crate) fn parse<T>(input: &[u8], result: &Result<T, CodecError>, elapsed: Option<Duration>) {
+        if let Some(elapsed) = elapsed {
+            ::metrics::histogram!(PARSE_DURATION).record(elapsed);
+        }
+        match result {
+            Ok(_) => {
+                let event_type = std::str::from_utf8(input).ok().and_then(event_type).unwrap_or("unknown").to_string();
+                ::metrics::counter!(MESSAGES_PARSED, EVENT_TYPE_LABEL => event_type).increment(1);
+            }
+            Err(error) => {
+                let event_type = error.event_type.clone().unwrap_or_else(|| "unknown".to_string());
+                ::metrics::counter!(PARSE_ERRORS, EVENT_TYPE_LABEL => event_type).increment(1);
+            }
+        }
+    }
+
+    #[cfg(any(feature = "spot", feature = "futures"))]
+    pub(crate) fn depth_desync(market: &'static str) {
+        ::metrics::counter!(DEPTH_DESYNCS, MARKET_LABEL => market).increment(1);
+    }
+
+    #[cfg(feature = "spot")]
+    pub(crate) fn trade_gap(symbol: &str, missing: u64) {
+        ::metrics::counter!(TRADE_GAPS, SYMBOL_LABEL => symbol.to_string()).increment(1);
+        ::metrics::counter!(TRADES_MISSING, SYMBOL_LABEL => symbol.to_string()).increment(missing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_MS: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn test_time_unit_detection() {
+        assert_eq!(TimeUnit::detect(EVENT_MS), TimeUnit::Milliseconds);
+        assert_eq!(TimeUnit::detect(EVENT_MS * 1_000), TimeUnit::Microseconds);
+        assert_eq!(TimeUnit::Milliseconds.to_micros(5), 5_000);
+    }
+
+    #[test]
+    fn test_latency_tracker_percentiles() {
+        let mut tracker = LatencyTracker::new(100);
+        assert_eq!(tracker.snapshot("btcusdt@trade"), None);
+
+        // 1 ms through 100 ms, event times in milliseconds, receive times in microseconds
+        for latency_ms in 1..=100 {
+            let event_time = EVENT_MS + latency_ms * 10;
+            let receive_time = (event_time + latency_ms) * 1_000;
+            assert_eq!(tracker.record("btcusdt@trade", event_time, receive_time), (latency_ms * 1_000) as i64);
+        }
+        let snapshot = tracker.snapshot("btcusdt@trade").unwrap();
+        assert_eq!(snapshot.samples, 100);
+        assert_eq!(snapshot.p50_us, 50_000);
+        assert_eq!(snapshot.p95_us, 95_000);
+        assert_eq!(snapshot.p99_us, 99_000);
+        assert_eq!(snapshot.negative, 0);
+
+        // The reservoir drops the oldest samples first
+        let mut tracker = LatencyTracker::new(4);
+        for latency_us in [900, 100, 200, 300, 400] {
+            tracker.record("btcusdt@depth", EVENT_MS * 1_000, EVENT_MS * 1_000 + latency_us);
+        }
+        let snapshot = tracker.snapshot("btcusdt@depth").unwrap();
+        assert_eq!((snapshot.samples, snapshot.p50_us, snapshot.p99_us), (4, 200, 400));
+    }
+
+    #[test]
+    fn test_latency_tracker_negative_latencies() {
+        let mut tracker = LatencyTracker::new(10);
+        assert_eq!(tracker.record("ethusdt@trade", EVENT_MS, EVENT_MS - 3), -3_000);
+        assert_eq!(tracker.record("ethusdt@trade", EVENT_MS, EVENT_MS - 1), -1_000);
+        assert_eq!(tracker.negative_count("ethusdt@trade"), 2);
+        assert_eq!(tracker.snapshot("ethusdt@trade"), None);
+
+        tracker.record("ethusdt@trade", EVENT_MS, EVENT_MS + 7);
+        let snapshot = tracker.snapshot("ethusdt@trade").unwrap();
+        assert_eq!((snapshot.samples, snapshot.p50_us, snapshot.negative), (1, 7_000, 2));
+        assert_eq!(tracker.negative_count("btcusdt@trade"), 0);
+    }
+
+    #[test]
+    fn test_default_cadences() {
+        assert_eq!(default_cadence_ms("btcusdt@bookTicker"), Some(0));
+        assert_eq!(default_cadence_ms("btcusdt@miniTicker"), Some(1_000));
+        assert_eq!(default_cadence_ms("btcusdt@depth@100ms"), Some(100));
+        assert_eq!(default_cadence_ms("btcusdt@kline_5m"), Some(300_000));
+        assert_eq!(default_cadence_ms("!ticker@arr"), Some(1_000));
+        assert_eq!(default_cadence_ms("btcusdt@unknown"), None);
+    }
+
+    #[test]
+    fn test_staleness_watchdog_reports_gaps() {
+        let mut watchdog = StalenessWatchdog::new(5_000);
+        let start = EVENT_MS;
+        let streams = ["btcusdt@bookTicker", "btcusdt@miniTicker", "btcusdt@kline_1m", "dogeusdc@trade", "ethusdt@depth@100ms"];
+        for stream in streams {
+            watchdog.watch(stream, start);
+        }
+        // An illiquid symbol may not trade for minutes
+        watchdog.set_threshold("dogeusdc@trade", None);
+        // Not a spot stream name, so only watched with an explicit threshold
+        watchdog.set_threshold("btcusdt@markPrice", Some(3_000));
+
+        for offset in (0..=10_000).step_by(100) {
+            for stream in ["btcusdt@bookTicker", "btcusdt@miniTicker", "btcusdt@markPrice"] {
+                watchdog.record(stream, start + offset - 2, start + offset);
+            }
+        }
+        // The depth stream never delivered and has exceeded 100 ms + 5 s since it was watched
+        let stale = watchdog.poll(start + 10_000);
+        assert_eq!(stale.len(), 1);
+        assert_eq!((stale[0].stream.as_str(), stale[0].last_event_time, stale[0].silent_ms), ("ethusdt@depth@100ms", None, 10_000));
+        watchdog.record("ethusdt@depth@100ms", start + 9_990, start + 10_000);
+
+        // bookTicker and markPrice stall after 10 s, miniTicker keeps going
+        for offset in (10_000..=20_000).step_by(1_000) {
+            watchdog.record("btcusdt@miniTicker", start + offset, start + offset);
+        }
+        let stale = watchdog.poll(start + 20_000);
+        let names: Vec<&str> = stale.iter().map(|stale| stale.stream.as_str()).collect();
+        assert_eq!(names, ["btcusdt@bookTicker", "btcusdt@markPrice", "ethusdt@depth@100ms"]);
+        assert_eq!(stale[0], StaleStream {
+            stream: "btcusdt@bookTicker".to_string(),
+            last_event_time: Some(start + 9_998),
+            last_receive_time: start + 10_000,
+            silent_ms: 10_000,
+            threshold_ms: 5_000,
+        });
+        assert_eq!(stale[1].threshold_ms, 3_000);
+
+        // The kline stream has not delivered either, but one minute plus grace has not passed yet
+        assert_eq!(watchdog.threshold_ms("btcusdt@kline_1m"), Some(65_000));
+        assert_eq!(watchdog.poll(start + 65_001).iter().filter(|stale| stale.stream == "btcusdt@kline_1m").count(), 1);
+
+        // An out of order receive time does not reset the silence
+        watchdog.record("btcusdt@bookTicker", start + 9_000, start + 9_500);
+        assert_eq!(watchdog.poll(start + 20_000)[0].last_receive_time, start + 10_000);
+        watchdog.unwatch("btcusdt@bookTicker");
+        assert_eq!(watchdog.poll(start + 20_000).len(), 2);
+    }
+
+    // (event type, symbol, bytes); no event type marks a parse error
+    const BATCH: [(Option<&str>, Option<&str>, usize); 6] = [
+        (Some("trade"), Some("BTCUSDT"), 180),
+        (Some("trade"), Some("ETHUSDT"), 175),
+        (Some("trade"), Some("BTCUSDT"), 182),
+        (Some("depthUpdate"), Some("BTCUSDT"), 1_024),
+        (Some("listenKeyExpired"), None, 90),
+        (None, None, 12),
+    ];
+
+    #[test]
+    fn test_message_stats_counts_and_diff() {
+        let mut stats = MessageStats::new();
+        for (event_type, symbol, bytes) in BATCH {
+            match event_type {
+                Some(event_type) => stats.record(event_type, symbol, bytes),
+                None => stats.record_parse_error(None, bytes),
+            }
+        }
+        let first = stats.snapshot();
+
+        assert_eq!(first.total, MessageCounters { messages: 5, bytes: 1_663, parse_errors: 1 });
+        assert_eq!(first.by_event_type["trade"], MessageCounters { messages: 3, bytes: 537, parse_errors: 0 });
+        assert_eq!(first.by_event_type["unknown"], MessageCounters { messages: 0, bytes: 12, parse_errors: 1 });
+        assert_eq!(first.by_symbol["BTCUSDT"].messages, 3);
+        assert_eq!(first.by_symbol["ETHUSDT"].bytes, 175);
+        assert_eq!(first.by_symbol.len(), 2);
+
+        stats.record("trade", Some("ETHUSDT"), 170);
+        stats.record("bookTicker", Some("BNBUSDT"), 120);
+        stats.record_parse_error(Some("trade"), 40);
+        let diff = stats.snapshot().diff(&first);
+
+        assert_eq!(diff.total, MessageCounters { messages: 2, bytes: 330, parse_errors: 1 });
+        assert_eq!(diff.by_event_type["trade"], MessageCounters { messages: 1, bytes: 210, parse_errors: 1 });
+        assert_eq!(diff.by_event_type["bookTicker"].messages, 1);
+        assert_eq!(diff.by_event_type["depthUpdate"], MessageCounters::default());
+        assert_eq!(diff.by_symbol["BTCUSDT"], MessageCounters::default());
+        assert_eq!(diff.by_symbol["BNBUSDT"].bytes, 120);
+
+        stats.reset();
+        assert_eq!(stats.snapshot(), MessageStatsSnapshot::default());
+    }
+
+    #[test]
+    fn test_atomic_message_stats_matches_single_threaded() {
+        let shared = Arc::new(AtomicMessageStats::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || {
+                    for (event_type, symbol, bytes) in BATCH {
+                        match event_type {
+                            Some(event_type) => shared.record(event_type, symbol, bytes),
+                            None => shared.record_parse_error(None, bytes),
+                        }
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+        let snapshot = shared.snapshot();
+        assert_eq!(snapshot.total, MessageCounters { messages: 20, bytes: 6_652, parse_errors: 4 });
+        assert_eq!(snapshot.by_event_type["trade"].messages, 12);
+        assert_eq!(snapshot.by_symbol["BTCUSDT"].messages, 12);
+    }
+
+    #[cfg(all(feature = "metrics", feature = "spot"))]
+    #[test]
+    fn test_metrics_facade_counters() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        use crate::batch::parse_many;
+        use crate::orderbook::SpotDepthSync;
+        use crate::rest::DepthSnapshot;
+        use crate::sequence::AggTradeSequencer;
+        use crate::websocket::{AggTrade, DepthUpdate, SpotEvent};
+
+        let trade = |id: u64| format!(r#"{{"e":"trade","E":1,"s":"BNBBTC","t":{id},"p":"0.001","q":"100","T":1,"m":true,"M":true}}"#);
+        let agg_trade = |id: u64| format!(r#"{{"e":"aggTrade","E":1,"s":"BNBBTC","a":{id},"p":"0.001","q":"100","f":{id},"l":{id},"T":1,"m":true,"M":true}}"#);
+        let depth = |first: u64, last: u64| format!(r#"{{"e":"depthUpdate","E":1,"s":"BNBBTC","U":{first},"u":{last},"b":[],"a":[]}}"#);
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        ::metrics::with_local_recorder(&recorder, || {
+            let frames = [trade(1), trade(2), agg_trade(7), r#"{"result":null,"id":1}"#.to_string(), r#"{"e":"trade","t":"x"}"#.to_string()];
+            let batch = SpotEvent::parse_many(frames.iter().map(String::as_str));
+            assert_eq!(batch.values.len(), 3);
+            assert_eq!(parse_many::<AggTrade>(["[1]"]).errors.len(), 1);
+
+            let mut sequencer = AggTradeSequencer::new();
+            sequencer.ingest(&AggTrade::from_json(&agg_trade(7)).unwrap());
+            sequencer.ingest(&AggTrade::from_json(&agg_trade(10)).unwrap());
+
+            let mut sync = SpotDepthSync::new();
+            sync.on_update(DepthUpdate::from_json(&depth(101, 105)).unwrap());
+            sync.on_snapshot(&DepthSnapshot::from_json(r#"{"lastUpdateId":100,"bids":[],"asks":[]}"#).unwrap());
+            sync.on_update(DepthUpdate::from_json(&depth(110, 112)).unwrap());
+        });
+
+        let metrics: Vec<(String, Vec<String>, DebugValue)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let labels = key.key().labels().map(|label| format!("{}={}", label.key(), label.value())).collect();
+                (key.key().name().to_string(), labels, value)
+            })
+            .collect();
+        let counter = |name: &str, label: &str| {
+            metrics.iter().find_map(|(metric, labels, value)| match value {
+                DebugValue::Counter(count) if metric == name && labels.iter().any(|l| l == label) => Some(*count),
+                _ => None,
+            })
+        };
+
+        assert_eq!(counter(MESSAGES_PARSED, "event_type=trade"), Some(2));
+        assert_eq!(counter(MESSAGES_PARSED, "event_type=aggTrade"), Some(1));
+        assert_eq!(counter(PARSE_ERRORS, "event_type=trade"), Some(1));
+        assert_eq!(counter(PARSE_ERRORS, "event_type=unknown"), Some(1));
+        assert_eq!(counter(DEPTH_DESYNCS, "market=spot"), Some(1));
+        assert_eq!(counter(TRADE_GAPS, "symbol=BNBBTC"), Some(1));
+        assert_eq!(counter(TRADES_MISSING, "symbol=BNBBTC"), Some(2));
+        let durations = metrics.iter().find_map(|(metric, _, value)| match value {
+            DebugValue::Histogram(samples) if metric == PARSE_DURATION => Some(samples.len()),
+            _ => None,
+        });
+        assert_eq!(durations, Some(5));
+    }
+}
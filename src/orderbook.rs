@@ -0,0 +1,882 @@
+//! Local order book maintained from REST snapshots and websocket diff depth updates.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::rest::DepthSnapshot;
+use crate::websocket::PriceLevel;
+
+cfg_spot! {
+    use crate::websocket::DepthUpdate;
+}
+cfg_futures! {
+    use crate::websocket::futures;
+}
+
+// Diff depth event of any product; sequencing is checked by the sync state machines, not the book
+pub trait DepthDiff {
+    fn first_update_id(&self) -> u64;
+    fn final_update_id(&self) -> u64;
+    fn bids(&self) -> &[PriceLevel];
+    fn asks(&self) -> &[PriceLevel];
+}
+
+#[cfg(feature = "spot")]
+impl<L: std::ops::Deref<Target = [PriceLevel]>> DepthDiff for DepthUpdate<String, L> {
+    fn first_update_id(&self) -> u64 {
+        self.first_update_id
+    }
+
+    fn final_update_id(&self) -> u64 {
+        self.final_update_id
+    }
+
+    fn bids(&self) -> &[PriceLevel] {
+        &self.bids
+    }
+
+    fn asks(&self) -> &[PriceLevel] {
+        &self.asks
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<L: std::ops::Deref<Target = [PriceLevel]>> DepthDiff for futures::DepthUpdate<L> {
+    fn first_update_id(&self) -> u64 {
+        self.first_update_id
+    }
+
+    fn final_update_id(&self) -> u64 {
+        self.final_update_id
+    }
+
+    fn bids(&self) -> &[PriceLevel] {
+        &self.bids
+    }
+
+    fn asks(&self) -> &[PriceLevel] {
+        &self.asks
+    }
+}
+
+// Prices are non-negative, and the bit patterns of non-negative floats sort like the values
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PriceKey(u64);
+
+impl PriceKey {
+    fn new(price: f64) -> Self {
+        PriceKey(price.to_bits())
+    }
+
+    fn price(self) -> f64 {
+        f64::from_bits(self.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OrderBook {
+    bids: BTreeMap<PriceKey, f64>,
+    asks: BTreeMap<PriceKey, f64>,
+    last_update_id: u64,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+
+    pub fn apply_snapshot(&mut self, snapshot: &DepthSnapshot) {
+        self.bids.clear();
+        self.asks.clear();
+        apply_levels(&mut self.bids, &snapshot.bids);
+        apply_levels(&mut self.asks, &snapshot.asks);
+        self.last_update_id = snapshot.last_update_id;
+    }
+
+    // Quantities are absolute, a zero quantity removes the level
+    pub fn apply_update<D: DepthDiff>(&mut self, update: &D) {
+        apply_levels(&mut self.bids, update.bids());
+        apply_levels(&mut self.asks, update.asks());
+        self.last_update_id = update.final_update_id();
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.iter().next_back().map(to_price_level)
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.iter().next().map(to_price_level)
+    }
+
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    // Same as `mid_price`
+    pub fn mid(&self) -> Option<f64> {
+        self.mid_price()
+    }
+
+    // Top of book prices weighted by the opposite side's size, leaning towards the thinner side
+    pub fn microprice(&self) -> Option<f64> {
+        let bid = self.best_bid()?;
+        let ask = self.best_ask()?;
+        Some((bid.price * ask.quantity + ask.price * bid.quantity) / (bid.quantity + ask.quantity))
+    }
+
+    // (bid - ask) / (bid + ask) quantity over the top `levels` of each side, from -1 to 1
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_quantity: f64 = self.bids.values().rev().take(levels).sum();
+        let ask_quantity: f64 = self.asks.values().take(levels).sum();
+        let total = bid_quantity + ask_quantity;
+        if total > 0.0 {
+            Some((bid_quantity - ask_quantity) / total)
+        } else {
+            None
+        }
+    }
+
+    // Up to `n` levels per side, bids highest first and asks lowest first
+    pub fn levels(&self, n: usize) -> (Vec<PriceLevel>, Vec<PriceLevel>) {
+        let bids = self.bids.iter().rev().take(n).map(to_price_level).collect();
+        let asks = self.asks.iter().take(n).map(to_price_level).collect();
+        (bids, asks)
+    }
+
+    // Quantity resting on `side` within `bps` basis points of the mid price
+    pub fn quantity_within_bps(&self, side: BookSide, bps: f64) -> Option<f64> {
+        let mid = self.mid()?;
+        let distance = mid * bps / 10_000.0;
+        let quantity = match side {
+            BookSide::Bid => self.bids.range(PriceKey::new((mid - distance).max(0.0))..).map(|(_, quantity)| quantity).sum(),
+            BookSide::Ask => self.asks.range(..=PriceKey::new(mid + distance)).map(|(_, quantity)| quantity).sum(),
+        };
+        Some(quantity)
+    }
+
+    // Bid and ask quantity resting within `bps` basis points of the mid price
+    pub fn total_quantity_within(&self, bps: f64) -> Option<(f64, f64)> {
+        Some((self.quantity_within_bps(BookSide::Bid, bps)?, self.quantity_within_bps(BookSide::Ask, bps)?))
+    }
+
+    // Average price of taking `quantity` from `side`, best level first; None if the side is too thin
+    pub fn vwap_to_fill(&self, side: BookSide, quantity: f64) -> Option<f64> {
+        if quantity <= 0.0 {
+            return None;
+        }
+        let levels: Box<dyn Iterator<Item = (&PriceKey, &f64)>> = match side {
+            BookSide::Bid => Box::new(self.bids.iter().rev()),
+            BookSide::Ask => Box::new(self.asks.iter()),
+        };
+        let mut remaining = quantity;
+        let mut notional = 0.0;
+        for (price, available) in levels {
+            let taken = remaining.min(*available);
+            notional += taken * price.price();
+            remaining -= taken;
+            if remaining <= 0.0 {
+                return Some(notional / quantity);
+            }
+        }
+        None
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+fn apply_levels(side: &mut BTreeMap<PriceKey, f64>, levels: &[PriceLevel]) {
+    for level in levels {
+        if level.is_removal() {
+            side.remove(&PriceKey::new(level.price));
+        } else {
+            side.insert(PriceKey::new(level.price), level.quantity);
+        }
+    }
+}
+
+fn to_price_level((price, quantity): (&PriceKey, &f64)) -> PriceLevel {
+    PriceLevel { price: price.price(), quantity: *quantity }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    Crossed { best_bid: f64, best_ask: f64 }, // Best bid at or above the best ask
+    UpdateIdNotIncreasing { previous: u64, got: u64 }, // Final update id did not advance past the previous one
+    UnsortedLevels { side: BookSide, previous: f64, price: f64 }, // Level prices out of order, e.g. a negative or NaN price
+    NegativeQuantity { side: BookSide, price: f64, quantity: f64 },
+}
+
+// Invariant checks run by ValidatedBook after every snapshot and update
+pub trait BookCheck {
+    fn check_snapshot(&mut self, book: &OrderBook) -> Vec<Violation>;
+    fn check_update<D: DepthDiff>(&mut self, update: &D, book: &OrderBook) -> Vec<Violation>;
+}
+
+// Disabled validation, compiles down to the bare book updates
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoValidation;
+
+impl BookCheck for NoValidation {
+    #[inline]
+    fn check_snapshot(&mut self, _book: &OrderBook) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn check_update<D: DepthDiff>(&mut self, _update: &D, _book: &OrderBook) -> Vec<Violation> {
+        Vec::new()
+    }
+}
+
+// Checks best bid < best ask, strictly increasing update ids, sorted level prices and non-negative
+// quantities; a snapshot restarts the update id sequence
+#[derive(Clone, Debug, Default)]
+pub struct BookValidator {
+    last_update_id: Option<u64>,
+}
+
+impl BookValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn check_book(&self, book: &OrderBook) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (side, levels) in [(BookSide::Bid, &book.bids), (BookSide::Ask, &book.asks)] {
+            let mut previous: Option<f64> = None;
+            for (price, quantity) in levels {
+                let price = price.price();
+                if let Some(previous) = previous {
+                    if price.partial_cmp(&previous) != Some(Ordering::Greater) {
+                        violations.push(Violation::UnsortedLevels { side, previous, price });
+                    }
+                }
+                if *quantity < 0.0 {
+                    violations.push(Violation::NegativeQuantity { side, price, quantity: *quantity });
+                }
+                previous = Some(price);
+            }
+        }
+        if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+            if bid.price >= ask.price {
+                violations.push(Violation::Crossed { best_bid: bid.price, best_ask: ask.price });
+            }
+        }
+        violations
+    }
+}
+
+impl BookCheck for BookValidator {
+    fn check_snapshot(&mut self, book: &OrderBook) -> Vec<Violation> {
+        self.last_update_id = Some(book.last_update_id());
+        self.check_book(book)
+    }
+
+    fn check_update<D: DepthDiff>(&mut self, update: &D, book: &OrderBook) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let got = update.final_update_id();
+        if let Some(previous) = self.last_update_id {
+            if got <= previous {
+                violations.push(Violation::UpdateIdNotIncreasing { previous, got });
+            }
+        }
+        self.last_update_id = Some(self.last_update_id.map_or(got, |previous| previous.max(got)));
+        violations.extend(self.check_book(book));
+        violations
+    }
+}
+
+// OrderBook that runs a BookCheck after every change, with NoValidation by default
+#[derive(Clone, Debug, Default)]
+pub struct ValidatedBook<V: BookCheck = NoValidation> {
+    book: OrderBook,
+    validator: V,
+}
+
+impl<V: BookCheck> ValidatedBook<V> {
+    pub fn new(validator: V) -> Self {
+        ValidatedBook { book: OrderBook::new(), validator }
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    pub fn apply_snapshot(&mut self, snapshot: &DepthSnapshot) -> Vec<Violation> {
+        self.book.apply_snapshot(snapshot);
+        self.validator.check_snapshot(&self.book)
+    }
+
+    pub fn apply_update<D: DepthDiff>(&mut self, update: &D) -> Vec<Violation> {
+        self.book.apply_update(update);
+        self.validator.check_update(update, &self.book)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DepthSyncOutput<U> {
+    NeedSnapshot,                        // Fetch a REST snapshot and pass it to on_snapshot
+    Buffering,                           // Update buffered until the snapshot arrives
+    Synced(Vec<U>),                      // Apply these updates to the book, in order
+    Desynced { expected: u64, got: u64 }, // Sequence broken, discard the book and fetch a new snapshot
+}
+
+// Counted with the `metrics` feature and logged at debug level with the `tracing` feature
+#[cfg(any(feature = "spot", feature = "futures"))]
+#[cfg_attr(not(any(feature = "metrics", feature = "tracing")), allow(unused_variables))]
+#[inline]
+fn record_desync(market: &'static str, expected: u64, got: u64) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::emit::depth_desync(market);
+    #[cfg(feature = "tracing")]
+    tracing::debug_span!("depth_sync", market).in_scope(|| tracing::debug!(expected, got, "depth sequence broken"));
+}
+
+#[cfg(all(feature = "tracing", any(feature = "spot", feature = "futures")))]
+fn snapshot_span(market: &'static str, snapshot: &DepthSnapshot, buffered: usize) -> tracing::span::EnteredSpan {
+    tracing::debug_span!("depth_sync", market, snapshot_update_id = snapshot.last_update_id, buffered).entered()
+}
+
+// Spot diff depth sync: buffer updates, drop those with `u <= lastUpdateId`, require the first applied
+// update to satisfy `U <= lastUpdateId + 1 <= u` and every later one to start at the previous `u + 1`
+#[cfg(feature = "spot")]
+#[derive(Clone, Debug, Default)]
+pub struct SpotDepthSync {
+    buffer: Vec<DepthUpdate>,
+    last_update_id: Option<u64>,
+    snapshot_requested: bool,
+}
+
+#[cfg(feature = "spot")]
+impl SpotDepthSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.last_update_id.is_some()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn on_update(&mut self, update: DepthUpdate) -> DepthSyncOutput<DepthUpdate> {
+        let Some(last_update_id) = self.last_update_id else {
+            self.buffer.push(update);
+            return self.request_snapshot();
+        };
+        let expected = last_update_id + 1;
+        if update.final_update_id < expected {
+            return DepthSyncOutput::Synced(Vec::new());
+        }
+        if update.first_update_id > expected {
+            let got = update.first_update_id;
+            self.reset();
+            self.buffer.push(update);
+            self.snapshot_requested = true;
+            record_desync("spot", expected, got);
+            return DepthSyncOutput::Desynced { expected, got };
+        }
+        self.last_update_id = Some(update.final_update_id);
+        DepthSyncOutput::Synced(vec![update])
+    }
+
+    // On Synced the caller applies the snapshot first and then the returned updates
+    pub fn on_snapshot(&mut self, snapshot: &DepthSnapshot) -> DepthSyncOutput<DepthUpdate> {
+        #[cfg(feature = "tracing")]
+        let _span = snapshot_span("spot", snapshot, self.buffer.len());
+        self.snapshot_requested = false;
+        self.buffer.retain(|update| update.final_update_id > snapshot.last_update_id);
+
+        let mut expected = snapshot.last_update_id + 1;
+        if let Some(first) = self.buffer.first() {
+            if first.first_update_id > expected {
+                // Snapshot older than the buffered stream, a newer one is needed
+                return self.request_snapshot();
+            }
+        }
+        for (index, update) in self.buffer.iter().enumerate() {
+            if update.first_update_id > expected {
+                // The updates from the gap on are what the next snapshot has to line up with
+                let got = update.first_update_id;
+                let restart_from = self.buffer.split_off(index);
+                self.reset();
+                self.buffer = restart_from;
+                self.snapshot_requested = true;
+                record_desync("spot", expected, got);
+                return DepthSyncOutput::Desynced { expected, got };
+            }
+            expected = update.final_update_id + 1;
+        }
+        self.last_update_id = Some(expected - 1);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(last_update_id = expected - 1, updates = self.buffer.len(), "depth synced");
+        DepthSyncOutput::Synced(std::mem::take(&mut self.buffer))
+    }
+
+    fn request_snapshot<U>(&mut self) -> DepthSyncOutput<U> {
+        if self.snapshot_requested {
+            DepthSyncOutput::Buffering
+        } else {
+            self.snapshot_requested = true;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(market = "spot", buffered = self.buffer.len(), "depth snapshot needed");
+            DepthSyncOutput::NeedSnapshot
+        }
+    }
+}
+
+// Futures diff depth sync: drop updates with `u < lastUpdateId`, require the first applied update to
+// satisfy `U <= lastUpdateId <= u` and every later one to carry the previous `u` as its `pu`. The first
+// update may arrive after the snapshot when nothing was buffered, the rules then apply to live updates
+#[cfg(feature = "futures")]
+#[derive(Clone, Debug, Default)]
+pub struct FuturesDepthSync {
+    buffer: Vec<futures::DepthUpdate>,
+    last_update_id: Option<u64>,
+    awaiting_first_update: bool,
+    snapshot_requested: bool,
+}
+
+#[cfg(feature = "futures")]
+impl FuturesDepthSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.last_update_id.is_some()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn on_update(&mut self, update: futures::DepthUpdate) -> DepthSyncOutput<futures::DepthUpdate> {
+        let Some(last_update_id) = self.last_update_id else {
+            self.buffer.push(update);
+            return self.request_snapshot();
+        };
+        if self.awaiting_first_update {
+            if update.final_update_id < last_update_id {
+                return DepthSyncOutput::Synced(Vec::new());
+            }
+            if update.first_update_id > last_update_id {
+                let got = update.first_update_id;
+                self.desync(Some(update));
+                record_desync("futures", last_update_id, got);
+                return DepthSyncOutput::Desynced { expected: last_update_id, got };
+            }
+            self.awaiting_first_update = false;
+            self.last_update_id = Some(update.final_update_id);
+            return DepthSyncOutput::Synced(vec![update]);
+        }
+        if update.previous_final_update_id != last_update_id {
+            let got = update.previous_final_update_id;
+            self.desync(Some(update));
+            record_desync("futures", last_update_id, got);
+            return DepthSyncOutput::Desynced { expected: last_update_id, got };
+        }
+        self.last_update_id = Some(update.final_update_id);
+        DepthSyncOutput::Synced(vec![update])
+    }
+
+    // On Synced the caller applies the snapshot first and then the returned updates
+    pub fn on_snapshot(&mut self, snapshot: &DepthSnapshot) -> DepthSyncOutput<futures::DepthUpdate> {
+        #[cfg(feature = "tracing")]
+        let _span = snapshot_span("futures", snapshot, self.buffer.len());
+        self.snapshot_requested = false;
+        self.buffer.retain(|update| update.final_update_id >= snapshot.last_update_id);
+
+        let Some(first) = self.buffer.first() else {
+            self.last_update_id = Some(snapshot.last_update_id);
+            self.awaiting_first_update = true;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(last_update_id = snapshot.last_update_id, updates = 0, "depth synced");
+            return DepthSyncOutput::Synced(Vec::new());
+        };
+        if first.first_update_id > snapshot.last_update_id {
+            // Snapshot older than the buffered stream, a newer one is needed
+            return self.request_snapshot();
+        }
+        let mut previous = first.final_update_id;
+        for (index, update) in self.buffer.iter().enumerate().skip(1) {
+            if update.previous_final_update_id != previous {
+                // The updates from the gap on are what the next snapshot has to line up with
+                let got = update.previous_final_update_id;
+                let restart_from = self.buffer.split_off(index);
+                self.desync(restart_from);
+                record_desync("futures", previous, got);
+                return DepthSyncOutput::Desynced { expected: previous, got };
+            }
+            previous = update.final_update_id;
+        }
+        self.last_update_id = Some(previous);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(last_update_id = previous, updates = self.buffer.len(), "depth synced");
+        DepthSyncOutput::Synced(std::mem::take(&mut self.buffer))
+    }
+
+    fn desync(&mut self, restart_from: impl IntoIterator<Item = futures::DepthUpdate>) {
+        self.reset();
+        self.buffer.extend(restart_from);
+        self.snapshot_requested = true;
+    }
+
+    fn request_snapshot<U>(&mut self) -> DepthSyncOutput<U> {
+        if self.snapshot_requested {
+            DepthSyncOutput::Buffering
+        } else {
+            self.snapshot_requested = true;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(market = "futures", buffered = self.buffer.len(), "depth snapshot needed");
+            DepthSyncOutput::NeedSnapshot
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: f64, quantity: f64) -> PriceLevel {
+        PriceLevel { price, quantity }
+    }
+
+    fn snapshot() -> DepthSnapshot {
+        DepthSnapshot::from_json(r#"{"lastUpdateId":100,"bids":[["100.0","1.0"],["99.5","2.0"],["99.0","3.0"]],"asks":[["100.5","1.5"],["101.0","2.5"],["102.0","4.0"]]}"#).unwrap()
+    }
+
+    #[test]
+    fn test_order_book_from_snapshot() {
+        let mut book = OrderBook::new();
+        book.apply_snapshot(&snapshot());
+
+        assert_eq!(book.last_update_id(), 100);
+        assert_eq!(book.best_bid(), Some(level(100.0, 1.0)));
+        assert_eq!(book.best_ask(), Some(level(100.5, 1.5)));
+        assert_eq!(book.mid_price(), Some(100.25));
+        assert_eq!(book.levels(2), (
+            vec![level(100.0, 1.0), level(99.5, 2.0)],
+            vec![level(100.5, 1.5), level(101.0, 2.5)],
+        ));
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_order_book_apply_updates() {
+        let mut book = OrderBook::new();
+        book.apply_snapshot(&snapshot());
+
+        // Replace the best bid, remove the best ask, add a level on each side
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":101,"u":103,"b":[["100.0","0.4"],["100.2","0.7"]],"a":[["100.5","0"],["100.8","3.0"]]}"#).unwrap();
+        book.apply_update(&update);
+        assert_eq!(book.last_update_id(), 103);
+        assert_eq!(book.levels(3), (
+            vec![level(100.2, 0.7), level(100.0, 0.4), level(99.5, 2.0)],
+            vec![level(100.8, 3.0), level(101.0, 2.5), level(102.0, 4.0)],
+        ));
+
+        // Futures updates feed the same book; removing an unknown level is a no-op
+        #[cfg(feature = "futures")]
+        {
+            let update = futures::DepthUpdate::from_json(r#"{"e":"depthUpdate","E":2,"T":2,"s":"BTCUSDT","U":104,"u":105,"pu":103,"b":[["100.2","0"],["98.0","0"]],"a":[["101.0","1.0"]]}"#).unwrap();
+            book.apply_update(&update);
+            assert_eq!(book.last_update_id(), 105);
+            assert_eq!(book.best_bid(), Some(level(100.0, 0.4)));
+            assert_eq!(book.levels(10), (
+                vec![level(100.0, 0.4), level(99.5, 2.0), level(99.0, 3.0)],
+                vec![level(100.8, 3.0), level(101.0, 1.0), level(102.0, 4.0)],
+            ));
+        }
+    }
+
+    #[test]
+    fn test_order_book_total_quantity_within() {
+        let mut book = OrderBook::new();
+        assert_eq!(book.total_quantity_within(10.0), None);
+
+        book.apply_snapshot(&snapshot());
+        // Mid 100.25, 100 bps is about 1.0025 either side: bids down to 99.2475, asks up to 101.2525
+        assert_eq!(book.total_quantity_within(100.0), Some((3.0, 4.0)));
+        assert_eq!(book.total_quantity_within(1.0), Some((0.0, 0.0)));
+        assert_eq!(book.total_quantity_within(1_000.0), Some((6.0, 8.0)));
+    }
+
+    #[cfg(feature = "spot")]
+    fn spot_update(first_update_id: u64, final_update_id: u64) -> DepthUpdate {
+        DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id,
+            final_update_id,
+            bids: vec![level(100.0, final_update_id as f64)],
+            asks: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "spot")]
+    fn ids(updates: &DepthSyncOutput<DepthUpdate>) -> Vec<(u64, u64)> {
+        match updates {
+            DepthSyncOutput::Synced(updates) => updates.iter().map(|u| (u.first_update_id, u.final_update_id)).collect(),
+            other => panic!("Expected Synced, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_spot_depth_sync_happy_path() {
+        let mut sync = SpotDepthSync::new();
+        assert_eq!(sync.on_update(spot_update(90, 95)), DepthSyncOutput::NeedSnapshot);
+        assert_eq!(sync.on_update(spot_update(96, 99)), DepthSyncOutput::Buffering);
+        assert_eq!(sync.on_update(spot_update(100, 104)), DepthSyncOutput::Buffering);
+        assert_eq!(sync.on_update(spot_update(105, 107)), DepthSyncOutput::Buffering);
+
+        // lastUpdateId 100: the first two updates are stale, 100..104 spans 101
+        let output = sync.on_snapshot(&snapshot());
+        assert_eq!(ids(&output), vec![(100, 104), (105, 107)]);
+        assert!(sync.is_synced());
+
+        let mut book = OrderBook::new();
+        book.apply_snapshot(&snapshot());
+        if let DepthSyncOutput::Synced(updates) = output {
+            updates.iter().for_each(|update| book.apply_update(update));
+        }
+        assert_eq!(book.last_update_id(), 107);
+        assert_eq!(book.best_bid(), Some(level(100.0, 107.0)));
+
+        assert_eq!(ids(&sync.on_update(spot_update(108, 110))), vec![(108, 110)]);
+        assert_eq!(ids(&sync.on_update(spot_update(105, 110))), vec![]);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_spot_depth_sync_gap_forces_resync() {
+        let mut sync = SpotDepthSync::new();
+        sync.on_update(spot_update(101, 103));
+        assert_eq!(ids(&sync.on_snapshot(&snapshot())), vec![(101, 103)]);
+
+        assert_eq!(sync.on_update(spot_update(106, 108)), DepthSyncOutput::Desynced { expected: 104, got: 106 });
+        assert!(!sync.is_synced());
+        assert_eq!(sync.on_update(spot_update(109, 110)), DepthSyncOutput::Buffering);
+
+        let fresh = DepthSnapshot::from_json(r#"{"lastUpdateId":107,"bids":[],"asks":[]}"#).unwrap();
+        assert_eq!(ids(&sync.on_snapshot(&fresh)), vec![(106, 108), (109, 110)]);
+
+        // A gap inside the buffer keeps the updates after it for the next snapshot
+        let mut sync = SpotDepthSync::new();
+        sync.on_update(spot_update(101, 103));
+        sync.on_update(spot_update(106, 108));
+        assert_eq!(sync.on_snapshot(&snapshot()), DepthSyncOutput::Desynced { expected: 104, got: 106 });
+        assert_eq!(sync.on_update(spot_update(109, 110)), DepthSyncOutput::Buffering);
+        assert_eq!(ids(&sync.on_snapshot(&fresh)), vec![(106, 108), (109, 110)]);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_spot_depth_sync_stale_snapshot_requires_refetch() {
+        let mut sync = SpotDepthSync::new();
+        assert_eq!(sync.on_update(spot_update(120, 125)), DepthSyncOutput::NeedSnapshot);
+
+        // lastUpdateId 100 + 1 is below the first buffered U of 120
+        assert_eq!(sync.on_snapshot(&snapshot()), DepthSyncOutput::NeedSnapshot);
+        assert!(!sync.is_synced());
+        assert_eq!(sync.on_update(spot_update(126, 127)), DepthSyncOutput::Buffering);
+
+        let fresh = DepthSnapshot::from_json(r#"{"lastUpdateId":122,"bids":[],"asks":[]}"#).unwrap();
+        assert_eq!(ids(&sync.on_snapshot(&fresh)), vec![(120, 125), (126, 127)]);
+    }
+
+    #[cfg(feature = "futures")]
+    fn futures_update(first_update_id: u64, final_update_id: u64, previous_final_update_id: u64) -> futures::DepthUpdate {
+        futures::DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: 0,
+            transaction_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id,
+            final_update_id,
+            previous_final_update_id,
+            bids: Vec::new(),
+            asks: vec![level(101.0, final_update_id as f64)],
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    fn futures_ids(updates: &DepthSyncOutput<futures::DepthUpdate>) -> Vec<u64> {
+        match updates {
+            DepthSyncOutput::Synced(updates) => updates.iter().map(|u| u.final_update_id).collect(),
+            other => panic!("Expected Synced, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_futures_depth_sync_correct_sequence() {
+        let mut sync = FuturesDepthSync::new();
+        assert_eq!(sync.on_update(futures_update(90, 98, 89)), DepthSyncOutput::NeedSnapshot);
+        assert_eq!(sync.on_update(futures_update(99, 103, 98)), DepthSyncOutput::Buffering);
+        assert_eq!(sync.on_update(futures_update(104, 106, 103)), DepthSyncOutput::Buffering);
+
+        // lastUpdateId 100 lies within 99..103
+        assert_eq!(futures_ids(&sync.on_snapshot(&snapshot())), vec![103, 106]);
+        assert_eq!(futures_ids(&sync.on_update(futures_update(107, 110, 106))), vec![110]);
+        assert!(sync.is_synced());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_futures_depth_sync_pu_mismatch_desyncs() {
+        let mut sync = FuturesDepthSync::new();
+        sync.on_update(futures_update(95, 102, 94));
+        assert_eq!(futures_ids(&sync.on_snapshot(&snapshot())), vec![102]);
+
+        assert_eq!(sync.on_update(futures_update(105, 108, 104)), DepthSyncOutput::Desynced { expected: 102, got: 104 });
+        assert!(!sync.is_synced());
+        assert_eq!(sync.on_update(futures_update(109, 111, 108)), DepthSyncOutput::Buffering);
+
+        let fresh = DepthSnapshot::from_json(r#"{"lastUpdateId":107,"bids":[],"asks":[]}"#).unwrap();
+        assert_eq!(futures_ids(&sync.on_snapshot(&fresh)), vec![108, 111]);
+
+        // A gap inside the buffer is detected when the snapshot arrives
+        let mut sync = FuturesDepthSync::new();
+        sync.on_update(futures_update(95, 102, 94));
+        sync.on_update(futures_update(106, 108, 105));
+        assert_eq!(sync.on_snapshot(&snapshot()), DepthSyncOutput::Desynced { expected: 102, got: 105 });
+
+        // and the updates from the gap on stay buffered for the next one
+        assert_eq!(sync.on_update(futures_update(109, 111, 108)), DepthSyncOutput::Buffering);
+        assert_eq!(futures_ids(&sync.on_snapshot(&fresh)), vec![108, 111]);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_futures_depth_sync_snapshot_before_first_update() {
+        let mut sync = FuturesDepthSync::new();
+        assert_eq!(sync.on_snapshot(&snapshot()), DepthSyncOutput::Synced(Vec::new()));
+
+        // Stale updates are dropped, the first one spanning lastUpdateId 100 is applied whatever its pu
+        assert_eq!(sync.on_update(futures_update(95, 99, 94)), DepthSyncOutput::Synced(Vec::new()));
+        assert_eq!(futures_ids(&sync.on_update(futures_update(98, 103, 97))), vec![103]);
+        assert_eq!(futures_ids(&sync.on_update(futures_update(104, 106, 103))), vec![106]);
+        assert_eq!(sync.on_update(futures_update(108, 109, 107)), DepthSyncOutput::Desynced { expected: 106, got: 107 });
+
+        // A first update past lastUpdateId means the snapshot is too old
+        let mut sync = FuturesDepthSync::new();
+        sync.on_snapshot(&snapshot());
+        assert_eq!(sync.on_update(futures_update(101, 104, 100)), DepthSyncOutput::Desynced { expected: 100, got: 101 });
+        let fresh = DepthSnapshot::from_json(r#"{"lastUpdateId":102,"bids":[],"asks":[]}"#).unwrap();
+        assert_eq!(futures_ids(&sync.on_snapshot(&fresh)), vec![104]);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_futures_depth_sync_initial_event_boundaries() {
+        // u == lastUpdateId is kept and may be the first applied event
+        let mut sync = FuturesDepthSync::new();
+        sync.on_update(futures_update(95, 99, 94));
+        sync.on_update(futures_update(99, 100, 99));
+        sync.on_update(futures_update(101, 104, 100));
+        assert_eq!(futures_ids(&sync.on_snapshot(&snapshot())), vec![100, 104]);
+
+        // U == lastUpdateId is still a valid first event
+        let mut sync = FuturesDepthSync::new();
+        sync.on_update(futures_update(100, 103, 99));
+        assert_eq!(futures_ids(&sync.on_snapshot(&snapshot())), vec![103]);
+
+        // U == lastUpdateId + 1 means the snapshot is too old
+        let mut sync = FuturesDepthSync::new();
+        sync.on_update(futures_update(101, 103, 100));
+        assert_eq!(sync.on_snapshot(&snapshot()), DepthSyncOutput::NeedSnapshot);
+        assert!(!sync.is_synced());
+    }
+
+    #[test]
+    fn test_order_book_analytics() {
+        let mut book = OrderBook::new();
+        book.apply_snapshot(&snapshot());
+
+        // Bids 100.0 x 1, 99.5 x 2, 99.0 x 3; asks 100.5 x 1.5, 101.0 x 2.5, 102.0 x 4
+        assert_eq!(book.mid(), Some(100.25));
+        assert_eq!(book.microprice(), Some((100.0 * 1.5 + 100.5 * 1.0) / 2.5));
+        assert_eq!(book.imbalance(1), Some(-0.2));
+        assert_eq!(book.imbalance(3), Some((6.0 - 8.0) / 14.0));
+        assert_eq!(book.quantity_within_bps(BookSide::Bid, 100.0), Some(3.0));
+        assert_eq!(book.quantity_within_bps(BookSide::Ask, 100.0), Some(4.0));
+        assert_eq!(book.vwap_to_fill(BookSide::Ask, 1.5), Some(100.5));
+        assert_eq!(book.vwap_to_fill(BookSide::Ask, 2.0), Some((1.5 * 100.5 + 0.5 * 101.0) / 2.0));
+        assert_eq!(book.vwap_to_fill(BookSide::Bid, 6.0), Some((100.0 + 199.0 + 297.0) / 6.0));
+        assert_eq!(book.vwap_to_fill(BookSide::Bid, 6.5), None);
+        assert_eq!(book.vwap_to_fill(BookSide::Bid, 0.0), None);
+    }
+
+    #[test]
+    fn test_order_book_analytics_one_sided_and_empty() {
+        let mut book = OrderBook::new();
+        assert_eq!(book.mid(), None);
+        assert_eq!(book.microprice(), None);
+        assert_eq!(book.imbalance(5), None);
+        assert_eq!(book.quantity_within_bps(BookSide::Bid, 10.0), None);
+        assert_eq!(book.vwap_to_fill(BookSide::Ask, 1.0), None);
+
+        book.apply_snapshot(&DepthSnapshot::from_json(r#"{"lastUpdateId":1,"bids":[["100.0","2.0"]],"asks":[]}"#).unwrap());
+        assert_eq!(book.mid(), None);
+        assert_eq!(book.microprice(), None);
+        assert_eq!(book.imbalance(5), Some(1.0));
+        assert_eq!(book.quantity_within_bps(BookSide::Bid, 10.0), None);
+        assert_eq!(book.vwap_to_fill(BookSide::Bid, 1.0), Some(100.0));
+        assert_eq!(book.vwap_to_fill(BookSide::Ask, 1.0), None);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_book_validator_reports_crossed_book_and_backwards_ids() {
+        let mut book = ValidatedBook::new(BookValidator::new());
+        assert_eq!(book.apply_snapshot(&snapshot()), Vec::new());
+
+        // A bid above the best ask crosses the book
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":101,"u":101,"b":[["101.0","1.0"]],"a":[]}"#).unwrap();
+        assert_eq!(book.apply_update(&update), vec![Violation::Crossed { best_bid: 101.0, best_ask: 100.5 }]);
+
+        // Uncrossing update with a good id, then one going backwards with a negative quantity
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":2,"s":"BTCUSDT","U":102,"u":102,"b":[["101.0","0"]],"a":[]}"#).unwrap();
+        assert_eq!(book.apply_update(&update), Vec::new());
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":3,"s":"BTCUSDT","U":98,"u":99,"b":[],"a":[["103.0","-1.0"]]}"#).unwrap();
+        assert_eq!(book.apply_update(&update), vec![
+            Violation::UpdateIdNotIncreasing { previous: 102, got: 99 },
+            Violation::NegativeQuantity { side: BookSide::Ask, price: 103.0, quantity: -1.0 },
+        ]);
+
+        // A negative price sorts after the positive ones in the book
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":4,"s":"BTCUSDT","U":103,"u":103,"b":[["-1.0","1.0"]],"a":[["103.0","0"]]}"#).unwrap();
+        assert_eq!(book.apply_update(&update), vec![Violation::UnsortedLevels { side: BookSide::Bid, previous: 100.0, price: -1.0 }]);
+
+        // A new snapshot restarts the sequence
+        assert_eq!(book.apply_snapshot(&snapshot()), Vec::new());
+        assert_eq!(book.book().last_update_id(), 100);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_validated_book_without_validation() {
+        let mut book: ValidatedBook = ValidatedBook::default();
+        book.apply_snapshot(&snapshot());
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":90,"u":90,"b":[["101.0","1.0"]],"a":[]}"#).unwrap();
+        assert_eq!(book.apply_update(&update), Vec::new());
+        assert_eq!(book.book().best_bid(), Some(level(101.0, 1.0)));
+    }
+}
@@ -0,0 +1,188 @@
+//! Venue independent order updates converted from spot and futures user data events.
+
+use crate::rest::TradeFill;
+use crate::websocket::{ExecutionType, OrderStatus, Side};
+
+cfg_spot! {
+    use crate::websocket::ExecutionReport;
+}
+cfg_futures! {
+    use crate::websocket::OrderTradeUpdate;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Venue {
+    Spot,
+    UsdMFutures,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderUpdate {
+    pub venue: Venue,                    // Where the order lives
+    pub symbol: String,                  // Symbol
+    pub order_id: u64,                   // Order ID
+    pub client_order_id: String,         // Client order ID of the order, not of a cancel request
+    pub side: Side,                      // Side
+    pub order_type: String,              // Order type
+    pub execution_type: ExecutionType,   // What happened to the order
+    pub status: OrderStatus,             // Order status after the update
+    pub quantity: f64,                   // Order quantity
+    pub price: f64,                      // Order price, 0 for market orders
+    pub last_fill: Option<TradeFill>,    // Present on trade executions
+    pub cumulative_quantity: f64,        // Filled quantity so far
+    pub average_price: Option<f64>,      // Average fill price, None before the first fill
+    pub event_time: u64,                 // Event time
+    pub transaction_time: u64,           // Transaction time
+}
+
+impl OrderUpdate {
+    pub fn is_fill(&self) -> bool {
+        self.last_fill.is_some()
+    }
+
+    pub fn remaining_quantity(&self) -> f64 {
+        (self.quantity - self.cumulative_quantity).max(0.0)
+    }
+}
+
+// Drops stop and iceberg quantities, order list id, reject reason, on-book flag, creation and working
+// times and the quote quantities; the cumulative quote quantity only feeds the average price
+#[cfg(feature = "spot")]
+impl From<ExecutionReport> for OrderUpdate {
+    fn from(report: ExecutionReport) -> Self {
+        let last_fill = TradeFill::from_execution_report(&report);
+        let average_price = (report.cumulative_filled_quantity > 0.0)
+            .then(|| report.cumulative_quote_quantity / report.cumulative_filled_quantity);
+        // On cancels `c` is the cancel request's ID and `C` the order's own
+        let client_order_id = if report.original_client_order_id.is_empty() {
+            report.client_order_id
+        } else {
+            report.original_client_order_id
+        };
+        OrderUpdate {
+            venue: Venue::Spot,
+            symbol: report.symbol,
+            order_id: report.order_id,
+            client_order_id,
+            side: report.side,
+            order_type: report.order_type,
+            execution_type: report.execution_type,
+            status: report.order_status,
+            quantity: report.order_quantity,
+            price: report.order_price,
+            last_fill,
+            cumulative_quantity: report.cumulative_filled_quantity,
+            average_price,
+            event_time: report.event_time,
+            transaction_time: report.transaction_time,
+        }
+    }
+}
+
+// Drops position side, reduce-only and close-position flags, stop, activation and callback settings,
+// bids and asks notional, realized profit, STP, price match and GTD fields. A fill without a pushed
+// commission has a commission of 0 and no commission asset
+#[cfg(feature = "futures")]
+impl From<OrderTradeUpdate> for OrderUpdate {
+    fn from(update: OrderTradeUpdate) -> Self {
+        let order = update.order;
+        let is_trade = matches!(order.execution_type, ExecutionType::Trade | ExecutionType::Calculated);
+        let last_fill = match u64::try_from(order.trade_id) {
+            Ok(trade_id) if is_trade && order.last_filled_quantity > 0.0 => Some(TradeFill {
+                symbol: order.symbol.clone(),
+                trade_id,
+                order_id: order.order_id,
+                side: order.side,
+                price: order.last_filled_price,
+                quantity: order.last_filled_quantity,
+                quote_quantity: order.last_filled_price * order.last_filled_quantity,
+                commission: order.commission.unwrap_or(0.0),
+                commission_asset: order.commission_asset,
+                time: order.trade_time,
+                is_maker: order.is_maker,
+            }),
+            _ => None,
+        };
+        OrderUpdate {
+            venue: Venue::UsdMFutures,
+            symbol: order.symbol,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            side: order.side,
+            order_type: order.order_type,
+            execution_type: order.execution_type,
+            status: order.order_status,
+            quantity: order.original_quantity,
+            price: order.original_price,
+            last_fill,
+            cumulative_quantity: order.filled_accumulated_quantity,
+            average_price: (order.average_price > 0.0).then_some(order.average_price),
+            event_time: update.event_time,
+            transaction_time: update.transaction_time,
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "spot", feature = "futures")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_order_update_from_execution_report() {
+        let json = r#"{"e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"cancelRequest","S":"BUY","o":"LIMIT","f":"GTC","q":"2.00000000","p":"0.10264410","P":"0.00000000","F":"0.00000000","g":-1,"C":"myOrder","x":"TRADE","X":"PARTIALLY_FILLED","r":"NONE","i":4293153,"l":"0.50000000","z":"1.00000000","L":"0.10260000","n":"0.00001","N":"BNB","T":1499405658657,"t":77,"I":8641984,"w":true,"m":true,"M":false,"O":1499405658657,"Z":"0.10262000","Y":"0.05130000","Q":"0.00000000"}"#;
+        let update = OrderUpdate::from(ExecutionReport::from_json(json).unwrap());
+
+        assert_eq!(update.venue, Venue::Spot);
+        assert_eq!((update.symbol.as_str(), update.order_id, update.client_order_id.as_str()), ("ETHBTC", 4293153, "myOrder"));
+        assert_eq!((update.side, update.execution_type, update.status), (Side::Buy, ExecutionType::Trade, OrderStatus::PartiallyFilled));
+        assert_eq!(update.last_fill, Some(TradeFill {
+            symbol: "ETHBTC".to_string(),
+            trade_id: 77,
+            order_id: 4293153,
+            side: Side::Buy,
+            price: 0.1026,
+            quantity: 0.5,
+            quote_quantity: 0.0513,
+            commission: 0.00001,
+            commission_asset: Some("BNB".to_string()),
+            time: 1499405658657,
+            is_maker: true,
+        }));
+        assert_eq!(update.cumulative_quantity, 1.0);
+        assert_eq!(update.remaining_quantity(), 1.0);
+        assert_eq!(update.average_price, Some(0.10262));
+        assert_eq!((update.event_time, update.transaction_time), (1499405658658, 1499405658657));
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_order_update_from_order_trade_update() {
+        // No commission pushed and no fill yet
+        let json = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"LIMIT","f":"GTC","q":"0.002","p":"7000","ap":"0","sp":"0","x":"NEW","X":"NEW","i":8886774,"l":"0","z":"0","L":"0","T":1568879465650,"t":0,"b":"0","a":"14","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"LIMIT","ps":"BOTH","cp":false,"rp":"0"}}"#;
+        let update = OrderUpdate::from(OrderTradeUpdate::from_json(json).unwrap());
+        assert_eq!(update.venue, Venue::UsdMFutures);
+        assert_eq!((update.client_order_id.as_str(), update.side, update.status), ("TEST", Side::Sell, OrderStatus::New));
+        assert_eq!((update.quantity, update.price), (0.002, 7000.0));
+        assert!(!update.is_fill());
+        assert_eq!(update.average_price, None);
+
+        let json = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465700,"T":1568879465699,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"LIMIT","f":"GTC","q":"0.002","p":"7000","ap":"7000.5","sp":"0","x":"TRADE","X":"FILLED","i":8886774,"l":"0.002","z":"0.002","L":"7000.5","N":"USDT","n":"0.0056","T":1568879465699,"t":42,"b":"0","a":"0","m":true,"R":false,"wt":"CONTRACT_PRICE","ot":"LIMIT","ps":"BOTH","cp":false,"rp":"0"}}"#;
+        let update = OrderUpdate::from(OrderTradeUpdate::from_json(json).unwrap());
+        assert_eq!(update.last_fill, Some(TradeFill {
+            symbol: "BTCUSDT".to_string(),
+            trade_id: 42,
+            order_id: 8886774,
+            side: Side::Sell,
+            price: 7000.5,
+            quantity: 0.002,
+            quote_quantity: 7000.5 * 0.002,
+            commission: 0.0056,
+            commission_asset: Some("USDT".to_string()),
+            time: 1568879465699,
+            is_maker: true,
+        }));
+        assert_eq!((update.cumulative_quantity, update.average_price), (0.002, Some(7000.5)));
+        assert_eq!(update.transaction_time, 1568879465699);
+    }
+}
@@ -0,0 +1,180 @@
+//! Futures positions and PnL maintained from user data stream events.
+
+use std::collections::HashMap;
+
+use crate::websocket::{AccountUpdate, ExecutionType, FuturesOrder, OrderTradeUpdate, PositionSide, Side};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PositionState {
+    pub size: f64,                       // Signed position size, negative when short
+    pub entry_price: f64,                // Average entry price, 0 when flat
+    pub realized_pnl: f64,               // Realized PnL accumulated by the tracker, before commission
+    pub commission: f64,                 // Commission paid on fills, in the commission asset
+}
+
+impl PositionState {
+    pub fn is_flat(&self) -> bool {
+        self.size == 0.0
+    }
+
+    pub fn unrealized_pnl(&self, mark_price: f64) -> f64 {
+        self.size * (mark_price - self.entry_price)
+    }
+
+    // Applies a fill of signed `quantity` at `price`, returning the PnL it realizes
+    fn apply_fill(&mut self, quantity: f64, price: f64, is_reduce_only: bool) -> f64 {
+        let mut quantity = quantity;
+        if self.size == 0.0 || self.size.signum() == quantity.signum() {
+            if is_reduce_only {
+                return 0.0;
+            }
+            let size = self.size + quantity;
+            self.entry_price = (self.size.abs() * self.entry_price + quantity.abs() * price) / size.abs();
+            self.size = size;
+            return 0.0;
+        }
+        if is_reduce_only && quantity.abs() > self.size.abs() {
+            quantity = -self.size;
+        }
+        let closed = quantity.abs().min(self.size.abs());
+        let realized = closed * (price - self.entry_price) * self.size.signum();
+        let size = self.size + quantity;
+        if size == 0.0 {
+            self.entry_price = 0.0;
+        } else if size.signum() != self.size.signum() {
+            // Flipped, the remainder is a new position opened at the fill price
+            self.entry_price = price;
+        }
+        self.size = size;
+        realized
+    }
+}
+
+// Positions keyed by symbol and position side, so hedge mode LONG and SHORT legs are tracked apart.
+// ORDER_TRADE_UPDATE fills move the position and realize PnL, preferring the exchange's `rp` when it
+// is non-zero; ACCOUNT_UPDATE positions overwrite size and entry price as the exchange's view
+#[derive(Clone, Debug, Default)]
+pub struct PositionTracker {
+    positions: HashMap<(String, PositionSide), PositionState>,
+    mark_prices: HashMap<String, f64>,
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PnL realized by the fill, None if the update is not a fill
+    pub fn on_order_trade_update(&mut self, update: &OrderTradeUpdate) -> Option<f64> {
+        self.apply_order(&update.order)
+    }
+
+    pub fn apply_order(&mut self, order: &FuturesOrder) -> Option<f64> {
+        if !matches!(order.execution_type, ExecutionType::Trade | ExecutionType::Calculated) || order.last_filled_quantity == 0.0 {
+            return None;
+        }
+        let quantity = match order.side {
+            Side::Buy => order.last_filled_quantity,
+            Side::Sell => -order.last_filled_quantity,
+        };
+        let position = self.positions.entry((order.symbol.clone(), order.position_side)).or_default();
+        let computed = position.apply_fill(quantity, order.last_filled_price, order.is_reduce_only);
+        let realized = if order.realized_profit != 0.0 { order.realized_profit } else { computed };
+        position.realized_pnl += realized;
+        position.commission += order.commission.unwrap_or(0.0);
+        Some(realized)
+    }
+
+    pub fn on_account_update(&mut self, update: &AccountUpdate) {
+        for reported in &update.update_data.positions {
+            let position = self.positions.entry((reported.symbol.clone(), reported.position_side)).or_default();
+            position.size = reported.position_amount;
+            position.entry_price = if reported.position_amount == 0.0 { 0.0 } else { reported.entry_price };
+        }
+    }
+
+    pub fn set_mark_price(&mut self, symbol: &str, mark_price: f64) {
+        self.mark_prices.insert(symbol.to_string(), mark_price);
+    }
+
+    pub fn position(&self, symbol: &str, position_side: PositionSide) -> Option<&PositionState> {
+        self.positions.get(&(symbol.to_string(), position_side))
+    }
+
+    // None until a mark price for the symbol has been set
+    pub fn unrealized_pnl(&self, symbol: &str, position_side: PositionSide) -> Option<f64> {
+        let mark_price = self.mark_prices.get(symbol)?;
+        Some(self.position(symbol, position_side)?.unrealized_pnl(*mark_price))
+    }
+
+    // Realized PnL over every position side of the symbol
+    pub fn realized_pnl(&self, symbol: &str) -> f64 {
+        self.positions.iter().filter(|((position_symbol, _), _)| position_symbol == symbol).map(|(_, position)| position.realized_pnl).sum()
+    }
+
+    pub fn reset(&mut self) {
+        self.positions.clear();
+        self.mark_prices.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(side: &str, quantity: f64, price: f64, reduce_only: bool, realized_profit: f64) -> OrderTradeUpdate {
+        OrderTradeUpdate::from_json(&format!(
+            r#"{{"e":"ORDER_TRADE_UPDATE","E":1,"T":1,"o":{{"s":"BTCUSDT","c":"c","S":"{side}","o":"MARKET","f":"GTC","q":"{quantity}","p":"0","ap":"{price}","sp":"0","x":"TRADE","X":"FILLED","i":1,"l":"{quantity}","z":"{quantity}","L":"{price}","N":"USDT","n":"0.01","T":1,"t":1,"b":"0","a":"0","m":false,"R":{reduce_only},"wt":"CONTRACT_PRICE","ot":"MARKET","ps":"BOTH","cp":false,"rp":"{realized_profit}"}}}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_position_tracker_partial_fills_and_flip() {
+        let mut tracker = PositionTracker::new();
+
+        // Build a long of 3 at an average of 100
+        assert_eq!(tracker.on_order_trade_update(&fill("BUY", 1.0, 97.0, false, 0.0)), Some(0.0));
+        assert_eq!(tracker.on_order_trade_update(&fill("BUY", 2.0, 101.5, false, 0.0)), Some(0.0));
+        let position = *tracker.position("BTCUSDT", PositionSide::Both).unwrap();
+        assert_eq!((position.size, position.entry_price), (3.0, 100.0));
+
+        // Partial close of 1 at 110 realizes 10, entry unchanged
+        assert_eq!(tracker.on_order_trade_update(&fill("SELL", 1.0, 110.0, false, 0.0)), Some(10.0));
+        assert_eq!(tracker.position("BTCUSDT", PositionSide::Both).unwrap().entry_price, 100.0);
+
+        // Selling 5 at 120 closes 2 for 40 and opens a short of 3 at 120
+        assert_eq!(tracker.on_order_trade_update(&fill("SELL", 5.0, 120.0, false, 0.0)), Some(40.0));
+        let position = *tracker.position("BTCUSDT", PositionSide::Both).unwrap();
+        assert_eq!((position.size, position.entry_price), (-3.0, 120.0));
+        tracker.set_mark_price("BTCUSDT", 115.0);
+        assert_eq!(tracker.unrealized_pnl("BTCUSDT", PositionSide::Both), Some(15.0));
+
+        // Reduce-only buy larger than the short only closes it: 3 * (120 - 118) = 6
+        assert_eq!(tracker.on_order_trade_update(&fill("BUY", 4.0, 118.0, true, 0.0)), Some(6.0));
+        let position = *tracker.position("BTCUSDT", PositionSide::Both).unwrap();
+        assert!(position.is_flat());
+        assert_eq!(position.entry_price, 0.0);
+        assert_eq!(tracker.realized_pnl("BTCUSDT"), 56.0);
+        assert!((position.commission - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_position_tracker_prefers_rp_and_account_update() {
+        let mut tracker = PositionTracker::new();
+        tracker.on_order_trade_update(&fill("BUY", 2.0, 100.0, false, 0.0));
+        // Exchange reported realized profit wins over the recomputed 10
+        assert_eq!(tracker.on_order_trade_update(&fill("SELL", 1.0, 110.0, false, 9.5)), Some(9.5));
+
+        let update = AccountUpdate::from_json(r#"{"e":"ACCOUNT_UPDATE","E":2,"T":2,"a":{"m":"ORDER","B":[],"P":[{"s":"BTCUSDT","pa":"1.5","ep":"101.0","cr":"0","up":"0","mt":"cross","iw":"0","ps":"BOTH"}]}}"#).unwrap();
+        tracker.on_account_update(&update);
+        let position = *tracker.position("BTCUSDT", PositionSide::Both).unwrap();
+        assert_eq!((position.size, position.entry_price, position.realized_pnl), (1.5, 101.0, 9.5));
+        assert_eq!(tracker.unrealized_pnl("BTCUSDT", PositionSide::Both), None);
+
+        // Order updates other than fills are ignored
+        let mut update = fill("BUY", 1.0, 100.0, false, 0.0);
+        update.order.execution_type = ExecutionType::New;
+        assert_eq!(tracker.on_order_trade_update(&update), None);
+    }
+}
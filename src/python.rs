@@ -0,0 +1,345 @@
+//! pyo3 classes wrapping the spot events and the subscription request builder, built into a Python
+//! extension module by the `python/` crate.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error::{parse, CodecError};
+use crate::websocket::{
+    AggTrade, DepthSpeed, DepthUpdate, Kline, KlineEvent, KlineInterval, SpotEvent, StreamName, StreamNameError,
+    SubscriptionRequest, SubscriptionRequestBuilder, Trade,
+};
+
+// Parse failures surface as ValueError with the full context of the Display impl
+impl From<CodecError> for PyErr {
+    fn from(error: CodecError) -> Self {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+impl From<StreamNameError> for PyErr {
+    fn from(error: StreamNameError) -> Self {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+// Frozen class around a codec type with a getter per listed field, `from_json` and `__repr__` from the
+// Display impl. Methods in the trailing block are added to the same `#[pymethods]`
+macro_rules! py_class {
+    ($class:ident($inner:ty) as $name:literal { $($field:ident: $type:ty),* $(,)? } { $($methods:tt)* }) => {
+        #[pyclass(name = $name, module = "s9_binance_codec", frozen)]
+        pub struct $class(pub $inner);
+
+        #[pymethods]
+        impl $class {
+            #[staticmethod]
+            fn from_json(json: &str) -> PyResult<Self> {
+                Ok($class(parse::<$inner>(json)?))
+            }
+
+            $(
+                #[getter]
+                fn $field(&self) -> $type {
+                    Clone::clone(&self.0.$field)
+                }
+            )*
+
+            fn __repr__(&self) -> String {
+                self.0.to_string()
+            }
+
+            $($methods)*
+        }
+    };
+}
+
+py_class!(PyTrade(Trade) as "Trade" {
+    event_type: Option<String>,
+    event_time: Option<u64>,
+    symbol: String,
+    trade_id: u64,
+    price: f64,
+    quantity: f64,
+    trade_time: u64,
+    is_buyer_market_maker: bool,
+} {});
+
+py_class!(PyAggTrade(AggTrade) as "AggTrade" {
+    event_type: String,
+    event_time: u64,
+    symbol: String,
+    aggregate_trade_id: u64,
+    price: f64,
+    quantity: f64,
+    first_trade_id: u64,
+    last_trade_id: u64,
+    trade_time: u64,
+    is_buyer_market_maker: bool,
+} {});
+
+py_class!(PyKline(Kline) as "Kline" {
+    start_time: u64,
+    close_time: u64,
+    symbol: String,
+    first_trade_id: i64,
+    last_trade_id: i64,
+    open_price: f64,
+    close_price: f64,
+    high_price: f64,
+    low_price: f64,
+    volume: f64,
+    number_of_trades: u64,
+    is_closed: bool,
+    quote_asset_volume: f64,
+    taker_buy_base_asset_volume: f64,
+    taker_buy_quote_asset_volume: f64,
+} {
+    // Binance notation, e.g. "1m"
+    #[getter]
+    fn interval(&self) -> &'static str {
+        self.0.interval.as_str()
+    }
+});
+
+py_class!(PyKlineEvent(KlineEvent) as "KlineEvent" {
+    event_type: String,
+    event_time: u64,
+    symbol: String,
+} {
+    #[getter]
+    fn kline(&self) -> PyKline {
+        PyKline(self.0.kline.clone())
+    }
+});
+
+py_class!(PyDepthUpdate(DepthUpdate) as "DepthUpdate" {
+    event_type: String,
+    event_time: u64,
+    symbol: String,
+    first_update_id: u64,
+    final_update_id: u64,
+} {
+    // (price, quantity) tuples in wire order, a zero quantity removes the level
+    #[getter]
+    fn bids(&self) -> Vec<(f64, f64)> {
+        self.0.bids.iter().map(|level| (level.price, level.quantity)).collect()
+    }
+
+    #[getter]
+    fn asks(&self) -> Vec<(f64, f64)> {
+        self.0.asks.iter().map(|level| (level.price, level.quantity)).collect()
+    }
+});
+
+py_class!(PySubscriptionRequest(SubscriptionRequest) as "SubscriptionRequest" {
+    method: String,
+    id: u64,
+} {
+    #[getter]
+    fn params(&self) -> Vec<String> {
+        self.0.params.to_vec()
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        self.0.to_json().map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+});
+
+// Chaining like the Rust builder, every method returns the builder itself
+#[pyclass(name = "SubscriptionRequestBuilder", module = "s9_binance_codec")]
+#[derive(Default)]
+pub struct PySubscriptionRequestBuilder(SubscriptionRequestBuilder);
+
+impl PySubscriptionRequestBuilder {
+    fn chain(
+        mut slf: PyRefMut<'_, Self>,
+        step: impl FnOnce(SubscriptionRequestBuilder) -> SubscriptionRequestBuilder,
+    ) -> PyRefMut<'_, Self> {
+        slf.0 = step(std::mem::take(&mut slf.0));
+        slf
+    }
+}
+
+fn depth_speed(speed_ms: u16) -> PyResult<DepthSpeed> {
+    match speed_ms {
+        100 => Ok(DepthSpeed::Ms100),
+        1000 => Ok(DepthSpeed::Ms1000),
+        _ => Err(PyValueError::new_err(format!("invalid depth speed {}ms, expected 100 or 1000", speed_ms))),
+    }
+}
+
+#[pymethods]
+impl PySubscriptionRequestBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn id(slf: PyRefMut<'_, Self>, id: u64) -> PyRefMut<'_, Self> {
+        Self::chain(slf, |builder| builder.id(id))
+    }
+
+    fn unsubscribe(slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        Self::chain(slf, SubscriptionRequestBuilder::unsubscribe)
+    }
+
+    // Any stream name accepted by `StreamName::from_str`, e.g. "btcusdt@depth@100ms"
+    fn stream<'py>(slf: PyRefMut<'py, Self>, stream: &str) -> PyResult<PyRefMut<'py, Self>> {
+        let stream: StreamName = stream.parse()?;
+        Ok(Self::chain(slf, |builder| builder.stream(stream)))
+    }
+
+    fn trade<'py>(slf: PyRefMut<'py, Self>, symbol: &str) -> PyRefMut<'py, Self> {
+        Self::chain(slf, |builder| builder.trade(symbol))
+    }
+
+    fn agg_trade<'py>(slf: PyRefMut<'py, Self>, symbol: &str) -> PyRefMut<'py, Self> {
+        Self::chain(slf, |builder| builder.agg_trade(symbol))
+    }
+
+    fn kline<'py>(slf: PyRefMut<'py, Self>, symbol: &str, interval: &str) -> PyResult<PyRefMut<'py, Self>> {
+        let interval: KlineInterval = interval.parse().map_err(PyValueError::new_err)?;
+        Ok(Self::chain(slf, |builder| builder.kline(symbol, interval)))
+    }
+
+    fn mini_ticker<'py>(slf: PyRefMut<'py, Self>, symbol: &str) -> PyRefMut<'py, Self> {
+        Self::chain(slf, |builder| builder.mini_ticker(symbol))
+    }
+
+    fn ticker<'py>(slf: PyRefMut<'py, Self>, symbol: &str) -> PyRefMut<'py, Self> {
+        Self::chain(slf, |builder| builder.ticker(symbol))
+    }
+
+    fn book_ticker<'py>(slf: PyRefMut<'py, Self>, symbol: &str) -> PyRefMut<'py, Self> {
+        Self::chain(slf, |builder| builder.book_ticker(symbol))
+    }
+
+    #[pyo3(signature = (symbol, levels, speed_ms = 1000))]
+    fn depth<'py>(slf: PyRefMut<'py, Self>, symbol: &str, levels: u16, speed_ms: u16) -> PyResult<PyRefMut<'py, Self>> {
+        let speed = depth_speed(speed_ms)?;
+        Ok(Self::chain(slf, |builder| builder.depth(symbol, levels, speed)))
+    }
+
+    #[pyo3(signature = (symbol, speed_ms = 1000))]
+    fn diff_depth<'py>(slf: PyRefMut<'py, Self>, symbol: &str, speed_ms: u16) -> PyResult<PyRefMut<'py, Self>> {
+        let speed = depth_speed(speed_ms)?;
+        Ok(Self::chain(slf, |builder| builder.diff_depth(symbol, speed)))
+    }
+
+    // Fails on the first invalid stream, the builder is left as it is
+    fn build(&self) -> PyResult<PySubscriptionRequest> {
+        Ok(PySubscriptionRequest(self.0.clone().build()?))
+    }
+}
+
+// Trade, AggTrade, KlineEvent or DepthUpdate by the event type. User data and unknown events are returned
+// as the dict `json.loads` gives
+#[pyfunction]
+fn parse_event(py: Python<'_>, json: &str) -> PyResult<Py<PyAny>> {
+    let event = match SpotEvent::parse(json)? {
+        SpotEvent::Trade(trade) => Py::new(py, PyTrade(trade))?.into_any(),
+        SpotEvent::AggTrade(trade) => Py::new(py, PyAggTrade(trade))?.into_any(),
+        SpotEvent::Kline(kline) => Py::new(py, PyKlineEvent(kline))?.into_any(),
+        SpotEvent::DepthUpdate(depth) => Py::new(py, PyDepthUpdate(depth))?.into_any(),
+        SpotEvent::UserData(_) | SpotEvent::Unknown(_) => py.import("json")?.call_method1("loads", (json,))?.unbind(),
+    };
+    Ok(event)
+}
+
+// Adds the classes and `parse_event` to the extension module
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyTrade>()?;
+    module.add_class::<PyAggTrade>()?;
+    module.add_class::<PyKline>()?;
+    module.add_class::<PyKlineEvent>()?;
+    module.add_class::<PyDepthUpdate>()?;
+    module.add_class::<PySubscriptionRequest>()?;
+    module.add_class::<PySubscriptionRequestBuilder>()?;
+    module.add_function(wrap_pyfunction!(parse_event, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::PyDict;
+
+    use super::*;
+    use crate::testutil;
+
+    // Runs the Python assertions against a fresh module with the fixtures bound as globals
+    fn run(code: &std::ffi::CStr) {
+        Python::initialize();
+        Python::attach(|py| -> PyResult<()> {
+            let module = PyModule::new(py, "s9_binance_codec")?;
+            register(&module)?;
+            let globals = PyDict::new(py);
+            globals.set_item("codec", module)?;
+            globals.set_item("trade_json", testutil::sample_trade_json())?;
+            globals.set_item("agg_trade_json", testutil::sample_agg_trade_json())?;
+            globals.set_item("kline_json", testutil::sample_kline_json())?;
+            globals.set_item("depth_json", testutil::sample_depth_update_json())?;
+            globals.set_item("balance_json", testutil::sample_balance_update_json())?;
+            py.run(code, Some(&globals), None)
+        })
+        .unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    #[test]
+    fn test_parse_event_classes() {
+        let trade = parse::<Trade>(testutil::sample_trade_json()).unwrap();
+        Python::initialize();
+        Python::attach(|py| {
+            let repr = Py::new(py, PyTrade(trade.clone())).unwrap().bind(py).repr().unwrap().to_string();
+            assert_eq!(repr, trade.to_string());
+        });
+
+        run(cr#"
+import json
+
+trade = codec.parse_event(trade_json)
+wire = json.loads(trade_json)
+assert type(trade).__name__ == "Trade"
+assert (trade.symbol, trade.trade_id, trade.price) == (wire["s"], wire["t"], float(wire["p"]))
+assert trade.is_buyer_market_maker == wire["m"]
+assert repr(trade).startswith("TRADE " + wire["s"])
+
+agg = codec.AggTrade.from_json(agg_trade_json)
+assert agg.aggregate_trade_id == json.loads(agg_trade_json)["a"]
+
+kline = codec.parse_event(kline_json)
+wire = json.loads(kline_json)["k"]
+assert kline.kline.interval == wire["i"]
+assert (kline.kline.close_price, kline.kline.is_closed) == (float(wire["c"]), wire["x"])
+
+depth = codec.parse_event(depth_json)
+wire = json.loads(depth_json)
+assert depth.final_update_id == wire["u"]
+assert depth.bids == [(float(price), float(quantity)) for price, quantity in wire["b"]]
+
+assert codec.parse_event(balance_json)["e"] == "balanceUpdate"
+
+try:
+    codec.parse_event('{"e":"trade","E":1,"s":"BNBBTC","t":"12345"}')
+    raise AssertionError("expected a ValueError")
+except ValueError as error:
+    assert "trade" in str(error)
+"#);
+    }
+
+    #[test]
+    fn test_subscription_request_builder() {
+        run(cr#"
+request = codec.SubscriptionRequestBuilder().id(7).trade("btcusdt").kline("btcusdt", "1m").diff_depth("bnbbtc", 100).build()
+assert request.params == ["btcusdt@trade", "btcusdt@kline_1m", "bnbbtc@depth@100ms"]
+assert request.to_json() == '{"method":"SUBSCRIBE","params":["btcusdt@trade","btcusdt@kline_1m","bnbbtc@depth@100ms"],"id":7}'
+assert repr(request) == "SUBSCRIBE #7 btcusdt@trade,btcusdt@kline_1m,bnbbtc@depth@100ms"
+
+for invalid in (lambda: codec.SubscriptionRequestBuilder().kline("btcusdt", "7m"), lambda: codec.SubscriptionRequestBuilder().depth("btcusdt", 7).build()):
+    try:
+        invalid()
+        raise AssertionError("expected a ValueError")
+    except ValueError:
+        pass
+"#);
+    }
+}
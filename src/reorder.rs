@@ -0,0 +1,204 @@
+//! Reordering of events that arrive slightly out of order, e.g. after fanning frames across worker
+//! threads or merging the connections of several shards.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::websocket::event::BinanceEvent;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SequencerStats {
+    pub released: u64,                   // Events handed out
+    pub reordered: u64,                  // Events that arrived behind a later event and were moved before it
+    pub late: u64,                       // Events older than an event already released, handed out as they came
+    pub untimed: u64,                    // Events without an event time, handed out as they came
+}
+
+// Event time, sequence id and arrival, in that order of precedence
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct SortKey {
+    event_time: u64,
+    sequence_id: u64,
+    arrival: u64,
+}
+
+struct Pending<T> {
+    key: SortKey,
+    event: T,
+}
+
+impl<T> PartialEq for Pending<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Pending<T> {}
+
+impl<T> PartialOrd for Pending<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Pending<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+// Holds events back until the newest event time is `window` past theirs, or until more than `capacity`
+// are held, and releases them sorted by event time with the sequence id breaking ties. The window is in
+// the unit of the stream's event times, milliseconds unless the connection asked for microseconds.
+// Events taken by `push` are handed back by `pop` and `flush`, never cloned.
+pub struct EventSequencer<T> {
+    window: u64,
+    capacity: usize,
+    pending: BinaryHeap<Reverse<Pending<T>>>,
+    ready: VecDeque<T>,
+    arrivals: u64,
+    newest_event_time: Option<u64>,
+    newest_pushed: Option<(u64, u64)>,
+    last_released: Option<(u64, u64)>,
+    stats: SequencerStats,
+}
+
+impl<T: BinanceEvent> EventSequencer<T> {
+    pub fn new(window: u64, capacity: usize) -> Self {
+        EventSequencer {
+            window,
+            capacity: capacity.max(1),
+            pending: BinaryHeap::new(),
+            ready: VecDeque::new(),
+            arrivals: 0,
+            newest_event_time: None,
+            newest_pushed: None,
+            last_released: None,
+            stats: SequencerStats::default(),
+        }
+    }
+
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn push(&mut self, event: T) {
+        let Some(event_time) = event.event_time() else {
+            self.stats.untimed += 1;
+            self.release(event);
+            return;
+        };
+        let order = (event_time, event.sequence_id().unwrap_or(0));
+        if self.last_released.is_some_and(|released| order < released) {
+            self.stats.late += 1;
+            self.release(event);
+            return;
+        }
+        if self.newest_pushed.is_some_and(|newest| order < newest) {
+            self.stats.reordered += 1;
+        }
+        self.newest_pushed = self.newest_pushed.max(Some(order));
+        self.newest_event_time = self.newest_event_time.max(Some(event_time));
+
+        let key = SortKey { event_time, sequence_id: order.1, arrival: self.arrivals };
+        self.arrivals += 1;
+        self.pending.push(Reverse(Pending { key, event }));
+        self.release_expired();
+    }
+
+    // Next released event, in order
+    pub fn pop(&mut self) -> Option<T> {
+        self.ready.pop_front()
+    }
+
+    // Releases every held event, e.g. at shutdown or when the stream goes quiet
+    pub fn flush(&mut self) -> impl Iterator<Item = T> + '_ {
+        while let Some(Reverse(pending)) = self.pending.pop() {
+            self.release_pending(pending);
+        }
+        self.ready.drain(..)
+    }
+
+    // Events held back, not counting released events not popped yet
+    pub fn buffered(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn stats(&self) -> SequencerStats {
+        self.stats
+    }
+
+    fn release_expired(&mut self) {
+        let horizon = self.newest_event_time.map_or(0, |newest| newest.saturating_sub(self.window));
+        while let Some(Reverse(oldest)) = self.pending.peek() {
+            if oldest.key.event_time > horizon && self.pending.len() <= self.capacity {
+                break;
+            }
+            let Some(Reverse(pending)) = self.pending.pop() else { break };
+            self.release_pending(pending);
+        }
+    }
+
+    fn release_pending(&mut self, pending: Pending<T>) {
+        self.last_released = self.last_released.max(Some((pending.key.event_time, pending.key.sequence_id)));
+        self.release(pending.event);
+    }
+
+    fn release(&mut self, event: T) {
+        self.stats.released += 1;
+        self.ready.push_back(event);
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+    use crate::testutil::SampleTrade;
+    use crate::websocket::Trade;
+
+    fn trade(trade_id: u64, time: u64) -> Trade {
+        let json = SampleTrade { trade_id, event_time: time, trade_time: time, ..SampleTrade::default() }.to_json();
+        Trade::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn test_scrambled_trades_come_out_in_order() {
+        // Trades 1..=10, 10ms apart, with trades 4 and 5 sharing an event time
+        let times = [1000, 1010, 1020, 1030, 1030, 1050, 1060, 1070, 1080, 1090];
+        let scrambled = [2, 1, 3, 5, 4, 7, 6, 8, 10, 9];
+        let mut sequencer = EventSequencer::new(25, 100);
+        let mut released = Vec::new();
+        for id in scrambled {
+            sequencer.push(trade(id, times[id as usize - 1]));
+            released.extend(std::iter::from_fn(|| sequencer.pop()).map(|trade| trade.trade_id));
+        }
+        assert!(released.len() < 10 && sequencer.buffered() > 0);
+        released.extend(sequencer.flush().map(|trade| trade.trade_id));
+
+        assert_eq!(released, (1..=10).collect::<Vec<_>>());
+        assert_eq!(sequencer.stats(), SequencerStats { released: 10, reordered: 4, late: 0, untimed: 0 });
+    }
+
+    #[test]
+    fn test_late_arrival_is_counted_and_released() {
+        let mut sequencer = EventSequencer::new(100, 2);
+        for (id, time) in [(1, 1000), (2, 1010), (4, 1030)] {
+            sequencer.push(trade(id, time));
+        }
+        // Over capacity, trade 1 was released to make room
+        assert_eq!(sequencer.pop().map(|trade| trade.trade_id), Some(1));
+        assert_eq!(sequencer.buffered(), 2);
+
+        // Trade 3 is moved before the held trade 4 and pushes trade 2 out, trade 0 is older than the
+        // released trades and is handed out as it came
+        sequencer.push(trade(3, 1020));
+        sequencer.push(trade(0, 990));
+        let released: Vec<u64> = sequencer.flush().map(|trade| trade.trade_id).collect();
+        assert_eq!(released, vec![2, 0, 3, 4]);
+        assert_eq!(sequencer.stats(), SequencerStats { released: 5, reordered: 1, late: 1, untimed: 0 });
+    }
+}
@@ -0,0 +1,3870 @@
+//! REST API response models.
+
+#[cfg(feature = "futures")]
+pub mod futures;
+mod order_list;
+
+pub use self::order_list::*;
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::format::PriceFormat;
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::websocket::{de_option_string_to_f64, de_string_or_number_to_u64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, AccountBalance, Kline, KlineInterval, OrderStatus, PriceLevel, Side};
+
+cfg_spot! {
+    use crate::websocket::{AggTrade, BookTicker, ExecutionReport, ExecutionType, Trade};
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RateLimitType {
+    RequestWeight,
+    Orders,
+    RawRequests,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RateLimitInterval {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    pub rate_limit_type: RateLimitType,  // Rate limit type
+    pub interval: RateLimitInterval,     // Interval unit
+    pub interval_num: u32,               // Number of interval units per window
+    pub limit: u32,                      // Limit per window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,              // Current usage, only reported in ws-api responses
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RateLimitWindow {
+    pub interval: RateLimitInterval,     // Interval unit
+    pub interval_num: u32,               // Number of interval units per window
+}
+
+impl RateLimitWindow {
+    pub fn new(interval: RateLimitInterval, interval_num: u32) -> Self {
+        RateLimitWindow { interval, interval_num }
+    }
+
+    // Header suffix such as `1M` or `10S`
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        let unit_start = suffix.len().checked_sub(1)?;
+        let interval = match suffix.get(unit_start..)?.to_ascii_uppercase().as_str() {
+            "S" => RateLimitInterval::Second,
+            "M" => RateLimitInterval::Minute,
+            "H" => RateLimitInterval::Hour,
+            "D" => RateLimitInterval::Day,
+            _ => return None,
+        };
+        let interval_num = suffix[..unit_start].parse().ok()?;
+        Some(RateLimitWindow { interval, interval_num })
+    }
+}
+
+// Usage headers of a REST response, e.g. X-MBX-USED-WEIGHT-1M, X-MBX-ORDER-COUNT-10S and Retry-After
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateLimitHeaders {
+    pub used_weight: Vec<(RateLimitWindow, u32)>, // Request weight used per window
+    pub order_count: Vec<(RateLimitWindow, u32)>, // Orders placed per window
+    pub retry_after: Option<u64>,        // Seconds to back off after a 429 or 418
+}
+
+impl RateLimitHeaders {
+    pub fn parse<'a>(headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        const USED_WEIGHT: &str = "X-MBX-USED-WEIGHT-";
+        const ORDER_COUNT: &str = "X-MBX-ORDER-COUNT-";
+
+        let mut parsed = RateLimitHeaders::default();
+        for (name, value) in headers {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("Retry-After") {
+                parsed.retry_after = value.parse().ok();
+            } else if let Some(suffix) = strip_prefix_ignore_case(name, USED_WEIGHT) {
+                if let (Some(window), Ok(used)) = (RateLimitWindow::from_suffix(suffix), value.parse()) {
+                    parsed.used_weight.push((window, used));
+                }
+            } else if let Some(suffix) = strip_prefix_ignore_case(name, ORDER_COUNT) {
+                if let (Some(window), Ok(count)) = (RateLimitWindow::from_suffix(suffix), value.parse()) {
+                    parsed.order_count.push((window, count));
+                }
+            }
+        }
+        parsed
+    }
+
+    pub fn used_weight(&self, window: RateLimitWindow) -> Option<u32> {
+        self.used_weight.iter().find(|(w, _)| *w == window).map(|(_, used)| *used)
+    }
+
+    pub fn order_count(&self, window: RateLimitWindow) -> Option<u32> {
+        self.order_count.iter().find(|(w, _)| *w == window).map(|(_, count)| *count)
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = value.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&value[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitBudget {
+    pub limit: Option<u32>,              // Limit per window, once known from exchangeInfo or ws-api
+    pub used: u32,                       // Last reported usage
+}
+
+impl RateLimitBudget {
+    pub fn remaining(&self) -> Option<u32> {
+        self.limit.map(|limit| limit.saturating_sub(self.used))
+    }
+}
+
+// One budget view fed by REST headers as well as the rateLimits arrays of exchangeInfo and ws-api responses
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitTracker {
+    budgets: HashMap<(RateLimitType, RateLimitWindow), RateLimitBudget>,
+    retry_after: Option<u64>,
+}
+
+impl RateLimitTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Limits always update; usage only when the entry carries a count, as ws-api responses do
+    pub fn update_from_rate_limits(&mut self, rate_limits: &[RateLimit]) {
+        for rate_limit in rate_limits {
+            let window = RateLimitWindow::new(rate_limit.interval, rate_limit.interval_num);
+            let budget = self.budgets.entry((rate_limit.rate_limit_type, window)).or_default();
+            budget.limit = Some(rate_limit.limit);
+            if let Some(count) = rate_limit.count {
+                budget.used = count;
+            }
+        }
+    }
+
+    pub fn update_from_headers(&mut self, headers: &RateLimitHeaders) {
+        for (window, used) in &headers.used_weight {
+            self.budgets.entry((RateLimitType::RequestWeight, *window)).or_default().used = *used;
+        }
+        for (window, count) in &headers.order_count {
+            self.budgets.entry((RateLimitType::Orders, *window)).or_default().used = *count;
+        }
+        self.retry_after = headers.retry_after;
+    }
+
+    pub fn budget(&self, rate_limit_type: RateLimitType, window: RateLimitWindow) -> Option<&RateLimitBudget> {
+        self.budgets.get(&(rate_limit_type, window))
+    }
+
+    pub fn remaining(&self, rate_limit_type: RateLimitType, window: RateLimitWindow) -> Option<u32> {
+        self.budget(rate_limit_type, window).and_then(RateLimitBudget::remaining)
+    }
+
+    // Retry-After of the most recent response, None once a response arrives without it
+    pub fn retry_after(&self) -> Option<u64> {
+        self.retry_after
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "filterType", rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "camelCase")]
+pub enum Filter {
+    PriceFilter {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        min_price: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        max_price: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        tick_size: f64,
+    },
+    LotSize {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        min_qty: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        max_qty: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        step_size: f64,
+    },
+    MinNotional {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        min_notional: f64,
+        apply_to_market: bool,
+        avg_price_mins: u32,
+    },
+    Notional {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        min_notional: f64,
+        apply_min_to_market: bool,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        max_notional: f64,
+        apply_max_to_market: bool,
+        avg_price_mins: u32,
+    },
+    IcebergParts {
+        limit: u32,
+    },
+    MarketLotSize {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        min_qty: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        max_qty: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        step_size: f64,
+    },
+    TrailingDelta {
+        min_trailing_above_delta: u32,
+        max_trailing_above_delta: u32,
+        min_trailing_below_delta: u32,
+        max_trailing_below_delta: u32,
+    },
+    PercentPriceBySide {
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        bid_multiplier_up: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        bid_multiplier_down: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        ask_multiplier_up: f64,
+        #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+        #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+        ask_multiplier_down: f64,
+        avg_price_mins: u32,
+    },
+    MaxNumOrders {
+        max_num_orders: u32,
+    },
+    MaxNumAlgoOrders {
+        max_num_algo_orders: u32,
+    },
+    #[serde(other)]
+    Other,                               // Filter type not modelled by this crate
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SymbolStatus {
+    PreTrading,
+    Trading,
+    PostTrading,
+    EndOfDay,
+    Halt,
+    AuctionMatch,
+    Break,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolInfo {
+    pub symbol: String,                  // Symbol
+    pub status: SymbolStatus,            // Trading status
+    pub base_asset: String,              // Base asset
+    pub base_asset_precision: u32,       // Base asset precision
+    pub quote_asset: String,             // Quote asset
+    pub quote_precision: u32,            // Quote precision
+    pub quote_asset_precision: u32,      // Quote asset precision
+    #[serde(default)]
+    pub base_commission_precision: u32,  // Base commission precision
+    #[serde(default)]
+    pub quote_commission_precision: u32, // Quote commission precision
+    pub order_types: Vec<String>,        // Allowed order types
+    #[serde(default)]
+    pub iceberg_allowed: bool,           // Iceberg orders allowed?
+    #[serde(default)]
+    pub oco_allowed: bool,               // OCO orders allowed?
+    #[serde(default)]
+    pub oto_allowed: bool,               // OTO orders allowed?
+    #[serde(default)]
+    pub quote_order_qty_market_allowed: bool, // Market orders by quote quantity allowed?
+    #[serde(default)]
+    pub allow_trailing_stop: bool,       // Trailing stop orders allowed?
+    #[serde(default)]
+    pub cancel_replace_allowed: bool,    // Cancel-replace allowed?
+    #[serde(default)]
+    pub is_spot_trading_allowed: bool,   // Spot trading allowed?
+    #[serde(default)]
+    pub is_margin_trading_allowed: bool, // Margin trading allowed?
+    pub filters: Vec<Filter>,            // Symbol filters
+    #[serde(default)]
+    pub permissions: Vec<String>,        // Permissions
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<String>>, // Permission sets
+    #[serde(default)]
+    pub default_self_trade_prevention_mode: Option<String>, // Default STP mode
+    #[serde(default)]
+    pub allowed_self_trade_prevention_modes: Vec<String>, // Allowed STP modes
+}
+
+impl SymbolInfo {
+    pub fn tick_size(&self) -> Option<f64> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::PriceFilter { tick_size, .. } => Some(*tick_size),
+            _ => None,
+        })
+    }
+
+    pub fn step_size(&self) -> Option<f64> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::LotSize { step_size, .. } => Some(*step_size),
+            _ => None,
+        })
+    }
+
+    // Decimals of the tick size, falling back to the quote asset precision
+    pub fn price_format(&self) -> PriceFormat {
+        self.tick_size()
+            .filter(|tick_size| *tick_size > 0.0)
+            .map_or(PriceFormat::Fixed(self.quote_asset_precision as u8), PriceFormat::from_step)
+    }
+
+    // Decimals of the lot step size, falling back to the base asset precision
+    pub fn quantity_format(&self) -> PriceFormat {
+        self.step_size()
+            .filter(|step_size| *step_size > 0.0)
+            .map_or(PriceFormat::Fixed(self.base_asset_precision as u8), PriceFormat::from_step)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeInfo {
+    pub timezone: String,                // Server timezone
+    pub server_time: u64,                // Server time
+    pub rate_limits: Vec<RateLimit>,     // Rate limits
+    #[serde(default)]
+    pub exchange_filters: Vec<Filter>,   // Exchange filters
+    pub symbols: Vec<SymbolInfo>,        // Symbols
+}
+
+impl ExchangeInfo {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn symbol(&self, symbol: &str) -> Option<&SymbolInfo> {
+        self.symbols.iter().find(|info| info.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    pub fn tick_size(&self, symbol: &str) -> Option<f64> {
+        self.symbol(symbol).and_then(SymbolInfo::tick_size)
+    }
+
+    pub fn price_format(&self, symbol: &str) -> Option<PriceFormat> {
+        self.symbol(symbol).map(SymbolInfo::price_format)
+    }
+}
+
+// Parameters of GET /api/v3/exchangeInfo and of the exchangeInfo ws-api method, all symbols when empty.
+// symbol, symbols and permissions each select the symbols on their own and cannot be combined
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ExchangeInfoRequest {
+    pub symbol: Option<String>,          // Symbol
+    pub symbols: Vec<String>,            // Symbols
+    pub permissions: Vec<String>,        // Permissions the symbols are traded with, e.g. "SPOT"
+}
+
+impl ExchangeInfoRequest {
+    pub fn new() -> Self {
+        ExchangeInfoRequest::default()
+    }
+
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    pub fn symbols(mut self, symbols: &[&str]) -> Self {
+        self.symbols = symbols.iter().map(|symbol| symbol.to_string()).collect();
+        self
+    }
+
+    pub fn permissions(mut self, permissions: &[&str]) -> Self {
+        self.permissions = permissions.iter().map(|permission| permission.to_string()).collect();
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        if self.symbol.is_some() && !self.symbols.is_empty() {
+            return Err(OrderRequestError::MutuallyExclusive("symbol", "symbols"));
+        }
+        if !self.permissions.is_empty() {
+            if self.symbol.is_some() {
+                return Err(OrderRequestError::MutuallyExclusive("symbol", "permissions"));
+            }
+            if !self.symbols.is_empty() {
+                return Err(OrderRequestError::MutuallyExclusive("symbols", "permissions"));
+            }
+        }
+        Ok(())
+    }
+
+    // Arrays are sent as compact JSON, a single permission as is, both percent-encoded like order params
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        self.validate()?;
+        let mut query = String::new();
+        if let Some(symbol) = &self.symbol {
+            push_param(&mut query, "symbol", symbol);
+        }
+        if !self.symbols.is_empty() {
+            push_param(&mut query, "symbols", &serde_json::Value::from(self.symbols.clone()).to_string());
+        }
+        match self.permissions.as_slice() {
+            [] => {}
+            [permission] => push_param(&mut query, "permissions", permission),
+            permissions => push_param(&mut query, "permissions", &serde_json::Value::from(permissions.to_vec()).to_string()),
+        }
+        Ok(query)
+    }
+
+    // `params` object of a ws-api exchangeInfo request, empty for all symbols
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        self.validate()?;
+        let mut params = serde_json::Map::new();
+        if let Some(symbol) = &self.symbol {
+            params.insert("symbol".to_string(), symbol.clone().into());
+        }
+        if !self.symbols.is_empty() {
+            params.insert("symbols".to_string(), self.symbols.clone().into());
+        }
+        if !self.permissions.is_empty() {
+            params.insert("permissions".to_string(), self.permissions.clone().into());
+        }
+        Ok(serde_json::Value::Object(params))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DepthSnapshot {
+    pub last_update_id: u64,             // Last update ID
+    #[serde(rename = "E", default, skip_serializing_if = "Option::is_none")]
+    pub message_output_time: Option<u64>, // Message output time (futures only)
+    #[serde(rename = "T", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_time: Option<u64>,   // Transaction time (futures only)
+    pub bids: Vec<PriceLevel>,           // Bids, best (highest) price first
+    pub asks: Vec<PriceLevel>,           // Asks, best (lowest) price first
+}
+
+impl DepthSnapshot {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, DepthSnapshot::from_json)
+    }
+
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.first()
+    }
+
+    pub fn is_crossed(&self) -> bool {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => bid.price >= ask.price,
+            _ => false,
+        }
+    }
+}
+
+// Integer that may be encoded as a JSON number or a numeric string
+struct FlexibleU64(u64);
+
+impl<'de> Deserialize<'de> for FlexibleU64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        de_string_or_number_to_u64(deserializer).map(FlexibleU64)
+    }
+}
+
+// Decimal that may be encoded as a numeric string or a JSON number
+struct FlexibleF64(f64);
+
+impl<'de> Deserialize<'de> for FlexibleF64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FlexibleF64Visitor;
+
+        impl de::Visitor<'_> for FlexibleF64Visitor {
+            type Value = FlexibleF64;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number or a numeric string")
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(FlexibleF64(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(FlexibleF64(v as f64))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(FlexibleF64(v as f64))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.parse::<f64>().map(FlexibleF64).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(FlexibleF64Visitor)
+    }
+}
+
+// One row of GET /api/v3/klines, a 12 element positional array
+#[derive(Clone, Debug, PartialEq)]
+pub struct KlineRow {
+    pub open_time: u64,                  // Kline open time
+    pub open_price: f64,                 // Open price
+    pub high_price: f64,                 // High price
+    pub low_price: f64,                  // Low price
+    pub close_price: f64,                // Close price
+    pub volume: f64,                     // Volume
+    pub close_time: u64,                 // Kline close time
+    pub quote_asset_volume: f64,         // Quote asset volume
+    pub number_of_trades: u64,           // Number of trades
+    pub taker_buy_base_asset_volume: f64, // Taker buy base asset volume
+    pub taker_buy_quote_asset_volume: f64, // Taker buy quote asset volume
+    pub ignore: String,                  // Unused field, ignore
+}
+
+impl KlineRow {
+    const FIELD_COUNT: usize = 12;
+
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // REST rows carry neither the symbol, the interval nor the trade id range, so the
+    // caller supplies the first two and the trade ids are set to -1. The last row of a
+    // response may still be forming, hence the explicit closed flag.
+    pub fn to_kline(&self, symbol: &str, interval: KlineInterval, is_closed: bool) -> Kline {
+        Kline {
+            start_time: self.open_time,
+            close_time: self.close_time,
+            symbol: symbol.to_string(),
+            interval,
+            first_trade_id: -1,
+            last_trade_id: -1,
+            open_price: self.open_price,
+            close_price: self.close_price,
+            high_price: self.high_price,
+            low_price: self.low_price,
+            volume: self.volume,
+            number_of_trades: self.number_of_trades,
+            is_closed,
+            quote_asset_volume: self.quote_asset_volume,
+            taker_buy_base_asset_volume: self.taker_buy_base_asset_volume,
+            taker_buy_quote_asset_volume: self.taker_buy_quote_asset_volume,
+            ignore: self.ignore.clone(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KlineRow {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KlineRowVisitor;
+
+        impl<'de> de::Visitor<'de> for KlineRowVisitor {
+            type Value = KlineRow;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a kline row array with {} elements", KlineRow::FIELD_COUNT)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                fn next<'de, A, T>(seq: &mut A, index: usize) -> Result<T, A::Error>
+                where
+                    A: de::SeqAccess<'de>,
+                    T: Deserialize<'de>,
+                {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(index, &KlineRowVisitor))
+                }
+
+                let row = KlineRow {
+                    open_time: next::<_, FlexibleU64>(&mut seq, 0)?.0,
+                    open_price: next::<_, FlexibleF64>(&mut seq, 1)?.0,
+                    high_price: next::<_, FlexibleF64>(&mut seq, 2)?.0,
+                    low_price: next::<_, FlexibleF64>(&mut seq, 3)?.0,
+                    close_price: next::<_, FlexibleF64>(&mut seq, 4)?.0,
+                    volume: next::<_, FlexibleF64>(&mut seq, 5)?.0,
+                    close_time: next::<_, FlexibleU64>(&mut seq, 6)?.0,
+                    quote_asset_volume: next::<_, FlexibleF64>(&mut seq, 7)?.0,
+                    number_of_trades: next::<_, FlexibleU64>(&mut seq, 8)?.0,
+                    taker_buy_base_asset_volume: next::<_, FlexibleF64>(&mut seq, 9)?.0,
+                    taker_buy_quote_asset_volume: next::<_, FlexibleF64>(&mut seq, 10)?.0,
+                    ignore: next(&mut seq, 11)?,
+                };
+                let mut extra = 0;
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    extra += 1;
+                }
+                if extra > 0 {
+                    return Err(de::Error::invalid_length(KlineRow::FIELD_COUNT + extra, &self));
+                }
+                Ok(row)
+            }
+        }
+
+        deserializer.deserialize_seq(KlineRowVisitor)
+    }
+}
+
+impl Serialize for KlineRow {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let format = PriceFormat::current();
+        (
+            self.open_time,
+            format.display(self.open_price),
+            format.display(self.high_price),
+            format.display(self.low_price),
+            format.display(self.close_price),
+            format.display(self.volume),
+            self.close_time,
+            format.display(self.quote_asset_volume),
+            self.number_of_trades,
+            format.display(self.taker_buy_base_asset_volume),
+            format.display(self.taker_buy_quote_asset_volume),
+            &self.ignore,
+        ).serialize(serializer)
+    }
+}
+
+// One row of GET /api/v3/uiKlines, positionally identical to a klines row
+pub type UiKlineRow = KlineRow;
+
+// Offset from UTC that kline intervals are aligned to, limited to -12:00 through +14:00
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeZone {
+    offset_minutes: i32,
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone { offset_minutes: 0 };
+
+    const MIN_OFFSET_MINUTES: i32 = -12 * 60;
+    const MAX_OFFSET_MINUTES: i32 = 14 * 60;
+
+    pub fn from_offset_minutes(offset_minutes: i32) -> Result<Self, String> {
+        if !(Self::MIN_OFFSET_MINUTES..=Self::MAX_OFFSET_MINUTES).contains(&offset_minutes) {
+            return Err(format!("Time zone offset {} minutes is outside -12:00 to +14:00", offset_minutes));
+        }
+        Ok(TimeZone { offset_minutes })
+    }
+
+    pub fn offset_minutes(&self) -> i32 {
+        self.offset_minutes
+    }
+}
+
+// Whole hours are sent as `8` or `-5`, anything else as `5:45` or `-3:30`; a leading `+` would
+// need escaping in a query string and is never produced
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.offset_minutes < 0 { "-" } else { "" };
+        let hours = self.offset_minutes.abs() / 60;
+        let minutes = self.offset_minutes.abs() % 60;
+        if minutes == 0 {
+            write!(f, "{}{}", sign, hours)
+        } else {
+            write!(f, "{}{}:{:02}", sign, hours, minutes)
+        }
+    }
+}
+
+impl std::str::FromStr for TimeZone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid time zone: {}", s);
+        let (sign, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (-1, &s[1..]),
+            Some(b'+') => (1, &s[1..]),
+            _ => (1, s),
+        };
+        let (hours, minutes) = match unsigned.split_once(':') {
+            Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+            Some(_) => return Err(invalid()),
+            None => (unsigned, "0"),
+        };
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+        if hours < 0 || !(0..60).contains(&minutes) {
+            return Err(invalid());
+        }
+        TimeZone::from_offset_minutes(sign * (hours * 60 + minutes))
+    }
+}
+
+// Parameters of GET /api/v3/klines and /uiKlines, and of the klines and uiKlines ws-api methods
+#[derive(Clone, Debug, PartialEq)]
+pub struct KlinesRequest {
+    pub symbol: String,                  // Symbol
+    pub interval: KlineInterval,         // Interval
+    pub start_time: Option<u64>,         // Start time
+    pub end_time: Option<u64>,           // End time
+    pub time_zone: Option<TimeZone>,     // Time zone, UTC when unset
+    pub limit: Option<u32>,              // Number of rows, 500 when unset, at most 1000
+}
+
+impl KlinesRequest {
+    pub fn new(symbol: &str, interval: KlineInterval) -> Self {
+        KlinesRequest {
+            symbol: symbol.to_string(),
+            interval,
+            start_time: None,
+            end_time: None,
+            time_zone: None,
+            limit: None,
+        }
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn to_query_string(&self) -> String {
+        let mut params = vec![
+            format!("symbol={}", self.symbol),
+            format!("interval={}", self.interval),
+        ];
+        if let Some(start_time) = self.start_time {
+            params.push(format!("startTime={}", start_time));
+        }
+        if let Some(end_time) = self.end_time {
+            params.push(format!("endTime={}", end_time));
+        }
+        if let Some(time_zone) = self.time_zone {
+            params.push(format!("timeZone={}", time_zone));
+        }
+        if let Some(limit) = self.limit {
+            params.push(format!("limit={}", limit));
+        }
+        params.join("&")
+    }
+
+    // `params` object of a ws-api klines request
+    pub fn to_params(&self) -> serde_json::Value {
+        let mut params = serde_json::Map::new();
+        params.insert("symbol".to_string(), self.symbol.clone().into());
+        params.insert("interval".to_string(), self.interval.as_str().into());
+        if let Some(start_time) = self.start_time {
+            params.insert("startTime".to_string(), start_time.into());
+        }
+        if let Some(end_time) = self.end_time {
+            params.insert("endTime".to_string(), end_time.into());
+        }
+        if let Some(time_zone) = self.time_zone {
+            params.insert("timeZone".to_string(), time_zone.to_string().into());
+        }
+        if let Some(limit) = self.limit {
+            params.insert("limit".to_string(), limit.into());
+        }
+        serde_json::Value::Object(params)
+    }
+}
+
+// One row of GET /api/v3/aggTrades
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AggTradeRow {
+    #[serde(rename = "a")]
+    pub aggregate_trade_id: u64,         // Aggregate trade ID
+    #[serde(rename = "p", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "q", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,             // First trade ID
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,              // Last trade ID
+    #[serde(rename = "T")]
+    pub trade_time: u64,                 // Timestamp
+    #[serde(rename = "m")]
+    pub is_buyer_market_maker: bool,     // Was the buyer the maker?
+    #[serde(rename = "M")]
+    pub is_best_match: bool,             // Was the trade the best price match?
+}
+
+impl AggTradeRow {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // REST rows carry no symbol and no event envelope, so the stream shape is completed
+    // with the requested symbol and the trade time standing in for the event time
+    #[cfg(feature = "spot")]
+    pub fn into_agg_trade(self, symbol: &str) -> AggTrade {
+        AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: self.trade_time,
+            symbol: symbol.to_string(),
+            aggregate_trade_id: self.aggregate_trade_id,
+            price: self.price,
+            quantity: self.quantity,
+            first_trade_id: self.first_trade_id,
+            last_trade_id: self.last_trade_id,
+            trade_time: self.trade_time,
+            is_buyer_market_maker: self.is_buyer_market_maker,
+            ignore: self.is_best_match,
+        }
+    }
+}
+
+// One row of GET /api/v3/trades and GET /api/v3/historicalTrades
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TradeRow {
+    pub id: u64,                         // Trade ID
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "qty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "quoteQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_quantity: f64,             // Quote quantity
+    pub time: u64,                       // Trade time
+    pub is_buyer_maker: bool,            // Was the buyer the maker?
+    pub is_best_match: bool,             // Was the trade the best price match?
+}
+
+impl TradeRow {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // REST rows carry no symbol, so it has to be supplied from the request
+    #[cfg(feature = "spot")]
+    pub fn into_trade(self, symbol: &str) -> Trade {
+        Trade {
+            event_type: None,
+            event_time: None,
+            symbol: symbol.to_string(),
+            trade_id: self.id,
+            price: self.price,
+            quantity: self.quantity,
+            trade_time: self.time,
+            is_buyer_market_maker: self.is_buyer_maker,
+            ignore: self.is_best_match,
+        }
+    }
+}
+
+// Ticker endpoints answer with a single object for `symbol=` and an array for `symbols=` or no symbol
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(item) => vec![item],
+            OneOrMany::Many(items) => items,
+        }
+    }
+}
+
+// GET /api/v3/ticker/24hr with type=FULL (the default)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker24hrFull {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change: f64,               // Price change
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change_percent: f64,       // Price change percent
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub weighted_avg_price: f64,         // Weighted average price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub prev_close_price: f64,           // Previous close price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_price: f64,                 // Last price
+    #[serde(rename = "lastQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_quantity: f64,              // Last quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_price: f64,                  // Best bid price
+    #[serde(rename = "bidQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_quantity: f64,               // Best bid quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_price: f64,                  // Best ask price
+    #[serde(rename = "askQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_quantity: f64,               // Best ask quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Total traded base asset volume
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_volume: f64,               // Total traded quote asset volume
+    pub open_time: u64,                  // Statistics open time
+    pub close_time: u64,                 // Statistics close time
+    pub first_id: i64,                   // First trade ID, -1 if there were no trades
+    pub last_id: i64,                    // Last trade ID, -1 if there were no trades
+    pub count: u64,                      // Total number of trades
+}
+
+// GET /api/v3/ticker/24hr with type=MINI
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker24hrMini {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_price: f64,                 // Last price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Total traded base asset volume
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_volume: f64,               // Total traded quote asset volume
+    pub open_time: u64,                  // Statistics open time
+    pub close_time: u64,                 // Statistics close time
+    pub first_id: i64,                   // First trade ID, -1 if there were no trades
+    pub last_id: i64,                    // Last trade ID, -1 if there were no trades
+    pub count: u64,                      // Total number of trades
+}
+
+// FULL is tried first because a MINI object lacks the FULL-only fields and falls through
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Ticker24hr {
+    Full(Ticker24hrFull),
+    Mini(Ticker24hrMini),
+}
+
+impl Ticker24hr {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            Ticker24hr::Full(ticker) => &ticker.symbol,
+            Ticker24hr::Mini(ticker) => &ticker.symbol,
+        }
+    }
+
+    pub fn last_price(&self) -> f64 {
+        match self {
+            Ticker24hr::Full(ticker) => ticker.last_price,
+            Ticker24hr::Mini(ticker) => ticker.last_price,
+        }
+    }
+}
+
+// Response shape of the ticker endpoints that take a `type` parameter
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TickerType {
+    Full,
+    Mini,
+}
+
+impl TickerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TickerType::Full => "FULL",
+            TickerType::Mini => "MINI",
+        }
+    }
+}
+
+// Parameters of GET /api/v3/ticker/tradingDay and of the ticker.tradingDay ws-api method. A single
+// symbol is answered with one ticker, a symbols array with one ticker per symbol
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradingDayTickerRequest {
+    pub symbols: OneOrMany<String>,      // Symbol, or up to 100 symbols
+    pub ticker_type: Option<TickerType>, // Response shape, FULL when unset
+    pub time_zone: Option<TimeZone>,     // Time zone the trading day starts in, UTC when unset
+}
+
+impl TradingDayTickerRequest {
+    pub fn symbol(symbol: &str) -> Self {
+        TradingDayTickerRequest { symbols: OneOrMany::One(symbol.to_string()), ticker_type: None, time_zone: None }
+    }
+
+    pub fn symbols(symbols: &[&str]) -> Self {
+        let symbols = symbols.iter().map(|symbol| symbol.to_string()).collect();
+        TradingDayTickerRequest { symbols: OneOrMany::Many(symbols), ticker_type: None, time_zone: None }
+    }
+
+    pub fn ticker_type(mut self, ticker_type: TickerType) -> Self {
+        self.ticker_type = Some(ticker_type);
+        self
+    }
+
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    // The symbols array is sent as compact JSON, percent-encoding is left to the HTTP client
+    pub fn to_query_string(&self) -> String {
+        let mut params = vec![match &self.symbols {
+            OneOrMany::One(symbol) => format!("symbol={}", symbol),
+            OneOrMany::Many(symbols) => format!("symbols={}", serde_json::Value::from(symbols.clone())),
+        }];
+        if let Some(ticker_type) = self.ticker_type {
+            params.push(format!("type={}", ticker_type.as_str()));
+        }
+        if let Some(time_zone) = self.time_zone {
+            params.push(format!("timeZone={}", time_zone));
+        }
+        params.join("&")
+    }
+
+    // `params` object of a ws-api ticker.tradingDay request
+    pub fn to_params(&self) -> serde_json::Value {
+        let mut params = serde_json::Map::new();
+        match &self.symbols {
+            OneOrMany::One(symbol) => params.insert("symbol".to_string(), symbol.clone().into()),
+            OneOrMany::Many(symbols) => params.insert("symbols".to_string(), symbols.clone().into()),
+        };
+        if let Some(ticker_type) = self.ticker_type {
+            params.insert("type".to_string(), ticker_type.as_str().into());
+        }
+        if let Some(time_zone) = self.time_zone {
+            params.insert("timeZone".to_string(), time_zone.to_string().into());
+        }
+        serde_json::Value::Object(params)
+    }
+}
+
+// GET /api/v3/ticker/tradingDay with type=FULL (the default). The statistics cover the current trading
+// day only, from its start in the requested time zone up to the last trade
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TradingDayTickerFull {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change: f64,               // Price change since the trading day open
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change_percent: f64,       // Price change percent
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub weighted_avg_price: f64,         // Weighted average price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_price: f64,                 // Last price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Total traded base asset volume
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_volume: f64,               // Total traded quote asset volume
+    pub open_time: u64,                  // Start of the trading day
+    pub close_time: u64,                 // End of the trading day
+    pub first_id: i64,                   // First trade ID, -1 if there were no trades
+    pub last_id: i64,                    // Last trade ID, -1 if there were no trades
+    pub count: u64,                      // Total number of trades
+}
+
+// GET /api/v3/ticker/tradingDay with type=MINI, the fields of a MINI 24hr ticker over the trading day
+pub type TradingDayTickerMini = Ticker24hrMini;
+
+// FULL is tried first because a MINI object lacks the FULL-only fields and falls through
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum TradingDayTicker {
+    Full(TradingDayTickerFull),
+    Mini(TradingDayTickerMini),
+}
+
+impl TradingDayTicker {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            TradingDayTicker::Full(ticker) => &ticker.symbol,
+            TradingDayTicker::Mini(ticker) => &ticker.symbol,
+        }
+    }
+
+    pub fn last_price(&self) -> f64 {
+        match self {
+            TradingDayTicker::Full(ticker) => ticker.last_price,
+            TradingDayTicker::Mini(ticker) => ticker.last_price,
+        }
+    }
+
+    // Open and close time of the trading day the statistics cover
+    pub fn trading_day(&self) -> (u64, u64) {
+        match self {
+            TradingDayTicker::Full(ticker) => (ticker.open_time, ticker.close_time),
+            TradingDayTicker::Mini(ticker) => (ticker.open_time, ticker.close_time),
+        }
+    }
+}
+
+// GET /api/v3/ticker/price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTicker {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Latest price
+}
+
+impl PriceTicker {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_price_map(tickers: &[PriceTicker]) -> HashMap<String, f64> {
+        tickers.iter().map(|ticker| (ticker.symbol.clone(), ticker.price)).collect()
+    }
+}
+
+// GET /api/v3/ticker/bookTicker
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookTickerSnapshot {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_price: f64,                  // Best bid price
+    #[serde(rename = "bidQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_quantity: f64,               // Best bid quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_price: f64,                  // Best ask price
+    #[serde(rename = "askQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_quantity: f64,               // Best ask quantity
+}
+
+impl BookTickerSnapshot {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl From<BookTickerSnapshot> for BookTicker {
+    fn from(snapshot: BookTickerSnapshot) -> Self {
+        BookTicker {
+            update_id: None,
+            symbol: snapshot.symbol,
+            best_bid_price: snapshot.bid_price,
+            best_bid_quantity: snapshot.bid_quantity,
+            best_ask_price: snapshot.ask_price,
+            best_ask_quantity: snapshot.ask_quantity,
+        }
+    }
+}
+
+// GET /api/v3/avgPrice
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AvgPrice {
+    pub mins: u32,                       // Average price interval in minutes
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Average price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_time: Option<u64>,         // Last trade time, missing from older responses
+}
+
+impl AvgPrice {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRates {
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maker: f64,                      // Maker commission rate
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker: f64,                      // Taker commission rate
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub buyer: f64,                      // Buyer commission rate
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub seller: f64,                     // Seller commission rate
+}
+
+// GET /api/v3/account
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInformation {
+    pub maker_commission: u32,           // Maker commission in basis points
+    pub taker_commission: u32,           // Taker commission in basis points
+    pub buyer_commission: u32,           // Buyer commission in basis points
+    pub seller_commission: u32,          // Seller commission in basis points
+    pub commission_rates: CommissionRates, // Commission rates
+    pub can_trade: bool,                 // Can trade
+    pub can_withdraw: bool,              // Can withdraw
+    pub can_deposit: bool,               // Can deposit
+    #[serde(default)]
+    pub brokered: bool,                  // Is a brokered account
+    #[serde(default)]
+    pub require_self_trade_prevention: bool, // Is self-trade prevention required
+    #[serde(default)]
+    pub prevent_sor: bool,               // Is smart order routing prevented
+    pub update_time: u64,                // Last update time
+    pub account_type: String,            // Account type, e.g. SPOT
+    pub balances: Vec<AccountBalance>,   // Balances
+    pub permissions: Vec<String>,        // Permissions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u64>,                // User ID
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>, // Fields added by Binance after this model was written
+}
+
+impl AccountInformation {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn balance_of(&self, asset: &str) -> Option<&AccountBalance> {
+        self.balances.iter().find(|balance| balance.asset.eq_ignore_ascii_case(asset))
+    }
+
+    // Look up a boolean flag this model has no field for yet
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        self.other.get(name).and_then(serde_json::Value::as_bool)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    Limit,
+    Market,
+    StopLoss,
+    StopLossLimit,
+    TakeProfit,
+    TakeProfitLimit,
+    LimitMaker,
+}
+
+impl OrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Limit => "LIMIT",
+            OrderType::Market => "MARKET",
+            OrderType::StopLoss => "STOP_LOSS",
+            OrderType::StopLossLimit => "STOP_LOSS_LIMIT",
+            OrderType::TakeProfit => "TAKE_PROFIT",
+            OrderType::TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+            OrderType::LimitMaker => "LIMIT_MAKER",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TimeInForce {
+    Gtc,
+    Ioc,
+    Fok,
+}
+
+impl TimeInForce {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Ioc => "IOC",
+            TimeInForce::Fok => "FOK",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NewOrderRespType {
+    Ack,
+    Result,
+    Full,
+}
+
+impl NewOrderRespType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NewOrderRespType::Ack => "ACK",
+            NewOrderRespType::Result => "RESULT",
+            NewOrderRespType::Full => "FULL",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradePreventionMode {
+    None,
+    ExpireTaker,
+    ExpireMaker,
+    ExpireBoth,
+    Decrement,
+    #[serde(other)]
+    Unknown,
+}
+
+impl SelfTradePreventionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelfTradePreventionMode::None => "NONE",
+            SelfTradePreventionMode::ExpireTaker => "EXPIRE_TAKER",
+            SelfTradePreventionMode::ExpireMaker => "EXPIRE_MAKER",
+            SelfTradePreventionMode::ExpireBoth => "EXPIRE_BOTH",
+            SelfTradePreventionMode::Decrement => "DECREMENT",
+            SelfTradePreventionMode::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderRequestError {
+    QuantityAndQuoteOrderQty,            // Both quantity and quoteOrderQty were set
+    QuoteOrderQtyNotMarket,              // quoteOrderQty is only valid on MARKET orders
+    MissingParameter(&'static str),      // A parameter the order type requires is unset
+    InvalidDecimal(&'static str),        // A decimal parameter is NaN, infinite or negative
+    UnsupportedOrderType(OrderType),     // The endpoint does not accept the order type, e.g. STOP_LOSS on SOR
+    InconsistentLegs(&'static str),      // The legs of an order list contradict each other
+    MutuallyExclusive(&'static str, &'static str), // Two parameters that cannot be set together were set
+}
+
+impl fmt::Display for OrderRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrderRequestError::QuantityAndQuoteOrderQty => write!(f, "quantity and quoteOrderQty are mutually exclusive"),
+            OrderRequestError::QuoteOrderQtyNotMarket => write!(f, "quoteOrderQty is only valid for MARKET orders"),
+            OrderRequestError::MissingParameter(name) => write!(f, "missing mandatory parameter {}", name),
+            OrderRequestError::InvalidDecimal(name) => write!(f, "invalid decimal value for {}", name),
+            OrderRequestError::UnsupportedOrderType(order_type) => write!(f, "order type {} is not supported", order_type.as_str()),
+            OrderRequestError::InconsistentLegs(reason) => write!(f, "inconsistent order list legs: {}", reason),
+            OrderRequestError::MutuallyExclusive(a, b) => write!(f, "{} and {} are mutually exclusive", a, b),
+        }
+    }
+}
+
+impl std::error::Error for OrderRequestError {}
+
+// Parameters of POST /api/v3/order, without the timestamp and signature added at signing time
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub side: Side,                      // Side
+    pub order_type: OrderType,           // Order type
+    pub time_in_force: Option<TimeInForce>, // Time in force
+    pub quantity: Option<f64>,           // Base asset quantity
+    pub quote_order_qty: Option<f64>,    // Quote asset quantity for MARKET orders
+    pub price: Option<f64>,              // Limit price
+    pub new_client_order_id: Option<String>, // Client order ID
+    pub stop_price: Option<f64>,         // Stop price for stop and take profit orders
+    pub iceberg_qty: Option<f64>,        // Iceberg quantity
+    pub new_order_resp_type: Option<NewOrderRespType>, // Response type
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+impl NewOrderRequest {
+    pub fn new(symbol: &str, side: Side, order_type: OrderType) -> Self {
+        NewOrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            time_in_force: None,
+            quantity: None,
+            quote_order_qty: None,
+            price: None,
+            new_client_order_id: None,
+            stop_price: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: f64) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn quote_order_qty(mut self, quote_order_qty: f64) -> Self {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: &str) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.to_string());
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: f64) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: NewOrderRespType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        if self.quantity.is_some() && self.quote_order_qty.is_some() {
+            return Err(OrderRequestError::QuantityAndQuoteOrderQty);
+        }
+        if self.quote_order_qty.is_some() && self.order_type != OrderType::Market {
+            return Err(OrderRequestError::QuoteOrderQtyNotMarket);
+        }
+        if self.order_type == OrderType::Market {
+            if self.quantity.is_none() && self.quote_order_qty.is_none() {
+                return Err(OrderRequestError::MissingParameter("quantity"));
+            }
+        } else if self.quantity.is_none() {
+            return Err(OrderRequestError::MissingParameter("quantity"));
+        }
+        if matches!(self.order_type, OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit)
+            && self.time_in_force.is_none()
+        {
+            return Err(OrderRequestError::MissingParameter("timeInForce"));
+        }
+        if matches!(self.order_type, OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit | OrderType::LimitMaker)
+            && self.price.is_none()
+        {
+            return Err(OrderRequestError::MissingParameter("price"));
+        }
+        if matches!(self.order_type, OrderType::StopLoss | OrderType::StopLossLimit | OrderType::TakeProfit | OrderType::TakeProfitLimit)
+            && self.stop_price.is_none()
+        {
+            return Err(OrderRequestError::MissingParameter("stopPrice"));
+        }
+        Ok(())
+    }
+
+    // Parameters in insertion order, which is the order Binance signs them in. Decimals use `PriceFormat::current()`
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    // Prices in the symbol's tick size decimals and quantities in its lot step size decimals
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    // `quoteOrderQty` is a quote asset amount, so it is formatted like a price
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        self.validate()?;
+
+        let mut query = String::with_capacity(128);
+        push_param(&mut query, "symbol", &self.symbol);
+        push_param(&mut query, "side", self.side.as_str());
+        push_param(&mut query, "type", self.order_type.as_str());
+        if let Some(time_in_force) = self.time_in_force {
+            push_param(&mut query, "timeInForce", time_in_force.as_str());
+        }
+        push_decimal(&mut query, "quantity", self.quantity, quantity_format)?;
+        push_decimal(&mut query, "quoteOrderQty", self.quote_order_qty, price_format)?;
+        push_decimal(&mut query, "price", self.price, price_format)?;
+        if let Some(new_client_order_id) = &self.new_client_order_id {
+            push_param(&mut query, "newClientOrderId", new_client_order_id);
+        }
+        push_decimal(&mut query, "stopPrice", self.stop_price, price_format)?;
+        push_decimal(&mut query, "icebergQty", self.iceberg_qty, quantity_format)?;
+        if let Some(new_order_resp_type) = self.new_order_resp_type {
+            push_param(&mut query, "newOrderRespType", new_order_resp_type.as_str());
+        }
+        if let Some(self_trade_prevention_mode) = self.self_trade_prevention_mode {
+            push_param(&mut query, "selfTradePreventionMode", self_trade_prevention_mode.as_str());
+        }
+        Ok(query)
+    }
+}
+
+// Parameters of POST /api/v3/order/test and the order.test ws-api method, an order validated by the
+// matching engine without being placed
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestOrderRequest {
+    pub order: NewOrderRequest,          // Order to test
+    pub compute_commission_rates: bool,  // Answer with the commission rates the order would pay
+}
+
+impl TestOrderRequest {
+    pub fn new(order: NewOrderRequest) -> Self {
+        TestOrderRequest { order, compute_commission_rates: false }
+    }
+
+    pub fn compute_commission_rates(mut self, compute_commission_rates: bool) -> Self {
+        self.compute_commission_rates = compute_commission_rates;
+        self
+    }
+
+    // The order's parameters followed by `computeCommissionRates=true` when set
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        let mut query = self.order.to_query_string_with(price_format, quantity_format)?;
+        if self.compute_commission_rates {
+            push_param(&mut query, "computeCommissionRates", "true");
+        }
+        Ok(query)
+    }
+}
+
+// Parameters of PUT /api/v3/order/amend/keepPriority and the order.amend.keepPriority ws-api method.
+// Reduces the quantity of an open order without losing its place in the queue
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmendOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub order_id: Option<u64>,           // Order ID, this or the original client order ID is required
+    pub orig_client_order_id: Option<String>, // Client order ID of the order
+    pub new_client_order_id: Option<String>, // New client order ID, unchanged when unset
+    pub new_qty: f64,                    // New quantity, below the current one
+}
+
+impl AmendOrderRequest {
+    pub fn by_order_id(symbol: &str, order_id: u64, new_qty: f64) -> Self {
+        AmendOrderRequest {
+            symbol: symbol.to_string(),
+            order_id: Some(order_id),
+            orig_client_order_id: None,
+            new_client_order_id: None,
+            new_qty,
+        }
+    }
+
+    pub fn by_client_order_id(symbol: &str, orig_client_order_id: &str, new_qty: f64) -> Self {
+        AmendOrderRequest {
+            symbol: symbol.to_string(),
+            order_id: None,
+            orig_client_order_id: Some(orig_client_order_id.to_string()),
+            new_client_order_id: None,
+            new_qty,
+        }
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: &str) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.to_string());
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        if self.order_id.is_none() && self.orig_client_order_id.is_none() {
+            return Err(OrderRequestError::MissingParameter("orderId"));
+        }
+        Ok(())
+    }
+
+    fn params(&self, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        if let Some(order_id) = self.order_id {
+            params.push("orderId", order_id);
+        }
+        params.push_str("origClientOrderId", self.orig_client_order_id.as_deref());
+        params.push_str("newClientOrderId", self.new_client_order_id.as_deref());
+        params.push_decimal("newQty", Some(self.new_qty), quantity_format)?;
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(PriceFormat::current())
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a ws-api order.amend.keepPriority request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        self.to_params_with(PriceFormat::current())
+    }
+
+    pub fn to_params_with(&self, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(quantity_format)?.into_params())
+    }
+}
+
+// Parameters of POST /api/v3/sor/order and the sor.order.place ws-api method. Smart order routing
+// accepts LIMIT and MARKET orders by base quantity only, so there is no quoteOrderQty or stopPrice
+#[derive(Clone, Debug, PartialEq)]
+pub struct SorOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub side: Side,                      // Side
+    pub order_type: OrderType,           // Order type, LIMIT or MARKET
+    pub time_in_force: Option<TimeInForce>, // Time in force
+    pub quantity: f64,                   // Base asset quantity
+    pub price: Option<f64>,              // Limit price
+    pub new_client_order_id: Option<String>, // Client order ID
+    pub iceberg_qty: Option<f64>,        // Iceberg quantity
+    pub new_order_resp_type: Option<NewOrderRespType>, // Response type
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+impl SorOrderRequest {
+    pub fn new(symbol: &str, side: Side, order_type: OrderType, quantity: f64) -> Self {
+        SorOrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            time_in_force: None,
+            quantity,
+            price: None,
+            new_client_order_id: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: &str) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.to_string());
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: NewOrderRespType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        match self.order_type {
+            OrderType::Limit => {
+                if self.time_in_force.is_none() {
+                    return Err(OrderRequestError::MissingParameter("timeInForce"));
+                }
+                if self.price.is_none() {
+                    return Err(OrderRequestError::MissingParameter("price"));
+                }
+            }
+            OrderType::Market => {}
+            order_type => return Err(OrderRequestError::UnsupportedOrderType(order_type)),
+        }
+        Ok(())
+    }
+
+    // Parameters in insertion order, see `NewOrderRequest::to_query_string`
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        self.validate()?;
+
+        let mut query = String::with_capacity(128);
+        push_param(&mut query, "symbol", &self.symbol);
+        push_param(&mut query, "side", self.side.as_str());
+        push_param(&mut query, "type", self.order_type.as_str());
+        if let Some(time_in_force) = self.time_in_force {
+            push_param(&mut query, "timeInForce", time_in_force.as_str());
+        }
+        push_decimal(&mut query, "quantity", Some(self.quantity), quantity_format)?;
+        push_decimal(&mut query, "price", self.price, price_format)?;
+        if let Some(new_client_order_id) = &self.new_client_order_id {
+            push_param(&mut query, "newClientOrderId", new_client_order_id);
+        }
+        push_decimal(&mut query, "icebergQty", self.iceberg_qty, quantity_format)?;
+        if let Some(new_order_resp_type) = self.new_order_resp_type {
+            push_param(&mut query, "newOrderRespType", new_order_resp_type.as_str());
+        }
+        if let Some(self_trade_prevention_mode) = self.self_trade_prevention_mode {
+            push_param(&mut query, "selfTradePreventionMode", self_trade_prevention_mode.as_str());
+        }
+        Ok(query)
+    }
+}
+
+// Parameters of POST /api/v3/sor/order/test and the sor.order.test ws-api method, answered with a
+// `TestOrderResponse`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SorTestOrderRequest {
+    pub order: SorOrderRequest,          // Order to test
+    pub compute_commission_rates: bool,  // Answer with the commission rates the order would pay
+}
+
+impl SorTestOrderRequest {
+    pub fn new(order: SorOrderRequest) -> Self {
+        SorTestOrderRequest { order, compute_commission_rates: false }
+    }
+
+    pub fn compute_commission_rates(mut self, compute_commission_rates: bool) -> Self {
+        self.compute_commission_rates = compute_commission_rates;
+        self
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        let mut query = self.order.to_query_string_with(price_format, quantity_format)?;
+        if self.compute_commission_rates {
+            push_param(&mut query, "computeCommissionRates", "true");
+        }
+        Ok(query)
+    }
+}
+
+fn push_key(query: &mut String, name: &str) {
+    if !query.is_empty() {
+        query.push('&');
+    }
+    query.push_str(name);
+    query.push('=');
+}
+
+fn push_param(query: &mut String, name: &str, value: &str) {
+    push_key(query, name);
+    push_encoded(query, value);
+}
+
+// Percent-encodes all but the RFC 3986 unreserved characters, so the string that is signed is the one
+// the server decodes, e.g. for client order ids with `:` or `/`
+fn push_encoded(query: &mut String, value: &str) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            query.push(byte as char);
+        } else {
+            query.extend(['%', HEX[usize::from(byte >> 4)] as char, HEX[usize::from(byte & 0xf)] as char]);
+        }
+    }
+}
+
+// None of the formats fall back to exponent notation, so only non-finite and negative values need rejecting
+fn push_decimal(query: &mut String, name: &'static str, value: Option<f64>, format: PriceFormat) -> Result<(), OrderRequestError> {
+    if let Some(value) = value {
+        if !value.is_finite() || value < 0.0 {
+            return Err(OrderRequestError::InvalidDecimal(name));
+        }
+        push_key(query, name);
+        format.push_to(query, value);
+    }
+    Ok(())
+}
+
+// Parameters in insertion order, rendered as a query string or a ws-api `params` object. Decimals are
+// strings in both renderings so the formats apply to ws-api requests too
+#[derive(Default)]
+struct Params(Vec<(&'static str, serde_json::Value)>);
+
+impl Params {
+    fn push(&mut self, name: &'static str, value: impl Into<serde_json::Value>) {
+        self.0.push((name, value.into()));
+    }
+
+    fn push_str(&mut self, name: &'static str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.push(name, value);
+        }
+    }
+
+    // Rejects the same values as `push_decimal` of plain orders
+    fn push_decimal(&mut self, name: &'static str, value: Option<f64>, format: PriceFormat) -> Result<(), OrderRequestError> {
+        if let Some(value) = value {
+            if !value.is_finite() || value < 0.0 {
+                return Err(OrderRequestError::InvalidDecimal(name));
+            }
+            let mut formatted = String::new();
+            format.push_to(&mut formatted, value);
+            self.push(name, formatted);
+        }
+        Ok(())
+    }
+
+    fn push_common(&mut self, list_client_order_id: Option<&str>, new_order_resp_type: Option<NewOrderRespType>, self_trade_prevention_mode: Option<SelfTradePreventionMode>) {
+        self.push_str("listClientOrderId", list_client_order_id);
+        self.push_str("newOrderRespType", new_order_resp_type.as_ref().map(NewOrderRespType::as_str));
+        self.push_str("selfTradePreventionMode", self_trade_prevention_mode.as_ref().map(SelfTradePreventionMode::as_str));
+    }
+
+    fn to_query_string(&self) -> String {
+        let mut query = String::with_capacity(256);
+        for (name, value) in &self.0 {
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(name);
+            query.push('=');
+            match value {
+                serde_json::Value::String(value) => push_encoded(&mut query, value),
+                value => push_encoded(&mut query, &value.to_string()),
+            }
+        }
+        query
+    }
+
+    fn into_params(self) -> serde_json::Value {
+        serde_json::Value::Object(self.0.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+    }
+}
+
+// newOrderRespType=ACK
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAck {
+    pub symbol: String,                  // Symbol
+    pub order_id: u64,                   // Order ID
+    pub order_list_id: i64,              // Order list ID, -1 unless part of an order list
+    pub client_order_id: String,         // Client order ID
+    pub transact_time: u64,              // Transaction time
+}
+
+// newOrderRespType=RESULT
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResult {
+    pub symbol: String,                  // Symbol
+    pub order_id: u64,                   // Order ID
+    pub order_list_id: i64,              // Order list ID, -1 unless part of an order list
+    pub client_order_id: String,         // Client order ID
+    pub transact_time: u64,              // Transaction time
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "origQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_quantity: f64,          // Original quantity
+    #[serde(rename = "executedQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub executed_quantity: f64,          // Executed quantity
+    #[serde(rename = "cummulativeQuoteQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cumulative_quote_quantity: f64,  // Cumulative quote asset quantity
+    pub status: OrderStatus,             // Order status
+    pub time_in_force: TimeInForce,      // Time in force
+    #[serde(rename = "type")]
+    pub order_type: OrderType,           // Order type
+    pub side: Side,                      // Side
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_time: Option<i64>,       // Time the order started working on the book, -1 while a stop is pending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "qty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub commission: f64,                 // Commission
+    pub commission_asset: String,        // Commission asset
+    pub trade_id: u64,                   // Trade ID
+}
+
+// newOrderRespType=FULL
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderFull {
+    #[serde(flatten)]
+    pub result: OrderResult,             // RESULT fields
+    pub fills: Vec<Fill>,                // Fills
+}
+
+// Variants are tried from the richest shape down, since every FULL response is also a valid RESULT and ACK
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum OrderResponse {
+    Full(OrderFull),
+    Result(OrderResult),
+    Ack(OrderAck),
+}
+
+impl OrderResponse {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn resp_type(&self) -> NewOrderRespType {
+        match self {
+            OrderResponse::Full(_) => NewOrderRespType::Full,
+            OrderResponse::Result(_) => NewOrderRespType::Result,
+            OrderResponse::Ack(_) => NewOrderRespType::Ack,
+        }
+    }
+
+    pub fn order_id(&self) -> u64 {
+        match self {
+            OrderResponse::Full(order) => order.result.order_id,
+            OrderResponse::Result(order) => order.order_id,
+            OrderResponse::Ack(order) => order.order_id,
+        }
+    }
+
+    pub fn fills(&self) -> &[Fill] {
+        match self {
+            OrderResponse::Full(order) => &order.fills,
+            _ => &[],
+        }
+    }
+}
+
+// Fill of a SOR order. Fills against an allocation have no trade, their trade ID is -1
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SorFill {
+    pub match_type: String,              // Match type, e.g. "ONE_PARTY_TRADE_REPORT"
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "qty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub commission: f64,                 // Commission
+    pub commission_asset: String,        // Commission asset
+    pub trade_id: i64,                   // Trade ID, -1 for allocations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc_id: Option<u64>,           // Allocation ID, only on allocation fills
+}
+
+// SOR newOrderRespType=RESULT
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SorOrderResult {
+    #[serde(flatten)]
+    pub result: OrderResult,             // RESULT fields
+    pub working_floor: String,           // Where the order works, "EXCHANGE" or "SOR"
+    pub used_sor: bool,                  // Was the order routed by SOR
+}
+
+// SOR newOrderRespType=FULL
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SorOrderFull {
+    #[serde(flatten)]
+    pub result: SorOrderResult,          // SOR RESULT fields
+    pub fills: Vec<SorFill>,             // Fills
+}
+
+// POST /api/v3/sor/order, tried from the richest shape down like `OrderResponse`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum SorOrderResponse {
+    Full(SorOrderFull),
+    Result(SorOrderResult),
+    Ack(OrderAck),
+}
+
+impl SorOrderResponse {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn resp_type(&self) -> NewOrderRespType {
+        match self {
+            SorOrderResponse::Full(_) => NewOrderRespType::Full,
+            SorOrderResponse::Result(_) => NewOrderRespType::Result,
+            SorOrderResponse::Ack(_) => NewOrderRespType::Ack,
+        }
+    }
+
+    pub fn order_id(&self) -> u64 {
+        match self {
+            SorOrderResponse::Full(order) => order.result.result.order_id,
+            SorOrderResponse::Result(order) => order.result.order_id,
+            SorOrderResponse::Ack(order) => order.order_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrderCommission {
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maker: f64,                      // Maker commission rate
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker: f64,                      // Taker commission rate
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,       // Is paying commissions in the discount asset enabled for the account
+    pub enabled_for_symbol: bool,        // Is the discount available on the symbol
+    pub discount_asset: String,          // Discount asset, e.g. BNB
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub discount: f64,                   // Discount rate when paying in the discount asset
+}
+
+// computeCommissionRates=true
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TestOrderCommissionRates {
+    pub standard_commission_for_order: OrderCommission, // Standard commission rates of the order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub special_commission_for_order: Option<OrderCommission>, // Special commission rates, on symbols that have them
+    pub tax_commission_for_order: OrderCommission, // Tax commission rates of the order
+    pub discount: CommissionDiscount,    // Discount on the standard commission
+}
+
+// POST /api/v3/order/test answers with an empty object unless commission rates were requested. `Empty`
+// accepts only `{}`, so error bodies and partial rates fail instead of passing as a successful test
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged, deny_unknown_fields)]
+pub enum TestOrderResponse {
+    CommissionRates(TestOrderCommissionRates),
+    Empty {},
+}
+
+impl TestOrderResponse {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn commission_rates(&self) -> Option<&TestOrderCommissionRates> {
+        match self {
+            TestOrderResponse::CommissionRates(rates) => Some(rates),
+            TestOrderResponse::Empty {} => None,
+        }
+    }
+}
+
+// DELETE /api/v3/order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CanceledOrder {
+    pub orig_client_order_id: String,    // Client order ID of the canceled order
+    #[serde(flatten)]
+    pub order: OrderResult,              // Order state after the cancel
+}
+
+impl CanceledOrder {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Order after an amend, quantities are named `qty` unlike in order placement responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrder {
+    pub symbol: String,                  // Symbol
+    pub order_id: u64,                   // Order ID
+    pub order_list_id: i64,              // Order list ID, -1 unless part of an order list
+    pub orig_client_order_id: String,    // Client order ID before the amend
+    pub client_order_id: String,         // Client order ID after the amend
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "qty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity after the amend
+    #[serde(rename = "executedQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub executed_quantity: f64,          // Executed quantity
+    #[serde(rename = "preventedQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub prevented_quantity: f64,         // Quantity prevented by self-trade prevention
+    #[serde(rename = "quoteOrderQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_order_quantity: f64,       // Quote order quantity
+    #[serde(rename = "cumulativeQuoteQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cumulative_quote_quantity: f64,  // Cumulative quote asset quantity
+    pub status: OrderStatus,             // Order status
+    pub time_in_force: TimeInForce,      // Time in force
+    #[serde(rename = "type")]
+    pub order_type: OrderType,           // Order type
+    pub side: Side,                      // Side
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_time: Option<i64>,       // Time the order started working on the book
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+// Order list of an amended leg, without the status type and time of `OrderList`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrderList {
+    pub order_list_id: i64,              // Order list ID
+    pub contingency_type: ContingencyType, // Contingency type
+    pub list_order_status: ListOrderStatus, // List order status
+    pub list_client_order_id: String,    // List client order ID
+    pub symbol: String,                  // Symbol
+    pub orders: Vec<OrderListOrder>,     // Orders in the list
+}
+
+// PUT /api/v3/order/amend/keepPriority. The amendment is identified by its execution ID, which the
+// REPLACED execution report of the order carries too
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub transact_time: u64,              // Transaction time
+    pub execution_id: u64,               // Execution ID of the amendment
+    pub amended_order: AmendedOrder,     // Order after the amend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_status: Option<AmendedOrderList>, // Order list, only when the order is a list leg
+}
+
+impl OrderAmendment {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    pub symbol: String,                  // Symbol
+    pub order_id: u64,                   // Order ID
+    pub client_order_id: String,         // Client order ID
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContingencyType {
+    Oco,
+    Oto,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ListStatusType {
+    Response,
+    ExecStarted,
+    Updated,
+    AllDone,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ListOrderStatus {
+    Executing,
+    AllDone,
+    Reject,
+    #[serde(other)]
+    Other,
+}
+
+// Full order object of an order list leg, as found in placement and cancel responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orig_client_order_id: Option<String>, // Client order ID of a canceled leg
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub stop_price: Option<f64>,         // Stop price
+    #[serde(rename = "icebergQty", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub iceberg_quantity: Option<f64>,   // Iceberg quantity
+    #[serde(flatten)]
+    pub order: OrderResult,              // Order state
+}
+
+// Order list in OCO placement, cancel and listStatus responses; listStatus queries omit orderReports
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: i64,              // Order list ID
+    pub contingency_type: ContingencyType, // Contingency type
+    pub list_status_type: ListStatusType, // List status type
+    pub list_order_status: ListOrderStatus, // List order status
+    pub list_client_order_id: String,    // List client order ID
+    pub transaction_time: u64,           // Transaction time
+    pub symbol: String,                  // Symbol
+    pub orders: Vec<OrderListOrder>,     // Orders in the list
+    #[serde(default)]
+    pub order_reports: Vec<OrderReport>, // Order state of each leg
+}
+
+impl OrderList {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Elements of DELETE /api/v3/openOrders, which cancels plain orders and order lists in one array
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum CanceledOpenOrder {
+    OrderList(OrderList),
+    Order(CanceledOrder),
+}
+
+impl CanceledOpenOrder {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// One row of GET /api/v3/myTrades
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MyTradeRow {
+    pub symbol: String,                  // Symbol
+    pub id: u64,                         // Trade ID
+    pub order_id: u64,                   // Order ID
+    pub order_list_id: i64,              // Order list ID, -1 unless part of an order list
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "qty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "quoteQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_quantity: f64,             // Quote quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub commission: f64,                 // Commission
+    pub commission_asset: String,        // Commission asset
+    pub time: u64,                       // Trade time
+    pub is_buyer: bool,                  // Was this account the buyer?
+    pub is_maker: bool,                  // Was this account the maker?
+    pub is_best_match: bool,             // Was the trade the best price match?
+}
+
+impl MyTradeRow {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// One row of GET /api/v3/myPreventedMatches and the myPreventedMatches ws-api method
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatchRow {
+    pub symbol: String,                  // Symbol
+    pub prevented_match_id: u64,         // Prevented match ID
+    pub taker_order_id: u64,             // Order ID of the taker
+    pub maker_symbol: String,            // Symbol of the maker order
+    pub maker_order_id: u64,             // Order ID of the maker
+    pub trade_group_id: i64,             // Trade group ID
+    pub self_trade_prevention_mode: SelfTradePreventionMode, // STP mode that prevented the match
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price the match would have traded at
+    #[serde(rename = "makerPreventedQuantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maker_prevented_quantity: f64,   // Quantity of the maker order that was prevented
+    pub transact_time: u64,              // Transaction time
+}
+
+impl PreventedMatchRow {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Own-account fill, built from either myTrades rows or TRADE execution reports
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TradeFill {
+    pub symbol: String,                  // Symbol
+    pub trade_id: u64,                   // Trade ID
+    pub order_id: u64,                   // Order ID
+    pub side: Side,                      // Side of this account
+    pub price: f64,                      // Price
+    pub quantity: f64,                   // Quantity
+    pub quote_quantity: f64,             // Quote quantity
+    pub commission: f64,                 // Commission
+    pub commission_asset: Option<String>, // Commission asset
+    pub time: u64,                       // Trade time
+    pub is_maker: bool,                  // Was this account the maker?
+}
+
+impl TradeFill {
+    // None unless the report is a TRADE execution, the only kind carrying a fill
+    #[cfg(feature = "spot")]
+    pub fn from_execution_report(report: &ExecutionReport) -> Option<Self> {
+        if report.execution_type != ExecutionType::Trade {
+            return None;
+        }
+        Some(TradeFill {
+            symbol: report.symbol.clone(),
+            trade_id: u64::try_from(report.trade_id).ok()?,
+            order_id: report.order_id,
+            side: report.side,
+            price: report.last_executed_price,
+            quantity: report.last_executed_quantity,
+            quote_quantity: report.last_quote_quantity,
+            commission: report.commission_amount,
+            commission_asset: report.commission_asset.clone(),
+            time: report.transaction_time,
+            is_maker: report.is_maker,
+        })
+    }
+}
+
+impl From<MyTradeRow> for TradeFill {
+    fn from(row: MyTradeRow) -> Self {
+        TradeFill {
+            symbol: row.symbol,
+            trade_id: row.id,
+            order_id: row.order_id,
+            side: if row.is_buyer { Side::Buy } else { Side::Sell },
+            price: row.price,
+            quantity: row.quantity,
+            quote_quantity: row.quote_quantity,
+            commission: row.commission,
+            commission_asset: Some(row.commission_asset),
+            time: row.time,
+            is_maker: row.is_maker,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnownErrorCode {
+    UnknownError,                        // -1000
+    Disconnected,                        // -1001
+    Unauthorized,                        // -1002
+    TooManyRequests,                     // -1003
+    UnexpectedResponse,                  // -1006
+    Timeout,                             // -1007
+    ServerBusy,                          // -1008
+    InvalidMessage,                      // -1013, usually a symbol filter failure
+    TooManyOrders,                       // -1015
+    InvalidTimestamp,                    // -1021
+    InvalidSignature,                    // -1022
+    IllegalChars,                        // -1100
+    MandatoryParamEmptyOrMalformed,      // -1102
+    InvalidSymbol,                       // -1121
+    NewOrderRejected,                    // -2010
+    CancelRejected,                      // -2011
+    NoSuchOrder,                         // -2013
+    BadApiKeyFormat,                     // -2014
+    RejectedApiKey,                      // -2015
+    Unknown(i64),                        // Any code not listed above
+}
+
+impl KnownErrorCode {
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            -1000 => KnownErrorCode::UnknownError,
+            -1001 => KnownErrorCode::Disconnected,
+            -1002 => KnownErrorCode::Unauthorized,
+            -1003 => KnownErrorCode::TooManyRequests,
+            -1006 => KnownErrorCode::UnexpectedResponse,
+            -1007 => KnownErrorCode::Timeout,
+            -1008 => KnownErrorCode::ServerBusy,
+            -1013 => KnownErrorCode::InvalidMessage,
+            -1015 => KnownErrorCode::TooManyOrders,
+            -1021 => KnownErrorCode::InvalidTimestamp,
+            -1022 => KnownErrorCode::InvalidSignature,
+            -1100 => KnownErrorCode::IllegalChars,
+            -1102 => KnownErrorCode::MandatoryParamEmptyOrMalformed,
+            -1121 => KnownErrorCode::InvalidSymbol,
+            -2010 => KnownErrorCode::NewOrderRejected,
+            -2011 => KnownErrorCode::CancelRejected,
+            -2013 => KnownErrorCode::NoSuchOrder,
+            -2014 => KnownErrorCode::BadApiKeyFormat,
+            -2015 => KnownErrorCode::RejectedApiKey,
+            code => KnownErrorCode::Unknown(code),
+        }
+    }
+
+    pub fn code(&self) -> i64 {
+        match self {
+            KnownErrorCode::UnknownError => -1000,
+            KnownErrorCode::Disconnected => -1001,
+            KnownErrorCode::Unauthorized => -1002,
+            KnownErrorCode::TooManyRequests => -1003,
+            KnownErrorCode::UnexpectedResponse => -1006,
+            KnownErrorCode::Timeout => -1007,
+            KnownErrorCode::ServerBusy => -1008,
+            KnownErrorCode::InvalidMessage => -1013,
+            KnownErrorCode::TooManyOrders => -1015,
+            KnownErrorCode::InvalidTimestamp => -1021,
+            KnownErrorCode::InvalidSignature => -1022,
+            KnownErrorCode::IllegalChars => -1100,
+            KnownErrorCode::MandatoryParamEmptyOrMalformed => -1102,
+            KnownErrorCode::InvalidSymbol => -1121,
+            KnownErrorCode::NewOrderRejected => -2010,
+            KnownErrorCode::CancelRejected => -2011,
+            KnownErrorCode::NoSuchOrder => -2013,
+            KnownErrorCode::BadApiKeyFormat => -2014,
+            KnownErrorCode::RejectedApiKey => -2015,
+            KnownErrorCode::Unknown(code) => *code,
+        }
+    }
+
+    pub fn is_rate_limit(&self) -> bool {
+        matches!(self, KnownErrorCode::TooManyRequests | KnownErrorCode::TooManyOrders)
+    }
+
+    // Transient conditions where the same request may succeed later; the outcome of an order
+    // request that timed out is unknown, so query it before resending
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            KnownErrorCode::Disconnected
+                | KnownErrorCode::TooManyRequests
+                | KnownErrorCode::UnexpectedResponse
+                | KnownErrorCode::Timeout
+                | KnownErrorCode::ServerBusy
+                | KnownErrorCode::InvalidTimestamp
+        )
+    }
+}
+
+// Error body shared by REST and websocket API responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BinanceApiError {
+    pub code: i64,                       // Error code
+    pub msg: String,                     // Error message
+}
+
+impl BinanceApiError {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn kind(&self) -> KnownErrorCode {
+        KnownErrorCode::from_code(self.code)
+    }
+
+    pub fn is_rate_limit(&self) -> bool {
+        self.kind().is_rate_limit()
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+impl fmt::Display for BinanceApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Binance API error {}: {}", self.code, self.msg)
+    }
+}
+
+impl std::error::Error for BinanceApiError {}
+
+// GET /api/v3/ping answers with an empty object
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Ping {}
+
+impl Ping {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// GET /api/v3/time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ServerTime {
+    pub server_time: u64,                // Server time in milliseconds
+}
+
+impl ServerTime {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// POST /api/v3/userDataStream and POST /sapi/v1/userDataStream
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKey {
+    pub listen_key: String,              // Listen key of the user data stream
+}
+
+impl ListenKey {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// POST /sapi/v1/userDataStream/isolated, one stream per isolated margin symbol. The response carries
+// only the listen key, the symbol is the requested one unless the response names it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginListenKey {
+    #[serde(default)]
+    pub symbol: String,                  // Isolated margin symbol
+    pub listen_key: String,              // Listen key of the symbol's user data stream
+}
+
+impl IsolatedMarginListenKey {
+    pub fn from_json(json: &str, symbol: &str) -> Result<Self, serde_json::Error> {
+        let mut key: Self = serde_json::from_str(json)?;
+        if key.symbol.is_empty() {
+            key.symbol = symbol.to_string();
+        }
+        Ok(key)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockSample {
+    pub offset_ms: i64,                  // Server clock minus local clock
+    pub round_trip_ms: u64,              // Local receive time minus local send time
+}
+
+// Rolling estimate of server clock offset from serverTime round trips
+#[derive(Clone, Debug)]
+pub struct ClockSkew {
+    samples: VecDeque<ClockSample>,
+    capacity: usize,
+}
+
+impl Default for ClockSkew {
+    fn default() -> Self {
+        ClockSkew::new(8)
+    }
+}
+
+impl ClockSkew {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        ClockSkew { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    // Assumes the server read its clock halfway through the round trip
+    pub fn add_sample(&mut self, local_send_ms: u64, local_receive_ms: u64, server_time_ms: u64) -> ClockSample {
+        let round_trip_ms = local_receive_ms.saturating_sub(local_send_ms);
+        let local_midpoint_ms = local_send_ms + round_trip_ms / 2;
+        let sample = ClockSample {
+            offset_ms: server_time_ms as i64 - local_midpoint_ms as i64,
+            round_trip_ms,
+        };
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        sample
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &ClockSample> {
+        self.samples.iter()
+    }
+
+    // Mean offset over the window
+    pub fn offset_ms(&self) -> Option<i64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let total: i64 = self.samples.iter().map(|sample| sample.offset_ms).sum();
+        Some(total / self.samples.len() as i64)
+    }
+
+    // Worst round trip over the window
+    pub fn round_trip_ms(&self) -> Option<u64> {
+        self.samples.iter().map(|sample| sample.round_trip_ms).max()
+    }
+
+    pub fn server_time_at(&self, local_ms: u64) -> Option<u64> {
+        self.offset_ms().map(|offset| (local_ms as i64 + offset).max(0) as u64)
+    }
+
+    // Whether a request stamped with the local clock lands inside recvWindow, i.e. it is neither older
+    // than recvWindow nor more than 1000 ms ahead of the server when it arrives
+    pub fn is_recv_window_safe(&self, recv_window_ms: u64) -> bool {
+        let (Some(offset), Some(round_trip)) = (self.offset_ms(), self.round_trip_ms()) else {
+            return false;
+        };
+        let worst_lag = offset + round_trip as i64;
+        worst_lag <= recv_window_ms as i64 && -offset < 1000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::with_price_format;
+    use crate::testutil::rest::{sample_exchange_info_json, sample_my_prevented_matches_json, sample_order_amend_keep_priority_json, sample_order_full_json, sample_sor_order_full_json, sample_test_order_commission_rates_json, sample_ticker_trading_day_json, sample_ticker_trading_day_mini_json};
+
+    #[test]
+    fn test_exchange_info_deserialization() {
+        let info = ExchangeInfo::from_json(sample_exchange_info_json()).unwrap();
+
+        assert_eq!(info.timezone, "UTC");
+        assert_eq!(info.server_time, 1565246363776);
+        assert_eq!(info.rate_limits[1], RateLimit {
+            rate_limit_type: RateLimitType::Orders,
+            interval: RateLimitInterval::Second,
+            interval_num: 10,
+            limit: 100,
+            count: None,
+        });
+        assert_eq!(info.symbols.len(), 2);
+
+        let eth_btc = info.symbol("ETHBTC").unwrap();
+        assert_eq!(eth_btc.status, SymbolStatus::Trading);
+        assert_eq!(eth_btc.base_asset, "ETH");
+        assert_eq!(eth_btc.quote_asset_precision, 8);
+        assert_eq!(eth_btc.permission_sets, vec![vec!["SPOT".to_string(), "MARGIN".to_string()]]);
+        assert_eq!(eth_btc.allowed_self_trade_prevention_modes.len(), 3);
+        assert_eq!(eth_btc.filters.len(), 9);
+        assert_eq!(eth_btc.filters[5], Filter::PercentPriceBySide {
+            bid_multiplier_up: 5.0,
+            bid_multiplier_down: 0.2,
+            ask_multiplier_up: 5.0,
+            ask_multiplier_down: 0.2,
+            avg_price_mins: 5,
+        });
+        assert_eq!(eth_btc.filters[8], Filter::MaxNumAlgoOrders { max_num_algo_orders: 5 });
+        assert_eq!(eth_btc.step_size(), Some(0.0001));
+    }
+
+    #[test]
+    fn test_exchange_info_unknown_filter_and_tick_size_lookup() {
+        let info = ExchangeInfo::from_json(sample_exchange_info_json()).unwrap();
+
+        let btc_usdt = info.symbol("btcusdt").unwrap();
+        assert_eq!(btc_usdt.filters[2], Filter::MinNotional { min_notional: 5.0, apply_to_market: true, avg_price_mins: 5 });
+        assert_eq!(btc_usdt.filters[3], Filter::Other);
+
+        assert_eq!(info.tick_size("ETHBTC"), Some(0.00001));
+        assert_eq!(info.tick_size("BTCUSDT"), Some(0.01));
+        assert_eq!(info.tick_size("XRPUSDT"), None);
+    }
+
+    #[test]
+    fn test_depth_snapshot_deserialization() {
+        let json = r#"{"lastUpdateId":1027024,"bids":[["4.00000000","431.00000000"],["3.99000000","9.00000000"]],"asks":[["4.00000200","12.00000000"]]}"#;
+        let snapshot = DepthSnapshot::from_json(json).unwrap();
+
+        assert_eq!(snapshot.last_update_id, 1027024);
+        assert_eq!(snapshot.message_output_time, None);
+        assert_eq!(snapshot.best_bid(), Some(&PriceLevel { price: 4.0, quantity: 431.0 }));
+        assert_eq!(snapshot.best_ask(), Some(&PriceLevel { price: 4.000002, quantity: 12.0 }));
+        assert!(!snapshot.is_crossed());
+
+        let crossed = r#"{"lastUpdateId":1,"bids":[["4.1","1"]],"asks":[["4.0","1"]]}"#;
+        assert!(DepthSnapshot::from_json(crossed).unwrap().is_crossed());
+    }
+
+    #[test]
+    fn test_futures_depth_snapshot_deserialization() {
+        let json = r#"{"lastUpdateId":1027024,"E":1589436922972,"T":1589436922959,"bids":[["4.00000000","431.00000000"]],"asks":[]}"#;
+        let snapshot = DepthSnapshot::from_json(json).unwrap();
+
+        assert_eq!(snapshot.message_output_time, Some(1589436922972));
+        assert_eq!(snapshot.transaction_time, Some(1589436922959));
+        assert_eq!(snapshot.best_ask(), None);
+        assert!(!snapshot.is_crossed());
+    }
+
+    #[test]
+    fn test_depth_snapshot_5000_levels() {
+        let levels = |start: f64, step: f64| {
+            (0..5000)
+                .map(|i| format!(r#"["{:.8}","{:.8}"]"#, start + step * i as f64, 1.0 + i as f64))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let json = format!(r#"{{"lastUpdateId":42,"bids":[{}],"asks":[{}]}}"#, levels(49999.99, -0.01), levels(50000.0, 0.01));
+        let snapshot = DepthSnapshot::from_json(&json).unwrap();
+
+        assert_eq!(snapshot.bids.len(), 5000);
+        assert_eq!(snapshot.asks.len(), 5000);
+        assert!(snapshot.bids.capacity() <= 2 * snapshot.bids.len());
+        assert!(snapshot.asks.capacity() <= 2 * snapshot.asks.len());
+        assert_eq!(snapshot.best_bid().unwrap().price, 49999.99);
+        assert_eq!(snapshot.best_ask().unwrap().price, 50000.0);
+        assert_eq!(snapshot.asks[4999].quantity, 5000.0);
+        assert!(!snapshot.is_crossed());
+    }
+
+    #[test]
+    fn test_kline_rows_deserialization() {
+        let json = r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"],[1499644800000,"0.01577100","0.01700000","0.01500000","0.01690000","1200.50000000",1500249599999,"19.73000000",95,"600.25000000","9.86000000","0"]]"#;
+        let rows = KlineRow::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], KlineRow {
+            open_time: 1499040000000,
+            open_price: 0.0163479,
+            high_price: 0.8,
+            low_price: 0.015758,
+            close_price: 0.015771,
+            volume: 148976.11427815,
+            close_time: 1499644799999,
+            quote_asset_volume: 2434.19055334,
+            number_of_trades: 308,
+            taker_buy_base_asset_volume: 1756.87402397,
+            taker_buy_quote_asset_volume: 28.46694368,
+            ignore: "0".to_string(),
+        });
+        assert_eq!(rows[1].open_time, 1499644800000);
+        assert_eq!(rows[1].number_of_trades, 95);
+
+        let kline = rows[0].to_kline("BNBBTC", KlineInterval::W1, true);
+        assert_eq!(kline.symbol, "BNBBTC");
+        assert_eq!(kline.interval, KlineInterval::W1);
+        assert_eq!(kline.start_time, 1499040000000);
+        assert_eq!(kline.close_price, 0.015771);
+        assert_eq!(kline.taker_buy_quote_asset_volume, 28.46694368);
+        assert!(kline.is_closed);
+    }
+
+    #[test]
+    fn test_kline_row_flexible_types_and_arity() {
+        let json = r#"["1499040000000",0.0163479,"0.8","0.015758","0.015771","148976.11427815","1499644799999","2434.19055334","308","1756.87402397","28.46694368","0"]"#;
+        let row: KlineRow = serde_json::from_str(json).unwrap();
+        assert_eq!(row.open_time, 1499040000000);
+        assert_eq!(row.open_price, 0.0163479);
+        assert_eq!(row.close_time, 1499644799999);
+        assert_eq!(row.number_of_trades, 308);
+
+        let too_short = r#"[1499040000000,"0.01634790","0.80000000"]"#;
+        assert!(serde_json::from_str::<KlineRow>(too_short).is_err());
+        let too_long = r#"[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0","extra"]"#;
+        assert!(serde_json::from_str::<KlineRow>(too_long).is_err());
+    }
+
+    #[test]
+    fn test_kline_row_scientific_notation() {
+        let json = r#"[1499040000000,"1.2e-7",8E-1,"1e-8","-2.5E3",1.5e2,1499644799999,"0","308","0E0","0","0"]"#;
+        let row: KlineRow = serde_json::from_str(json).unwrap();
+        assert_eq!(row.open_price, 0.00000012);
+        assert_eq!(row.high_price, 0.8);
+        assert_eq!(row.low_price, 0.00000001);
+        assert_eq!(row.close_price, -2500.0);
+        assert_eq!(row.volume, 150.0);
+        assert_eq!(row.taker_buy_base_asset_volume, 0.0);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_agg_trade_rows_deserialization_and_conversion() {
+        let json = r#"[{"a":26129,"p":"0.01633102","q":"4.70443515","f":27781,"l":27781,"T":1498793709153,"m":true,"M":true},{"a":26130,"p":"0.01633200","q":"0.50000000","f":27782,"l":27785,"T":1498793709160,"m":false,"M":true},{"a":26131,"p":"0.01633000","q":"1.25000000","f":27786,"l":27786,"T":1498793709201,"m":true,"M":true}]"#;
+        let rows = AggTradeRow::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], AggTradeRow {
+            aggregate_trade_id: 26130,
+            price: 0.016332,
+            quantity: 0.5,
+            first_trade_id: 27782,
+            last_trade_id: 27785,
+            trade_time: 1498793709160,
+            is_buyer_market_maker: false,
+            is_best_match: true,
+        });
+
+        let trades: Vec<AggTrade> = rows.into_iter().map(|row| row.into_agg_trade("ETHBTC")).collect();
+        assert_eq!(trades[0].event_type, "aggTrade");
+        assert_eq!(trades[0].event_time, 1498793709153);
+        assert_eq!(trades[0].symbol, "ETHBTC");
+        assert_eq!(trades[0].aggregate_trade_id, 26129);
+        assert_eq!(trades[0].price, 0.01633102);
+        assert_eq!(trades[1].first_trade_id, 27782);
+        assert_eq!(trades[1].last_trade_id, 27785);
+        assert!(!trades[1].is_buyer_market_maker);
+        assert_eq!(trades[2].aggregate_trade_id, 26131);
+        assert_eq!(trades[2].quantity, 1.25);
+    }
+
+    #[test]
+    fn test_trade_rows_deserialization() {
+        let json = r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true},{"id":28458,"price":"4.00000200","qty":"0.50000000","quoteQty":"2.000001","time":1499865549601,"isBuyerMaker":false,"isBestMatch":true}]"#;
+        let rows = TradeRow::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], TradeRow {
+            id: 28457,
+            price: 4.000001,
+            quantity: 12.0,
+            quote_quantity: 48.000012,
+            time: 1499865549590,
+            is_buyer_maker: true,
+            is_best_match: true,
+        });
+        assert_eq!(rows[1].id, 28458);
+        assert!(!rows[1].is_buyer_maker);
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_trade_row_into_trade() {
+        let json = r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true}]"#;
+        let row = TradeRow::from_json_array(json).unwrap().remove(0);
+
+        assert_eq!(row.into_trade("BNBBTC"), Trade {
+            event_type: None,
+            event_time: None,
+            symbol: "BNBBTC".to_string(),
+            trade_id: 28457,
+            price: 4.000001,
+            quantity: 12.0,
+            trade_time: 1499865549590,
+            is_buyer_market_maker: true,
+            ignore: true,
+        });
+    }
+
+    #[test]
+    fn test_ticker_24hr_full_single_deserialization() {
+        let json = r#"{"symbol":"BNBBTC","priceChange":"-94.99999800","priceChangePercent":"-95.960","weightedAvgPrice":"0.29628482","prevClosePrice":"0.10002000","lastPrice":"4.00000200","lastQty":"200.00000000","bidPrice":"4.00000000","bidQty":"100.00000000","askPrice":"4.00000200","askQty":"100.00000000","openPrice":"99.00000000","highPrice":"100.00000000","lowPrice":"0.10000000","volume":"8913.30000000","quoteVolume":"15.30000000","openTime":1499783499040,"closeTime":1499869899040,"firstId":28385,"lastId":28460,"count":76}"#;
+        let expected = Ticker24hrFull {
+            symbol: "BNBBTC".to_string(),
+            price_change: -94.999998,
+            price_change_percent: -95.96,
+            weighted_avg_price: 0.29628482,
+            prev_close_price: 0.10002,
+            last_price: 4.000002,
+            last_quantity: 200.0,
+            bid_price: 4.0,
+            bid_quantity: 100.0,
+            ask_price: 4.000002,
+            ask_quantity: 100.0,
+            open_price: 99.0,
+            high_price: 100.0,
+            low_price: 0.1,
+            volume: 8913.3,
+            quote_volume: 15.3,
+            open_time: 1499783499040,
+            close_time: 1499869899040,
+            first_id: 28385,
+            last_id: 28460,
+            count: 76,
+        };
+
+        match Ticker24hr::from_json(json).unwrap() {
+            OneOrMany::One(Ticker24hr::Full(ticker)) => assert_eq!(ticker, expected),
+            other => panic!("Expected a single FULL ticker, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ticker_24hr_mini_single_deserialization() {
+        let json = r#"{"symbol":"BNBBTC","openPrice":"99.00000000","highPrice":"100.00000000","lowPrice":"0.10000000","lastPrice":"4.00000200","volume":"8913.30000000","quoteVolume":"15.30000000","openTime":1499783499040,"closeTime":1499869899040,"firstId":-1,"lastId":-1,"count":0}"#;
+
+        match Ticker24hr::from_json(json).unwrap() {
+            OneOrMany::One(Ticker24hr::Mini(ticker)) => {
+                assert_eq!(ticker.symbol, "BNBBTC");
+                assert_eq!(ticker.open_price, 99.0);
+                assert_eq!(ticker.last_price, 4.000002);
+                assert_eq!(ticker.quote_volume, 15.3);
+                assert_eq!(ticker.first_id, -1);
+                assert_eq!(ticker.last_id, -1);
+                assert_eq!(ticker.count, 0);
+            }
+            other => panic!("Expected a single MINI ticker, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ticker_24hr_array_deserialization() {
+        let json = r#"[{"symbol":"BTCUSDT","priceChange":"-154.13000000","priceChangePercent":"-0.740","weightedAvgPrice":"20677.46305250","prevClosePrice":"20825.27000000","lastPrice":"20671.14000000","lastQty":"0.00030000","bidPrice":"20671.13000000","bidQty":"0.55418000","askPrice":"20671.14000000","askQty":"0.07149000","openPrice":"20825.27000000","highPrice":"20972.46000000","lowPrice":"20327.92000000","volume":"72.65112300","quoteVolume":"1502240.91155513","openTime":1655432400000,"closeTime":1655446835460,"firstId":11147809,"lastId":11149775,"count":1967},{"symbol":"BNBBTC","priceChange":"0.00008530","priceChangePercent":"0.823","weightedAvgPrice":"0.01043129","prevClosePrice":"0.01036170","lastPrice":"0.01044700","lastQty":"0.00600000","bidPrice":"0.01044700","bidQty":"0.00200000","askPrice":"0.01044800","askQty":"1.02200000","openPrice":"0.01036170","highPrice":"0.01049530","lowPrice":"0.01036000","volume":"1314.81000000","quoteVolume":"13.71523110","openTime":1655432400000,"closeTime":1655446776407,"firstId":520806,"lastId":521062,"count":257}]"#;
+        let tickers = Ticker24hr::from_json(json).unwrap().into_vec();
+
+        assert_eq!(tickers.len(), 2);
+        assert!(tickers.iter().all(|ticker| matches!(ticker, Ticker24hr::Full(_))));
+        assert_eq!(tickers[0].symbol(), "BTCUSDT");
+        assert_eq!(tickers[0].last_price(), 20671.14);
+        assert_eq!(tickers[1].symbol(), "BNBBTC");
+        assert_eq!(tickers[1].last_price(), 0.010447);
+    }
+
+    #[test]
+    fn test_exchange_info_request() {
+        assert_eq!(ExchangeInfoRequest::new().to_query_string().unwrap(), "");
+        assert_eq!(ExchangeInfoRequest::new().symbol("BNBBTC").to_query_string().unwrap(), "symbol=BNBBTC");
+        assert_eq!(ExchangeInfoRequest::new().permissions(&["SPOT"]).to_query_string().unwrap(), "permissions=SPOT");
+        assert_eq!(ExchangeInfoRequest::new().symbols(&["BNBBTC", "BTCUSDT"]).to_query_string().unwrap(), "symbols=%5B%22BNBBTC%22%2C%22BTCUSDT%22%5D");
+        assert_eq!(ExchangeInfoRequest::new().permissions(&["MARGIN", "LEVERAGED"]).to_query_string().unwrap(), "permissions=%5B%22MARGIN%22%2C%22LEVERAGED%22%5D");
+        assert_eq!(
+            ExchangeInfoRequest::new().symbols(&["BNBBTC"]).permissions(&["MARGIN", "LEVERAGED"]).to_query_string(),
+            Err(OrderRequestError::MutuallyExclusive("symbols", "permissions"))
+        );
+    }
+
+    #[test]
+    fn test_trading_day_ticker_full_single() {
+        let request = TradingDayTickerRequest::symbol("BTCUSDT").time_zone(TimeZone::UTC);
+        assert_eq!(request.to_query_string(), "symbol=BTCUSDT&timeZone=0");
+
+        match TradingDayTicker::from_json(sample_ticker_trading_day_json()).unwrap() {
+            OneOrMany::One(TradingDayTicker::Full(ticker)) => {
+                assert_eq!(ticker.symbol, "BTCUSDT");
+                assert_eq!(ticker.price_change, -83.13);
+                assert_eq!(ticker.weighted_avg_price, 26234.58803036);
+                assert_eq!(ticker.last_price, 26221.67);
+                assert_eq!(ticker.quote_volume, 485217905.0421048);
+                assert_eq!((ticker.open_time, ticker.close_time), (1695686400000, 1695772799999));
+                assert_eq!(ticker.count, 697727);
+            }
+            other => panic!("Expected a single FULL ticker, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_trading_day_ticker_mini_array() {
+        let request = TradingDayTickerRequest::symbols(&["BTCUSDT", "BNBUSDT"])
+            .ticker_type(TickerType::Mini)
+            .time_zone("+08:00".parse().unwrap());
+        assert_eq!(request.to_query_string(), r#"symbols=["BTCUSDT","BNBUSDT"]&type=MINI&timeZone=8"#);
+        assert_eq!(request.to_params(), serde_json::json!({"symbols": ["BTCUSDT", "BNBUSDT"], "type": "MINI", "timeZone": "8"}));
+
+        let tickers = TradingDayTicker::from_json(sample_ticker_trading_day_mini_json()).unwrap().into_vec();
+        assert_eq!(tickers.len(), 2);
+        assert!(tickers.iter().all(|ticker| matches!(ticker, TradingDayTicker::Mini(_))));
+        assert_eq!(tickers[1].symbol(), "BNBUSDT");
+        assert_eq!(tickers[1].last_price(), 213.4);
+        // The trading day starts at midnight UTC+8
+        assert!(tickers.iter().all(|ticker| ticker.trading_day() == (1695657600000, 1695743999999)));
+    }
+
+    #[test]
+    fn test_price_ticker_single_deserialization() {
+        let json = r#"{"symbol":"LTCBTC","price":"4.00000200"}"#;
+
+        assert_eq!(PriceTicker::from_json(json).unwrap(), OneOrMany::One(PriceTicker {
+            symbol: "LTCBTC".to_string(),
+            price: 4.000002,
+        }));
+    }
+
+    #[test]
+    fn test_price_ticker_array_to_price_map() {
+        let json = r#"[{"symbol":"LTCBTC","price":"4.00000200"},{"symbol":"ETHBTC","price":"0.07946600"}]"#;
+        let tickers = PriceTicker::from_json(json).unwrap().into_vec();
+        let prices = PriceTicker::to_price_map(&tickers);
+
+        assert_eq!(tickers.len(), 2);
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["LTCBTC"], 4.000002);
+        assert_eq!(prices["ETHBTC"], 0.079466);
+        assert_eq!(prices.get("BNBBTC"), None);
+    }
+
+    #[test]
+    fn test_book_ticker_snapshot_single_deserialization() {
+        let json = r#"{"symbol":"LTCBTC","bidPrice":"4.00000000","bidQty":"431.00000000","askPrice":"4.00000200","askQty":"9.00000000"}"#;
+
+        assert_eq!(BookTickerSnapshot::from_json(json).unwrap(), OneOrMany::One(BookTickerSnapshot {
+            symbol: "LTCBTC".to_string(),
+            bid_price: 4.0,
+            bid_quantity: 431.0,
+            ask_price: 4.000002,
+            ask_quantity: 9.0,
+        }));
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_book_ticker_snapshot_array_into_book_ticker() {
+        let json = r#"[{"symbol":"LTCBTC","bidPrice":"4.00000000","bidQty":"431.00000000","askPrice":"4.00000200","askQty":"9.00000000"},{"symbol":"ETHBTC","bidPrice":"0.07946700","bidQty":"9.00000000","askPrice":"100000.00000000","askQty":"1000.00000000"}]"#;
+        let tickers: Vec<BookTicker> = BookTickerSnapshot::from_json(json).unwrap()
+            .into_vec()
+            .into_iter()
+            .map(BookTicker::from)
+            .collect();
+
+        assert_eq!(tickers.len(), 2);
+        assert_eq!(tickers[1], BookTicker {
+            update_id: None,
+            symbol: "ETHBTC".to_string(),
+            best_bid_price: 0.079467,
+            best_bid_quantity: 9.0,
+            best_ask_price: 100000.0,
+            best_ask_quantity: 1000.0,
+        });
+    }
+
+    #[test]
+    fn test_avg_price_deserialization() {
+        let json = r#"{"mins":5,"price":"9.35751834","closeTime":1694061154503}"#;
+        assert_eq!(AvgPrice::from_json(json).unwrap(), AvgPrice {
+            mins: 5,
+            price: 9.35751834,
+            close_time: Some(1694061154503),
+        });
+
+        let json = r#"{"mins":5,"price":"9.35751834"}"#;
+        assert_eq!(AvgPrice::from_json(json).unwrap(), AvgPrice {
+            mins: 5,
+            price: 9.35751834,
+            close_time: None,
+        });
+    }
+
+    #[test]
+    fn test_account_information_deserialization() {
+        let json = r#"{
+            "makerCommission": 15,
+            "takerCommission": 15,
+            "buyerCommission": 0,
+            "sellerCommission": 0,
+            "commissionRates": {"maker": "0.00150000", "taker": "0.00150000", "buyer": "0.00000000", "seller": "0.00000000"},
+            "canTrade": true,
+            "canWithdraw": true,
+            "canDeposit": true,
+            "brokered": false,
+            "requireSelfTradePrevention": false,
+            "preventSor": false,
+            "updateTime": 123456789,
+            "accountType": "SPOT",
+            "balances": [
+                {"asset": "BTC", "free": "4723846.89208129", "locked": "0.00000000"},
+                {"asset": "LTC", "free": "4763368.68006011", "locked": "0.00000000"},
+                {"asset": "ETH", "free": "12.50000000", "locked": "1.25000000"},
+                {"asset": "BNB", "free": "3.14159265", "locked": "0.00000000"},
+                {"asset": "USDT", "free": "10250.75000000", "locked": "500.00000000"},
+                {"asset": "BUSD", "free": "0.00000000", "locked": "0.00000000"},
+                {"asset": "XRP", "free": "1500.00000000", "locked": "0.00000000"},
+                {"asset": "ADA", "free": "800.00000000", "locked": "200.00000000"},
+                {"asset": "SOL", "free": "42.00000000", "locked": "0.00000000"},
+                {"asset": "DOGE", "free": "100000.00000000", "locked": "0.00000000"},
+                {"asset": "DOT", "free": "0.50000000", "locked": "0.00000000"},
+                {"asset": "TRX", "free": "2500.00000000", "locked": "0.00000000"}
+            ],
+            "permissions": ["SPOT"],
+            "uid": 354937868,
+            "allowTrailingStop": true
+        }"#;
+        let account = AccountInformation::from_json(json).unwrap();
+
+        assert_eq!(account.maker_commission, 15);
+        assert_eq!(account.commission_rates.maker, 0.0015);
+        assert_eq!(account.commission_rates.seller, 0.0);
+        assert!(account.can_trade);
+        assert!(!account.require_self_trade_prevention);
+        assert_eq!(account.update_time, 123456789);
+        assert_eq!(account.account_type, "SPOT");
+        assert_eq!(account.balances.len(), 12);
+        assert_eq!(account.permissions, vec!["SPOT".to_string()]);
+        assert_eq!(account.uid, Some(354937868));
+
+        assert_eq!(account.balance_of("BTC"), Some(&AccountBalance {
+            asset: "BTC".to_string(),
+            free: 4723846.89208129,
+            locked: 0.0,
+        }));
+        assert_eq!(account.balance_of("usdt").unwrap().locked, 500.0);
+        assert_eq!(account.balance_of("PEPE"), None);
+
+        assert_eq!(account.flag("allowTrailingStop"), Some(true));
+        assert_eq!(account.flag("unknownFlag"), None);
+    }
+
+    #[test]
+    fn test_new_order_request_limit_query_string() {
+        let request = NewOrderRequest::new("BTCUSDT", Side::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::Gtc)
+            .quantity(0.00001)
+            .price(25000.5)
+            .new_client_order_id("my-order-1")
+            .new_order_resp_type(NewOrderRespType::Full)
+            .self_trade_prevention_mode(SelfTradePreventionMode::ExpireTaker);
+
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.00001&price=25000.5&newClientOrderId=my-order-1&newOrderRespType=FULL&selfTradePreventionMode=EXPIRE_TAKER"
+        );
+
+        let request = NewOrderRequest::new("BTCUSDT", Side::Buy, OrderType::Market).quantity(1.0).new_client_order_id("desk/1:a b&c=d");
+        assert!(request.to_query_string().unwrap().ends_with("&newClientOrderId=desk%2F1%3Aa%20b%26c%3Dd"));
+    }
+
+    #[test]
+    fn test_new_order_request_market_quote_qty_query_string() {
+        let request = NewOrderRequest::new("ETHUSDT", Side::Sell, OrderType::Market)
+            .quote_order_qty(100.0);
+
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=ETHUSDT&side=SELL&type=MARKET&quoteOrderQty=100"
+        );
+    }
+
+    #[test]
+    fn test_new_order_request_query_string_precision() {
+        let info = ExchangeInfo::from_json(sample_exchange_info_json()).unwrap();
+        let eth_btc = info.symbol("ETHBTC").unwrap();
+        assert_eq!(eth_btc.price_format(), PriceFormat::Fixed(5));
+        assert_eq!(eth_btc.quantity_format(), PriceFormat::Fixed(4));
+        assert_eq!(info.price_format("BTCUSDT"), Some(PriceFormat::Fixed(2)));
+
+        let request = NewOrderRequest::new("ETHBTC", Side::Buy, OrderType::StopLossLimit)
+            .time_in_force(TimeInForce::Gtc)
+            .quantity(123456.0)
+            .price(0.0725)
+            .stop_price(0.07);
+        assert_eq!(
+            request.to_query_string_for(eth_btc).unwrap(),
+            "symbol=ETHBTC&side=BUY&type=STOP_LOSS_LIMIT&timeInForce=GTC&quantity=123456.0000&price=0.07250&stopPrice=0.07000"
+        );
+        assert_eq!(
+            request.to_query_string_with(PriceFormat::WIRE, PriceFormat::Trimmed(8)).unwrap(),
+            "symbol=ETHBTC&side=BUY&type=STOP_LOSS_LIMIT&timeInForce=GTC&quantity=123456&price=0.07250000&stopPrice=0.07000000"
+        );
+        assert_eq!(
+            with_price_format(PriceFormat::WIRE, || request.to_query_string()).unwrap(),
+            "symbol=ETHBTC&side=BUY&type=STOP_LOSS_LIMIT&timeInForce=GTC&quantity=123456.00000000&price=0.07250000&stopPrice=0.07000000"
+        );
+    }
+
+    #[test]
+    fn test_wire_serialization_price_format() {
+        let json = r#"[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]"#;
+        let row: KlineRow = serde_json::from_str(json).unwrap();
+        assert_eq!(with_price_format(PriceFormat::WIRE, || serde_json::to_string(&row)).unwrap(), json);
+        assert_eq!(
+            serde_json::to_string(&row).unwrap(),
+            r#"[1499040000000,"0.0163479","0.8","0.015758","0.015771","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]"#
+        );
+
+        let levels = [PriceLevel { price: 0.00000001, quantity: 123456.0 }];
+        assert_eq!(with_price_format(PriceFormat::WIRE, || serde_json::to_string(&levels)).unwrap(), r#"[["0.00000001","123456.00000000"]]"#);
+        assert_eq!(with_price_format(PriceFormat::Fixed(2), || serde_json::to_string(&levels)).unwrap(), r#"[["0.00","123456.00"]]"#);
+        assert_eq!(serde_json::to_string(&levels).unwrap(), r#"[["0.00000001","123456"]]"#);
+    }
+
+    #[test]
+    fn test_new_order_request_rejects_contradictory_parameters() {
+        let request = NewOrderRequest::new("ETHUSDT", Side::Buy, OrderType::Market)
+            .quantity(1.0)
+            .quote_order_qty(100.0);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::QuantityAndQuoteOrderQty));
+
+        let request = NewOrderRequest::new("ETHUSDT", Side::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::Gtc)
+            .quote_order_qty(100.0)
+            .price(2000.0);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::QuoteOrderQtyNotMarket));
+
+        let request = NewOrderRequest::new("ETHUSDT", Side::Buy, OrderType::Limit)
+            .quantity(1.0)
+            .price(2000.0);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::MissingParameter("timeInForce")));
+
+        let request = NewOrderRequest::new("ETHUSDT", Side::Buy, OrderType::Market)
+            .quantity(f64::NAN);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::InvalidDecimal("quantity")));
+    }
+
+    #[test]
+    fn test_test_order_request_query_string() {
+        let order = NewOrderRequest::new("BTCUSDT", Side::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::Gtc)
+            .quantity(0.001)
+            .price(25000.0);
+        assert_eq!(TestOrderRequest::new(order.clone()).to_query_string().unwrap(), order.to_query_string().unwrap());
+        assert_eq!(
+            TestOrderRequest::new(order).compute_commission_rates(true).to_query_string().unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.001&price=25000&computeCommissionRates=true"
+        );
+
+        let order = NewOrderRequest::new("BTCUSDT", Side::Buy, OrderType::Limit).quantity(0.001);
+        assert_eq!(TestOrderRequest::new(order).compute_commission_rates(true).to_query_string(), Err(OrderRequestError::MissingParameter("timeInForce")));
+    }
+
+    #[test]
+    fn test_prevented_match_rows() {
+        let rows = PreventedMatchRow::from_json_array(sample_my_prevented_matches_json()).unwrap();
+        assert_eq!(rows, vec![PreventedMatchRow {
+            symbol: "BTCUSDT".to_string(),
+            prevented_match_id: 1,
+            taker_order_id: 5,
+            maker_symbol: "BTCUSDT".to_string(),
+            maker_order_id: 3,
+            trade_group_id: 1,
+            self_trade_prevention_mode: SelfTradePreventionMode::ExpireMaker,
+            price: 1.1,
+            maker_prevented_quantity: 1.3,
+            transact_time: 1669101687094,
+        }]);
+    }
+
+    #[test]
+    fn test_amend_order_request() {
+        let request = AmendOrderRequest::by_order_id("BTCUSDT", 33, 5.0).new_client_order_id("PFaq6hIHxqFENGfdtn4J6Q");
+        assert_eq!(request.to_query_string().unwrap(), "symbol=BTCUSDT&orderId=33&newClientOrderId=PFaq6hIHxqFENGfdtn4J6Q&newQty=5");
+        assert_eq!(
+            request.to_params_with(PriceFormat::WIRE).unwrap(),
+            serde_json::json!({"symbol": "BTCUSDT", "orderId": 33, "newClientOrderId": "PFaq6hIHxqFENGfdtn4J6Q", "newQty": "5.00000000"})
+        );
+
+        let request = AmendOrderRequest::by_client_order_id("BTCUSDT", "5xrgbMyg6z36NzBn2pbT8H", 0.5);
+        assert_eq!(request.to_query_string().unwrap(), "symbol=BTCUSDT&origClientOrderId=5xrgbMyg6z36NzBn2pbT8H&newQty=0.5");
+
+        let mut request = AmendOrderRequest::by_order_id("BTCUSDT", 33, f64::NAN);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::InvalidDecimal("newQty")));
+        request.order_id = None;
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::MissingParameter("orderId")));
+    }
+
+    #[test]
+    fn test_order_amendment() {
+        let amendment = OrderAmendment::from_json(sample_order_amend_keep_priority_json()).unwrap();
+        assert_eq!((amendment.transact_time, amendment.execution_id), (1741926410242, 75));
+        assert_eq!(amendment.list_status, None);
+
+        let order = &amendment.amended_order;
+        assert_eq!((order.order_id, order.orig_client_order_id.as_str(), order.client_order_id.as_str()), (33, "5xrgbMyg6z36NzBn2pbT8H", "PFaq6hIHxqFENGfdtn4J6Q"));
+        assert_eq!((order.price, order.quantity, order.executed_quantity), (6.0, 5.0, 0.0));
+        assert_eq!((order.status, order.order_type, order.side), (OrderStatus::New, OrderType::Limit, Side::Sell));
+
+        let list_status = r#""listStatus":{"orderListId":1,"contingencyType":"OTO","listOrderStatus":"EXECUTING","listClientOrderId":"AT7FTxZXylVSwRoZs52mt3","symbol":"BTCUSDT","orders":[{"symbol":"BTCUSDT","orderId":33,"clientOrderId":"PFaq6hIHxqFENGfdtn4J6Q"},{"symbol":"BTCUSDT","orderId":34,"clientOrderId":"QmuHNXoYNZAbmxSvXvK1K9"}]}"#;
+        let json = sample_order_amend_keep_priority_json().replace(r#""orderListId":-1"#, r#""orderListId":1"#).replace("}}", &format!("}},{}}}", list_status));
+        let list = OrderAmendment::from_json(&json).unwrap().list_status.unwrap();
+        assert_eq!((list.contingency_type, list.orders.len()), (ContingencyType::Oto, 2));
+    }
+
+    #[test]
+    fn test_sor_order_request_query_string() {
+        let request = SorOrderRequest::new("BTCUSDT", Side::Buy, OrderType::Limit, 0.5)
+            .time_in_force(TimeInForce::Gtc)
+            .price(31000.0)
+            .new_order_resp_type(NewOrderRespType::Full);
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.5&price=31000&newOrderRespType=FULL"
+        );
+        assert_eq!(
+            SorTestOrderRequest::new(request).compute_commission_rates(true).to_query_string().unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=0.5&price=31000&newOrderRespType=FULL&computeCommissionRates=true"
+        );
+
+        let request = SorOrderRequest::new("BTCUSDT", Side::Sell, OrderType::StopLoss, 0.5);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::UnsupportedOrderType(OrderType::StopLoss)));
+        let request = SorOrderRequest::new("BTCUSDT", Side::Sell, OrderType::Limit, 0.5).time_in_force(TimeInForce::Gtc);
+        assert_eq!(request.to_query_string(), Err(OrderRequestError::MissingParameter("price")));
+    }
+
+    #[test]
+    fn test_sor_order_full_response() {
+        let response = SorOrderResponse::from_json(sample_sor_order_full_json()).unwrap();
+        assert_eq!((response.resp_type(), response.order_id()), (NewOrderRespType::Full, 2));
+
+        let SorOrderResponse::Full(order) = response else { panic!("expected a FULL response") };
+        assert_eq!((order.result.working_floor.as_str(), order.result.used_sor), ("SOR", true));
+        assert_eq!((order.result.result.status, order.result.result.executed_quantity), (OrderStatus::Filled, 0.5));
+        assert_eq!(order.fills, vec![SorFill {
+            match_type: "ONE_PARTY_TRADE_REPORT".to_string(),
+            price: 28000.0,
+            quantity: 0.5,
+            commission: 0.0,
+            commission_asset: "BTC".to_string(),
+            trade_id: -1,
+            alloc_id: Some(0),
+        }]);
+
+        // Without SOR fields only the ACK fields match
+        assert_eq!(SorOrderResponse::from_json(sample_order_full_json()).unwrap().resp_type(), NewOrderRespType::Ack);
+    }
+
+    #[test]
+    fn test_test_order_response() {
+        let response = TestOrderResponse::from_json(sample_test_order_commission_rates_json()).unwrap();
+        let rates = response.commission_rates().unwrap();
+        assert_eq!(rates.standard_commission_for_order, OrderCommission { maker: 0.00000112, taker: 0.00000114 });
+        assert_eq!(rates.tax_commission_for_order.taker, 0.00000114);
+        assert_eq!(rates.special_commission_for_order, None);
+        assert!(rates.discount.enabled_for_account && rates.discount.enabled_for_symbol);
+        assert_eq!((rates.discount.discount_asset.as_str(), rates.discount.discount), ("BNB", 0.25));
+
+        let response = TestOrderResponse::from_json("{}").unwrap();
+        assert_eq!(response, TestOrderResponse::Empty {});
+        assert_eq!(response.commission_rates(), None);
+        assert_eq!(serde_json::to_string(&response).unwrap(), "{}");
+
+        assert!(TestOrderResponse::from_json(r#"{"code":-2010,"msg":"Account has insufficient balance"}"#).is_err());
+        let partial = r#"{"standardCommissionForOrder":{"maker":"0.00000112","taker":"0.00000114"}}"#;
+        assert!(TestOrderResponse::from_json(partial).is_err());
+    }
+
+    #[test]
+    fn test_order_response_ack_deserialization() {
+        let json = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595}"#;
+        let response = OrderResponse::from_json(json).unwrap();
+
+        assert_eq!(response.resp_type(), NewOrderRespType::Ack);
+        assert_eq!(response, OrderResponse::Ack(OrderAck {
+            symbol: "BTCUSDT".to_string(),
+            order_id: 28,
+            order_list_id: -1,
+            client_order_id: "6gCrw2kRUAF9CvJDGP16IP".to_string(),
+            transact_time: 1507725176595,
+        }));
+    }
+
+    #[test]
+    fn test_order_response_result_deserialization() {
+        let json = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595,"price":"0.00000000","origQty":"10.00000000","executedQty":"10.00000000","origQuoteOrderQty":"0.000000","cummulativeQuoteQty":"10.00000000","status":"FILLED","timeInForce":"GTC","type":"MARKET","side":"SELL","workingTime":1507725176595,"selfTradePreventionMode":"NONE"}"#;
+
+        match OrderResponse::from_json(json).unwrap() {
+            OrderResponse::Result(order) => {
+                assert_eq!(order.order_id, 28);
+                assert_eq!(order.original_quantity, 10.0);
+                assert_eq!(order.executed_quantity, 10.0);
+                assert_eq!(order.cumulative_quote_quantity, 10.0);
+                assert_eq!(order.status, OrderStatus::Filled);
+                assert_eq!(order.time_in_force, TimeInForce::Gtc);
+                assert_eq!(order.order_type, OrderType::Market);
+                assert_eq!(order.side, Side::Sell);
+                assert_eq!(order.working_time, Some(1507725176595));
+                assert_eq!(order.self_trade_prevention_mode, Some(SelfTradePreventionMode::None));
+            }
+            other => panic!("Expected a RESULT response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_order_response_full_deserialization() {
+        let json = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595,"price":"0.00000000","origQty":"10.00000000","executedQty":"10.00000000","origQuoteOrderQty":"0.000000","cummulativeQuoteQty":"10.00000000","status":"FILLED","timeInForce":"GTC","type":"MARKET","side":"SELL","workingTime":1507725176595,"selfTradePreventionMode":"NONE","fills":[{"price":"4000.00000000","qty":"1.00000000","commission":"4.00000000","commissionAsset":"USDT","tradeId":56},{"price":"3999.00000000","qty":"5.00000000","commission":"19.99500000","commissionAsset":"USDT","tradeId":57}]}"#;
+        let response = OrderResponse::from_json(json).unwrap();
+
+        assert_eq!(response.resp_type(), NewOrderRespType::Full);
+        assert_eq!(response.order_id(), 28);
+        assert_eq!(response.fills().len(), 2);
+        assert_eq!(response.fills()[1], Fill {
+            price: 3999.0,
+            quantity: 5.0,
+            commission: 19.995,
+            commission_asset: "USDT".to_string(),
+            trade_id: 57,
+        });
+    }
+
+    #[test]
+    fn test_canceled_order_deserialization() {
+        let json = r#"{"symbol":"LTCBTC","origClientOrderId":"myOrder1","orderId":4,"orderListId":-1,"clientOrderId":"cancelMyOrder1","transactTime":1684804350068,"price":"2.00000000","origQty":"1.00000000","executedQty":"0.00000000","cummulativeQuoteQty":"0.00000000","status":"CANCELED","timeInForce":"GTC","type":"LIMIT","side":"BUY","selfTradePreventionMode":"NONE"}"#;
+        let canceled = CanceledOrder::from_json(json).unwrap();
+
+        assert_eq!(canceled.orig_client_order_id, "myOrder1");
+        assert_eq!(canceled.order.client_order_id, "cancelMyOrder1");
+        assert_eq!(canceled.order.order_id, 4);
+        assert_eq!(canceled.order.price, 2.0);
+        assert_eq!(canceled.order.status, OrderStatus::Canceled);
+        assert_eq!(canceled.order.working_time, None);
+    }
+
+    #[test]
+    fn test_canceled_open_orders_mixed_deserialization() {
+        let json = r#"[
+            {"symbol":"BTCUSDT","origClientOrderId":"E6APeyTJvkMvLMYMqu1KQ4","orderId":11,"orderListId":-1,"clientOrderId":"pXLV6Hz6mprAcVYpVMTGgx","transactTime":1684804350068,"price":"0.089853","origQty":"0.178622","executedQty":"0.000000","cummulativeQuoteQty":"0.000000","status":"CANCELED","timeInForce":"GTC","type":"LIMIT","side":"BUY","selfTradePreventionMode":"NONE"},
+            {"orderListId":1929,"contingencyType":"OCO","listStatusType":"ALL_DONE","listOrderStatus":"ALL_DONE","listClientOrderId":"2inzWQdDvZLHbbAmAozX2N","transactionTime":1585230948299,"symbol":"BTCUSDT","orders":[{"symbol":"BTCUSDT","orderId":20,"clientOrderId":"CwOOIPHSmYywx6jZX77TdL"},{"symbol":"BTCUSDT","orderId":21,"clientOrderId":"461cPg51vQjV3zIMOXNz39"}],"orderReports":[{"symbol":"BTCUSDT","origClientOrderId":"CwOOIPHSmYywx6jZX77TdL","orderId":20,"orderListId":1929,"clientOrderId":"pXLV6Hz6mprAcVYpVMTGgx","transactTime":1688005070874,"price":"1.00000000","origQty":"10.00000000","executedQty":"0.00000000","cummulativeQuoteQty":"0.00000000","status":"CANCELED","timeInForce":"GTC","type":"STOP_LOSS_LIMIT","side":"BUY","stopPrice":"1.00000000","selfTradePreventionMode":"NONE"},{"symbol":"BTCUSDT","origClientOrderId":"461cPg51vQjV3zIMOXNz39","orderId":21,"orderListId":1929,"clientOrderId":"pXLV6Hz6mprAcVYpVMTGgx","transactTime":1688005070874,"price":"3.00000000","origQty":"10.00000000","executedQty":"0.00000000","cummulativeQuoteQty":"0.00000000","status":"CANCELED","timeInForce":"GTC","type":"LIMIT_MAKER","side":"BUY","selfTradePreventionMode":"NONE"}]}
+        ]"#;
+        let canceled = CanceledOpenOrder::from_json_array(json).unwrap();
+
+        assert_eq!(canceled.len(), 2);
+        match &canceled[0] {
+            CanceledOpenOrder::Order(order) => {
+                assert_eq!(order.orig_client_order_id, "E6APeyTJvkMvLMYMqu1KQ4");
+                assert_eq!(order.order.order_id, 11);
+            }
+            other => panic!("Expected a plain order, got {:?}", other),
+        }
+        match &canceled[1] {
+            CanceledOpenOrder::OrderList(list) => {
+                assert_eq!(list.order_list_id, 1929);
+                assert_eq!(list.contingency_type, ContingencyType::Oco);
+                assert_eq!(list.orders.len(), 2);
+                assert_eq!(list.orders[1].order_id, 21);
+                assert_eq!(list.order_reports.len(), 2);
+                assert_eq!(list.order_reports[0].orig_client_order_id.as_deref(), Some("CwOOIPHSmYywx6jZX77TdL"));
+                assert_eq!(list.order_reports[0].order.order_type, OrderType::StopLossLimit);
+                assert_eq!(list.order_reports[1].order.order_type, OrderType::LimitMaker);
+            }
+            other => panic!("Expected an order list, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_oco_order_list_deserialization() {
+        let json = r#"{"orderListId":0,"contingencyType":"OCO","listStatusType":"EXEC_STARTED","listOrderStatus":"EXECUTING","listClientOrderId":"lH1YDkuQKWiXVXHPSKYEIp","transactionTime":1710485608839,"symbol":"LTCBTC","orders":[{"symbol":"LTCBTC","orderId":10,"clientOrderId":"44nZvqpemY7sVYgPYbvPih"},{"symbol":"LTCBTC","orderId":11,"clientOrderId":"NuMp0nVYnciDiFmVqfpBqK"}],"orderReports":[{"symbol":"LTCBTC","orderId":10,"orderListId":0,"clientOrderId":"44nZvqpemY7sVYgPYbvPih","transactTime":1710485608839,"price":"1.00000000","origQty":"5.00000000","executedQty":"0.00000000","origQuoteOrderQty":"0.000000","cummulativeQuoteQty":"0.00000000","status":"NEW","timeInForce":"GTC","type":"STOP_LOSS_LIMIT","side":"SELL","stopPrice":"1.00000000","workingTime":-1,"icebergQty":"1.00000000","selfTradePreventionMode":"NONE"},{"symbol":"LTCBTC","orderId":11,"orderListId":0,"clientOrderId":"NuMp0nVYnciDiFmVqfpBqK","transactTime":1710485608839,"price":"3.00000000","origQty":"5.00000000","executedQty":"0.00000000","origQuoteOrderQty":"0.000000","cummulativeQuoteQty":"0.00000000","status":"NEW","timeInForce":"GTC","type":"LIMIT_MAKER","side":"SELL","workingTime":1710485608839,"selfTradePreventionMode":"NONE"}]}"#;
+        let list = OrderList::from_json(json).unwrap();
+
+        assert_eq!(list.order_list_id, 0);
+        assert_eq!(list.contingency_type, ContingencyType::Oco);
+        assert_eq!(list.list_status_type, ListStatusType::ExecStarted);
+        assert_eq!(list.list_order_status, ListOrderStatus::Executing);
+        assert_eq!(list.list_client_order_id, "lH1YDkuQKWiXVXHPSKYEIp");
+        assert_eq!(list.orders, vec![
+            OrderListOrder { symbol: "LTCBTC".to_string(), order_id: 10, client_order_id: "44nZvqpemY7sVYgPYbvPih".to_string() },
+            OrderListOrder { symbol: "LTCBTC".to_string(), order_id: 11, client_order_id: "NuMp0nVYnciDiFmVqfpBqK".to_string() },
+        ]);
+
+        let stop_leg = &list.order_reports[0];
+        assert_eq!(stop_leg.orig_client_order_id, None);
+        assert_eq!(stop_leg.stop_price, Some(1.0));
+        assert_eq!(stop_leg.iceberg_quantity, Some(1.0));
+        assert_eq!(stop_leg.order.order_type, OrderType::StopLossLimit);
+        assert_eq!(stop_leg.order.status, OrderStatus::New);
+        assert_eq!(stop_leg.order.working_time, Some(-1));
+
+        let limit_leg = &list.order_reports[1];
+        assert_eq!(limit_leg.stop_price, None);
+        assert_eq!(limit_leg.order.order_type, OrderType::LimitMaker);
+        assert_eq!(limit_leg.order.price, 3.0);
+    }
+
+    #[test]
+    fn test_order_list_status_fallbacks() {
+        let json = r#"{"orderListId":31,"contingencyType":"OTOCO","listStatusType":"RESTARTED","listOrderStatus":"ALL_DONE","listClientOrderId":"wuB13fmulKj3YjdqWEcsnp","transactionTime":1565246080644,"symbol":"LTCBTC","orders":[{"symbol":"LTCBTC","orderId":4,"clientOrderId":"r3EH2N76dHfLoSZWIUw1bT"}]}"#;
+        let list = OrderList::from_json(json).unwrap();
+
+        assert_eq!(list.contingency_type, ContingencyType::Other);
+        assert_eq!(list.list_status_type, ListStatusType::Other);
+        assert_eq!(list.list_order_status, ListOrderStatus::AllDone);
+        assert!(list.order_reports.is_empty());
+    }
+
+    #[test]
+    fn test_my_trade_rows_deserialization() {
+        let json = r#"[{"symbol":"BNBBTC","id":28457,"orderId":100234,"orderListId":-1,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","commission":"10.10000000","commissionAsset":"BNB","time":1499865549590,"isBuyer":true,"isMaker":false,"isBestMatch":true},{"symbol":"BNBBTC","id":28458,"orderId":100235,"orderListId":-1,"price":"4.00000200","qty":"2.00000000","quoteQty":"8.000004","commission":"0.00100000","commissionAsset":"BTC","time":1499865549601,"isBuyer":false,"isMaker":true,"isBestMatch":true},{"symbol":"BNBBTC","id":28459,"orderId":100236,"orderListId":7,"price":"4.00000000","qty":"1.00000000","quoteQty":"4.00000000","commission":"0.00050000","commissionAsset":"BTC","time":1499865549650,"isBuyer":false,"isMaker":false,"isBestMatch":true}]"#;
+        let rows = MyTradeRow::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].is_buyer && !rows[0].is_maker);
+        assert!(!rows[1].is_buyer && rows[1].is_maker);
+        assert!(!rows[2].is_buyer && !rows[2].is_maker);
+        assert_eq!(rows[2].order_list_id, 7);
+
+        let fills: Vec<TradeFill> = rows.into_iter().map(TradeFill::from).collect();
+        assert_eq!(fills[0].side, Side::Buy);
+        assert_eq!(fills[1], TradeFill {
+            symbol: "BNBBTC".to_string(),
+            trade_id: 28458,
+            order_id: 100235,
+            side: Side::Sell,
+            price: 4.000002,
+            quantity: 2.0,
+            quote_quantity: 8.000004,
+            commission: 0.001,
+            commission_asset: Some("BTC".to_string()),
+            time: 1499865549601,
+            is_maker: true,
+        });
+    }
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_trade_fill_from_execution_report() {
+        let json = r#"{"e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"mUvoqJxFIILMdfAW5iGSOW","S":"BUY","o":"LIMIT","f":"GTC","q":"1.00000000","p":"0.10264410","P":"0.00000000","F":"0.00000000","g":-1,"C":"","x":"TRADE","X":"PARTIALLY_FILLED","r":"NONE","i":4293153,"l":"0.25000000","z":"0.25000000","L":"0.10264410","n":"0.00002500","N":"ETH","T":1499405658657,"t":1234,"I":8641984,"w":true,"m":true,"M":false,"O":1499405658657,"Z":"0.02566102","Y":"0.02566102","Q":"0.00000000","W":1499405658657}"#;
+        let report = ExecutionReport::from_json(json).unwrap();
+
+        assert_eq!(TradeFill::from_execution_report(&report), Some(TradeFill {
+            symbol: "ETHBTC".to_string(),
+            trade_id: 1234,
+            order_id: 4293153,
+            side: Side::Buy,
+            price: 0.1026441,
+            quantity: 0.25,
+            quote_quantity: 0.02566102,
+            commission: 0.000025,
+            commission_asset: Some("ETH".to_string()),
+            time: 1499405658657,
+            is_maker: true,
+        }));
+
+        let json = json.replace(r#""x":"TRADE""#, r#""x":"NEW""#);
+        let report = ExecutionReport::from_json(&json).unwrap();
+        assert_eq!(TradeFill::from_execution_report(&report), None);
+    }
+
+    #[test]
+    fn test_binance_api_error_known_codes() {
+        let error = BinanceApiError::from_json(r#"{"code":-1121,"msg":"Invalid symbol."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::InvalidSymbol);
+        assert!(!error.is_retryable());
+        assert_eq!(error.to_string(), "Binance API error -1121: Invalid symbol.");
+
+        let error = BinanceApiError::from_json(r#"{"code":-1003,"msg":"Too much request weight used; current limit is 6000 request weight per 1 MINUTE. Please use WebSocket Streams for live updates to avoid polling the API."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::TooManyRequests);
+        assert!(error.is_rate_limit());
+        assert!(error.is_retryable());
+
+        let error = BinanceApiError::from_json(r#"{"code":-1013,"msg":"Filter failure: LOT_SIZE"}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::InvalidMessage);
+        assert!(!error.is_rate_limit());
+
+        let error = BinanceApiError::from_json(r#"{"code":-1021,"msg":"Timestamp for this request was 1000ms ahead of the server's time."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::InvalidTimestamp);
+        assert!(error.is_retryable());
+
+        let error = BinanceApiError::from_json(r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::NewOrderRejected);
+
+        let error = BinanceApiError::from_json(r#"{"code":-2011,"msg":"Unknown order sent."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::CancelRejected);
+        assert_eq!(error.kind().code(), -2011);
+    }
+
+    #[test]
+    fn test_binance_api_error_unknown_code() {
+        let error = BinanceApiError::from_json(r#"{"code":-9999,"msg":"Something new."}"#).unwrap();
+        assert_eq!(error.kind(), KnownErrorCode::Unknown(-9999));
+        assert_eq!(error.kind().code(), -9999);
+        assert!(!error.is_rate_limit());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_rate_limit_headers_parse() {
+        let headers = vec![
+            ("Content-Type", "application/json;charset=UTF-8"),
+            ("x-mbx-used-weight", "1250"),
+            ("x-mbx-used-weight-1m", "1250"),
+            ("X-MBX-USED-WEIGHT-1D", "40210"),
+            ("X-MBX-ORDER-COUNT-10S", "3"),
+            ("X-MBX-UUID", "d8e4b0c2-0f4f-4f1e-9a1e-0c5d8d6f2c11"),
+            ("retry-after", "7"),
+        ];
+        let parsed = RateLimitHeaders::parse(headers);
+
+        let minute = RateLimitWindow::new(RateLimitInterval::Minute, 1);
+        let day = RateLimitWindow::new(RateLimitInterval::Day, 1);
+        let ten_seconds = RateLimitWindow::new(RateLimitInterval::Second, 10);
+        assert_eq!(parsed.used_weight, vec![(minute, 1250), (day, 40210)]);
+        assert_eq!(parsed.used_weight(day), Some(40210));
+        assert_eq!(parsed.order_count(ten_seconds), Some(3));
+        assert_eq!(parsed.order_count(minute), None);
+        assert_eq!(parsed.retry_after, Some(7));
+    }
+
+    #[test]
+    fn test_rate_limit_tracker_combines_headers_and_rate_limits() {
+        let minute = RateLimitWindow::new(RateLimitInterval::Minute, 1);
+        let ten_seconds = RateLimitWindow::new(RateLimitInterval::Second, 10);
+        let json = r#"[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":6000},{"rateLimitType":"ORDERS","interval":"SECOND","intervalNum":10,"limit":100}]"#;
+        let exchange_limits: Vec<RateLimit> = serde_json::from_str(json).unwrap();
+
+        let mut tracker = RateLimitTracker::new();
+        tracker.update_from_rate_limits(&exchange_limits);
+        assert_eq!(tracker.remaining(RateLimitType::RequestWeight, minute), Some(6000));
+
+        tracker.update_from_headers(&RateLimitHeaders::parse(vec![("X-MBX-USED-WEIGHT-1M", "1250"), ("X-MBX-ORDER-COUNT-10S", "3")]));
+        assert_eq!(tracker.remaining(RateLimitType::RequestWeight, minute), Some(4750));
+        assert_eq!(tracker.remaining(RateLimitType::Orders, ten_seconds), Some(97));
+        assert_eq!(tracker.retry_after(), None);
+
+        let json = r#"[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":6000,"count":1300}]"#;
+        let ws_limits: Vec<RateLimit> = serde_json::from_str(json).unwrap();
+        tracker.update_from_rate_limits(&ws_limits);
+        assert_eq!(tracker.budget(RateLimitType::RequestWeight, minute), Some(&RateLimitBudget { limit: Some(6000), used: 1300 }));
+
+        tracker.update_from_headers(&RateLimitHeaders::parse(vec![("X-MBX-USED-WEIGHT-1M", "6001"), ("Retry-After", "30")]));
+        assert_eq!(tracker.remaining(RateLimitType::RequestWeight, minute), Some(0));
+        assert_eq!(tracker.retry_after(), Some(30));
+    }
+
+    #[test]
+    fn test_server_time_and_ping_deserialization() {
+        assert_eq!(ServerTime::from_json(r#"{"serverTime":1499827319559}"#).unwrap().server_time, 1499827319559);
+        assert_eq!(Ping::from_json("{}").unwrap(), Ping {});
+    }
+
+    #[test]
+    fn test_clock_skew_estimate() {
+        let mut skew = ClockSkew::new(3);
+        assert_eq!(skew.offset_ms(), None);
+        assert!(!skew.is_recv_window_safe(5000));
+
+        // Server 250 ms ahead, 40 ms round trip
+        assert_eq!(skew.add_sample(1_000_000, 1_000_040, 1_000_270), ClockSample { offset_ms: 250, round_trip_ms: 40 });
+        skew.add_sample(2_000_000, 2_000_060, 2_000_280);
+        skew.add_sample(3_000_000, 3_000_020, 3_000_250);
+        assert_eq!(skew.offset_ms(), Some(246));
+        assert_eq!(skew.round_trip_ms(), Some(60));
+        assert_eq!(skew.server_time_at(4_000_000), Some(4_000_246));
+        assert!(skew.is_recv_window_safe(5000));
+        assert!(!skew.is_recv_window_safe(300));
+
+        // The window forgets the oldest sample
+        skew.add_sample(4_000_000, 4_000_020, 4_000_010);
+        assert_eq!(skew.samples().count(), 3);
+        assert_eq!(skew.offset_ms(), Some(163));
+        assert_eq!(skew.round_trip_ms(), Some(60));
+    }
+
+    #[test]
+    fn test_clock_skew_local_clock_ahead() {
+        let mut skew = ClockSkew::default();
+        skew.add_sample(1_000_000, 1_000_010, 998_505);
+        assert_eq!(skew.offset_ms(), Some(-1500));
+        assert!(!skew.is_recv_window_safe(60_000));
+    }
+
+    #[test]
+    fn test_time_zone_parse_and_format() {
+        assert_eq!("8".parse::<TimeZone>().unwrap().to_string(), "8");
+        assert_eq!("+08:00".parse::<TimeZone>().unwrap().to_string(), "8");
+        assert_eq!("05:45".parse::<TimeZone>().unwrap().to_string(), "5:45");
+        assert_eq!("-3:30".parse::<TimeZone>().unwrap().offset_minutes(), -210);
+        assert_eq!("-12:00".parse::<TimeZone>().unwrap().to_string(), "-12");
+        assert_eq!("+14:00".parse::<TimeZone>().unwrap().to_string(), "14");
+        assert_eq!(TimeZone::UTC.to_string(), "0");
+
+        assert!("-12:30".parse::<TimeZone>().is_err());
+        assert!("14:01".parse::<TimeZone>().is_err());
+        assert!("5:60".parse::<TimeZone>().is_err());
+        assert!("5:5".parse::<TimeZone>().is_err());
+        assert!("UTC".parse::<TimeZone>().is_err());
+        assert!(TimeZone::from_offset_minutes(15 * 60).is_err());
+    }
+
+    #[test]
+    fn test_klines_request_with_time_zone() {
+        let request = KlinesRequest::new("BNBBTC", KlineInterval::H1)
+            .start_time(1655969280000)
+            .time_zone("-3:30".parse().unwrap())
+            .limit(2);
+
+        assert_eq!(request.to_query_string(), "symbol=BNBBTC&interval=1h&startTime=1655969280000&timeZone=-3:30&limit=2");
+        assert_eq!(request.to_params(), serde_json::json!({
+            "symbol": "BNBBTC",
+            "interval": "1h",
+            "startTime": 1655969280000u64,
+            "timeZone": "-3:30",
+            "limit": 2
+        }));
+        assert_eq!(KlinesRequest::new("BNBBTC", KlineInterval::Mo1).to_query_string(), "symbol=BNBBTC&interval=1M");
+    }
+
+    #[test]
+    fn test_ui_kline_rows_deserialization() {
+        let json = r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]]"#;
+        let rows = UiKlineRow::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].open_time, 1499040000000);
+        assert_eq!(rows[0].high_price, 0.8);
+        assert_eq!(rows[0].number_of_trades, 308);
+    }
+}
@@ -0,0 +1,505 @@
+//! USD-M futures REST response models and order requests.
+
+mod order;
+
+pub use self::order::*;
+
+use serde::{Deserialize, Serialize};
+
+use super::OneOrMany;
+use crate::websocket::{
+    de_option_string_to_f64, de_string_or_bool, de_string_or_number_to_u64, de_string_to_f64, ser_f64_as_string,
+    ser_option_f64_as_string, MarginType, PositionSide,
+};
+
+// GET /fapi/v1/premiumIndex
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PremiumIndex {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Mark price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub estimated_settle_price: f64,     // Estimated settle price, only useful in the last hour before settlement
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_funding_rate: f64,          // Latest funding rate
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub interest_rate: f64,              // Interest rate
+    pub next_funding_time: u64,          // Next funding time
+    pub time: u64,                       // Time
+}
+
+impl PremiumIndex {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// One row of GET /fapi/v1/fundingRate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub funding_rate: f64,               // Funding rate
+    pub funding_time: u64,               // Funding time
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub mark_price: Option<f64>,         // Mark price at funding time, empty for old rows
+}
+
+impl FundingRate {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// GET /fapi/v1/openInterest
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_interest: f64,              // Open interest in contracts
+    pub time: u64,                       // Time
+}
+
+impl OpenInterest {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// One row of GET /futures/data/openInterestHist
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterestHist {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub sum_open_interest: f64,          // Total open interest
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub sum_open_interest_value: f64,    // Total open interest value
+    #[serde(deserialize_with = "de_string_or_number_to_u64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub timestamp: u64,                  // Period timestamp, sent as a string or a number
+}
+
+impl OpenInterestHist {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// One row of GET /fapi/v2/positionRisk
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRisk {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub entry_price: f64,                // Entry price
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub break_even_price: Option<f64>,   // Break-even price
+    pub margin_type: MarginType,         // Margin type
+    #[serde(deserialize_with = "de_string_or_bool")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::BoolOrString"))]
+    pub is_auto_add_margin: bool,        // Is isolated margin topped up automatically?
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub isolated_margin: f64,            // Isolated margin
+    #[serde(deserialize_with = "de_string_or_number_to_u64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub leverage: u64,                   // Leverage
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub liquidation_price: f64,          // Liquidation price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Mark price
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub max_notional_value: Option<f64>, // Maximum notional value at the current leverage
+    #[serde(rename = "positionAmt", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_amount: f64,            // Position amount, negative for shorts in one-way mode
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub notional: Option<f64>,           // Notional value
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub isolated_wallet: Option<f64>,    // Isolated wallet balance
+    #[serde(rename = "unRealizedProfit", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unrealized_profit: f64,          // Unrealized profit
+    pub position_side: PositionSide,     // Position side
+    #[serde(default)]
+    pub update_time: u64,                // Last update time
+}
+
+impl PositionRisk {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAsset {
+    pub asset: String,                   // Asset
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub wallet_balance: f64,             // Wallet balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unrealized_profit: f64,          // Unrealized profit
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub margin_balance: f64,             // Margin balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maint_margin: f64,               // Maintenance margin required
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub initial_margin: f64,             // Total initial margin required
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_initial_margin: f64,    // Initial margin required for positions
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_order_initial_margin: f64,  // Initial margin required for open orders
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cross_wallet_balance: f64,       // Crossed wallet balance
+    #[serde(rename = "crossUnPnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cross_unrealized_pnl: f64,       // Unrealized profit of crossed positions
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub available_balance: f64,          // Available balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub max_withdraw_amount: f64,        // Maximum amount for transfer out
+    #[serde(default)]
+    pub margin_available: bool,          // Can the asset be used as margin in multi-assets mode?
+    pub update_time: u64,                // Last update time
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPosition {
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub initial_margin: f64,             // Initial margin required at the current mark price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maint_margin: f64,               // Maintenance margin required
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unrealized_profit: f64,          // Unrealized profit
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_initial_margin: f64,    // Initial margin required for the position
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_order_initial_margin: f64,  // Initial margin required for open orders
+    #[serde(deserialize_with = "de_string_or_number_to_u64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub leverage: u64,                   // Leverage
+    pub isolated: bool,                  // Is the position isolated?
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub entry_price: f64,                // Average entry price
+    #[serde(default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub break_even_price: Option<f64>,   // Break-even price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub max_notional: f64,               // Maximum notional value at the current leverage
+    pub position_side: PositionSide,     // Position side
+    #[serde(rename = "positionAmt", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_amount: f64,            // Position amount, negative for shorts in one-way mode
+    pub update_time: u64,                // Last update time
+}
+
+// GET /fapi/v2/account
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAccount {
+    pub fee_tier: u32,                   // Commission tier
+    pub can_trade: bool,                 // Can trade
+    pub can_deposit: bool,               // Can deposit
+    pub can_withdraw: bool,              // Can withdraw
+    pub update_time: u64,                // Last update time
+    #[serde(default)]
+    pub multi_assets_margin: bool,       // Is multi-assets mode enabled?
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_initial_margin: f64,       // Total initial margin required
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_maint_margin: f64,         // Total maintenance margin required
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_wallet_balance: f64,       // Total wallet balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_unrealized_profit: f64,    // Total unrealized profit
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_margin_balance: f64,       // Total margin balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_position_initial_margin: f64, // Initial margin required for positions
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_open_order_initial_margin: f64, // Initial margin required for open orders
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_cross_wallet_balance: f64, // Crossed wallet balance
+    #[serde(rename = "totalCrossUnPnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_cross_unrealized_pnl: f64, // Unrealized profit of crossed positions
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub available_balance: f64,          // Available balance
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub max_withdraw_amount: f64,        // Maximum amount for transfer out
+    pub assets: Vec<AccountAsset>,       // Assets
+    pub positions: Vec<AccountPosition>, // Positions of all symbols
+}
+
+impl FuturesAccount {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeType {
+    Transfer,
+    WelcomeBonus,
+    RealizedPnl,
+    FundingFee,
+    Commission,
+    InsuranceClear,
+    ReferralKickback,
+    CommissionRebate,
+    ApiRebate,
+    ContestReward,
+    CrossCollateralTransfer,
+    OptionsPremiumFee,
+    OptionsSettleProfit,
+    InternalTransfer,
+    AutoExchange,
+    #[serde(rename = "DELIVERED_SETTELMENT")]
+    DeliveredSettlement,                 // Misspelled on the wire
+    CoinSwapDeposit,
+    CoinSwapWithdraw,
+    PositionLimitIncreaseFee,
+    #[serde(other)]
+    Unknown,
+}
+
+// One row of GET /fapi/v1/income
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    pub symbol: String,                  // Symbol, empty for account-wide income such as transfers
+    pub income_type: IncomeType,         // Income type
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub income: f64,                     // Income amount, negative for outflows
+    pub asset: String,                   // Income asset
+    pub info: String,                    // Extra information
+    pub time: u64,                       // Time
+    pub tran_id: u64,                    // Transaction ID
+    pub trade_id: String,                // Trade ID, empty unless the income stems from a trade
+}
+
+impl Income {
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// GET /fapi/v1/ticker/bookTicker and the ticker.book ws-api method, a single object when a symbol
+// is given and an array otherwise
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesBookTicker {
+    pub last_update_id: u64,             // Order book update ID
+    pub symbol: String,                  // Symbol
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_price: f64,                  // Best bid price
+    #[serde(rename = "bidQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_quantity: f64,               // Best bid quantity
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_price: f64,                  // Best ask price
+    #[serde(rename = "askQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_quantity: f64,               // Best ask quantity
+    pub time: u64,                       // Transaction time
+}
+
+impl FuturesBookTicker {
+    pub fn from_json(json: &str) -> Result<OneOrMany<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::rest::futures::sample_premium_index_json;
+
+    #[test]
+    fn test_premium_index_single_deserialization() {
+        let expected = PremiumIndex {
+            symbol: "BTCUSDT".to_string(),
+            mark_price: 11793.63104562,
+            index_price: 11781.8049597,
+            estimated_settle_price: 11781.16138815,
+            last_funding_rate: 0.00038246,
+            interest_rate: 0.0001,
+            next_funding_time: 1597392000000,
+            time: 1597370495002,
+        };
+        assert_eq!(PremiumIndex::from_json(sample_premium_index_json()).unwrap(), OneOrMany::One(expected));
+    }
+
+    #[test]
+    fn test_premium_index_array_deserialization() {
+        let json = format!(
+            r#"[{},{}]"#,
+            sample_premium_index_json(),
+            r#"{"symbol":"ETHUSDT","markPrice":"380.12000000","indexPrice":"379.95000000","estimatedSettlePrice":"379.90000000","lastFundingRate":"-0.00012000","interestRate":"0.00010000","nextFundingTime":1597392000000,"time":1597370495003}"#
+        );
+        let indices = PremiumIndex::from_json(&json).unwrap().into_vec();
+
+        assert_eq!(indices.len(), 2);
+        assert_eq!(indices[0].symbol, "BTCUSDT");
+        assert_eq!(indices[1].symbol, "ETHUSDT");
+        assert_eq!(indices[1].last_funding_rate, -0.00012);
+    }
+
+    #[test]
+    fn test_funding_rate_history_deserialization() {
+        let json = r#"[{"symbol":"BTCUSDT","fundingRate":"-0.03750000","fundingTime":1570608000000,"markPrice":""},{"symbol":"BTCUSDT","fundingRate":"0.00010000","fundingTime":1570636800000,"markPrice":"34287.54619963"},{"symbol":"BTCUSDT","fundingRate":"0.00006000","fundingTime":1570665600003}]"#;
+        let rows = FundingRate::from_json_array(json).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].funding_rate, -0.0375);
+        assert_eq!(rows[0].mark_price, None);
+        assert_eq!(rows[1], FundingRate {
+            symbol: "BTCUSDT".to_string(),
+            funding_rate: 0.0001,
+            funding_time: 1570636800000,
+            mark_price: Some(34287.54619963),
+        });
+        assert_eq!(rows[2].mark_price, None);
+    }
+
+    #[test]
+    fn test_open_interest_deserialization() {
+        let json = r#"{"openInterest":"10659.509","symbol":"BTCUSDT","time":1589437530011}"#;
+        assert_eq!(OpenInterest::from_json(json).unwrap(), OpenInterest {
+            symbol: "BTCUSDT".to_string(),
+            open_interest: 10659.509,
+            time: 1589437530011,
+        });
+
+        let json = r#"[{"symbol":"BTCUSDT","sumOpenInterest":"20403.63700000","sumOpenInterestValue":"150570784.07809979","CMCCirculatingSupply":"165880.538","timestamp":"1583127900000"},{"symbol":"BTCUSDT","sumOpenInterest":"20401.36700000","sumOpenInterestValue":"149940752.14464448","timestamp":1583128200000}]"#;
+        let rows = OpenInterestHist::from_json_array(json).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].sum_open_interest, 20403.637);
+        assert_eq!(rows[0].timestamp, 1583127900000);
+        assert_eq!(rows[1].timestamp, 1583128200000);
+    }
+
+    #[test]
+    fn test_position_risk_deserialization() {
+        let json = r#"[{"entryPrice":"0.00000","breakEvenPrice":"0.0","marginType":"isolated","isAutoAddMargin":"false","isolatedMargin":"0.00000000","leverage":"10","liquidationPrice":"0","markPrice":"6679.50671178","maxNotionalValue":"20000000","positionAmt":"0.000","notional":"0","isolatedWallet":"0","symbol":"BTCUSDT","unRealizedProfit":"0.00000000","positionSide":"BOTH","updateTime":0},{"entryPrice":"2150.25","breakEvenPrice":"2151.11","marginType":"cross","isAutoAddMargin":"false","isolatedMargin":"0.00000000","leverage":"20","liquidationPrice":"2890.12345678","markPrice":"2140.50000000","maxNotionalValue":"5000000","positionAmt":"-1.500","notional":"-3210.75000000","isolatedWallet":"0","symbol":"ETHUSDT","unRealizedProfit":"14.62500000","positionSide":"BOTH","updateTime":1625474304765}]"#;
+        let positions = PositionRisk::from_json_array(json).unwrap();
+
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].margin_type, MarginType::Isolated);
+        assert!(!positions[0].is_auto_add_margin);
+        assert_eq!(positions[0].leverage, 10);
+
+        let short = &positions[1];
+        assert_eq!(short.margin_type, MarginType::Crossed);
+        assert_eq!(short.position_amount, -1.5);
+        assert_eq!(short.notional, Some(-3210.75));
+        assert_eq!(short.break_even_price, Some(2151.11));
+        assert_eq!(short.liquidation_price, 2890.12345678);
+        assert_eq!(short.unrealized_profit, 14.625);
+        assert_eq!(short.position_side, PositionSide::Both);
+    }
+
+    #[test]
+    fn test_futures_account_deserialization() {
+        let json = r#"{"feeTier":0,"canTrade":true,"canDeposit":true,"canWithdraw":true,"updateTime":0,"multiAssetsMargin":false,"tradeGroupId":-1,"totalInitialMargin":"0.00000000","totalMaintMargin":"0.00000000","totalWalletBalance":"23.72469206","totalUnrealizedProfit":"-1.25000000","totalMarginBalance":"22.47469206","totalPositionInitialMargin":"0.00000000","totalOpenOrderInitialMargin":"0.00000000","totalCrossWalletBalance":"23.72469206","totalCrossUnPnl":"-1.25000000","availableBalance":"22.47469206","maxWithdrawAmount":"22.47469206","assets":[{"asset":"USDT","walletBalance":"23.72469206","unrealizedProfit":"-1.25000000","marginBalance":"22.47469206","maintMargin":"0.00000000","initialMargin":"0.00000000","positionInitialMargin":"0.00000000","openOrderInitialMargin":"0.00000000","crossWalletBalance":"23.72469206","crossUnPnl":"-1.25000000","availableBalance":"22.47469206","maxWithdrawAmount":"22.47469206","marginAvailable":true,"updateTime":1625474304765}],"positions":[{"symbol":"BTCUSDT","initialMargin":"0","maintMargin":"0","unrealizedProfit":"-1.25000000","positionInitialMargin":"0","openOrderInitialMargin":"0","leverage":"100","isolated":true,"entryPrice":"30125.50","breakEvenPrice":"30137.55","maxNotional":"250000","positionSide":"SHORT","positionAmt":"-0.010","bidNotional":"0","askNotional":"0","updateTime":1625474304765}]}"#;
+        let account = FuturesAccount::from_json(json).unwrap();
+
+        assert_eq!(account.total_wallet_balance, 23.72469206);
+        assert_eq!(account.total_cross_unrealized_pnl, -1.25);
+        assert_eq!(account.assets.len(), 1);
+        assert_eq!(account.assets[0].asset, "USDT");
+        assert!(account.assets[0].margin_available);
+        assert_eq!(account.positions[0].position_side, PositionSide::Short);
+        assert_eq!(account.positions[0].position_amount, -0.01);
+        assert_eq!(account.positions[0].leverage, 100);
+        assert_eq!(account.positions[0].break_even_price, Some(30137.55));
+    }
+
+    #[test]
+    fn test_income_history_deserialization() {
+        let json = r#"[{"symbol":"","incomeType":"TRANSFER","income":"-0.37500000","asset":"USDT","info":"TRANSFER","time":1570608000000,"tranId":9689322392,"tradeId":""},{"symbol":"BTCUSDT","incomeType":"FUNDING_FEE","income":"-0.01612359","asset":"USDT","info":"","time":1570636800000,"tranId":9689322393,"tradeId":""},{"symbol":"BTCUSDT","incomeType":"DELIVERED_SETTELMENT","income":"1.50000000","asset":"USDT","info":"","time":1570636800001,"tranId":9689322394,"tradeId":""},{"symbol":"BTCUSDT","incomeType":"STRATEGY_UMFUTURES_TRANSFER","income":"5.00000000","asset":"USDT","info":"","time":1570636800002,"tranId":9689322395,"tradeId":""}]"#;
+        let rows = Income::from_json_array(json).unwrap();
+
+        assert_eq!(rows[0].income_type, IncomeType::Transfer);
+        assert_eq!(rows[0].symbol, "");
+        assert_eq!(rows[1], Income {
+            symbol: "BTCUSDT".to_string(),
+            income_type: IncomeType::FundingFee,
+            income: -0.01612359,
+            asset: "USDT".to_string(),
+            info: "".to_string(),
+            time: 1570636800000,
+            tran_id: 9689322393,
+            trade_id: "".to_string(),
+        });
+        assert_eq!(rows[2].income_type, IncomeType::DeliveredSettlement);
+        assert_eq!(rows[3].income_type, IncomeType::Unknown);
+    }
+}
@@ -0,0 +1,483 @@
+//! USD-M futures order requests and the order object answered by placing, modifying and cancelling.
+//! Futures orders carry a position side and trigger settings that spot orders lack, and accept their own
+//! order types and times in force.
+
+use serde::{Deserialize, Serialize};
+
+use crate::format::PriceFormat;
+use crate::rest::{OrderRequestError, Params, SelfTradePreventionMode};
+use crate::websocket::{de_option_string_to_f64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, OrderStatus, PositionSide, Side};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FuturesOrderType {
+    Limit,
+    Market,
+    Stop,
+    StopMarket,
+    TakeProfit,
+    TakeProfitMarket,
+    TrailingStopMarket,
+}
+
+impl FuturesOrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FuturesOrderType::Limit => "LIMIT",
+            FuturesOrderType::Market => "MARKET",
+            FuturesOrderType::Stop => "STOP",
+            FuturesOrderType::StopMarket => "STOP_MARKET",
+            FuturesOrderType::TakeProfit => "TAKE_PROFIT",
+            FuturesOrderType::TakeProfitMarket => "TAKE_PROFIT_MARKET",
+            FuturesOrderType::TrailingStopMarket => "TRAILING_STOP_MARKET",
+        }
+    }
+}
+
+// GTX is post only, GTD needs a goodTillDate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FuturesTimeInForce {
+    Gtc,
+    Ioc,
+    Fok,
+    Gtx,
+    Gtd,
+}
+
+impl FuturesTimeInForce {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FuturesTimeInForce::Gtc => "GTC",
+            FuturesTimeInForce::Ioc => "IOC",
+            FuturesTimeInForce::Fok => "FOK",
+            FuturesTimeInForce::Gtx => "GTX",
+            FuturesTimeInForce::Gtd => "GTD",
+        }
+    }
+}
+
+// Price that triggers stop and take profit orders
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WorkingType {
+    MarkPrice,
+    ContractPrice,
+}
+
+impl WorkingType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkingType::MarkPrice => "MARK_PRICE",
+            WorkingType::ContractPrice => "CONTRACT_PRICE",
+        }
+    }
+}
+
+// Parameters of POST /fapi/v1/order and the order.place ws-api method, without the timestamp and
+// signature added at signing time
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuturesNewOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub side: Side,                      // Side
+    pub position_side: Option<PositionSide>, // Position side, BOTH when unset, LONG or SHORT in hedge mode
+    pub order_type: FuturesOrderType,    // Order type
+    pub time_in_force: Option<FuturesTimeInForce>, // Time in force
+    pub quantity: Option<f64>,           // Quantity, not sent with closePosition
+    pub reduce_only: Option<bool>,       // Only reduce the position, one-way mode only
+    pub price: Option<f64>,              // Limit price
+    pub new_client_order_id: Option<String>, // Client order ID
+    pub stop_price: Option<f64>,         // Trigger price of stop and take profit orders
+    pub close_position: Option<bool>,    // Close the whole position, STOP_MARKET and TAKE_PROFIT_MARKET only
+    pub activation_price: Option<f64>,   // Activation price of a trailing stop, the latest price when unset
+    pub callback_rate: Option<f64>,      // Callback rate of a trailing stop in percent, 0.1 to 10
+    pub working_type: Option<WorkingType>, // Trigger price type, CONTRACT_PRICE when unset
+    pub price_protect: Option<bool>,     // Reject triggers far from the mark price
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+    pub good_till_date: Option<u64>,     // Expiry of a GTD order
+}
+
+impl FuturesNewOrderRequest {
+    pub fn new(symbol: &str, side: Side, order_type: FuturesOrderType) -> Self {
+        FuturesNewOrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            position_side: None,
+            order_type,
+            time_in_force: None,
+            quantity: None,
+            reduce_only: None,
+            price: None,
+            new_client_order_id: None,
+            stop_price: None,
+            close_position: None,
+            activation_price: None,
+            callback_rate: None,
+            working_type: None,
+            price_protect: None,
+            self_trade_prevention_mode: None,
+            good_till_date: None,
+        }
+    }
+
+    pub fn position_side(mut self, position_side: PositionSide) -> Self {
+        self.position_side = Some(position_side);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: FuturesTimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: f64) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = Some(reduce_only);
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: &str) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.to_string());
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: f64) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn close_position(mut self, close_position: bool) -> Self {
+        self.close_position = Some(close_position);
+        self
+    }
+
+    pub fn activation_price(mut self, activation_price: f64) -> Self {
+        self.activation_price = Some(activation_price);
+        self
+    }
+
+    pub fn callback_rate(mut self, callback_rate: f64) -> Self {
+        self.callback_rate = Some(callback_rate);
+        self
+    }
+
+    pub fn working_type(mut self, working_type: WorkingType) -> Self {
+        self.working_type = Some(working_type);
+        self
+    }
+
+    pub fn price_protect(mut self, price_protect: bool) -> Self {
+        self.price_protect = Some(price_protect);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn good_till_date(mut self, good_till_date: u64) -> Self {
+        self.good_till_date = Some(good_till_date);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        let closes_position = self.close_position == Some(true);
+        if self.reduce_only.is_some() && matches!(self.position_side, Some(PositionSide::Long | PositionSide::Short)) {
+            return Err(OrderRequestError::MutuallyExclusive("reduceOnly", "positionSide"));
+        }
+        if closes_position && self.quantity.is_some() {
+            return Err(OrderRequestError::MutuallyExclusive("closePosition", "quantity"));
+        }
+        if closes_position && self.reduce_only.is_some() {
+            return Err(OrderRequestError::MutuallyExclusive("closePosition", "reduceOnly"));
+        }
+        let is_market_trigger = matches!(self.order_type, FuturesOrderType::StopMarket | FuturesOrderType::TakeProfitMarket);
+        if self.quantity.is_none() && !(closes_position && is_market_trigger) {
+            return Err(OrderRequestError::MissingParameter("quantity"));
+        }
+        if self.order_type == FuturesOrderType::Limit && self.time_in_force.is_none() {
+            return Err(OrderRequestError::MissingParameter("timeInForce"));
+        }
+        if matches!(self.order_type, FuturesOrderType::Limit | FuturesOrderType::Stop | FuturesOrderType::TakeProfit) && self.price.is_none() {
+            return Err(OrderRequestError::MissingParameter("price"));
+        }
+        if (is_market_trigger || matches!(self.order_type, FuturesOrderType::Stop | FuturesOrderType::TakeProfit)) && self.stop_price.is_none() {
+            return Err(OrderRequestError::MissingParameter("stopPrice"));
+        }
+        if self.order_type == FuturesOrderType::TrailingStopMarket && self.callback_rate.is_none() {
+            return Err(OrderRequestError::MissingParameter("callbackRate"));
+        }
+        if self.time_in_force == Some(FuturesTimeInForce::Gtd) && self.good_till_date.is_none() {
+            return Err(OrderRequestError::MissingParameter("goodTillDate"));
+        }
+        Ok(())
+    }
+
+    fn params(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        params.push("side", self.side.as_str());
+        params.push_str("positionSide", self.position_side.as_ref().map(PositionSide::as_str));
+        params.push("type", self.order_type.as_str());
+        params.push_str("timeInForce", self.time_in_force.as_ref().map(FuturesTimeInForce::as_str));
+        params.push_decimal("quantity", self.quantity, quantity_format)?;
+        if let Some(reduce_only) = self.reduce_only {
+            params.push("reduceOnly", reduce_only);
+        }
+        params.push_decimal("price", self.price, price_format)?;
+        params.push_str("newClientOrderId", self.new_client_order_id.as_deref());
+        params.push_decimal("stopPrice", self.stop_price, price_format)?;
+        if let Some(close_position) = self.close_position {
+            params.push("closePosition", close_position);
+        }
+        params.push_decimal("activationPrice", self.activation_price, price_format)?;
+        // Binance accepts a single decimal on the callback rate
+        params.push_decimal("callbackRate", self.callback_rate, PriceFormat::Trimmed(1))?;
+        params.push_str("workingType", self.working_type.as_ref().map(WorkingType::as_str));
+        if let Some(price_protect) = self.price_protect {
+            params.push("priceProtect", price_protect);
+        }
+        params.push_str("selfTradePreventionMode", self.self_trade_prevention_mode.as_ref().map(SelfTradePreventionMode::as_str));
+        if let Some(good_till_date) = self.good_till_date {
+            params.push("goodTillDate", good_till_date);
+        }
+        Ok(params)
+    }
+
+    // Parameters in insertion order. Decimals use `PriceFormat::current()`
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a futures ws-api order.place request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_params_with(format, format)
+    }
+
+    pub fn to_params_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.into_params())
+    }
+}
+
+// Parameters of PUT /fapi/v1/order and the order.modify ws-api method. Only LIMIT orders can be
+// modified, side, quantity and price are always sent
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuturesModifyOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub order_id: Option<u64>,           // Order ID, this or the original client order ID is required
+    pub orig_client_order_id: Option<String>, // Client order ID of the order
+    pub side: Side,                      // Side, must match the order
+    pub quantity: f64,                   // New quantity
+    pub price: f64,                      // New price
+}
+
+impl FuturesModifyOrderRequest {
+    pub fn by_order_id(symbol: &str, order_id: u64, side: Side, quantity: f64, price: f64) -> Self {
+        FuturesModifyOrderRequest { symbol: symbol.to_string(), order_id: Some(order_id), orig_client_order_id: None, side, quantity, price }
+    }
+
+    pub fn by_client_order_id(symbol: &str, orig_client_order_id: &str, side: Side, quantity: f64, price: f64) -> Self {
+        FuturesModifyOrderRequest {
+            symbol: symbol.to_string(),
+            order_id: None,
+            orig_client_order_id: Some(orig_client_order_id.to_string()),
+            side,
+            quantity,
+            price,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        if self.order_id.is_none() && self.orig_client_order_id.is_none() {
+            return Err(OrderRequestError::MissingParameter("orderId"));
+        }
+        Ok(())
+    }
+
+    fn params(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        if let Some(order_id) = self.order_id {
+            params.push("orderId", order_id);
+        }
+        params.push_str("origClientOrderId", self.orig_client_order_id.as_deref());
+        params.push("side", self.side.as_str());
+        params.push_decimal("quantity", Some(self.quantity), quantity_format)?;
+        params.push_decimal("price", Some(self.price), price_format)?;
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a futures ws-api order.modify request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_params_with(format, format)
+    }
+
+    pub fn to_params_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.into_params())
+    }
+}
+
+// Parameters of DELETE /fapi/v1/order and the order.cancel ws-api method
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuturesCancelOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub order_id: Option<u64>,           // Order ID, this or the original client order ID is required
+    pub orig_client_order_id: Option<String>, // Client order ID of the order
+}
+
+impl FuturesCancelOrderRequest {
+    pub fn by_order_id(symbol: &str, order_id: u64) -> Self {
+        FuturesCancelOrderRequest { symbol: symbol.to_string(), order_id: Some(order_id), orig_client_order_id: None }
+    }
+
+    pub fn by_client_order_id(symbol: &str, orig_client_order_id: &str) -> Self {
+        FuturesCancelOrderRequest { symbol: symbol.to_string(), order_id: None, orig_client_order_id: Some(orig_client_order_id.to_string()) }
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        if self.order_id.is_none() && self.orig_client_order_id.is_none() {
+            return Err(OrderRequestError::MissingParameter("orderId"));
+        }
+        Ok(())
+    }
+
+    fn params(&self) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        if let Some(order_id) = self.order_id {
+            params.push("orderId", order_id);
+        }
+        params.push_str("origClientOrderId", self.orig_client_order_id.as_deref());
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        Ok(self.params()?.to_query_string())
+    }
+
+    // `params` object of a futures ws-api order.cancel request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params()?.into_params())
+    }
+}
+
+// Order object answered by placing, modifying, cancelling and querying a futures order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesOrderResult {
+    pub order_id: u64,                   // Order ID
+    pub symbol: String,                  // Symbol
+    pub status: OrderStatus,             // Order status
+    pub client_order_id: String,         // Client order ID
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub avg_price: f64,                  // Average fill price
+    #[serde(rename = "origQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_quantity: f64,          // Original quantity
+    #[serde(rename = "executedQty", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub executed_quantity: f64,          // Executed quantity
+    #[serde(rename = "cumQty", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub cumulative_quantity: Option<f64>, // Cumulative quantity, absent on queried orders
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cum_quote: f64,                  // Cumulative quote asset quantity
+    pub time_in_force: FuturesTimeInForce, // Time in force
+    #[serde(rename = "type")]
+    pub order_type: FuturesOrderType,    // Order type
+    pub reduce_only: bool,               // Reduce only?
+    pub close_position: bool,            // Closes the whole position?
+    pub side: Side,                      // Side
+    pub position_side: PositionSide,     // Position side
+    #[serde(deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub stop_price: f64,                 // Stop price, 0 for orders without a trigger
+    pub working_type: WorkingType,       // Trigger price type
+    pub price_protect: bool,             // Price protection on?
+    pub orig_type: FuturesOrderType,     // Order type before a trigger turned it into another
+    #[serde(rename = "activatePrice", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub activation_price: Option<f64>,   // Activation price, trailing stops only
+    #[serde(rename = "priceRate", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub callback_rate: Option<f64>,      // Callback rate, trailing stops only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_match: Option<String>,     // Price match mode, e.g. "NONE" or "OPPONENT"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+    #[serde(default)]
+    pub good_till_date: u64,             // Expiry of a GTD order, 0 otherwise
+    pub update_time: u64,                // Last update time
+}
+
+impl FuturesOrderResult {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_order_validation() {
+        let order = FuturesNewOrderRequest::new("BTCUSDT", Side::Sell, FuturesOrderType::Limit).quantity(0.01).price(43187.0);
+        assert_eq!(order.validate(), Err(OrderRequestError::MissingParameter("timeInForce")));
+
+        let order = order.time_in_force(FuturesTimeInForce::Gtc).position_side(PositionSide::Short).reduce_only(true);
+        assert_eq!(order.validate(), Err(OrderRequestError::MutuallyExclusive("reduceOnly", "positionSide")));
+
+        let close = FuturesNewOrderRequest::new("BTCUSDT", Side::Buy, FuturesOrderType::StopMarket).stop_price(41000.0).close_position(true);
+        assert_eq!(
+            close.to_query_string_with(PriceFormat::Fixed(1), PriceFormat::Fixed(3)).unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=STOP_MARKET&stopPrice=41000.0&closePosition=true"
+        );
+        assert_eq!(close.quantity(0.01).validate(), Err(OrderRequestError::MutuallyExclusive("closePosition", "quantity")));
+
+        let trailing = FuturesNewOrderRequest::new("BTCUSDT", Side::Sell, FuturesOrderType::TrailingStopMarket).quantity(0.01);
+        assert_eq!(trailing.validate(), Err(OrderRequestError::MissingParameter("callbackRate")));
+        let query = trailing.callback_rate(1.0).working_type(WorkingType::MarkPrice).price_protect(true).to_query_string_with(PriceFormat::Fixed(1), PriceFormat::Fixed(3)).unwrap();
+        assert_eq!(query, "symbol=BTCUSDT&side=SELL&type=TRAILING_STOP_MARKET&quantity=0.010&callbackRate=1&workingType=MARK_PRICE&priceProtect=true");
+
+        assert_eq!(FuturesCancelOrderRequest::by_order_id("BTCUSDT", 328971409).to_query_string().unwrap(), "symbol=BTCUSDT&orderId=328971409");
+    }
+}
@@ -0,0 +1,553 @@
+//! Order list placement requests: POST /api/v3/orderList/oco, /oto and /otoco and the matching
+//! orderList.place.oco, orderList.place.oto and orderList.place.otoco ws-api methods. Each request renders
+//! as a query string or as a ws-api `params` object and is answered with an `OrderList`.
+
+use crate::format::PriceFormat;
+use crate::websocket::Side;
+use super::{NewOrderRespType, OrderRequestError, OrderType, Params, SelfTradePreventionMode, SymbolInfo, TimeInForce};
+
+// Parameter names of one leg, e.g. `abovePrice` or `pendingBelowStopPrice`
+struct LegKeys {
+    order_type: &'static str,
+    client_order_id: &'static str,
+    price: &'static str,
+    stop_price: &'static str,
+    trailing_delta: &'static str,
+    time_in_force: &'static str,
+    iceberg_qty: &'static str,
+}
+
+macro_rules! leg_keys {
+    ($prefix:literal) => {
+        LegKeys {
+            order_type: concat!($prefix, "Type"),
+            client_order_id: concat!($prefix, "ClientOrderId"),
+            price: concat!($prefix, "Price"),
+            stop_price: concat!($prefix, "StopPrice"),
+            trailing_delta: concat!($prefix, "TrailingDelta"),
+            time_in_force: concat!($prefix, "TimeInForce"),
+            iceberg_qty: concat!($prefix, "IcebergQty"),
+        }
+    };
+}
+
+const ABOVE: LegKeys = leg_keys!("above");
+const BELOW: LegKeys = leg_keys!("below");
+const WORKING: LegKeys = leg_keys!("working");
+const PENDING: LegKeys = leg_keys!("pending");
+const PENDING_ABOVE: LegKeys = leg_keys!("pendingAbove");
+const PENDING_BELOW: LegKeys = leg_keys!("pendingBelow");
+
+fn is_stop_loss(order_type: OrderType) -> bool {
+    matches!(order_type, OrderType::StopLoss | OrderType::StopLossLimit)
+}
+
+// Price the leg triggers or rests at, trailing legs have none
+fn trigger_price(leg: &ListLeg) -> Option<f64> {
+    leg.stop_price.or(leg.price)
+}
+
+// One order of a list. Side and quantity belong to the list, or to the working and pending halves of
+// an OTO, so they are not part of the leg
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListLeg {
+    pub order_type: OrderType,           // Order type
+    pub client_order_id: Option<String>, // Client order ID
+    pub price: Option<f64>,              // Limit price
+    pub stop_price: Option<f64>,         // Stop price
+    pub trailing_delta: Option<u64>,     // Trailing delta in basis points, in place of or with the stop price
+    pub time_in_force: Option<TimeInForce>, // Time in force
+    pub iceberg_qty: Option<f64>,        // Iceberg quantity
+}
+
+impl ListLeg {
+    pub fn new(order_type: OrderType) -> Self {
+        ListLeg {
+            order_type,
+            client_order_id: None,
+            price: None,
+            stop_price: None,
+            trailing_delta: None,
+            time_in_force: None,
+            iceberg_qty: None,
+        }
+    }
+
+    pub fn client_order_id(mut self, client_order_id: &str) -> Self {
+        self.client_order_id = Some(client_order_id.to_string());
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: f64) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn trailing_delta(mut self, trailing_delta: u64) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    fn validate(&self, keys: &LegKeys) -> Result<(), OrderRequestError> {
+        let order_type = self.order_type;
+        if matches!(order_type, OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit) && self.time_in_force.is_none() {
+            return Err(OrderRequestError::MissingParameter(keys.time_in_force));
+        }
+        if matches!(order_type, OrderType::Limit | OrderType::LimitMaker | OrderType::StopLossLimit | OrderType::TakeProfitLimit) && self.price.is_none() {
+            return Err(OrderRequestError::MissingParameter(keys.price));
+        }
+        if matches!(order_type, OrderType::StopLoss | OrderType::StopLossLimit | OrderType::TakeProfit | OrderType::TakeProfitLimit)
+            && self.stop_price.is_none()
+            && self.trailing_delta.is_none()
+        {
+            return Err(OrderRequestError::MissingParameter(keys.stop_price));
+        }
+        Ok(())
+    }
+
+    fn push_to(&self, params: &mut Params, keys: &LegKeys, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<(), OrderRequestError> {
+        self.validate(keys)?;
+        params.push(keys.order_type, self.order_type.as_str());
+        params.push_str(keys.client_order_id, self.client_order_id.as_deref());
+        params.push_decimal(keys.price, self.price, price_format)?;
+        params.push_decimal(keys.stop_price, self.stop_price, price_format)?;
+        if let Some(trailing_delta) = self.trailing_delta {
+            params.push(keys.trailing_delta, trailing_delta);
+        }
+        params.push_str(keys.time_in_force, self.time_in_force.as_ref().map(TimeInForce::as_str));
+        params.push_decimal(keys.iceberg_qty, self.iceberg_qty, quantity_format)
+    }
+}
+
+// The above leg must trigger above the below leg. A SELL list takes profit above and stops the loss
+// below, a BUY list the other way around
+fn validate_oco_legs(side: Side, above: &ListLeg, below: &ListLeg) -> Result<(), OrderRequestError> {
+    for leg in [above, below] {
+        if matches!(leg.order_type, OrderType::Limit | OrderType::Market) {
+            return Err(OrderRequestError::UnsupportedOrderType(leg.order_type));
+        }
+    }
+    let stop_loss = match side {
+        Side::Buy => above,
+        Side::Sell => below,
+    };
+    if !is_stop_loss(stop_loss.order_type) || is_stop_loss(above.order_type) == is_stop_loss(below.order_type) {
+        return Err(OrderRequestError::InconsistentLegs("an OCO needs a stop loss leg on the losing side and a limit maker or take profit leg on the other"));
+    }
+    if let (Some(above_price), Some(below_price)) = (trigger_price(above), trigger_price(below)) {
+        if above_price <= below_price {
+            return Err(OrderRequestError::InconsistentLegs("the above leg must trigger above the below leg"));
+        }
+    }
+    Ok(())
+}
+
+fn validate_working(working: &ListLeg) -> Result<(), OrderRequestError> {
+    match working.order_type {
+        OrderType::Limit | OrderType::LimitMaker => Ok(()),
+        order_type => Err(OrderRequestError::UnsupportedOrderType(order_type)),
+    }
+}
+
+// One cancels the other: two legs on the same side, whichever fills or triggers first cancels the other
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcoOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub side: Side,                      // Side of both legs
+    pub quantity: f64,                   // Quantity of both legs
+    pub above: ListLeg,                  // Leg with the higher price
+    pub below: ListLeg,                  // Leg with the lower price
+    pub list_client_order_id: Option<String>, // Client ID of the list
+    pub new_order_resp_type: Option<NewOrderRespType>, // Response type
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+impl OcoOrderRequest {
+    pub fn new(symbol: &str, side: Side, quantity: f64, above: ListLeg, below: ListLeg) -> Self {
+        OcoOrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+            above,
+            below,
+            list_client_order_id: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: &str) -> Self {
+        self.list_client_order_id = Some(list_client_order_id.to_string());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: NewOrderRespType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        self.above.validate(&ABOVE)?;
+        self.below.validate(&BELOW)?;
+        validate_oco_legs(self.side, &self.above, &self.below)
+    }
+
+    fn params(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        params.push("side", self.side.as_str());
+        params.push_decimal("quantity", Some(self.quantity), quantity_format)?;
+        self.above.push_to(&mut params, &ABOVE, price_format, quantity_format)?;
+        self.below.push_to(&mut params, &BELOW, price_format, quantity_format)?;
+        params.push_common(self.list_client_order_id.as_deref(), self.new_order_resp_type, self.self_trade_prevention_mode);
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a ws-api orderList.place.oco request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_params_with(format, format)
+    }
+
+    pub fn to_params_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.into_params())
+    }
+}
+
+// One triggers the other: the pending order is placed once the working order fills
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtoOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub working_side: Side,              // Side of the working order
+    pub working_quantity: f64,           // Quantity of the working order
+    pub working: ListLeg,                // Working order, LIMIT or LIMIT_MAKER
+    pub pending_side: Side,              // Side of the pending order
+    pub pending_quantity: f64,           // Quantity of the pending order
+    pub pending: ListLeg,                // Pending order
+    pub list_client_order_id: Option<String>, // Client ID of the list
+    pub new_order_resp_type: Option<NewOrderRespType>, // Response type
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+impl OtoOrderRequest {
+    pub fn new(symbol: &str, working_side: Side, working_quantity: f64, working: ListLeg, pending_side: Side, pending_quantity: f64, pending: ListLeg) -> Self {
+        OtoOrderRequest {
+            symbol: symbol.to_string(),
+            working_side,
+            working_quantity,
+            working,
+            pending_side,
+            pending_quantity,
+            pending,
+            list_client_order_id: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: &str) -> Self {
+        self.list_client_order_id = Some(list_client_order_id.to_string());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: NewOrderRespType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        self.working.validate(&WORKING)?;
+        self.pending.validate(&PENDING)?;
+        validate_working(&self.working)
+    }
+
+    fn params(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        params.push("workingSide", self.working_side.as_str());
+        params.push_decimal("workingQuantity", Some(self.working_quantity), quantity_format)?;
+        self.working.push_to(&mut params, &WORKING, price_format, quantity_format)?;
+        params.push("pendingSide", self.pending_side.as_str());
+        params.push_decimal("pendingQuantity", Some(self.pending_quantity), quantity_format)?;
+        self.pending.push_to(&mut params, &PENDING, price_format, quantity_format)?;
+        params.push_common(self.list_client_order_id.as_deref(), self.new_order_resp_type, self.self_trade_prevention_mode);
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a ws-api orderList.place.oto request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_params_with(format, format)
+    }
+
+    pub fn to_params_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.into_params())
+    }
+}
+
+// One triggers a one cancels the other: an OCO pair is placed once the working order fills
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtocoOrderRequest {
+    pub symbol: String,                  // Symbol
+    pub working_side: Side,              // Side of the working order
+    pub working_quantity: f64,           // Quantity of the working order
+    pub working: ListLeg,                // Working order, LIMIT or LIMIT_MAKER
+    pub pending_side: Side,              // Side of both pending legs
+    pub pending_quantity: f64,           // Quantity of both pending legs
+    pub pending_above: ListLeg,          // Pending leg with the higher price
+    pub pending_below: ListLeg,          // Pending leg with the lower price
+    pub list_client_order_id: Option<String>, // Client ID of the list
+    pub new_order_resp_type: Option<NewOrderRespType>, // Response type
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+}
+
+impl OtocoOrderRequest {
+    // Pending legs above, then below
+    pub fn new(symbol: &str, working_side: Side, working_quantity: f64, working: ListLeg, pending_side: Side, pending_quantity: f64, pending: [ListLeg; 2]) -> Self {
+        let [pending_above, pending_below] = pending;
+        OtocoOrderRequest {
+            symbol: symbol.to_string(),
+            working_side,
+            working_quantity,
+            working,
+            pending_side,
+            pending_quantity,
+            pending_above,
+            pending_below,
+            list_client_order_id: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: &str) -> Self {
+        self.list_client_order_id = Some(list_client_order_id.to_string());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: NewOrderRespType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderRequestError> {
+        self.working.validate(&WORKING)?;
+        self.pending_above.validate(&PENDING_ABOVE)?;
+        self.pending_below.validate(&PENDING_BELOW)?;
+        validate_working(&self.working)?;
+        validate_oco_legs(self.pending_side, &self.pending_above, &self.pending_below)
+    }
+
+    fn params(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<Params, OrderRequestError> {
+        self.validate()?;
+        let mut params = Params::default();
+        params.push("symbol", self.symbol.as_str());
+        params.push("workingSide", self.working_side.as_str());
+        params.push_decimal("workingQuantity", Some(self.working_quantity), quantity_format)?;
+        self.working.push_to(&mut params, &WORKING, price_format, quantity_format)?;
+        params.push("pendingSide", self.pending_side.as_str());
+        params.push_decimal("pendingQuantity", Some(self.pending_quantity), quantity_format)?;
+        self.pending_above.push_to(&mut params, &PENDING_ABOVE, price_format, quantity_format)?;
+        self.pending_below.push_to(&mut params, &PENDING_BELOW, price_format, quantity_format)?;
+        params.push_common(self.list_client_order_id.as_deref(), self.new_order_resp_type, self.self_trade_prevention_mode);
+        Ok(params)
+    }
+
+    pub fn to_query_string(&self) -> Result<String, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_query_string_with(format, format)
+    }
+
+    pub fn to_query_string_for(&self, info: &SymbolInfo) -> Result<String, OrderRequestError> {
+        self.to_query_string_with(info.price_format(), info.quantity_format())
+    }
+
+    pub fn to_query_string_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<String, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.to_query_string())
+    }
+
+    // `params` object of a ws-api orderList.place.otoco request
+    pub fn to_params(&self) -> Result<serde_json::Value, OrderRequestError> {
+        let format = PriceFormat::current();
+        self.to_params_with(format, format)
+    }
+
+    pub fn to_params_with(&self, price_format: PriceFormat, quantity_format: PriceFormat) -> Result<serde_json::Value, OrderRequestError> {
+        Ok(self.params(price_format, quantity_format)?.into_params())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::rest::{ContingencyType, ListOrderStatus, ListStatusType, OrderList};
+    use crate::testutil::rest::sample_order_list_json;
+
+    // LTCBNB example of the orderList.place.oco documentation
+    fn oco() -> OcoOrderRequest {
+        OcoOrderRequest::new(
+            "LTCBNB",
+            Side::Buy,
+            1.0,
+            ListLeg::new(OrderType::StopLossLimit).price(1.5).stop_price(1.50000001).time_in_force(TimeInForce::Gtc),
+            ListLeg::new(OrderType::LimitMaker).price(1.49999999),
+        )
+    }
+
+    #[test]
+    fn test_oco_request() {
+        let request = oco();
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=LTCBNB&side=BUY&quantity=1&aboveType=STOP_LOSS_LIMIT&abovePrice=1.5&aboveStopPrice=1.50000001&aboveTimeInForce=GTC&belowType=LIMIT_MAKER&belowPrice=1.49999999"
+        );
+        assert_eq!(
+            request.to_params().unwrap(),
+            json!({
+                "symbol": "LTCBNB",
+                "side": "BUY",
+                "quantity": "1",
+                "aboveType": "STOP_LOSS_LIMIT",
+                "abovePrice": "1.5",
+                "aboveStopPrice": "1.50000001",
+                "aboveTimeInForce": "GTC",
+                "belowType": "LIMIT_MAKER",
+                "belowPrice": "1.49999999",
+            })
+        );
+
+        let request = oco().list_client_order_id("my-list").self_trade_prevention_mode(SelfTradePreventionMode::ExpireMaker);
+        assert!(request.to_query_string().unwrap().ends_with("&listClientOrderId=my-list&selfTradePreventionMode=EXPIRE_MAKER"));
+        let request = oco().list_client_order_id("my list:1");
+        assert!(request.to_query_string().unwrap().ends_with("&listClientOrderId=my%20list%3A1"));
+    }
+
+    #[test]
+    fn test_oto_request() {
+        let request = OtoOrderRequest::new(
+            "LTCBNB",
+            Side::Sell,
+            1.0,
+            ListLeg::new(OrderType::Limit).price(1.0).time_in_force(TimeInForce::Gtc),
+            Side::Buy,
+            1.0,
+            ListLeg::new(OrderType::Market),
+        );
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=LTCBNB&workingSide=SELL&workingQuantity=1&workingType=LIMIT&workingPrice=1&workingTimeInForce=GTC&pendingSide=BUY&pendingQuantity=1&pendingType=MARKET"
+        );
+        assert_eq!(request.to_params().unwrap()["workingType"], "LIMIT");
+    }
+
+    #[test]
+    fn test_otoco_request() {
+        let request = OtocoOrderRequest::new(
+            "LTCBNB",
+            Side::Buy,
+            5.0,
+            ListLeg::new(OrderType::Limit).price(1.5).time_in_force(TimeInForce::Gtc),
+            Side::Sell,
+            5.0,
+            [ListLeg::new(OrderType::LimitMaker).price(5.0), ListLeg::new(OrderType::StopLoss).trailing_delta(100)],
+        );
+        assert_eq!(
+            request.to_query_string().unwrap(),
+            "symbol=LTCBNB&workingSide=BUY&workingQuantity=5&workingType=LIMIT&workingPrice=1.5&workingTimeInForce=GTC&pendingSide=SELL&pendingQuantity=5&pendingAboveType=LIMIT_MAKER&pendingAbovePrice=5&pendingBelowType=STOP_LOSS&pendingBelowTrailingDelta=100"
+        );
+        assert_eq!(request.to_params().unwrap()["pendingBelowTrailingDelta"], 100);
+    }
+
+    #[test]
+    fn test_inconsistent_legs() {
+        let mut request = oco();
+        request.side = Side::Sell;
+        assert!(matches!(request.to_query_string(), Err(OrderRequestError::InconsistentLegs(_))));
+
+        let mut request = oco();
+        request.below.price = Some(2.0);
+        assert_eq!(request.validate(), Err(OrderRequestError::InconsistentLegs("the above leg must trigger above the below leg")));
+
+        let mut request = oco();
+        request.above.time_in_force = None;
+        assert_eq!(request.validate(), Err(OrderRequestError::MissingParameter("aboveTimeInForce")));
+
+        let mut request = oco();
+        request.below = ListLeg::new(OrderType::Limit).price(1.0).time_in_force(TimeInForce::Gtc);
+        assert_eq!(request.validate(), Err(OrderRequestError::UnsupportedOrderType(OrderType::Limit)));
+
+        let request = OtoOrderRequest::new("LTCBNB", Side::Sell, 1.0, ListLeg::new(OrderType::Market), Side::Buy, 1.0, ListLeg::new(OrderType::TakeProfit));
+        assert_eq!(request.validate(), Err(OrderRequestError::MissingParameter("pendingStopPrice")));
+        let request = OtoOrderRequest::new("LTCBNB", Side::Sell, 1.0, ListLeg::new(OrderType::Market), Side::Buy, 1.0, ListLeg::new(OrderType::Market));
+        assert_eq!(request.validate(), Err(OrderRequestError::UnsupportedOrderType(OrderType::Market)));
+    }
+
+    #[test]
+    fn test_oco_placement_result() {
+        let list = OrderList::from_json(sample_order_list_json()).unwrap();
+        assert_eq!((list.contingency_type, list.list_status_type, list.list_order_status), (ContingencyType::Oco, ListStatusType::ExecStarted, ListOrderStatus::Executing));
+        assert_eq!(list.orders.len(), 2);
+        assert_eq!(list.order_reports.iter().map(|report| report.order.order_type).collect::<Vec<_>>(), vec![OrderType::StopLossLimit, OrderType::LimitMaker]);
+        assert_eq!(list.order_reports[0].stop_price, Some(1.0));
+    }
+}
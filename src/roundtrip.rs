@@ -0,0 +1,389 @@
+//! Round-trip property of every message type: deserialize a captured payload, serialize it, deserialize
+//! the result and compare, plus a check that no type implementing both traits is left out of the registry.
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::testutil;
+
+// Catches Serialize and Deserialize disagreeing, e.g. a rename or alias on one side only, a skipped
+// field or a custom serializer whose output the deserializer rejects
+pub(crate) fn roundtrip<T: DeserializeOwned + Serialize + PartialEq + Debug>(name: &str, fixture: &str) {
+    let parsed: T = serde_json::from_str(fixture).unwrap_or_else(|error| panic!("{name}: {error}"));
+    let json = serde_json::to_string(&parsed).unwrap_or_else(|error| panic!("{name}: {error}"));
+    let reparsed: T = serde_json::from_str(&json).unwrap_or_else(|error| panic!("{name}: {error} in {json}"));
+    assert_eq!(reparsed, parsed, "{name}: serialized as {json}");
+}
+
+macro_rules! assert_roundtrip {
+    ($type:ty, $fixture:expr) => {
+        assert_roundtrip!($type, $fixture, stringify!($type))
+    };
+    ($type:ty, $fixture:expr, $name:expr) => {
+        $crate::roundtrip::roundtrip::<$type>($name, AsRef::<str>::as_ref(&$fixture))
+    };
+}
+
+// Member of a captured payload addressed by a JSON pointer, e.g. the kline of a kline event at "/k"
+fn at(json: &str, pointer: &str) -> String {
+    let value: Value = serde_json::from_str(json).unwrap();
+    value.pointer(pointer).unwrap_or_else(|| panic!("no {pointer} in {json}")).to_string()
+}
+
+// The type registered under a name, usually the name itself, e.g. `Vec<Filter>` for `Filter`
+macro_rules! registered_type {
+    ($name:ident) => { $name };
+    ($name:ident, $type:ty) => { $type };
+}
+
+// One section per source file, listing every type of the file with a fixture. A name may appear more
+// than once to round-trip several fixtures
+macro_rules! registry {
+    ($(
+        $(#[$meta:meta])*
+        $section:ident in $file:literal, use $($module:ident)::+ {
+            $($name:ident $(: $type:ty)? => $fixture:expr,)*
+        }
+    )*) => {
+        $(
+            $(#[$meta])*
+            mod $section {
+                #[allow(unused_imports)]
+                use super::{at, testutil};
+                use $($module)::+::*;
+
+                #[test]
+                fn test_roundtrip() {
+                    $(assert_roundtrip!(registered_type!($name $(, $type)?), $fixture, concat!($file, ": ", stringify!($name)));)*
+                }
+            }
+        )*
+
+        // (file, type name) of every registered type
+        #[cfg(all(feature = "spot", feature = "futures", feature = "options"))]
+        fn registered() -> Vec<(&'static str, &'static str)> {
+            let mut registered = Vec::new();
+            $(
+                $(#[$meta])*
+                registered.extend([$(($file, stringify!($name))),*]);
+            )*
+            registered
+        }
+    };
+}
+
+registry! {
+    symbol in "src/symbol.rs", use crate::symbol {
+        Symbol => r#""BTCUSDT""#,
+    }
+
+    websocket in "src/websocket.rs", use crate::websocket {
+        ScalarValue: Vec<ScalarValue> => r#"[null,true,5,-5,0.5,"value"]"#,
+        SubscriptionRequest => r#"{"method":"SUBSCRIBE","params":["btcusdt@aggTrade","btcusdt@depth"],"id":1}"#,
+        SubscriptionResponse => r#"{"result":null,"id":1}"#,
+        SubscriptionResponse => r#"{"result":["btcusdt@aggTrade"],"id":3}"#,
+        PropertyResponse => r#"{"result":true,"id":5}"#,
+        Side => at(testutil::sample_execution_report_json(), "/S"),
+        ExecutionType => at(testutil::sample_execution_report_json(), "/x"),
+        OrderStatus => at(testutil::sample_execution_report_json(), "/X"),
+        KlineInterval => at(testutil::sample_kline_json(), "/k/i"),
+        Kline => at(testutil::sample_kline_json(), "/k"),
+        PriceLevel => at(testutil::sample_depth_update_json(), "/b/0"),
+    }
+
+    #[cfg(feature = "spot")]
+    websocket_spot in "src/websocket/spot.rs", use crate::websocket::spot {
+        Trade => testutil::sample_trade_json(),
+        KlineEvent => testutil::sample_kline_json(),
+        AggTrade => testutil::sample_agg_trade_json(),
+        BookTicker => testutil::sample_book_ticker_json(),
+        DepthUpdate => testutil::sample_depth_update_json(),
+        PartialDepth => testutil::sample_partial_depth_json(),
+        PartialDepthFixed: PartialDepthFixed<5> => testutil::sample_partial_depth_json(),
+    }
+
+    #[cfg(feature = "spot")]
+    blvt in "src/websocket/blvt.rs", use crate::websocket::blvt {
+        BasketEntry => at(testutil::blvt::sample_token_nav_json(), "/b/0"),
+        TokenNav => testutil::blvt::sample_token_nav_json(),
+        NavKline => at(testutil::blvt::sample_nav_kline_json(), "/k"),
+        NavKlineEvent => testutil::blvt::sample_nav_kline_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    websocket_futures in "src/websocket/futures.rs", use crate::websocket::futures {
+        MarkPriceUpdate => testutil::futures::sample_mark_price_update_json(),
+        AggTrade => testutil::futures::sample_agg_trade_json(),
+        Kline => at(testutil::futures::sample_kline_json(), "/k"),
+        KlineEvent => testutil::futures::sample_kline_json(),
+        ContinuousKlineEvent => testutil::futures::sample_continuous_kline_json(),
+        BookTicker => testutil::futures::sample_book_ticker_json(),
+        DepthUpdate => testutil::futures::sample_depth_update_json(),
+        LiquidationOrder => at(testutil::futures::sample_force_order_json(), "/o"),
+        ForceOrder => testutil::futures::sample_force_order_json(),
+        CompositeIndexComponent => at(testutil::futures::sample_composite_index_json(), "/c/0"),
+        CompositeIndex => testutil::futures::sample_composite_index_json(),
+        ContractStatus => at(testutil::futures::sample_contract_info_json(), "/cs"),
+        ContractBracket => at(testutil::futures::sample_contract_info_json(), "/bks/0"),
+        ContractInfo => testutil::futures::sample_contract_info_json(),
+        AssetIndex => testutil::futures::sample_asset_index_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    delivery in "src/websocket/delivery.rs", use crate::websocket::delivery {
+        IndexPriceUpdate => testutil::delivery::sample_index_price_update_json(),
+        MarkPriceUpdate => testutil::delivery::sample_mark_price_update_json(),
+        Kline => at(testutil::delivery::sample_kline_json(), "/k"),
+        KlineEvent => testutil::delivery::sample_kline_json(),
+        Ticker => testutil::delivery::sample_ticker_json(),
+    }
+
+    #[cfg(feature = "options")]
+    options in "src/websocket/options.rs", use crate::websocket::options {
+        OptionKind => at(testutil::options::sample_option_pair_json(), "/d"),
+        Trade => testutil::options::sample_trade_json(),
+        Ticker => testutil::options::sample_ticker_json(),
+        IndexPrice => testutil::options::sample_index_json(),
+        MarkPrice: Vec<MarkPrice> => testutil::options::sample_mark_price_json(),
+        Kline => at(testutil::options::sample_kline_json(), "/k"),
+        KlineEvent => testutil::options::sample_kline_json(),
+        OpenInterest: Vec<OpenInterest> => testutil::options::sample_open_interest_json(),
+        OpenSymbolInfo => testutil::options::sample_option_pair_json(),
+        Depth => testutil::options::sample_depth_json(),
+    }
+
+    user_data in "src/websocket/user_data.rs", use crate::websocket::user_data {
+        AccountBalance => at(testutil::sample_outbound_account_position_json(), "/B/0"),
+        ListenKeyExpired => testutil::sample_listen_key_expired_json(),
+        ListenKeyExpired => testutil::futures::sample_listen_key_expired_json(),
+    }
+
+    #[cfg(feature = "spot")]
+    user_data_spot in "src/websocket/user_data/spot.rs", use crate::websocket::user_data {
+        ExecutionReport => testutil::sample_execution_report_json(),
+        ExecutionReport => testutil::sample_execution_report_stp_json(),
+        OutboundAccountPosition => testutil::sample_outbound_account_position_json(),
+        BalanceUpdate => testutil::sample_balance_update_json(),
+    }
+
+    #[cfg(feature = "spot")]
+    user_data_margin in "src/websocket/user_data/margin.rs", use crate::websocket::user_data {
+        MarginLevelStatus => at(testutil::margin::sample_margin_level_status_change_json(), "/s"),
+        MarginLevelStatusChange => testutil::margin::sample_margin_level_status_change_json(),
+        LiabilityChange => testutil::margin::sample_liability_change_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    user_data_futures in "src/websocket/user_data/futures.rs", use crate::websocket::user_data {
+        PositionSide => at(testutil::futures::sample_margin_call_json(), "/p/0/ps"),
+        MarginType => at(testutil::futures::sample_margin_call_json(), "/p/0/mt"),
+        MarginCallPosition => at(testutil::futures::sample_margin_call_json(), "/p/0"),
+        MarginCall => testutil::futures::sample_margin_call_json(),
+        LeverageConfig => at(testutil::futures::sample_account_config_update_leverage_json(), "/ac"),
+        MultiAssetsConfig => at(testutil::futures::sample_account_config_update_multi_assets_json(), "/ai"),
+        AccountConfigUpdate => testutil::futures::sample_account_config_update_leverage_json(),
+        AccountConfigUpdate => testutil::futures::sample_account_config_update_multi_assets_json(),
+        TradeLite => testutil::futures::sample_trade_lite_json(),
+        StrategyUpdateInfo => at(testutil::futures::sample_strategy_update_json(), "/su"),
+        StrategyUpdate => testutil::futures::sample_strategy_update_json(),
+        GridUpdateInfo => at(testutil::futures::sample_grid_update_json(), "/gu"),
+        GridUpdate => testutil::futures::sample_grid_update_json(),
+        FuturesBalance => at(testutil::futures::sample_account_update_json(), "/a/B/0"),
+        FuturesPosition => at(testutil::futures::sample_account_update_json(), "/a/P/0"),
+        AccountUpdateData => at(testutil::futures::sample_account_update_json(), "/a"),
+        AccountUpdate => testutil::futures::sample_account_update_json(),
+        FuturesOrder => at(testutil::futures::sample_order_trade_update_json(), "/o"),
+        OrderTradeUpdate => testutil::futures::sample_order_trade_update_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    user_data_portfolio in "src/websocket/user_data/portfolio.rs", use crate::websocket::user_data {
+        RiskLevelStatus => at(testutil::portfolio::sample_risk_level_change_json(), "/s"),
+        RiskLevelChange => testutil::portfolio::sample_risk_level_change_json(),
+        OrderLoss => at(testutil::portfolio::sample_open_order_loss_json(), "/O/0"),
+        OpenOrderLoss => testutil::portfolio::sample_open_order_loss_json(),
+        ConditionalOrder => at(testutil::portfolio::sample_conditional_order_trade_update_json(), "/so"),
+        ConditionalOrderTradeUpdate => testutil::portfolio::sample_conditional_order_trade_update_json(),
+        PortfolioLiabilityChange => testutil::portfolio::sample_liability_change_json(),
+    }
+
+    ws_api in "src/ws_api.rs", use crate::ws_api {
+        WsApiId => r#""d3783d8d-f8d1-4d2c-b8a0-b7596af5a664""#,
+        WsApiId => "7",
+        WsApiRequest => r#"{"id":"d3783d8d-f8d1-4d2c-b8a0-b7596af5a664","method":"account.rateLimits.orders","params":{"apiKey":"vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A","timestamp":1660801839480}}"#,
+        WsApiResponse: WsApiResponse<Vec<crate::rest::RateLimit>> => testutil::ws_api::sample_account_rate_limits_orders_json(),
+        WsApiResponse: WsApiResponse<crate::rest::ExchangeInfo> => testutil::ws_api::sample_exchange_info_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    ws_api_futures in "src/ws_api.rs", use crate::ws_api {
+        WsApiResponse: WsApiResponse<crate::rest::futures::FuturesOrderResult> => testutil::ws_api::sample_futures_order_modify_json(),
+    }
+
+    rest in "src/rest.rs", use crate::rest {
+        RateLimitType => at(testutil::rest::sample_exchange_info_json(), "/rateLimits/0/rateLimitType"),
+        RateLimitInterval => at(testutil::rest::sample_exchange_info_json(), "/rateLimits/0/interval"),
+        RateLimit => at(testutil::rest::sample_exchange_info_json(), "/rateLimits/0"),
+        Filter: Vec<Filter> => at(testutil::rest::sample_exchange_info_json(), "/symbols/0/filters"),
+        Filter: Vec<Filter> => at(testutil::rest::sample_exchange_info_json(), "/symbols/1/filters"),
+        SymbolStatus => at(testutil::rest::sample_exchange_info_json(), "/symbols/0/status"),
+        SymbolInfo => at(testutil::rest::sample_exchange_info_json(), "/symbols/0"),
+        ExchangeInfo => testutil::rest::sample_exchange_info_json(),
+        DepthSnapshot => testutil::rest::sample_depth_snapshot_json(),
+        DepthSnapshot => testutil::rest::futures::sample_depth_snapshot_json(),
+        KlineRow: Vec<KlineRow> => testutil::rest::sample_klines_json(),
+        AggTradeRow: Vec<AggTradeRow> => testutil::rest::sample_agg_trades_json(),
+        TradeRow: Vec<TradeRow> => testutil::rest::sample_trades_json(),
+        OneOrMany: OneOrMany<PriceTicker> => testutil::rest::sample_ticker_price_json(),
+        OneOrMany: OneOrMany<PriceTicker> => testutil::rest::sample_ticker_prices_json(),
+        Ticker24hrFull => testutil::rest::sample_ticker_24hr_json(),
+        Ticker24hrMini => testutil::rest::sample_ticker_24hr_mini_json(),
+        Ticker24hr => testutil::rest::sample_ticker_24hr_json(),
+        Ticker24hr => testutil::rest::sample_ticker_24hr_mini_json(),
+        TickerType => r#""MINI""#,
+        TradingDayTickerFull => testutil::rest::sample_ticker_trading_day_json(),
+        TradingDayTicker => testutil::rest::sample_ticker_trading_day_json(),
+        TradingDayTicker: Vec<TradingDayTicker> => testutil::rest::sample_ticker_trading_day_mini_json(),
+        PriceTicker => testutil::rest::sample_ticker_price_json(),
+        BookTickerSnapshot => testutil::rest::sample_book_ticker_json(),
+        AvgPrice => testutil::rest::sample_avg_price_json(),
+        CommissionRates => at(testutil::rest::sample_account_json(), "/commissionRates"),
+        AccountInformation => testutil::rest::sample_account_json(),
+        OrderType => at(testutil::rest::sample_order_result_json(), "/type"),
+        TimeInForce => at(testutil::rest::sample_order_result_json(), "/timeInForce"),
+        NewOrderRespType => r#""FULL""#,
+        SelfTradePreventionMode => at(testutil::rest::sample_order_result_json(), "/selfTradePreventionMode"),
+        OrderAck => testutil::rest::sample_order_ack_json(),
+        OrderResult => testutil::rest::sample_order_result_json(),
+        Fill => at(testutil::rest::sample_order_full_json(), "/fills/0"),
+        OrderFull => testutil::rest::sample_order_full_json(),
+        OrderResponse => testutil::rest::sample_order_ack_json(),
+        OrderResponse => testutil::rest::sample_order_result_json(),
+        OrderResponse => testutil::rest::sample_order_full_json(),
+        SorFill => at(testutil::rest::sample_sor_order_full_json(), "/fills/0"),
+        SorOrderResult => r#"{"symbol":"BTCUSDT","orderId":2,"orderListId":-1,"clientOrderId":"sBI1KM6nNtOfj5tccZKKGG","transactTime":1689149087774,"price":"31000.00000000","origQty":"0.50000000","executedQty":"0.50000000","cummulativeQuoteQty":"14000.00000000","status":"FILLED","timeInForce":"GTC","type":"LIMIT","side":"BUY","workingTime":1689149087774,"workingFloor":"SOR","selfTradePreventionMode":"NONE","usedSor":true}"#,
+        SorOrderFull => testutil::rest::sample_sor_order_full_json(),
+        SorOrderResponse => testutil::rest::sample_sor_order_full_json(),
+        OrderCommission => at(testutil::rest::sample_test_order_commission_rates_json(), "/standardCommissionForOrder"),
+        CommissionDiscount => at(testutil::rest::sample_test_order_commission_rates_json(), "/discount"),
+        TestOrderCommissionRates => testutil::rest::sample_test_order_commission_rates_json(),
+        TestOrderResponse => testutil::rest::sample_test_order_commission_rates_json(),
+        TestOrderResponse => testutil::rest::sample_ping_json(),
+        CanceledOrder => testutil::rest::sample_cancel_order_json(),
+        AmendedOrder => at(testutil::rest::sample_order_amend_keep_priority_json(), "/amendedOrder"),
+        AmendedOrderList => r#"{"orderListId":1,"contingencyType":"OTO","listOrderStatus":"EXECUTING","listClientOrderId":"AT7FTxZXylVSwRoZs52mt3","symbol":"BTCUSDT","orders":[{"symbol":"BTCUSDT","orderId":33,"clientOrderId":"PFaq6hIHxqFENGfdtn4J6Q"}]}"#,
+        OrderAmendment => testutil::rest::sample_order_amend_keep_priority_json(),
+        OrderListOrder => at(testutil::rest::sample_order_list_json(), "/orders/0"),
+        ContingencyType => at(testutil::rest::sample_order_list_json(), "/contingencyType"),
+        ListStatusType => at(testutil::rest::sample_order_list_json(), "/listStatusType"),
+        ListOrderStatus => at(testutil::rest::sample_order_list_json(), "/listOrderStatus"),
+        OrderReport => at(testutil::rest::sample_order_list_json(), "/orderReports/0"),
+        OrderList => testutil::rest::sample_order_list_json(),
+        CanceledOpenOrder: Vec<CanceledOpenOrder> => testutil::rest::sample_cancel_open_orders_json(),
+        MyTradeRow: Vec<MyTradeRow> => testutil::rest::sample_my_trades_json(),
+        PreventedMatchRow: Vec<PreventedMatchRow> => testutil::rest::sample_my_prevented_matches_json(),
+        TradeFill => r#"{"symbol":"BNBBTC","trade_id":28457,"order_id":100234,"side":"BUY","price":4.000001,"quantity":12.0,"quote_quantity":48.000012,"commission":10.1,"commission_asset":"BNB","time":1499865549590,"is_maker":false}"#,
+        BinanceApiError => testutil::rest::sample_error_json(),
+        Ping => testutil::rest::sample_ping_json(),
+        ServerTime => testutil::rest::sample_time_json(),
+        ListenKey => testutil::rest::sample_listen_key_json(),
+        IsolatedMarginListenKey => testutil::rest::sample_listen_key_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    rest_futures in "src/rest/futures.rs", use crate::rest::futures {
+        PremiumIndex => testutil::rest::futures::sample_premium_index_json(),
+        FundingRate: Vec<FundingRate> => testutil::rest::futures::sample_funding_rate_json(),
+        OpenInterest => testutil::rest::futures::sample_open_interest_json(),
+        OpenInterestHist: Vec<OpenInterestHist> => testutil::rest::futures::sample_open_interest_hist_json(),
+        PositionRisk: Vec<PositionRisk> => testutil::rest::futures::sample_position_risk_json(),
+        AccountAsset => at(testutil::rest::futures::sample_account_json(), "/assets/0"),
+        AccountPosition => at(testutil::rest::futures::sample_account_json(), "/positions/0"),
+        FuturesAccount => testutil::rest::futures::sample_account_json(),
+        IncomeType => at(testutil::rest::futures::sample_income_json(), "/0/incomeType"),
+        Income: Vec<Income> => testutil::rest::futures::sample_income_json(),
+        FuturesBookTicker => testutil::rest::futures::sample_book_ticker_json(),
+    }
+
+    #[cfg(feature = "futures")]
+    rest_futures_order in "src/rest/futures/order.rs", use crate::rest::futures {
+        FuturesOrderType => at(testutil::ws_api::sample_futures_order_modify_json(), "/result/type"),
+        FuturesTimeInForce => at(testutil::ws_api::sample_futures_order_modify_json(), "/result/timeInForce"),
+        WorkingType => at(testutil::ws_api::sample_futures_order_modify_json(), "/result/workingType"),
+        FuturesOrderResult => at(testutil::ws_api::sample_futures_order_modify_json(), "/result"),
+    }
+}
+
+#[cfg(all(test, feature = "spot", feature = "futures", feature = "options"))]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    // `pub` types deriving both traits and types with hand-written impls of both, as (file, type name)
+    fn serde_types() -> BTreeSet<(String, String)> {
+        fn ident(rest: &str) -> &str {
+            &rest[..rest.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len())]
+        }
+        fn name_after<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+            Some(ident(&line[line.find(prefix)? + prefix.len()..]))
+        }
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut found = BTreeSet::new();
+        let mut pending = vec![root.join("src")];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if path.extension().is_none_or(|extension| extension != "rs") {
+                    continue;
+                }
+                let file = path.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+                let source = fs::read_to_string(&path).unwrap();
+                let (mut derives, mut serialize, mut deserialize) = (Vec::new(), BTreeSet::new(), BTreeSet::new());
+                for line in source.lines().map(str::trim) {
+                    if let Some(list) = line.strip_prefix("#[derive(").and_then(|list| list.strip_suffix(")]")) {
+                        derives.extend(list.split(',').filter_map(|derive| derive.trim().rsplit("::").next()));
+                        continue;
+                    }
+                    if line.starts_with("#[") || line.starts_with("//") {
+                        continue;
+                    }
+                    let name = line.strip_prefix("pub struct ").or_else(|| line.strip_prefix("pub enum ")).map(ident);
+                    if let Some(name) = name.filter(|_| derives.contains(&"Serialize") && derives.contains(&"Deserialize")) {
+                        found.insert((file.clone(), name.to_string()));
+                    }
+                    derives.clear();
+                    if line.starts_with("impl") {
+                        serialize.extend(name_after(line, "Serialize for "));
+                        deserialize.extend(name_after(line, "Deserialize<'de> for "));
+                    }
+                }
+                found.extend(serialize.intersection(&deserialize).map(|name| (file.clone(), name.to_string())));
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_every_serde_type_is_registered() {
+        let registered: BTreeSet<(String, String)> =
+            registered().into_iter().map(|(file, name)| (file.to_string(), name.to_string())).collect();
+        let found = serde_types();
+        assert!(found.len() > 100, "scan found only {} types", found.len());
+
+        let missing: Vec<_> = found.difference(&registered).collect();
+        assert!(missing.is_empty(), "add a fixture to the registry in src/roundtrip.rs for {missing:?}");
+        let stale: Vec<_> = registered.difference(&found).collect();
+        assert!(stale.is_empty(), "registered but not found in their file: {stale:?}");
+    }
+}
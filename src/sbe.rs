@@ -0,0 +1,388 @@
+//! Decoder for the Simple Binary Encoding (SBE) spot market data streams, schema id 1 version 0.
+
+use std::fmt;
+
+use crate::websocket::{BookTicker, DepthUpdate, PartialDepth, PriceLevel, Trade};
+
+pub const SCHEMA_ID: u16 = 1;
+pub const SCHEMA_VERSION: u16 = 0;
+
+pub const TRADES_TEMPLATE_ID: u16 = 10000;
+pub const BEST_BID_ASK_TEMPLATE_ID: u16 = 10001;
+pub const DEPTH_SNAPSHOT_TEMPLATE_ID: u16 = 10002;
+pub const DEPTH_DIFF_TEMPLATE_ID: u16 = 10003;
+
+const MESSAGE_HEADER_LENGTH: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SbeError {
+    Truncated { offset: usize, needed: usize }, // Frame ended `needed` bytes short of a field at `offset`
+    UnsupportedSchema { schema_id: u16, version: u16 }, // Not the spot market data schema
+    UnsupportedTemplate(u16),            // Template id of a message this decoder does not know
+    InvalidBool(u8),                     // boolEnum other than 0 or 1
+    InvalidSymbol,                       // Symbol is not UTF-8
+    NegativeValue(&'static str),         // Negative value in a field that maps to an unsigned one
+}
+
+impl fmt::Display for SbeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SbeError::Truncated { offset, needed } => write!(f, "frame truncated, {} more bytes needed at byte {}", needed, offset),
+            SbeError::UnsupportedSchema { schema_id, version } => write!(f, "unsupported schema {} version {}", schema_id, version),
+            SbeError::UnsupportedTemplate(template_id) => write!(f, "unsupported template {}", template_id),
+            SbeError::InvalidBool(value) => write!(f, "invalid boolean value {}", value),
+            SbeError::InvalidSymbol => write!(f, "symbol is not valid UTF-8"),
+            SbeError::NegativeValue(field) => write!(f, "negative {}", field),
+        }
+    }
+}
+
+impl std::error::Error for SbeError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub block_length: u16,               // Length of the root block, lets newer versions append fields
+    pub template_id: u16,                // Message type
+    pub schema_id: u16,                  // Schema id, 1 for the spot streams
+    pub version: u16,                    // Schema version
+}
+
+impl MessageHeader {
+    pub fn decode(frame: &[u8]) -> Result<Self, SbeError> {
+        let mut reader = Reader::new(frame);
+        Ok(MessageHeader {
+            block_length: reader.u16()?,
+            template_id: reader.u16()?,
+            schema_id: reader.u16()?,
+            version: reader.u16()?,
+        })
+    }
+}
+
+// Timestamps are in microseconds, so the JSON equivalents are those of streams opened with
+// `timeUnit=MICROSECOND`
+#[derive(Clone, Debug, PartialEq)]
+pub enum SbeMessage {
+    Trades(Vec<Trade>),                  // <symbol>@trade, one event may carry several trades
+    BestBidAsk { event_time: u64, ticker: BookTicker }, // <symbol>@bestBidAsk
+    DepthSnapshot { event_time: u64, symbol: String, depth: PartialDepth }, // <symbol>@depth20
+    DepthDiff(DepthUpdate),              // <symbol>@depth
+}
+
+// Versions after SCHEMA_VERSION are decoded too, fields they append are skipped using the block lengths
+pub fn decode(frame: &[u8]) -> Result<SbeMessage, SbeError> {
+    let header = MessageHeader::decode(frame)?;
+    if header.schema_id != SCHEMA_ID {
+        return Err(SbeError::UnsupportedSchema { schema_id: header.schema_id, version: header.version });
+    }
+    let mut reader = Reader::new(frame);
+    reader.position = MESSAGE_HEADER_LENGTH;
+    let block_end = MESSAGE_HEADER_LENGTH + header.block_length as usize;
+    match header.template_id {
+        TRADES_TEMPLATE_ID => decode_trades(&mut reader, block_end),
+        BEST_BID_ASK_TEMPLATE_ID => decode_best_bid_ask(&mut reader, block_end),
+        DEPTH_SNAPSHOT_TEMPLATE_ID => decode_depth_snapshot(&mut reader, block_end),
+        DEPTH_DIFF_TEMPLATE_ID => decode_depth_diff(&mut reader, block_end),
+        template_id => Err(SbeError::UnsupportedTemplate(template_id)),
+    }
+}
+
+// Prices and quantities are sent as a mantissa with one exponent per message. Powers of ten up to
+// 10^22 are exact in f64, so the division rounds the same way as parsing the decimal string
+pub fn decimal(mantissa: i64, exponent: i8) -> f64 {
+    if exponent < 0 {
+        mantissa as f64 / 10f64.powi(-(exponent as i32))
+    } else {
+        mantissa as f64 * 10f64.powi(exponent as i32)
+    }
+}
+
+fn decode_trades(reader: &mut Reader, block_end: usize) -> Result<SbeMessage, SbeError> {
+    let event_time = reader.timestamp("event time")?;
+    let transact_time = reader.timestamp("transact time")?;
+    let price_exponent = reader.i8()?;
+    let quantity_exponent = reader.i8()?;
+    reader.skip_to(block_end)?;
+
+    let (block_length, count) = reader.group_size()?;
+    let mut rows = Vec::with_capacity(count.min(reader.remaining() / block_length.max(1)));
+    for _ in 0..count {
+        let row_end = reader.position + block_length;
+        let trade_id = reader.unsigned("trade id")?;
+        let price = decimal(reader.i64()?, price_exponent);
+        let quantity = decimal(reader.i64()?, quantity_exponent);
+        let is_buyer_market_maker = reader.bool()?;
+        reader.skip_to(row_end)?;
+        rows.push((trade_id, price, quantity, is_buyer_market_maker));
+    }
+    let symbol = reader.symbol()?;
+
+    // isBestMatch is a constant in the schema and not on the wire
+    let trades = rows
+        .into_iter()
+        .map(|(trade_id, price, quantity, is_buyer_market_maker)| Trade {
+            event_type: Some("trade".to_string()),
+            event_time: Some(event_time),
+            symbol: symbol.clone(),
+            trade_id,
+            price,
+            quantity,
+            trade_time: transact_time,
+            is_buyer_market_maker,
+            ignore: true,
+        })
+        .collect();
+    Ok(SbeMessage::Trades(trades))
+}
+
+fn decode_best_bid_ask(reader: &mut Reader, block_end: usize) -> Result<SbeMessage, SbeError> {
+    let event_time = reader.timestamp("event time")?;
+    let update_id = reader.unsigned("book update id")?;
+    let price_exponent = reader.i8()?;
+    let quantity_exponent = reader.i8()?;
+    let best_bid_price = decimal(reader.i64()?, price_exponent);
+    let best_bid_quantity = decimal(reader.i64()?, quantity_exponent);
+    let best_ask_price = decimal(reader.i64()?, price_exponent);
+    let best_ask_quantity = decimal(reader.i64()?, quantity_exponent);
+    reader.skip_to(block_end)?;
+    let symbol = reader.symbol()?;
+    Ok(SbeMessage::BestBidAsk {
+        event_time,
+        ticker: BookTicker { update_id: Some(update_id), symbol, best_bid_price, best_bid_quantity, best_ask_price, best_ask_quantity },
+    })
+}
+
+fn decode_depth_snapshot(reader: &mut Reader, block_end: usize) -> Result<SbeMessage, SbeError> {
+    let event_time = reader.timestamp("event time")?;
+    let last_update_id = reader.unsigned("book update id")?;
+    let price_exponent = reader.i8()?;
+    let quantity_exponent = reader.i8()?;
+    reader.skip_to(block_end)?;
+    let bids = reader.levels(price_exponent, quantity_exponent)?;
+    let asks = reader.levels(price_exponent, quantity_exponent)?;
+    let symbol = reader.symbol()?;
+    Ok(SbeMessage::DepthSnapshot { event_time, symbol, depth: PartialDepth { last_update_id, bids, asks } })
+}
+
+fn decode_depth_diff(reader: &mut Reader, block_end: usize) -> Result<SbeMessage, SbeError> {
+    let event_time = reader.timestamp("event time")?;
+    let first_update_id = reader.unsigned("first book update id")?;
+    let final_update_id = reader.unsigned("last book update id")?;
+    let price_exponent = reader.i8()?;
+    let quantity_exponent = reader.i8()?;
+    reader.skip_to(block_end)?;
+    let bids = reader.levels(price_exponent, quantity_exponent)?;
+    let asks = reader.levels(price_exponent, quantity_exponent)?;
+    let symbol = reader.symbol()?;
+    Ok(SbeMessage::DepthDiff(DepthUpdate {
+        event_type: "depthUpdate".to_string(),
+        event_time,
+        symbol,
+        first_update_id,
+        final_update_id,
+        bids,
+        asks,
+    }))
+}
+
+// Little-endian reader, every read checks the remaining length
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, position: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SbeError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + N)
+            .ok_or_else(|| SbeError::Truncated { offset: self.position, needed: N - self.remaining() })?;
+        self.position += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn skip_to(&mut self, position: usize) -> Result<(), SbeError> {
+        if position > self.bytes.len() {
+            return Err(SbeError::Truncated { offset: self.position, needed: position - self.bytes.len() });
+        }
+        self.position = self.position.max(position);
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8, SbeError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn i8(&mut self) -> Result<i8, SbeError> {
+        Ok(i8::from_le_bytes(self.take()?))
+    }
+
+    fn u16(&mut self) -> Result<u16, SbeError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, SbeError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, SbeError> {
+        Ok(i64::from_le_bytes(self.take()?))
+    }
+
+    fn bool(&mut self) -> Result<bool, SbeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(SbeError::InvalidBool(value)),
+        }
+    }
+
+    // IDs are int64 in the schema and u64 in the JSON structs
+    fn unsigned(&mut self, field: &'static str) -> Result<u64, SbeError> {
+        u64::try_from(self.i64()?).map_err(|_| SbeError::NegativeValue(field))
+    }
+
+    fn timestamp(&mut self, field: &'static str) -> Result<u64, SbeError> {
+        self.unsigned(field)
+    }
+
+    // groupSizeEncoding: u16 block length and u32 count
+    fn group_size(&mut self) -> Result<(usize, usize), SbeError> {
+        Ok((self.u16()? as usize, self.u32()? as usize))
+    }
+
+    // groupSize16Encoding: u16 block length and u16 count, used by the depth groups
+    fn levels(&mut self, price_exponent: i8, quantity_exponent: i8) -> Result<Vec<PriceLevel>, SbeError> {
+        let block_length = self.u16()? as usize;
+        let count = self.u16()? as usize;
+        let mut levels = Vec::with_capacity(count.min(self.remaining() / block_length.max(1)));
+        for _ in 0..count {
+            let row_end = self.position + block_length;
+            let price = decimal(self.i64()?, price_exponent);
+            let quantity = decimal(self.i64()?, quantity_exponent);
+            self.skip_to(row_end)?;
+            levels.push(PriceLevel { price, quantity });
+        }
+        Ok(levels)
+    }
+
+    // varString8: u8 length followed by the bytes
+    fn symbol(&mut self) -> Result<String, SbeError> {
+        let length = self.u8()? as usize;
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + length)
+            .ok_or_else(|| SbeError::Truncated { offset: self.position, needed: length - self.remaining() })?;
+        self.position += length;
+        String::from_utf8(bytes.to_vec()).map_err(|_| SbeError::InvalidSymbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRADES: &[u8] = include_bytes!("../fixtures/sbe/trades.bin");
+    const BEST_BID_ASK: &[u8] = include_bytes!("../fixtures/sbe/best_bid_ask.bin");
+    const DEPTH_SNAPSHOT: &[u8] = include_bytes!("../fixtures/sbe/depth_snapshot.bin");
+    const DEPTH_DIFF: &[u8] = include_bytes!("../fixtures/sbe/depth_diff.bin");
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(decimal(1, -3), 0.001);
+        assert_eq!(decimal(6_543_210_000_000, -8), 65432.1);
+        assert_eq!(decimal(-25, -1), -2.5);
+        assert_eq!(decimal(12, 2), 1200.0);
+    }
+
+    #[test]
+    fn test_decode_trades_matches_json() {
+        let SbeMessage::Trades(trades) = decode(TRADES).unwrap() else {
+            panic!("expected trades");
+        };
+        let expected = [
+            r#"{"e":"trade","E":1672515782136412,"s":"BNBBTC","t":12345,"p":"0.00100000","q":"100.00000000","T":1672515782136001,"m":true,"M":true}"#,
+            r#"{"e":"trade","E":1672515782136412,"s":"BNBBTC","t":12346,"p":"0.00100100","q":"2.50000000","T":1672515782136001,"m":false,"M":true}"#,
+        ];
+        let expected: Vec<Trade> = expected.into_iter().map(|json| Trade::from_json(json).unwrap()).collect();
+        assert_eq!(trades, expected);
+    }
+
+    #[test]
+    fn test_decode_best_bid_ask_matches_json() {
+        let SbeMessage::BestBidAsk { event_time, ticker } = decode(BEST_BID_ASK).unwrap() else {
+            panic!("expected best bid ask");
+        };
+        let json = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        assert_eq!(ticker, BookTicker::from_json(json).unwrap());
+        assert_eq!(event_time, 1672515782136412);
+    }
+
+    #[test]
+    fn test_decode_depth_matches_json() {
+        let SbeMessage::DepthSnapshot { event_time, symbol, depth } = decode(DEPTH_SNAPSHOT).unwrap() else {
+            panic!("expected depth snapshot");
+        };
+        let json = r#"{"lastUpdateId":160,"bids":[["0.0024","10"],["0.0023","0.5"]],"asks":[["0.0026","100"]]}"#;
+        assert_eq!(depth, PartialDepth::from_json(json).unwrap());
+        assert_eq!((event_time, symbol.as_str()), (1672515782136412, "BNBBTC"));
+
+        let update = decode(DEPTH_DIFF).unwrap();
+        let json = r#"{"e":"depthUpdate","E":1672515782136412,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"]],"a":[["0.0026","100"],["0.0027","0"]]}"#;
+        assert_eq!(update, SbeMessage::DepthDiff(DepthUpdate::from_json(json).unwrap()));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let mut frame = TRADES.to_vec();
+        frame[2..4].copy_from_slice(&10004u16.to_le_bytes());
+        assert_eq!(decode(&frame), Err(SbeError::UnsupportedTemplate(10004)));
+        assert_eq!(SbeError::UnsupportedTemplate(10004).to_string(), "unsupported template 10004");
+
+        let mut frame = TRADES.to_vec();
+        frame[4..6].copy_from_slice(&2u16.to_le_bytes());
+        assert_eq!(decode(&frame), Err(SbeError::UnsupportedSchema { schema_id: 2, version: 0 }));
+
+        assert_eq!(decode(&TRADES[..TRADES.len() - 2]), Err(SbeError::Truncated { offset: TRADES.len() - 6, needed: 2 }));
+        assert_eq!(decode(&TRADES[..5]), Err(SbeError::Truncated { offset: 4, needed: 1 }));
+    }
+
+    #[test]
+    fn test_decode_skips_fields_of_newer_versions() {
+        // Version 1 with two extra root bytes and one extra byte per depth level
+        let mut frame = vec![];
+        frame.extend_from_slice(&28u16.to_le_bytes());
+        frame.extend_from_slice(&DEPTH_DIFF_TEMPLATE_ID.to_le_bytes());
+        frame.extend_from_slice(&SCHEMA_ID.to_le_bytes());
+        frame.extend_from_slice(&1u16.to_le_bytes());
+        frame.extend_from_slice(&1672515782136412i64.to_le_bytes());
+        frame.extend_from_slice(&157i64.to_le_bytes());
+        frame.extend_from_slice(&160i64.to_le_bytes());
+        frame.extend_from_slice(&[-4i8 as u8, 0, 0xff, 0xff]);
+        frame.extend_from_slice(&17u16.to_le_bytes());
+        frame.extend_from_slice(&1u16.to_le_bytes());
+        frame.extend_from_slice(&24i64.to_le_bytes());
+        frame.extend_from_slice(&10i64.to_le_bytes());
+        frame.push(0xff);
+        frame.extend_from_slice(&17u16.to_le_bytes());
+        frame.extend_from_slice(&0u16.to_le_bytes());
+        frame.push(6);
+        frame.extend_from_slice(b"BNBBTC");
+
+        let SbeMessage::DepthDiff(update) = decode(&frame).unwrap() else {
+            panic!("expected depth diff");
+        };
+        assert_eq!((update.first_update_id, update.final_update_id), (157, 160));
+        assert_eq!(update.bids[..], [PriceLevel::new(0.0024, 10.0)]);
+        assert!(update.asks.is_empty());
+        assert_eq!(update.symbol, "BNBBTC");
+    }
+}
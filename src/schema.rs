@@ -0,0 +1,190 @@
+//! JSON Schemas of the message types, describing the wire format accepted by deserialization.
+
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::rest::KlineRow;
+use crate::symbol::Symbol;
+use crate::websocket::PriceLevel;
+
+// Decimal or scientific notation, as accepted by `str::parse::<f64>` for the numbers Binance sends
+pub const NUMERIC_PATTERN: &str = r"^-?[0-9]+(\.[0-9]+)?([eE][-+]?[0-9]+)?$";
+
+// Number sent as a string, e.g. "0.00100000", the schema of fields parsed into an `f64`
+pub struct NumericString;
+
+impl JsonSchema for NumericString {
+    fn schema_name() -> Cow<'static, str> {
+        "NumericString".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": NUMERIC_PATTERN,
+        })
+    }
+}
+
+// Numeric string that may be null, empty or missing, all parsed as None
+pub struct OptionalNumericString;
+
+impl JsonSchema for OptionalNumericString {
+    fn schema_name() -> Cow<'static, str> {
+        "OptionalNumericString".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": ["string", "null"],
+            "pattern": r"^(-?[0-9]+(\.[0-9]+)?([eE][-+]?[0-9]+)?)?$",
+        })
+    }
+}
+
+// Unsigned integer sent either as a JSON number or as a string
+pub struct IntegerOrString;
+
+impl JsonSchema for IntegerOrString {
+    fn schema_name() -> Cow<'static, str> {
+        "IntegerOrString".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "pattern": "^[0-9]+$" },
+            ],
+        })
+    }
+}
+
+// Bool sent either as a JSON bool or as "true"/"false"
+pub struct BoolOrString;
+
+impl JsonSchema for BoolOrString {
+    fn schema_name() -> Cow<'static, str> {
+        "BoolOrString".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "anyOf": [
+                { "type": "boolean" },
+                { "type": "string", "enum": ["true", "false"] },
+            ],
+        })
+    }
+}
+
+// Options trade side sent as "1" for buy and "-1" for sell
+pub struct SignedSide;
+
+impl JsonSchema for SignedSide {
+    fn schema_name() -> Cow<'static, str> {
+        "SignedSide".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "enum": ["1", "-1"],
+        })
+    }
+}
+
+impl JsonSchema for Symbol {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "Symbol".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({ "type": "string" })
+    }
+}
+
+// [price, quantity] as numeric strings
+impl JsonSchema for PriceLevel {
+    fn schema_name() -> Cow<'static, str> {
+        "PriceLevel".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let number = generator.subschema_for::<NumericString>();
+        json_schema!({
+            "type": "array",
+            "prefixItems": [number, number],
+            "minItems": 2,
+            "maxItems": 2,
+        })
+    }
+}
+
+// [open time, open, high, low, close, volume, close time, quote volume, trades, taker buy base volume,
+// taker buy quote volume, ignore], numeric fields may also be sent as JSON numbers
+impl JsonSchema for KlineRow {
+    fn schema_name() -> Cow<'static, str> {
+        "KlineRow".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let integer = generator.subschema_for::<IntegerOrString>();
+        let number = json_schema!({
+            "anyOf": [
+                { "type": "number" },
+                generator.subschema_for::<NumericString>(),
+            ],
+        });
+        json_schema!({
+            "type": "array",
+            "prefixItems": [
+                integer, number, number, number, number, number,
+                integer, number, integer, number, number,
+            ],
+            "minItems": 11,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use schemars::schema_for;
+
+    use crate::websocket::Trade;
+    use super::*;
+
+    #[test]
+    fn test_trade_schema() {
+        let schema = serde_json::to_value(schema_for!(Trade)).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+
+        let price = &properties["p"];
+        let price = match price.get("$ref").and_then(|reference| reference.as_str()) {
+            Some(reference) => &schema["$defs"][reference.trim_start_matches("#/$defs/")],
+            None => price,
+        };
+        assert_eq!(price["type"], "string");
+        assert_eq!(price["pattern"], NUMERIC_PATTERN);
+        assert_eq!(properties["t"]["type"], "integer");
+
+        let mut names: Vec<&str> = properties.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["E", "M", "T", "e", "m", "p", "q", "s", "t"]);
+    }
+
+    #[test]
+    fn test_price_level_and_optional_schemas() {
+        let levels = serde_json::to_value(schema_for!(Vec<PriceLevel>)).unwrap();
+        assert_eq!(levels["items"]["$ref"], "#/$defs/PriceLevel");
+        assert_eq!(levels["$defs"]["PriceLevel"]["prefixItems"][0]["$ref"], "#/$defs/NumericString");
+
+        let optional = serde_json::to_value(schema_for!(OptionalNumericString)).unwrap();
+        assert_eq!(optional["type"], serde_json::json!(["string", "null"]));
+    }
+}
@@ -0,0 +1,249 @@
+//! Sequence checks for trade streams across drops and reconnects.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::websocket::{AggTrade, Trade};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Continuity {
+    Ok,                                  // Next expected message, or the first one for the symbol
+    Gap { missing_from: u64, missing_to: u64 }, // Aggregate ids that never arrived, inclusive
+    TradeGap { missing_from: u64, missing_to: u64 }, // Next aggregate id, but trade ids between the two messages never arrived, inclusive
+    Duplicate,                           // Aggregate id or trade ids already seen
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AggTradeBaseline {
+    aggregate_trade_id: u64,
+    last_trade_id: u64,
+}
+
+// Aggregate ids of a symbol increase by one per message and each message covers the trade ids `f..=l`
+// right after the previous `l`, so a jump in either means trades were dropped. A message whose aggregate
+// id or first trade id does not move past the baseline is a replay
+#[derive(Clone, Debug, Default)]
+pub struct AggTradeSequencer {
+    baselines: HashMap<String, AggTradeBaseline>,
+}
+
+impl AggTradeSequencer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ingest(&mut self, trade: &AggTrade) -> Continuity {
+        let next = AggTradeBaseline {
+            aggregate_trade_id: trade.aggregate_trade_id,
+            last_trade_id: trade.last_trade_id,
+        };
+        let Some(baseline) = self.baselines.get_mut(&trade.symbol) else {
+            self.baselines.insert(trade.symbol.clone(), next);
+            return Continuity::Ok;
+        };
+        if trade.aggregate_trade_id <= baseline.aggregate_trade_id || trade.first_trade_id <= baseline.last_trade_id {
+            return Continuity::Duplicate;
+        }
+        let expected = baseline.aggregate_trade_id + 1;
+        let expected_trade_id = baseline.last_trade_id + 1;
+        *baseline = next;
+        if trade.aggregate_trade_id > expected {
+            #[cfg(feature = "metrics")]
+            crate::metrics::emit::trade_gap(&trade.symbol, trade.aggregate_trade_id - expected);
+            Continuity::Gap { missing_from: expected, missing_to: trade.aggregate_trade_id - 1 }
+        } else if trade.first_trade_id > expected_trade_id {
+            Continuity::TradeGap { missing_from: expected_trade_id, missing_to: trade.first_trade_id - 1 }
+        } else {
+            Continuity::Ok
+        }
+    }
+
+    pub fn last_aggregate_trade_id(&self, symbol: &str) -> Option<u64> {
+        self.baselines.get(symbol).map(|baseline| baseline.aggregate_trade_id)
+    }
+
+    // After a reconnect the next message of the symbol starts a new baseline
+    pub fn reset(&mut self, symbol: &str) {
+        self.baselines.remove(symbol);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.baselines.clear();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeCheck {
+    Fresh,                               // Newer than anything seen for the symbol
+    Duplicate,                           // Already seen, or too old to tell
+    OutOfOrder,                          // Not seen before but older than the newest trade
+}
+
+#[derive(Clone, Debug, Default)]
+struct SymbolTrades {
+    high_watermark: u64,
+    recent: BTreeSet<u64>,
+}
+
+// Remembers the highest trade id per symbol plus the ids within `window` below it. Ids at or below
+// `high_watermark - window` are treated as duplicates, so replayed history is never reprocessed.
+#[derive(Clone, Debug)]
+pub struct TradeDeduper {
+    window: u64,
+    symbols: HashMap<String, SymbolTrades>,
+}
+
+impl TradeDeduper {
+    pub fn new(window: u64) -> Self {
+        TradeDeduper { window: window.max(1), symbols: HashMap::new() }
+    }
+
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+
+    pub fn check_and_record(&mut self, trade: &Trade) -> TradeCheck {
+        self.check_and_record_id(&trade.symbol, trade.trade_id)
+    }
+
+    pub fn check_and_record_id(&mut self, symbol: &str, trade_id: u64) -> TradeCheck {
+        let Some(trades) = self.symbols.get_mut(symbol) else {
+            let mut recent = BTreeSet::new();
+            recent.insert(trade_id);
+            self.symbols.insert(symbol.to_string(), SymbolTrades { high_watermark: trade_id, recent });
+            return TradeCheck::Fresh;
+        };
+
+        if trade_id > trades.high_watermark {
+            trades.high_watermark = trade_id;
+            trades.recent.insert(trade_id);
+            // Evict everything that fell out of the window, oldest first
+            let floor = trade_id.saturating_sub(self.window - 1);
+            trades.recent = trades.recent.split_off(&floor);
+            return TradeCheck::Fresh;
+        }
+        if trade_id + self.window <= trades.high_watermark {
+            return TradeCheck::Duplicate;
+        }
+        if trades.recent.insert(trade_id) {
+            TradeCheck::OutOfOrder
+        } else {
+            TradeCheck::Duplicate
+        }
+    }
+
+    pub fn reset(&mut self, symbol: &str) {
+        self.symbols.remove(symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agg_trade(symbol: &str, aggregate_trade_id: u64, first_trade_id: u64, last_trade_id: u64) -> AggTrade {
+        AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: 0,
+            symbol: symbol.to_string(),
+            aggregate_trade_id,
+            price: 1.0,
+            quantity: 1.0,
+            first_trade_id,
+            last_trade_id,
+            trade_time: 0,
+            is_buyer_market_maker: false,
+            ignore: true,
+        }
+    }
+
+    #[test]
+    fn test_agg_trade_sequencer_contiguous_run() {
+        let mut sequencer = AggTradeSequencer::new();
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 10, 100, 102)), Continuity::Ok);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 11, 103, 103)), Continuity::Ok);
+        assert_eq!(sequencer.ingest(&agg_trade("ETHUSDT", 500, 9000, 9000)), Continuity::Ok);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 12, 104, 110)), Continuity::Ok);
+        assert_eq!(sequencer.last_aggregate_trade_id("BTCUSDT"), Some(12));
+        assert_eq!(sequencer.last_aggregate_trade_id("ETHUSDT"), Some(500));
+    }
+
+    #[test]
+    fn test_agg_trade_sequencer_gap_and_duplicate() {
+        let mut sequencer = AggTradeSequencer::new();
+        sequencer.ingest(&agg_trade("BTCUSDT", 10, 100, 102));
+
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 14, 120, 121)), Continuity::Gap { missing_from: 11, missing_to: 13 });
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 15, 122, 122)), Continuity::Ok);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 15, 122, 122)), Continuity::Duplicate);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 12, 105, 106)), Continuity::Duplicate);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 16, 120, 122)), Continuity::Duplicate);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 16, 122, 125)), Continuity::Duplicate);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 16, 123, 125)), Continuity::Ok);
+        assert_eq!(sequencer.last_aggregate_trade_id("BTCUSDT"), Some(16));
+
+        // Contiguous aggregate ids with trade ids missing in between
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 17, 130, 131)), Continuity::TradeGap { missing_from: 126, missing_to: 129 });
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 18, 132, 132)), Continuity::Ok);
+    }
+
+    #[test]
+    fn test_agg_trade_sequencer_reset_after_reconnect() {
+        let mut sequencer = AggTradeSequencer::new();
+        sequencer.ingest(&agg_trade("BTCUSDT", 10, 100, 102));
+        sequencer.ingest(&agg_trade("ETHUSDT", 500, 9000, 9000));
+
+        sequencer.reset("BTCUSDT");
+        assert_eq!(sequencer.last_aggregate_trade_id("BTCUSDT"), None);
+        assert_eq!(sequencer.ingest(&agg_trade("BTCUSDT", 40, 300, 301)), Continuity::Ok);
+        assert_eq!(sequencer.ingest(&agg_trade("ETHUSDT", 502, 9002, 9002)), Continuity::Gap { missing_from: 501, missing_to: 501 });
+
+        sequencer.reset_all();
+        assert_eq!(sequencer.ingest(&agg_trade("ETHUSDT", 400, 8000, 8000)), Continuity::Ok);
+    }
+
+    fn trade(symbol: &str, trade_id: u64) -> Trade {
+        Trade {
+            event_type: Some("trade".to_string()),
+            event_time: Some(0),
+            symbol: symbol.to_string(),
+            trade_id,
+            price: 1.0,
+            quantity: 1.0,
+            trade_time: 0,
+            is_buyer_market_maker: false,
+            ignore: true,
+        }
+    }
+
+    #[test]
+    fn test_trade_deduper_increasing_fast_path() {
+        let mut deduper = TradeDeduper::new(4);
+        for trade_id in 100..110 {
+            assert_eq!(deduper.check_and_record(&trade("BTCUSDT", trade_id)), TradeCheck::Fresh);
+        }
+        assert_eq!(deduper.check_and_record(&trade("ETHUSDT", 100)), TradeCheck::Fresh);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 115)), TradeCheck::Fresh);
+    }
+
+    #[test]
+    fn test_trade_deduper_duplicates_inside_and_outside_window() {
+        let mut deduper = TradeDeduper::new(4);
+        deduper.check_and_record(&trade("BTCUSDT", 100));
+        deduper.check_and_record(&trade("BTCUSDT", 101));
+        deduper.check_and_record(&trade("BTCUSDT", 103));
+
+        // Window holds 100..=103
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 101)), TradeCheck::Duplicate);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 102)), TradeCheck::OutOfOrder);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 102)), TradeCheck::Duplicate);
+
+        // Window moves to 103..=106; 102 is evicted and now outside
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 106)), TradeCheck::Fresh);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 102)), TradeCheck::Duplicate);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 104)), TradeCheck::OutOfOrder);
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 50)), TradeCheck::Duplicate);
+
+        deduper.reset("BTCUSDT");
+        assert_eq!(deduper.check_and_record(&trade("BTCUSDT", 50)), TradeCheck::Fresh);
+    }
+}
@@ -0,0 +1,190 @@
+//! Rolling trade statistics per symbol.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::candles::TradeTick;
+use crate::websocket::{AggTrade, Trade};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct StatsBucket {
+    start: u64,
+    notional: f64,
+    volume: f64,
+    buy_volume: f64,
+    trade_count: u64,
+    max_price: f64,
+    min_price: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TradeStatsSnapshot {
+    pub vwap: Option<f64>,               // Volume weighted average price, None without volume
+    pub volume: f64,                     // Total base asset volume
+    pub buy_volume: f64,                 // Volume where the taker bought
+    pub sell_volume: f64,                // Volume where the taker sold
+    pub trade_count: u64,                // Number of trade messages
+    pub max_price: Option<f64>,          // Highest price
+    pub min_price: Option<f64>,          // Lowest price
+}
+
+// Time window of `window_ms` split into buckets of `bucket_ms`; whole buckets expire at once, so the
+// effective window is between `window_ms - bucket_ms` and `window_ms` long
+#[derive(Clone, Debug)]
+pub struct TradeStats {
+    window_ms: u64,
+    bucket_ms: u64,
+    symbols: HashMap<String, VecDeque<StatsBucket>>,
+}
+
+impl TradeStats {
+    pub fn new(window_ms: u64, bucket_ms: u64) -> Self {
+        let bucket_ms = bucket_ms.clamp(1, window_ms.max(1));
+        TradeStats { window_ms: window_ms.max(bucket_ms), bucket_ms, symbols: HashMap::new() }
+    }
+
+    pub fn ingest_trade(&mut self, trade: &Trade) {
+        self.record(&trade.symbol, TradeTick::from(trade));
+    }
+
+    pub fn ingest_agg_trade(&mut self, trade: &AggTrade) {
+        self.record(&trade.symbol, TradeTick::from(trade));
+    }
+
+    pub fn record(&mut self, symbol: &str, tick: TradeTick) {
+        let start = tick.trade_time - tick.trade_time % self.bucket_ms;
+        let buckets = self.symbols.entry(symbol.to_string()).or_default();
+        match buckets.back_mut() {
+            Some(bucket) if bucket.start == start => add_to_bucket(bucket, &tick),
+            Some(bucket) if bucket.start > start => {
+                // Late trade, folded into the newest bucket not after it; dropped if older than every bucket
+                if let Some(bucket) = buckets.iter_mut().rev().find(|bucket| bucket.start <= start) {
+                    add_to_bucket(bucket, &tick);
+                }
+            }
+            _ => {
+                let mut bucket = StatsBucket {
+                    start,
+                    notional: 0.0,
+                    volume: 0.0,
+                    buy_volume: 0.0,
+                    trade_count: 0,
+                    max_price: tick.price,
+                    min_price: tick.price,
+                };
+                add_to_bucket(&mut bucket, &tick);
+                buckets.push_back(bucket);
+            }
+        }
+        expire(buckets, tick.trade_time, self.window_ms);
+    }
+
+    // Statistics over the window ending at `now_ms`
+    pub fn snapshot(&mut self, symbol: &str, now_ms: u64) -> TradeStatsSnapshot {
+        let Some(buckets) = self.symbols.get_mut(symbol) else {
+            return TradeStatsSnapshot::default();
+        };
+        expire(buckets, now_ms, self.window_ms);
+
+        let mut snapshot = TradeStatsSnapshot::default();
+        let mut notional = 0.0;
+        for bucket in buckets.iter() {
+            notional += bucket.notional;
+            snapshot.volume += bucket.volume;
+            snapshot.buy_volume += bucket.buy_volume;
+            snapshot.trade_count += bucket.trade_count;
+            snapshot.max_price = Some(snapshot.max_price.map_or(bucket.max_price, |max| max.max(bucket.max_price)));
+            snapshot.min_price = Some(snapshot.min_price.map_or(bucket.min_price, |min| min.min(bucket.min_price)));
+        }
+        snapshot.sell_volume = snapshot.volume - snapshot.buy_volume;
+        if snapshot.volume > 0.0 {
+            snapshot.vwap = Some(notional / snapshot.volume);
+        }
+        snapshot
+    }
+
+    pub fn reset(&mut self) {
+        self.symbols.clear();
+    }
+}
+
+fn add_to_bucket(bucket: &mut StatsBucket, tick: &TradeTick) {
+    bucket.notional += tick.price * tick.quantity;
+    bucket.volume += tick.quantity;
+    if !tick.is_buyer_market_maker {
+        bucket.buy_volume += tick.quantity;
+    }
+    bucket.trade_count += 1;
+    bucket.max_price = bucket.max_price.max(tick.price);
+    bucket.min_price = bucket.min_price.min(tick.price);
+}
+
+fn expire(buckets: &mut VecDeque<StatsBucket>, now_ms: u64, window_ms: u64) {
+    let cutoff = now_ms.saturating_sub(window_ms);
+    while buckets.front().is_some_and(|bucket| bucket.start < cutoff) {
+        buckets.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(trade_id: u64, price: f64, quantity: f64, trade_time: u64, is_buyer_market_maker: bool) -> Trade {
+        Trade {
+            event_type: Some("trade".to_string()),
+            event_time: Some(trade_time),
+            symbol: "BTCUSDT".to_string(),
+            trade_id,
+            price,
+            quantity,
+            trade_time,
+            is_buyer_market_maker,
+            ignore: true,
+        }
+    }
+
+    #[test]
+    fn test_trade_stats_rolling_window() {
+        let mut stats = TradeStats::new(60_000, 1_000);
+        stats.ingest_trade(&trade(1, 100.0, 1.0, 1_000, false));
+        stats.ingest_trade(&trade(2, 110.0, 3.0, 10_500, true));
+        stats.ingest_trade(&trade(3, 90.0, 1.0, 10_900, false));
+
+        let snapshot = stats.snapshot("BTCUSDT", 20_000);
+        assert_eq!(snapshot.vwap, Some(104.0));
+        assert_eq!(snapshot.volume, 5.0);
+        assert_eq!(snapshot.buy_volume, 2.0);
+        assert_eq!(snapshot.sell_volume, 3.0);
+        assert_eq!(snapshot.trade_count, 3);
+        assert_eq!(snapshot.max_price, Some(110.0));
+        assert_eq!(snapshot.min_price, Some(90.0));
+
+        stats.ingest_trade(&trade(4, 120.0, 1.0, 65_000, false));
+        // The bucket at 1 s expired, the one at 10 s has not
+        let snapshot = stats.snapshot("BTCUSDT", 65_000);
+        assert_eq!(snapshot.vwap, Some((330.0 + 90.0 + 120.0) / 5.0));
+        assert_eq!(snapshot.buy_volume, 2.0);
+        assert_eq!(snapshot.sell_volume, 3.0);
+        assert_eq!(snapshot.trade_count, 3);
+
+        // Only the last trade is left
+        let snapshot = stats.snapshot("BTCUSDT", 100_000);
+        assert_eq!(snapshot.vwap, Some(120.0));
+        assert_eq!(snapshot.buy_volume, 1.0);
+        assert_eq!(snapshot.sell_volume, 0.0);
+        assert_eq!(snapshot.min_price, Some(120.0));
+
+        let snapshot = stats.snapshot("BTCUSDT", 200_000);
+        assert_eq!(snapshot, TradeStatsSnapshot::default());
+    }
+
+    #[test]
+    fn test_trade_stats_reset_and_unknown_symbol() {
+        let mut stats = TradeStats::new(60_000, 1_000);
+        assert_eq!(stats.snapshot("ETHUSDT", 0), TradeStatsSnapshot::default());
+
+        stats.ingest_trade(&trade(1, 100.0, 1.0, 1_000, false));
+        stats.reset();
+        assert_eq!(stats.snapshot("BTCUSDT", 1_000).trade_count, 0);
+    }
+}
@@ -0,0 +1,290 @@
+//! `Stream` adapter parsing websocket text frames into events, independent of the transport and runtime.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::error::{instrumented, CodecError};
+use crate::websocket::frame::{classify, FrameKind};
+
+cfg_spot! {
+    use crate::websocket::SpotEvent;
+}
+cfg_futures! {
+    use crate::websocket::futures::FuturesEvent;
+}
+
+// What happens to a frame that fails to parse
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    #[default]
+    Skip,                                // Drop the frame and count it in `skipped()`
+    Yield,                               // Yield the error and keep going
+    Abort,                               // Yield the error and end the stream
+}
+
+// One item of the wrapped stream, e.g. a `String` or with the `tungstenite` feature a `Result<Message, E>`
+pub trait IntoFrame {
+    type Text: AsRef<str>;
+    type Error;
+
+    // Frame text, None for frames that carry no event such as pings and binary frames
+    fn into_frame(self) -> Result<Option<Self::Text>, Self::Error>;
+}
+
+impl IntoFrame for String {
+    type Text = String;
+    type Error = Infallible;
+
+    fn into_frame(self) -> Result<Option<String>, Infallible> {
+        Ok(Some(self))
+    }
+}
+
+impl<'a> IntoFrame for &'a str {
+    type Text = &'a str;
+    type Error = Infallible;
+
+    fn into_frame(self) -> Result<Option<&'a str>, Infallible> {
+        Ok(Some(self))
+    }
+}
+
+#[cfg(feature = "tungstenite")]
+impl<E> IntoFrame for Result<tungstenite::Message, E> {
+    type Text = tungstenite::Utf8Bytes;
+    type Error = E;
+
+    fn into_frame(self) -> Result<Option<tungstenite::Utf8Bytes>, E> {
+        match self? {
+            tungstenite::Message::Text(text) => Ok(Some(text)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum EventStreamError<E> {
+    Transport(E),                        // Error of the wrapped stream, always yielded
+    Codec(CodecError),                   // Frame that failed to parse, yielded unless the policy is `Skip`
+}
+
+impl<E: fmt::Display> fmt::Display for EventStreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventStreamError::Transport(error) => write!(f, "transport error: {}", error),
+            EventStreamError::Codec(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for EventStreamError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EventStreamError::Transport(error) => Some(error),
+            EventStreamError::Codec(error) => Some(error),
+        }
+    }
+}
+
+// Acks, responses and errors to requests are counted in `controls()` and not yielded. The wrapped
+// stream must be `Unpin`, pin it with `Box::pin` otherwise
+pub struct EventStream<S, T> {
+    inner: S,
+    from_json: fn(&str) -> Result<T, serde_json::Error>,
+    policy: ErrorPolicy,
+    skipped: usize,
+    controls: usize,
+    done: bool,
+}
+
+impl<S, T> EventStream<S, T> {
+    pub fn new(inner: S, from_json: fn(&str) -> Result<T, serde_json::Error>) -> Self {
+        EventStream { inner, from_json, policy: ErrorPolicy::default(), skipped: 0, controls: 0, done: false }
+    }
+
+    pub fn policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // Frames dropped under `ErrorPolicy::Skip`
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub fn controls(&self) -> usize {
+        self.controls
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+#[cfg(feature = "spot")]
+impl<S> EventStream<S, SpotEvent> {
+    pub fn spot(inner: S) -> Self {
+        EventStream::new(inner, SpotEvent::from_json)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<S> EventStream<S, FuturesEvent> {
+    pub fn futures(inner: S) -> Self {
+        EventStream::new(inner, FuturesEvent::from_json)
+    }
+}
+
+impl<S, T> Stream for EventStream<S, T>
+where
+    S: Stream + Unpin,
+    S::Item: IntoFrame,
+{
+    type Item = Result<T, EventStreamError<<S::Item as IntoFrame>::Error>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            let item = match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(item)) => item,
+                Poll::Ready(None) => break,
+                Poll::Pending => return Poll::Pending,
+            };
+            let text = match item.into_frame() {
+                Ok(Some(text)) => text,
+                Ok(None) => continue,
+                Err(error) => {
+                    this.done = this.policy == ErrorPolicy::Abort;
+                    return Poll::Ready(Some(Err(EventStreamError::Transport(error))));
+                }
+            };
+            let text = text.as_ref();
+            if matches!(classify(text), FrameKind::Ack { .. } | FrameKind::Response { .. } | FrameKind::Error { .. }) {
+                this.controls += 1;
+                continue;
+            }
+            let from_json = this.from_json;
+            match instrumented(text.as_bytes(), || from_json(text).map_err(|error| CodecError::new(error, text))) {
+                Ok(event) => return Poll::Ready(Some(Ok(event))),
+                Err(_) if this.policy == ErrorPolicy::Skip => this.skipped += 1,
+                Err(error) => {
+                    this.done = this.policy == ErrorPolicy::Abort;
+                    return Poll::Ready(Some(Err(EventStreamError::Codec(error))));
+                }
+            }
+        }
+        this.done = true;
+        Poll::Ready(None)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done { (0, Some(0)) } else { (0, self.inner.size_hint().1) }
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+    use ::futures::executor::block_on;
+    use ::futures::stream::{self, StreamExt};
+
+    use crate::testutil::SampleTrade;
+
+    const ACK: &str = r#"{"result":null,"id":1}"#;
+    const CORRUPT: &str = r#"{"e":"trade","E":1672515782138,"s":"BNBBTC","t":"12347"}"#;
+
+    fn trade(trade_id: u64) -> String {
+        SampleTrade { trade_id, ..SampleTrade::default() }.to_json()
+    }
+
+    fn frames() -> Vec<String> {
+        vec![trade(12345), ACK.to_string(), CORRUPT.to_string(), trade(12346)]
+    }
+
+    fn trade_id(event: &SpotEvent) -> u64 {
+        match event {
+            SpotEvent::Trade(trade) => trade.trade_id,
+            other => panic!("expected a trade, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_event_stream_skip_policy() {
+        let mut events = EventStream::spot(stream::iter(frames()));
+        let ids: Vec<u64> = block_on((&mut events).map(|event| trade_id(&event.unwrap())).collect());
+
+        assert_eq!(ids, vec![12345, 12346]);
+        assert_eq!(events.skipped(), 1);
+        assert_eq!(events.controls(), 1);
+    }
+
+    #[test]
+    fn test_event_stream_yield_policy() {
+        let events: Vec<_> = block_on(EventStream::spot(stream::iter(frames())).policy(ErrorPolicy::Yield).collect());
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(trade_id(events[0].as_ref().unwrap()), 12345);
+        match &events[1] {
+            Err(EventStreamError::Codec(error)) => assert_eq!(error.event_type.as_deref(), Some("trade")),
+            other => panic!("expected a codec error, got {:?}", other),
+        }
+        assert_eq!(trade_id(events[2].as_ref().unwrap()), 12346);
+    }
+
+    #[test]
+    fn test_event_stream_abort_policy() {
+        let mut events = EventStream::spot(stream::iter(frames())).policy(ErrorPolicy::Abort);
+
+        assert_eq!(trade_id(&block_on(events.next()).unwrap().unwrap()), 12345);
+        assert!(matches!(block_on(events.next()), Some(Err(EventStreamError::Codec(_)))));
+        assert!(block_on(events.next()).is_none());
+        assert!(block_on(events.next()).is_none());
+        assert_eq!(block_on(events.into_inner().count()), 1);
+    }
+
+    struct Fallible(Result<String, &'static str>);
+
+    impl IntoFrame for Fallible {
+        type Text = String;
+        type Error = &'static str;
+
+        fn into_frame(self) -> Result<Option<String>, &'static str> {
+            self.0.map(Some)
+        }
+    }
+
+    #[test]
+    fn test_event_stream_transport_errors() {
+        let frames = [Fallible(Ok(trade(12345))), Fallible(Err("connection reset")), Fallible(Ok(trade(12346)))];
+        let events: Vec<_> = block_on(EventStream::spot(stream::iter(frames)).collect());
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[1], Err(EventStreamError::Transport("connection reset"))));
+        assert_eq!(trade_id(events[2].as_ref().unwrap()), 12346);
+
+        let frames = [Fallible(Err("closed")), Fallible(Ok(trade(12345)))];
+        let events: Vec<_> = block_on(EventStream::spot(stream::iter(frames)).policy(ErrorPolicy::Abort).collect());
+        assert_eq!(events.len(), 1);
+    }
+
+    #[cfg(feature = "tungstenite")]
+    #[test]
+    fn test_event_stream_tungstenite_messages() {
+        use tungstenite::Message;
+
+        let messages: Vec<Result<Message, tungstenite::Error>> = vec![
+            Ok(Message::Ping(Default::default())),
+            Ok(Message::text(trade(12345))),
+            Ok(Message::text(ACK)),
+            Err(tungstenite::Error::ConnectionClosed),
+        ];
+        let events: Vec<_> = block_on(EventStream::spot(stream::iter(messages)).collect());
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(trade_id(events[0].as_ref().unwrap()), 12345);
+        assert!(matches!(events[1], Err(EventStreamError::Transport(tungstenite::Error::ConnectionClosed))));
+    }
+}
@@ -0,0 +1,338 @@
+//! Interned symbols, so hot stream structs share one allocation per distinct symbol.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::batch::{parse_many, ParseBatch};
+use crate::error::{parse_with, CodecError};
+
+// Cheap to clone, compares by value. Structs parsed through an interner hold clones of the interned Arc,
+// e.g. `Trade<Symbol>` from `SymbolInterner::parse`
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    pub fn new(symbol: &str) -> Self {
+        Symbol(Arc::from(symbol))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // True when both symbols point at the same interned allocation
+    pub fn ptr_eq(&self, other: &Symbol) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(symbol: &str) -> Self {
+        Symbol::new(symbol)
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+// Interns through the interner currently parsing on this thread, or allocates when there is none
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor)
+    }
+}
+
+struct SymbolVisitor;
+
+impl Visitor<'_> for SymbolVisitor {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a symbol string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Symbol, E> {
+        Ok(CURRENT.with(|current| match &*current.borrow() {
+            Some(Pool::Local(pool)) => intern_local(pool, value),
+            Some(Pool::Shared(pool)) => intern_shared(pool, value),
+            None => Symbol::new(value),
+        }))
+    }
+}
+
+#[derive(Clone)]
+enum Pool {
+    Local(Rc<RefCell<HashSet<Arc<str>>>>),
+    Shared(Arc<RwLock<HashSet<Arc<str>>>>),
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Pool>> = const { RefCell::new(None) };
+}
+
+// Installs a pool for the duration of one parse and restores the previous one, also on panic
+struct PoolGuard(Option<Pool>);
+
+impl PoolGuard {
+    fn install(pool: Pool) -> Self {
+        PoolGuard(CURRENT.with(|current| current.replace(Some(pool))))
+    }
+}
+
+impl Drop for PoolGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+fn intern_local(pool: &RefCell<HashSet<Arc<str>>>, symbol: &str) -> Symbol {
+    let mut pool = pool.borrow_mut();
+    if let Some(existing) = pool.get(symbol) {
+        return Symbol(existing.clone());
+    }
+    let interned: Arc<str> = Arc::from(symbol);
+    pool.insert(interned.clone());
+    Symbol(interned)
+}
+
+fn intern_shared(pool: &RwLock<HashSet<Arc<str>>>, symbol: &str) -> Symbol {
+    if let Some(existing) = pool.read().unwrap_or_else(PoisonError::into_inner).get(symbol) {
+        return Symbol(existing.clone());
+    }
+    let mut pool = pool.write().unwrap_or_else(PoisonError::into_inner);
+    // Another thread may have inserted it between the read and the write lock
+    if let Some(existing) = pool.get(symbol) {
+        return Symbol(existing.clone());
+    }
+    let interned: Arc<str> = Arc::from(symbol);
+    pool.insert(interned.clone());
+    Symbol(interned)
+}
+
+// Single-threaded interner, clones share the same pool
+#[derive(Clone, Default)]
+pub struct SymbolInterner {
+    pool: Rc<RefCell<HashSet<Arc<str>>>>,
+}
+
+impl SymbolInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&self, symbol: &str) -> Symbol {
+        intern_local(&self.pool, symbol)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pool.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pool.borrow().is_empty()
+    }
+
+    // Every `Symbol` deserialized while parsing is interned in this pool
+    pub fn parse<T: DeserializeOwned>(&self, json: &str) -> Result<T, serde_json::Error> {
+        let _guard = PoolGuard::install(Pool::Local(self.pool.clone()));
+        serde_json::from_str(json)
+    }
+
+    pub fn parse_slice<T: DeserializeOwned>(&self, json: &[u8]) -> Result<T, serde_json::Error> {
+        let _guard = PoolGuard::install(Pool::Local(self.pool.clone()));
+        serde_json::from_slice(json)
+    }
+
+    pub fn parse_codec<T: DeserializeOwned>(&self, json: &str) -> Result<T, CodecError> {
+        parse_with(json, |json| self.parse(json))
+    }
+
+    // The pool is installed once for the whole batch
+    pub fn parse_many<'a, T: DeserializeOwned>(&self, frames: impl IntoIterator<Item = &'a str>) -> ParseBatch<T> {
+        let _guard = PoolGuard::install(Pool::Local(self.pool.clone()));
+        parse_many(frames)
+    }
+}
+
+// Thread-safe interner, clones share the same pool across threads
+#[derive(Clone, Default)]
+pub struct SyncSymbolInterner {
+    pool: Arc<RwLock<HashSet<Arc<str>>>>,
+}
+
+impl SyncSymbolInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&self, symbol: &str) -> Symbol {
+        intern_shared(&self.pool, symbol)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pool.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn parse<T: DeserializeOwned>(&self, json: &str) -> Result<T, serde_json::Error> {
+        let _guard = PoolGuard::install(Pool::Shared(self.pool.clone()));
+        serde_json::from_str(json)
+    }
+
+    pub fn parse_slice<T: DeserializeOwned>(&self, json: &[u8]) -> Result<T, serde_json::Error> {
+        let _guard = PoolGuard::install(Pool::Shared(self.pool.clone()));
+        serde_json::from_slice(json)
+    }
+
+    pub fn parse_codec<T: DeserializeOwned>(&self, json: &str) -> Result<T, CodecError> {
+        parse_with(json, |json| self.parse(json))
+    }
+
+    pub fn parse_many<'a, T: DeserializeOwned>(&self, frames: impl IntoIterator<Item = &'a str>) -> ParseBatch<T> {
+        let _guard = PoolGuard::install(Pool::Shared(self.pool.clone()));
+        parse_many(frames)
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+    use crate::websocket::event::BinanceEvent;
+    use crate::websocket::{BookTicker, Trade};
+
+    fn trade_json(symbol: &str, id: u64) -> String {
+        format!(
+            r#"{{"e":"trade","E":1672515782136,"s":"{symbol}","t":{id},"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}}"#
+        )
+    }
+
+    #[test]
+    fn test_interner_shares_symbol_allocation() {
+        let interner = SymbolInterner::new();
+        let trades: Vec<Trade<Symbol>> =
+            (0..1000).map(|id| interner.parse(&trade_json("ETHUSDT", id)).unwrap()).collect();
+
+        assert_eq!(interner.len(), 1);
+        assert!(trades.iter().all(|trade| trade.symbol.ptr_eq(&trades[0].symbol)));
+        assert_eq!(trades[999].trade_id, 999);
+        assert_eq!(trades[0].symbol(), Some("ETHUSDT"));
+        assert!(interner.intern("ETHUSDT").ptr_eq(&trades[0].symbol));
+    }
+
+    #[test]
+    fn test_interner_adds_new_symbol() {
+        let interner = SymbolInterner::new();
+        let eth: Trade<Symbol> = interner.parse(&trade_json("ETHUSDT", 1)).unwrap();
+        let btc: Trade<Symbol> = interner.parse(&trade_json("BTCUSDT", 2)).unwrap();
+        let eth_again: Trade<Symbol> = interner.parse(&trade_json("ETHUSDT", 3)).unwrap();
+
+        assert_eq!(interner.len(), 2);
+        assert_eq!(&*btc.symbol, "BTCUSDT");
+        assert!(!btc.symbol.ptr_eq(&eth.symbol));
+        assert!(eth_again.symbol.ptr_eq(&eth.symbol));
+
+        let ticker: BookTicker<Symbol> = interner
+            .parse(r#"{"u":400900217,"s":"BTCUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#)
+            .unwrap();
+        assert!(ticker.symbol.ptr_eq(&btc.symbol));
+    }
+
+    #[test]
+    fn test_symbol_without_interner_and_default_string() {
+        let interner = SymbolInterner::new();
+        let interned: Trade<Symbol> = interner.parse(&trade_json("ETHUSDT", 1)).unwrap();
+        // Outside of an interner parse the pool is uninstalled again
+        let plain: Trade<Symbol> = serde_json::from_str(&trade_json("ETHUSDT", 2)).unwrap();
+        assert_eq!(plain.symbol, interned.symbol);
+        assert!(!plain.symbol.ptr_eq(&interned.symbol));
+
+        let owned = Trade::from_json(&trade_json("ETHUSDT", 3)).unwrap();
+        assert_eq!(owned.symbol, "ETHUSDT");
+        assert_eq!(serde_json::to_string(&interned.symbol).unwrap(), r#""ETHUSDT""#);
+    }
+
+    #[test]
+    fn test_interner_parse_many() {
+        let interner = SymbolInterner::new();
+        let frames: Vec<String> = (0..100).map(|id| trade_json(if id % 2 == 0 { "ETHUSDT" } else { "BTCUSDT" }, id)).collect();
+        let batch = interner.parse_many::<Trade<Symbol>>(frames.iter().map(String::as_str).chain(["{}"]));
+
+        assert_eq!(batch.values.len(), 100);
+        assert_eq!(batch.errors.len(), 1);
+        assert_eq!(batch.errors[0].0, 100);
+        assert_eq!(interner.len(), 2);
+        assert!(batch.values[2].symbol.ptr_eq(&batch.values[98].symbol));
+        assert!(batch.values[1].symbol.ptr_eq(&interner.intern("BTCUSDT")));
+    }
+
+    #[test]
+    fn test_sync_interner_across_threads() {
+        let interner = SyncSymbolInterner::new();
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let interner = interner.clone();
+                std::thread::spawn(move || {
+                    (0..250)
+                        .map(|id| interner.parse::<Trade<Symbol>>(&trade_json("ETHUSDT", thread * 250 + id)).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let trades: Vec<Trade<Symbol>> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+
+        assert_eq!(trades.len(), 1000);
+        assert_eq!(interner.len(), 1);
+        let first = interner.intern("ETHUSDT");
+        assert!(trades.iter().all(|trade| trade.symbol.ptr_eq(&first)));
+
+        let err = interner.parse_codec::<Trade<Symbol>>(r#"{"e":"trade","s":1}"#).unwrap_err();
+        assert!(err.is_data());
+        assert_eq!(interner.len(), 1);
+    }
+}
@@ -0,0 +1,296 @@
+//! Captured Binance payloads and builders of wire-accurate messages, for the tests of this crate and of
+//! downstream code.
+
+// A function per captured payload in `fixtures/json`, returned without the trailing newline
+macro_rules! samples {
+    ($($(#[$meta:meta])* $sample:ident => $fixture:literal,)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $sample() -> &'static str {
+                include_str!(concat!("../fixtures/json/", $fixture)).trim_end()
+            }
+        )*
+    };
+}
+
+samples! {
+    sample_trade_json => "spot/trade.json",
+    sample_agg_trade_json => "spot/agg_trade.json",
+    sample_kline_json => "spot/kline.json",
+    sample_book_ticker_json => "spot/book_ticker.json",
+    sample_depth_update_json => "spot/depth_update.json",
+    sample_partial_depth_json => "spot/partial_depth.json",
+    sample_execution_report_json => "spot/execution_report.json",
+    sample_execution_report_stp_json => "spot/execution_report_stp.json",
+    sample_outbound_account_position_json => "spot/outbound_account_position.json",
+    sample_balance_update_json => "spot/balance_update.json",
+    sample_listen_key_expired_json => "spot/listen_key_expired.json",
+}
+
+// WebSocket API responses
+pub mod ws_api {
+    samples! {
+        sample_account_rate_limits_orders_json => "ws_api/account_rate_limits_orders.json",
+        sample_exchange_info_json => "ws_api/exchange_info.json",
+        sample_futures_order_modify_json => "ws_api/futures_order_modify.json",
+    }
+}
+
+// Cross and isolated margin user data
+pub mod margin {
+    samples! {
+        sample_margin_level_status_change_json => "margin/margin_level_status_change.json",
+        sample_liability_change_json => "margin/liability_change.json",
+    }
+}
+
+// Portfolio margin user data
+pub mod portfolio {
+    samples! {
+        sample_risk_level_change_json => "portfolio/risk_level_change.json",
+        sample_open_order_loss_json => "portfolio/open_order_loss.json",
+        sample_conditional_order_trade_update_json => "portfolio/conditional_order_trade_update.json",
+        sample_liability_change_json => "portfolio/liability_change.json",
+    }
+}
+
+// USD-M futures streams and user data
+pub mod futures {
+    samples! {
+        sample_mark_price_update_json => "futures/mark_price_update.json",
+        sample_agg_trade_json => "futures/agg_trade.json",
+        sample_kline_json => "futures/kline.json",
+        sample_continuous_kline_json => "futures/continuous_kline.json",
+        sample_book_ticker_json => "futures/book_ticker.json",
+        sample_depth_update_json => "futures/depth_update.json",
+        sample_force_order_json => "futures/force_order.json",
+        sample_composite_index_json => "futures/composite_index.json",
+        sample_contract_info_json => "futures/contract_info.json",
+        sample_asset_index_json => "futures/asset_index.json",
+        sample_margin_call_json => "futures/margin_call.json",
+        sample_account_config_update_leverage_json => "futures/account_config_update_leverage.json",
+        sample_account_config_update_multi_assets_json => "futures/account_config_update_multi_assets.json",
+        sample_trade_lite_json => "futures/trade_lite.json",
+        sample_strategy_update_json => "futures/strategy_update.json",
+        sample_grid_update_json => "futures/grid_update.json",
+        sample_account_update_json => "futures/account_update.json",
+        sample_order_trade_update_json => "futures/order_trade_update.json",
+        sample_listen_key_expired_json => "futures/listen_key_expired.json",
+    }
+}
+
+// COIN-M futures streams
+pub mod delivery {
+    samples! {
+        sample_index_price_update_json => "delivery/index_price_update.json",
+        sample_mark_price_update_json => "delivery/mark_price_update.json",
+        sample_kline_json => "delivery/kline.json",
+        sample_ticker_json => "delivery/ticker.json",
+    }
+}
+
+// Leveraged token NAV streams
+pub mod blvt {
+    samples! {
+        sample_token_nav_json => "blvt/token_nav.json",
+        sample_nav_kline_json => "blvt/nav_kline.json",
+    }
+}
+
+// European options streams, mark price and open interest are array streams
+pub mod options {
+    samples! {
+        sample_trade_json => "options/trade.json",
+        sample_ticker_json => "options/ticker.json",
+        sample_index_json => "options/index.json",
+        sample_mark_price_json => "options/mark_price.json",
+        sample_kline_json => "options/kline.json",
+        sample_open_interest_json => "options/open_interest.json",
+        sample_option_pair_json => "options/option_pair.json",
+        sample_depth_json => "options/depth.json",
+    }
+}
+
+// REST responses, the futures ones are those of the USD-M `/fapi` endpoints
+pub mod rest {
+    samples! {
+        sample_ping_json => "rest/ping.json",
+        sample_time_json => "rest/time.json",
+        sample_exchange_info_json => "rest/exchange_info.json",
+        sample_depth_snapshot_json => "rest/depth_snapshot.json",
+        sample_klines_json => "rest/klines.json",
+        sample_agg_trades_json => "rest/agg_trades.json",
+        sample_trades_json => "rest/trades.json",
+        sample_ticker_24hr_json => "rest/ticker_24hr.json",
+        sample_ticker_24hr_mini_json => "rest/ticker_24hr_mini.json",
+        sample_ticker_trading_day_json => "rest/ticker_trading_day.json",
+        sample_ticker_trading_day_mini_json => "rest/ticker_trading_day_mini.json",
+        sample_ticker_price_json => "rest/ticker_price.json",
+        sample_ticker_prices_json => "rest/ticker_prices.json",
+        sample_book_ticker_json => "rest/book_ticker.json",
+        sample_avg_price_json => "rest/avg_price.json",
+        sample_account_json => "rest/account.json",
+        sample_order_ack_json => "rest/order_ack.json",
+        sample_order_result_json => "rest/order_result.json",
+        sample_order_full_json => "rest/order_full.json",
+        sample_sor_order_full_json => "rest/sor_order_full.json",
+        sample_test_order_commission_rates_json => "rest/test_order_commission_rates.json",
+        sample_cancel_order_json => "rest/cancel_order.json",
+        sample_order_amend_keep_priority_json => "rest/order_amend_keep_priority.json",
+        sample_cancel_open_orders_json => "rest/cancel_open_orders.json",
+        sample_order_list_json => "rest/order_list.json",
+        sample_my_trades_json => "rest/my_trades.json",
+        sample_my_prevented_matches_json => "rest/my_prevented_matches.json",
+        sample_error_json => "rest/error.json",
+        sample_listen_key_json => "rest/listen_key.json",
+    }
+
+    pub mod futures {
+        samples! {
+            sample_depth_snapshot_json => "rest/futures/depth_snapshot.json",
+            sample_premium_index_json => "rest/futures/premium_index.json",
+            sample_funding_rate_json => "rest/futures/funding_rate.json",
+            sample_open_interest_json => "rest/futures/open_interest.json",
+            sample_open_interest_hist_json => "rest/futures/open_interest_hist.json",
+            sample_position_risk_json => "rest/futures/position_risk.json",
+            sample_account_json => "rest/futures/account.json",
+            sample_income_json => "rest/futures/income.json",
+            sample_book_ticker_json => "rest/futures/book_ticker.json",
+        }
+    }
+}
+
+// `data` wrapped in the envelope of a combined stream connection
+pub fn make_combined(stream: &str, data: &str) -> String {
+    format!(r#"{{"stream":"{}","data":{}}}"#, stream, data)
+}
+
+cfg_spot! {
+    use serde::Serialize;
+
+    use crate::format::{with_price_format, PriceFormat};
+    use crate::websocket::{AggTrade, BookTicker, DepthUpdate, PriceLevel, Trade};
+
+    // Serialized with the fixed 8 decimal prices spot streams send
+    fn to_wire<T: Serialize>(message: &T) -> String {
+        with_price_format(PriceFormat::WIRE, || serde_json::to_string(message).expect("messages serialize"))
+    }
+
+    // Fields a test usually varies, the others take the values of `sample_trade_json`
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct SampleTrade {
+        pub symbol: String,
+        pub trade_id: u64,
+        pub price: f64,
+        pub quantity: f64,
+        pub event_time: u64,
+        pub trade_time: u64,
+        pub is_buyer_market_maker: bool,
+    }
+
+    impl Default for SampleTrade {
+        fn default() -> Self {
+            SampleTrade {
+                symbol: "ETHUSDT".to_string(),
+                trade_id: 2921785139,
+                price: 4532.56,
+                quantity: 0.0132,
+                event_time: 1759680390108723,
+                trade_time: 1759680390108254,
+                is_buyer_market_maker: true,
+            }
+        }
+    }
+
+    impl SampleTrade {
+        pub fn to_json(&self) -> String {
+            to_wire(&Trade {
+                event_type: Some("trade".to_string()),
+                event_time: Some(self.event_time),
+                symbol: self.symbol.clone(),
+                trade_id: self.trade_id,
+                price: self.price,
+                quantity: self.quantity,
+                trade_time: self.trade_time,
+                is_buyer_market_maker: self.is_buyer_market_maker,
+                ignore: true,
+            })
+        }
+    }
+
+    // Event and trade time both at `time`
+    pub fn make_trade(symbol: &str, price: f64, quantity: f64, time: u64) -> String {
+        SampleTrade { symbol: symbol.to_string(), price, quantity, event_time: time, trade_time: time, ..SampleTrade::default() }.to_json()
+    }
+
+    // Trades `first_trade_id..=last_trade_id` aggregated at one price
+    pub fn make_agg_trade(symbol: &str, aggregate_trade_id: u64, first_trade_id: u64, last_trade_id: u64, price: f64, quantity: f64, time: u64) -> String {
+        to_wire(&AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: time,
+            symbol: symbol.to_string(),
+            aggregate_trade_id,
+            price,
+            quantity,
+            first_trade_id,
+            last_trade_id,
+            trade_time: time,
+            is_buyer_market_maker: true,
+            ignore: true,
+        })
+    }
+
+    // Levels as (price, quantity), a zero quantity removes the level
+    pub fn make_depth_update(symbol: &str, first_update_id: u64, final_update_id: u64, bids: &[(f64, f64)], asks: &[(f64, f64)], time: u64) -> String {
+        let levels = |levels: &[(f64, f64)]| levels.iter().map(|&(price, quantity)| PriceLevel::new(price, quantity)).collect::<Vec<_>>();
+        to_wire(&DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: time,
+            symbol: symbol.to_string(),
+            first_update_id,
+            final_update_id,
+            bids: levels(bids),
+            asks: levels(asks),
+        })
+    }
+
+    pub fn make_book_ticker(symbol: &str, update_id: u64, bid: (f64, f64), ask: (f64, f64)) -> String {
+        to_wire(&BookTicker {
+            update_id: Some(update_id),
+            symbol: symbol.to_string(),
+            best_bid_price: bid.0,
+            best_bid_quantity: bid.1,
+            best_ask_price: ask.0,
+            best_ask_quantity: ask.1,
+        })
+    }
+}
+
+// Every sample is parsed with its type and compared with the serialized form in `websocket::golden`
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+    use crate::websocket::SpotEvent;
+
+    #[test]
+    fn test_sample_trade_matches_capture() {
+        assert_eq!(SampleTrade::default().to_json(), sample_trade_json());
+        let trade = Trade::from_json(&make_trade("ETHUSDT", 4532.56, 0.0132, 1759680390108723)).unwrap();
+        assert_eq!(trade, Trade { trade_time: 1759680390108723, ..Trade::from_json(sample_trade_json()).unwrap() });
+    }
+
+    #[test]
+    fn test_builders_parse() {
+        let trade = Trade::from_json(&make_trade("BNBBTC", 0.001, 100.0, 1672515782136)).unwrap();
+        assert_eq!((trade.symbol.as_str(), trade.price, trade.quantity, trade.trade_time), ("BNBBTC", 0.001, 100.0, 1672515782136));
+
+        let json = make_agg_trade("BNBBTC", 12345, 100, 105, 0.001, 100.0, 1672515782136);
+        assert!(json.contains(r#""p":"0.00100000""#));
+        assert!(matches!(SpotEvent::from_json(&json), Ok(SpotEvent::AggTrade(trade)) if trade.last_trade_id == 105));
+
+        let json = make_depth_update("BNBBTC", 157, 160, &[(0.0024, 10.0)], &[(0.0026, 100.0), (0.0027, 0.0)], 1672515782136);
+        assert_eq!(DepthUpdate::from_json(&json).unwrap(), DepthUpdate::from_json(sample_depth_update_json()).unwrap());
+
+        assert_eq!(make_book_ticker("BNBUSDT", 400900217, (25.3519, 31.21), (25.3652, 40.66)), sample_book_ticker_json());
+    }
+}
@@ -0,0 +1,141 @@
+//! Venue independent trades converted from spot and futures trade types.
+
+use crate::orders::Venue;
+use crate::websocket::Side;
+
+cfg_spot! {
+    use crate::rest::{AggTradeRow, TradeRow};
+    use crate::websocket::{AggTrade, Trade};
+}
+cfg_futures! {
+    use crate::websocket::futures;
+}
+
+// Trade IDs and aggregate trade IDs are separate sequences and must not be compared with each other;
+// an aggregate covers the trade IDs from `first` to `last` of the same symbol
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MarketTradeId {
+    Trade(u64),                          // Individual trade, from the trade stream or GET /api/v3/trades
+    Aggregate { id: u64, first: u64, last: u64 }, // Aggregate trade, from aggTrade streams or GET /api/v3/aggTrades
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketTrade {
+    pub venue: Venue,                    // Where the trade happened
+    pub symbol: String,                  // Symbol
+    pub id: MarketTradeId,               // Trade or aggregate trade ID
+    pub price: f64,                      // Price
+    pub quantity: f64,                   // Base asset quantity
+    pub timestamp: u64,                  // Trade time
+    pub aggressor: Side,                 // Taker side, SELL when the buyer was the maker
+}
+
+impl MarketTrade {
+    // REST rows carry no symbol, so it has to be supplied from the request
+    #[cfg(feature = "spot")]
+    pub fn from_trade_row(symbol: &str, row: TradeRow) -> Self {
+        MarketTrade::from(row.into_trade(symbol))
+    }
+
+    #[cfg(feature = "spot")]
+    pub fn from_agg_trade_row(symbol: &str, row: AggTradeRow) -> Self {
+        MarketTrade::from(row.into_agg_trade(symbol))
+    }
+
+    pub fn notional(&self) -> f64 {
+        self.price * self.quantity
+    }
+}
+
+#[cfg(any(feature = "spot", feature = "futures"))]
+fn aggressor(is_buyer_market_maker: bool) -> Side {
+    if is_buyer_market_maker { Side::Sell } else { Side::Buy }
+}
+
+#[cfg(feature = "spot")]
+impl From<Trade> for MarketTrade {
+    fn from(trade: Trade) -> Self {
+        MarketTrade {
+            venue: Venue::Spot,
+            symbol: trade.symbol,
+            id: MarketTradeId::Trade(trade.trade_id),
+            price: trade.price,
+            quantity: trade.quantity,
+            timestamp: trade.trade_time,
+            aggressor: aggressor(trade.is_buyer_market_maker),
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl From<AggTrade> for MarketTrade {
+    fn from(trade: AggTrade) -> Self {
+        MarketTrade {
+            venue: Venue::Spot,
+            symbol: trade.symbol,
+            id: MarketTradeId::Aggregate { id: trade.aggregate_trade_id, first: trade.first_trade_id, last: trade.last_trade_id },
+            price: trade.price,
+            quantity: trade.quantity,
+            timestamp: trade.trade_time,
+            aggressor: aggressor(trade.is_buyer_market_maker),
+        }
+    }
+}
+
+// The quantity includes RPI orders, `normal_quantity` is dropped
+#[cfg(feature = "futures")]
+impl From<futures::AggTrade> for MarketTrade {
+    fn from(trade: futures::AggTrade) -> Self {
+        MarketTrade {
+            venue: Venue::UsdMFutures,
+            symbol: trade.symbol,
+            id: MarketTradeId::Aggregate { id: trade.aggregate_trade_id, first: trade.first_trade_id, last: trade.last_trade_id },
+            price: trade.price,
+            quantity: trade.quantity,
+            timestamp: trade.trade_time,
+            aggressor: aggressor(trade.is_buyer_market_maker),
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "spot", feature = "futures")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "spot")]
+    #[test]
+    fn test_market_trade_from_each_source() {
+        let trade = Trade::from_json(r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#).unwrap();
+        let agg_trade = AggTrade::from_json(r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#).unwrap();
+        let row = TradeRow::from_json_array(r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true}]"#).unwrap().remove(0);
+
+        let spot = MarketTrade::from(trade);
+        assert_eq!(spot, MarketTrade {
+            venue: Venue::Spot,
+            symbol: "BNBBTC".to_string(),
+            id: MarketTradeId::Trade(12345),
+            price: 0.001,
+            quantity: 100.0,
+            timestamp: 1672515782136,
+            aggressor: Side::Sell,
+        });
+        // Same numeric ID, different ID space
+        let aggregate = MarketTrade::from(agg_trade);
+        assert_eq!(aggregate.id, MarketTradeId::Aggregate { id: 12345, first: 100, last: 105 });
+        assert_ne!(aggregate.id, spot.id);
+        assert_eq!((aggregate.price, aggregate.quantity, aggregate.aggressor), (spot.price, spot.quantity, spot.aggressor));
+
+        let rest = MarketTrade::from_trade_row("BNBBTC", row);
+        assert_eq!((rest.id, rest.price, rest.timestamp, rest.aggressor), (MarketTradeId::Trade(28457), 4.000001, 1499865549590, Side::Sell));
+        assert_eq!(rest.notional(), 4.000001 * 12.0);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_market_trade_from_futures_agg_trade() {
+        let futures_trade = futures::AggTrade::from_json(r#"{"e":"aggTrade","E":123456789,"s":"BTCUSDT","a":5933014,"p":"0.001","q":"100","nq":"95","f":100,"l":105,"T":123456785,"m":false}"#).unwrap();
+        let futures = MarketTrade::from(futures_trade);
+        assert_eq!((futures.venue, futures.symbol.as_str(), futures.timestamp, futures.aggressor), (Venue::UsdMFutures, "BTCUSDT", 123456785, Side::Buy));
+        assert_eq!(futures.quantity, 100.0);
+    }
+}
@@ -0,0 +1,102 @@
+//! wasm-bindgen bindings for parsing frames and building requests in the browser or under Node.
+
+use js_sys::JsString;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::error::parse;
+use crate::websocket::frame::{classify, split_combined, FrameKind};
+use crate::websocket::{SubscriptionRequest, Trade};
+
+// Numbers as JS numbers and maps as plain objects, ids and times stay exact below 2^53
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+// Trade with the wire field names, e.g. `{ e: "trade", s: "BNBBTC", p: "0.001", ... }`. The error
+// message carries the event type, offset and a snippet of the frame
+#[wasm_bindgen(js_name = parseTrade)]
+pub fn parse_trade(json: &str) -> Result<JsValue, JsError> {
+    to_js(&parse::<Trade>(json)?)
+}
+
+// SUBSCRIBE request for the streams in their normalized form, fails on unknown stream names
+#[wasm_bindgen(js_name = buildSubscribe)]
+pub fn build_subscribe(streams: Vec<JsString>, id: u32) -> Result<String, JsError> {
+    let mut request = SubscriptionRequest::new(id.into());
+    for stream in streams {
+        request.try_add_stream(&String::from(stream))?;
+    }
+    Ok(request.to_json()?)
+}
+
+#[derive(Serialize)]
+struct Classified<'a> {
+    kind: &'static str,                  // "ack", "response", "error", "event", "combinedEvent" or "unknown"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,                     // Request id of acks, responses and errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<&'a str>,             // Stream name of a combined event
+}
+
+// `{ kind, id?, stream? }` for a frame, without parsing the event itself
+#[wasm_bindgen(js_name = classifyFrame)]
+pub fn classify_frame(frame: &str) -> Result<JsValue, JsError> {
+    let (kind, id) = match classify(frame) {
+        FrameKind::Ack { id } => ("ack", id),
+        FrameKind::Response { id } => ("response", id),
+        FrameKind::Error { id } => ("error", id),
+        FrameKind::Event => ("event", None),
+        FrameKind::CombinedEvent => ("combinedEvent", None),
+        FrameKind::Unknown => ("unknown", None),
+    };
+    let stream = match kind {
+        "combinedEvent" => split_combined(frame).map(|(stream, _)| stream),
+        _ => None,
+    };
+    to_js(&Classified { kind, id, stream })
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::testutil::sample_trade_json;
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        js_sys::Reflect::get(value, &key.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_parse_trade() {
+        let trade = parse_trade(sample_trade_json()).unwrap();
+        assert_eq!(get(&trade, "s").as_string().as_deref(), Some("ETHUSDT"));
+        assert_eq!(get(&trade, "t").as_f64(), Some(2921785139.0));
+        assert_eq!(get(&trade, "m").as_bool(), Some(true));
+
+        assert!(parse_trade(r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":"12345"}"#).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_build_subscribe() {
+        let streams = vec![JsString::from("btcusdt@trade"), JsString::from("bnbbtc@depth@100ms")];
+        assert_eq!(
+            build_subscribe(streams, 1).unwrap(),
+            r#"{"method":"SUBSCRIBE","params":["btcusdt@trade","bnbbtc@depth@100ms"],"id":1}"#
+        );
+        assert!(build_subscribe(vec![JsString::from("btcusdt@nonsense")], 2).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_classify_frame() {
+        let ack = classify_frame(r#"{"result":null,"id":7}"#).unwrap();
+        assert_eq!(get(&ack, "kind").as_string().as_deref(), Some("ack"));
+        assert_eq!(get(&ack, "id").as_f64(), Some(7.0));
+
+        let combined = classify_frame(&format!(r#"{{"stream":"bnbbtc@trade","data":{}}}"#, sample_trade_json())).unwrap();
+        assert_eq!(get(&combined, "kind").as_string().as_deref(), Some("combinedEvent"));
+        assert_eq!(get(&combined, "stream").as_string().as_deref(), Some("bnbbtc@trade"));
+        assert!(get(&combined, "id").is_undefined());
+    }
+}
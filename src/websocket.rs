@@ -1,42 +1,262 @@
-use serde::{de, Deserialize, Deserializer, Serialize};
-use serde::de::Error;
+#[cfg(any(feature = "spot", feature = "futures", feature = "options"))]
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
-fn de_string_to_u64<'de, D>(deserializer: D) -> Result<u64, D::Error>
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::format::PriceFormat;
+
+#[cfg(feature = "spot")]
+pub mod blvt;
+#[cfg(feature = "futures")]
+pub mod delivery;
+mod display;
+pub mod event;
+pub mod frame;
+#[cfg(all(test, any(feature = "spot", feature = "futures", feature = "options")))]
+mod golden;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(feature = "spot")]
+pub mod message;
+#[cfg(feature = "options")]
+pub mod options;
+#[cfg(feature = "spot")]
+pub mod router;
+#[cfg(feature = "spot")]
+pub mod spot;
+pub mod user_data;
+
+// Market types are re-exported at their paths from before the split into per-market modules
+#[cfg(feature = "futures")]
+pub use futures::{AssetIndex, CompositeIndex, CompositeIndexComponent, ContractBracket, ContractInfo, ContractStatus};
+#[cfg(feature = "spot")]
+pub use message::{CombinedEnvelope, ControlResponse, Message};
+#[cfg(feature = "spot")]
+pub use spot::*;
+pub use user_data::*;
+
+pub(crate) fn de_string_to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
-    s.parse::<u64>().map_err(serde::de::Error::custom)
+    s.parse::<f64>().map_err(serde::de::Error::custom)
 }
 
-fn de_string_to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+pub(crate) fn de_option_string_to_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: String = Deserialize::deserialize(deserializer)?;
-    s.parse::<f64>().map_err(serde::de::Error::custom)
+    let s: Option<String> = Deserialize::deserialize(deserializer)?;
+    match s.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => s.parse::<f64>().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+// Numbers are sent as strings, formatted with the thread's `PriceFormat`, see `with_price_format`
+pub(crate) fn ser_f64_as_string<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    PriceFormat::current().display(*value).serialize(serializer)
 }
 
-fn de_from_str<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+pub(crate) fn ser_option_f64_as_string<S>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error>
 where
-    D: de::Deserializer<'de>
+    S: Serializer,
 {
-    let value = serde_json::Value::deserialize(deserializer)?;
     match value {
-        serde_json::Value::String(s) => Ok(Some(s)),
-        serde_json::Value::Bool(b) => Ok(Some(b.to_string())),
-        _ => Err(Error::custom("Failed to deserialize to String or bool")),
+        Some(value) => serializer.serialize_some(&PriceFormat::current().display(*value)),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Ids the options streams send as strings, serialized back the same way
+#[cfg(feature = "options")]
+pub(crate) fn ser_u64_as_string<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+// Empty strings are sent for fields that do not apply, e.g. the commission asset of an unfilled order
+#[cfg(any(feature = "spot", feature = "futures"))]
+pub(crate) fn de_option_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Deserialize::deserialize(deserializer)?;
+    Ok(s.filter(|s| !s.is_empty()))
+}
+
+pub(crate) fn de_string_or_number_to_u64<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrNumberVisitor;
+
+    impl de::Visitor<'_> for StringOrNumberVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an unsigned integer or a numeric string")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse::<u64>().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(StringOrNumberVisitor)
+}
+
+#[cfg(feature = "futures")]
+pub(crate) fn de_string_or_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrBoolVisitor;
+
+    impl de::Visitor<'_> for StringOrBoolVisitor {
+        type Value = bool;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a bool or a \"true\"/\"false\" string")
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse::<bool>().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(StringOrBoolVisitor)
+}
+
+// Numeric string parsed without an intermediate String allocation
+struct StringF64(f64);
+
+impl<'de> Deserialize<'de> for StringF64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StringF64Visitor;
+
+        impl de::Visitor<'_> for StringF64Visitor {
+            type Value = StringF64;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a numeric string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.parse::<f64>().map(StringF64).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(StringF64Visitor)
+    }
+}
+
+// Field that may be a string, bool, number or null, e.g. the result of GET_PROPERTY
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum ScalarValue {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Str(String),
+}
+
+impl ScalarValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, ScalarValue::Null)
+    }
+
+    // Also accepts "true" and "false"
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ScalarValue::Bool(b) => Some(*b),
+            ScalarValue::Str(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ScalarValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // Also parses numeric strings, as Binance sends most decimals quoted
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ScalarValue::U64(n) => Some(*n as f64),
+            ScalarValue::I64(n) => Some(*n as f64),
+            ScalarValue::F64(n) => Some(*n),
+            ScalarValue::Str(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ScalarValue::U64(n) => Some(*n),
+            ScalarValue::Str(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ScalarValue::U64(n) => i64::try_from(*n).ok(),
+            ScalarValue::I64(n) => Some(*n),
+            ScalarValue::Str(s) => s.parse().ok(),
+            _ => None,
+        }
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SubscriptionRequest {
-    #[serde(alias = "method")]
+    #[serde(rename = "method")]
     pub method: String,
-    #[serde(alias = "params")]
+    #[serde(rename = "params")]
     pub params: Vec<String>,    // streams to subscribe to
-    #[serde(alias = "id")]
+    #[serde(rename = "id")]
     pub id: u64,
 }
 
@@ -50,20 +270,40 @@ impl SubscriptionRequest {
     }
 
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(id = self.id, method = %self.method, streams = self.params.len(), "subscription request serialized");
         serde_json::to_string(&self)
     }
 
+    // Same streams under a new id, e.g. to restore the subscriptions on a new connection
+    pub fn resubscribe(&self, id: u64) -> Self {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(previous_id = self.id, id, streams = self.params.len(), "resubscribe request generated");
+        SubscriptionRequest { method: self.method.clone(), params: self.params.clone(), id }
+    }
+
     pub fn add_stream(&mut self, stream: &str) {
         self.params.push(stream.to_string());
     }
+
+    // Adds the stream in its normalized form if it is a known spot stream name
+    pub fn try_add_stream(&mut self, stream: &str) -> Result<(), StreamNameError> {
+        let stream = stream.parse::<StreamName>()?.to_stream()?;
+        self.params.push(stream);
+        Ok(())
+    }
+
+    pub fn builder() -> SubscriptionRequestBuilder {
+        SubscriptionRequestBuilder::new()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SubscriptionResponse {
-    #[serde(alias = "result")]
+    #[serde(rename = "result")]
     pub result: Option<Vec<String>>,
-    #[serde(alias = "id")]
+    #[serde(rename = "id")]
     pub id: u64,
 }
 
@@ -71,37 +311,707 @@ impl SubscriptionResponse {
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
+
+    // True for the `{"result":null,"id":N}` ack of `request`
+    pub fn acknowledges(&self, request: &SubscriptionRequest) -> bool {
+        let matched = self.id == request.id && self.result.is_none();
+        #[cfg(feature = "tracing")]
+        if matched {
+            tracing::trace!(id = self.id, method = %request.method, "subscription ack matched");
+        }
+        matched
+    }
 }
 
+// Response to GET_PROPERTY and SET_PROPERTY, e.g. `{"result":true,"id":5}` for the `combined` property
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-#[serde(rename_all = "kebab-case")]
-pub struct Trade {
-    #[serde(alias = "e")]
-    pub event_type: String,         // Event type
-    #[serde(alias = "E")]
-    pub event_time: u64,            // Event time
-    #[serde(alias = "s")]
-    pub symbol: String,             // Symbol
-    #[serde(alias = "t")]
-    pub trade_id: u64,              // Trade ID
-    #[serde(alias = "p", deserialize_with = "de_string_to_f64")]
-    pub price: f64,                 // Price
-    #[serde(alias = "q", deserialize_with = "de_string_to_f64")]
-    pub quantity: f64,              // Quantity
-    #[serde(alias = "T")]
-    pub trade_time: u64,            // Trade time
-    #[serde(alias = "m")]
-    pub is_buyer_market_maker: bool, // Is the buyer the market maker?
-    #[serde(alias = "M")]
-    pub ignore: bool,               // Ignore
-}
-
-impl Trade {
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PropertyResponse {
+    #[serde(rename = "result")]
+    pub result: ScalarValue,    // Property value, null for SET_PROPERTY
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+impl PropertyResponse {
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
 }
 
+// Spot depth stream update speed; 1000ms is the default and carries no suffix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DepthSpeed {
+    Ms100,
+    Ms1000,
+}
+
+impl DepthSpeed {
+    fn suffix(&self) -> &'static str {
+        match self {
+            DepthSpeed::Ms100 => "@100ms",
+            DepthSpeed::Ms1000 => "",
+        }
+    }
+}
+
+// Window of the rolling window ticker streams
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RollingWindow {
+    H1,
+    H4,
+    D1,
+}
+
+impl RollingWindow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollingWindow::H1 => "1h",
+            RollingWindow::H4 => "4h",
+            RollingWindow::D1 => "1d",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamNameError {
+    InvalidSymbol(String),               // Empty or not ASCII alphanumeric
+    InvalidDepthLevels(u16),             // Partial depth supports 5, 10 and 20 levels
+    EmptyStream,                         // Raw stream name is empty
+    MissingSeparator(String),            // No `@` between symbol and stream kind
+    UnknownStreamKind(String),           // Suffix after the `@` is not a known stream
+    InvalidKlineInterval(String),        // Kline interval is not one of KlineInterval
+    InvalidDepthSpeed(String),           // Depth speed other than 100ms or 1000ms
+    InvalidRollingWindow(String),        // Rolling window other than 1h, 4h or 1d
+    InvalidAllMarketStream(String),      // `!` stream that is not one of the all market arrays
+}
+
+impl fmt::Display for StreamNameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamNameError::InvalidSymbol(symbol) => write!(f, "invalid symbol {:?}", symbol),
+            StreamNameError::InvalidDepthLevels(levels) => write!(f, "invalid depth levels {}, expected 5, 10 or 20", levels),
+            StreamNameError::EmptyStream => write!(f, "empty stream name"),
+            StreamNameError::MissingSeparator(stream) => write!(f, "missing '@' in stream name {:?}", stream),
+            StreamNameError::UnknownStreamKind(kind) => write!(f, "unknown stream kind {:?}", kind),
+            StreamNameError::InvalidKlineInterval(interval) => write!(f, "invalid kline interval {:?}", interval),
+            StreamNameError::InvalidDepthSpeed(speed) => write!(f, "invalid depth speed {:?}, expected 100ms or 1000ms", speed),
+            StreamNameError::InvalidRollingWindow(window) => write!(f, "invalid rolling window {:?}, expected 1h, 4h or 1d", window),
+            StreamNameError::InvalidAllMarketStream(stream) => write!(f, "invalid all market stream {:?}", stream),
+        }
+    }
+}
+
+impl std::error::Error for StreamNameError {}
+
+// Spot market streams; symbols are lowercased when the name is built
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StreamName {
+    Trade(String),                       // <symbol>@trade
+    AggTrade(String),                    // <symbol>@aggTrade
+    Kline(String, KlineInterval),        // <symbol>@kline_<interval>
+    MiniTicker(String),                  // <symbol>@miniTicker
+    Ticker(String),                      // <symbol>@ticker
+    RollingTicker(String, RollingWindow), // <symbol>@ticker_<window>
+    BookTicker(String),                  // <symbol>@bookTicker
+    AvgPrice(String),                    // <symbol>@avgPrice
+    PartialDepth(String, u16, DepthSpeed), // <symbol>@depth<levels>[@100ms]
+    DiffDepth(String, DepthSpeed),       // <symbol>@depth[@100ms]
+    AllMiniTickers,                      // !miniTicker@arr
+    AllTickers,                          // !ticker@arr
+    AllRollingTickers(RollingWindow),    // !ticker_<window>@arr
+    TokenNav(String),                    // <tokenName>@tokenNav
+    NavKline(String, KlineInterval),     // <tokenName>@nav_kline_<interval>
+    Raw(String),                         // Passed through as is
+}
+
+impl StreamName {
+    pub fn to_stream(&self) -> Result<String, StreamNameError> {
+        let stream = match self {
+            StreamName::Trade(symbol) => format!("{}@trade", normalize_symbol(symbol)?),
+            StreamName::AggTrade(symbol) => format!("{}@aggTrade", normalize_symbol(symbol)?),
+            StreamName::Kline(symbol, interval) => format!("{}@kline_{}", normalize_symbol(symbol)?, interval),
+            StreamName::MiniTicker(symbol) => format!("{}@miniTicker", normalize_symbol(symbol)?),
+            StreamName::Ticker(symbol) => format!("{}@ticker", normalize_symbol(symbol)?),
+            StreamName::RollingTicker(symbol, window) => format!("{}@ticker_{}", normalize_symbol(symbol)?, window.as_str()),
+            StreamName::BookTicker(symbol) => format!("{}@bookTicker", normalize_symbol(symbol)?),
+            StreamName::AvgPrice(symbol) => format!("{}@avgPrice", normalize_symbol(symbol)?),
+            StreamName::PartialDepth(symbol, levels, speed) => {
+                if !matches!(levels, 5 | 10 | 20) {
+                    return Err(StreamNameError::InvalidDepthLevels(*levels));
+                }
+                format!("{}@depth{}{}", normalize_symbol(symbol)?, levels, speed.suffix())
+            }
+            StreamName::DiffDepth(symbol, speed) => format!("{}@depth{}", normalize_symbol(symbol)?, speed.suffix()),
+            StreamName::AllMiniTickers => "!miniTicker@arr".to_string(),
+            StreamName::AllTickers => "!ticker@arr".to_string(),
+            StreamName::AllRollingTickers(window) => format!("!ticker_{}@arr", window.as_str()),
+            StreamName::TokenNav(token) => format!("{}@tokenNav", normalize_token(token)?),
+            StreamName::NavKline(token, interval) => format!("{}@nav_kline_{}", normalize_token(token)?, interval),
+            StreamName::Raw(stream) if stream.is_empty() => return Err(StreamNameError::EmptyStream),
+            StreamName::Raw(stream) => stream.clone(),
+        };
+        Ok(stream)
+    }
+}
+
+// Parses `<symbol>@<kind>[@<speed>]` and the `!`-prefixed all market streams, never into Raw
+impl FromStr for StreamName {
+    type Err = StreamNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(StreamNameError::EmptyStream);
+        }
+        if let Some(all_market) = s.strip_prefix('!') {
+            let invalid = || StreamNameError::InvalidAllMarketStream(s.to_string());
+            let kind = all_market.strip_suffix("@arr").ok_or_else(invalid)?;
+            return match kind {
+                "miniTicker" => Ok(StreamName::AllMiniTickers),
+                "ticker" => Ok(StreamName::AllTickers),
+                _ => match kind.strip_prefix("ticker_") {
+                    Some(window) => Ok(StreamName::AllRollingTickers(parse_rolling_window(window)?)),
+                    None => Err(invalid()),
+                },
+            };
+        }
+        let (name, rest) = s.split_once('@').ok_or_else(|| StreamNameError::MissingSeparator(s.to_string()))?;
+        let symbol = normalize_symbol(name)?;
+        let (kind, speed) = match rest.split_once('@') {
+            Some((kind, speed)) => (kind, Some(speed)),
+            None => (rest, None),
+        };
+        let stream = match kind {
+            "trade" => StreamName::Trade(symbol),
+            "aggTrade" => StreamName::AggTrade(symbol),
+            "miniTicker" => StreamName::MiniTicker(symbol),
+            "ticker" => StreamName::Ticker(symbol),
+            "bookTicker" => StreamName::BookTicker(symbol),
+            "avgPrice" => StreamName::AvgPrice(symbol),
+            "tokenNav" => StreamName::TokenNav(normalize_token(name)?),
+            "depth" => return Ok(StreamName::DiffDepth(symbol, parse_depth_speed(speed)?)),
+            _ => {
+                if let Some(interval) = kind.strip_prefix("kline_") {
+                    let interval = interval.parse().map_err(|_| StreamNameError::InvalidKlineInterval(interval.to_string()))?;
+                    StreamName::Kline(symbol, interval)
+                } else if let Some(interval) = kind.strip_prefix("nav_kline_") {
+                    let interval = interval.parse().map_err(|_| StreamNameError::InvalidKlineInterval(interval.to_string()))?;
+                    StreamName::NavKline(normalize_token(name)?, interval)
+                } else if let Some(window) = kind.strip_prefix("ticker_") {
+                    StreamName::RollingTicker(symbol, parse_rolling_window(window)?)
+                } else if let Some(levels) = kind.strip_prefix("depth").and_then(|levels| levels.parse::<u16>().ok()) {
+                    if !matches!(levels, 5 | 10 | 20) {
+                        return Err(StreamNameError::InvalidDepthLevels(levels));
+                    }
+                    return Ok(StreamName::PartialDepth(symbol, levels, parse_depth_speed(speed)?));
+                } else {
+                    return Err(StreamNameError::UnknownStreamKind(kind.to_string()));
+                }
+            }
+        };
+        match speed {
+            Some(_) => Err(StreamNameError::UnknownStreamKind(rest.to_string())),
+            None => Ok(stream),
+        }
+    }
+}
+
+fn parse_depth_speed(speed: Option<&str>) -> Result<DepthSpeed, StreamNameError> {
+    match speed {
+        None | Some("1000ms") => Ok(DepthSpeed::Ms1000),
+        Some("100ms") => Ok(DepthSpeed::Ms100),
+        Some(speed) => Err(StreamNameError::InvalidDepthSpeed(speed.to_string())),
+    }
+}
+
+fn parse_rolling_window(window: &str) -> Result<RollingWindow, StreamNameError> {
+    match window {
+        "1h" => Ok(RollingWindow::H1),
+        "4h" => Ok(RollingWindow::H4),
+        "1d" => Ok(RollingWindow::D1),
+        _ => Err(StreamNameError::InvalidRollingWindow(window.to_string())),
+    }
+}
+
+fn normalize_symbol(symbol: &str) -> Result<String, StreamNameError> {
+    if symbol.is_empty() || !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(StreamNameError::InvalidSymbol(symbol.to_string()));
+    }
+    Ok(symbol.to_ascii_lowercase())
+}
+
+// BLVT streams take the token name in upper case, e.g. BTCDOWN@tokenNav, unlike the symbol streams
+fn normalize_token(token: &str) -> Result<String, StreamNameError> {
+    normalize_symbol(token).map(|token| token.to_ascii_uppercase())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubscriptionRequestBuilder {
+    method: &'static str,
+    id: u64,
+    streams: Vec<StreamName>,
+}
+
+impl Default for SubscriptionRequestBuilder {
+    fn default() -> Self {
+        SubscriptionRequestBuilder { method: "SUBSCRIBE", id: 1, streams: Vec::new() }
+    }
+}
+
+impl SubscriptionRequestBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn unsubscribe(mut self) -> Self {
+        self.method = "UNSUBSCRIBE";
+        self
+    }
+
+    pub fn stream(mut self, stream: StreamName) -> Self {
+        self.streams.push(stream);
+        self
+    }
+
+    pub fn trade(self, symbol: &str) -> Self {
+        self.stream(StreamName::Trade(symbol.to_string()))
+    }
+
+    pub fn agg_trade(self, symbol: &str) -> Self {
+        self.stream(StreamName::AggTrade(symbol.to_string()))
+    }
+
+    pub fn kline(self, symbol: &str, interval: KlineInterval) -> Self {
+        self.stream(StreamName::Kline(symbol.to_string(), interval))
+    }
+
+    pub fn mini_ticker(self, symbol: &str) -> Self {
+        self.stream(StreamName::MiniTicker(symbol.to_string()))
+    }
+
+    pub fn ticker(self, symbol: &str) -> Self {
+        self.stream(StreamName::Ticker(symbol.to_string()))
+    }
+
+    pub fn book_ticker(self, symbol: &str) -> Self {
+        self.stream(StreamName::BookTicker(symbol.to_string()))
+    }
+
+    // Partial book depth with 5, 10 or 20 levels
+    pub fn depth(self, symbol: &str, levels: u16, speed: DepthSpeed) -> Self {
+        self.stream(StreamName::PartialDepth(symbol.to_string(), levels, speed))
+    }
+
+    pub fn diff_depth(self, symbol: &str, speed: DepthSpeed) -> Self {
+        self.stream(StreamName::DiffDepth(symbol.to_string(), speed))
+    }
+
+    // Leveraged token NAV, e.g. for BTCDOWN
+    pub fn token_nav(self, token: &str) -> Self {
+        self.stream(StreamName::TokenNav(token.to_string()))
+    }
+
+    pub fn nav_kline(self, token: &str, interval: KlineInterval) -> Self {
+        self.stream(StreamName::NavKline(token.to_string(), interval))
+    }
+
+    pub fn raw(self, stream: &str) -> Self {
+        self.stream(StreamName::Raw(stream.to_string()))
+    }
+
+    // Fails on the first invalid stream
+    pub fn build(self) -> Result<SubscriptionRequest, StreamNameError> {
+        let params = self.streams.iter().map(StreamName::to_stream).collect::<Result<Vec<_>, _>>()?;
+        Ok(SubscriptionRequest { method: self.method.to_string(), params, id: self.id })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        }
+    }
+}
+
+// Depth diff levels stored inline, for `DepthUpdate<String, InlineLevels>` and
+// `futures::DepthUpdate<InlineLevels>`. Most diffs touch fewer than 16 levels, more spill to the heap.
+// Serializes exactly like a Vec. The default depth updates keep a Vec with or without the feature
+#[cfg(feature = "smallvec")]
+pub const LEVELS_INLINE: usize = 16;
+
+#[cfg(feature = "smallvec")]
+pub type InlineLevels = smallvec::SmallVec<[PriceLevel; LEVELS_INLINE]>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriceLevel {
+    pub price: f64,                      // Price level
+    pub quantity: f64,                   // Quantity
+}
+
+impl PriceLevel {
+    pub fn new(price: f64, quantity: f64) -> Self {
+        PriceLevel { price, quantity }
+    }
+
+    // In diff depth updates a zero quantity removes the level
+    pub fn is_removal(&self) -> bool {
+        self.quantity == 0.0
+    }
+
+    // Highest price first, for `sort_by`
+    pub fn bid_order(a: &PriceLevel, b: &PriceLevel) -> Ordering {
+        b.price.total_cmp(&a.price)
+    }
+
+    // Lowest price first, for `sort_by`
+    pub fn ask_order(a: &PriceLevel, b: &PriceLevel) -> Ordering {
+        a.price.total_cmp(&b.price)
+    }
+
+    pub fn sort_bids(levels: &mut [PriceLevel]) {
+        levels.sort_by(PriceLevel::bid_order);
+    }
+
+    pub fn sort_asks(levels: &mut [PriceLevel]) {
+        levels.sort_by(PriceLevel::ask_order);
+    }
+}
+
+// Sent and received as a `["price", "quantity"]` string pair, serialized in `PriceFormat::current()`
+impl<'de> Deserialize<'de> for PriceLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawPriceLevel(StringF64, StringF64);
+
+        let RawPriceLevel(StringF64(price), StringF64(quantity)) = RawPriceLevel::deserialize(deserializer)?;
+        Ok(PriceLevel { price, quantity })
+    }
+}
+
+impl Serialize for PriceLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let format = PriceFormat::current();
+        (format.display(self.price), format.display(self.quantity)).serialize(serializer)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionType {
+    New,
+    Canceled,
+    Replaced,
+    Rejected,
+    Trade,
+    Expired,
+    Calculated,                          // Futures liquidation execution
+    Amendment,                           // Futures order modified
+    TradePrevention,                     // Spot match prevented by self-trade prevention
+    #[serde(other)]
+    Unknown,
+}
+
+impl ExecutionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionType::New => "NEW",
+            ExecutionType::Canceled => "CANCELED",
+            ExecutionType::Replaced => "REPLACED",
+            ExecutionType::Rejected => "REJECTED",
+            ExecutionType::Trade => "TRADE",
+            ExecutionType::Expired => "EXPIRED",
+            ExecutionType::Calculated => "CALCULATED",
+            ExecutionType::Amendment => "AMENDMENT",
+            ExecutionType::TradePrevention => "TRADE_PREVENTION",
+            ExecutionType::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    PendingCancel,
+    Rejected,
+    Expired,
+    ExpiredInMatch,
+    NewInsurance,                        // Futures liquidation with insurance fund
+    NewAdl,                              // Futures counterparty liquidation
+    #[serde(other)]
+    Unknown,
+}
+
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::New => "NEW",
+            OrderStatus::PartiallyFilled => "PARTIALLY_FILLED",
+            OrderStatus::Filled => "FILLED",
+            OrderStatus::Canceled => "CANCELED",
+            OrderStatus::PendingCancel => "PENDING_CANCEL",
+            OrderStatus::Rejected => "REJECTED",
+            OrderStatus::Expired => "EXPIRED",
+            OrderStatus::ExpiredInMatch => "EXPIRED_IN_MATCH",
+            OrderStatus::NewInsurance => "NEW_INSURANCE",
+            OrderStatus::NewAdl => "NEW_ADL",
+            OrderStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KlineInterval {
+    #[serde(rename = "1s")]
+    S1,
+    #[serde(rename = "1m")]
+    M1,
+    #[serde(rename = "3m")]
+    M3,
+    #[serde(rename = "5m")]
+    M5,
+    #[serde(rename = "15m")]
+    M15,
+    #[serde(rename = "30m")]
+    M30,
+    #[serde(rename = "1h")]
+    H1,
+    #[serde(rename = "2h")]
+    H2,
+    #[serde(rename = "4h")]
+    H4,
+    #[serde(rename = "6h")]
+    H6,
+    #[serde(rename = "8h")]
+    H8,
+    #[serde(rename = "12h")]
+    H12,
+    #[serde(rename = "1d")]
+    D1,
+    #[serde(rename = "3d")]
+    D3,
+    #[serde(rename = "1w")]
+    W1,
+    #[serde(rename = "1M")]
+    Mo1,
+}
+
+impl KlineInterval {
+    pub const ALL: [KlineInterval; 16] = [
+        KlineInterval::S1, KlineInterval::M1, KlineInterval::M3, KlineInterval::M5,
+        KlineInterval::M15, KlineInterval::M30, KlineInterval::H1, KlineInterval::H2,
+        KlineInterval::H4, KlineInterval::H6, KlineInterval::H8, KlineInterval::H12,
+        KlineInterval::D1, KlineInterval::D3, KlineInterval::W1, KlineInterval::Mo1,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KlineInterval::S1 => "1s",
+            KlineInterval::M1 => "1m",
+            KlineInterval::M3 => "3m",
+            KlineInterval::M5 => "5m",
+            KlineInterval::M15 => "15m",
+            KlineInterval::M30 => "30m",
+            KlineInterval::H1 => "1h",
+            KlineInterval::H2 => "2h",
+            KlineInterval::H4 => "4h",
+            KlineInterval::H6 => "6h",
+            KlineInterval::H8 => "8h",
+            KlineInterval::H12 => "12h",
+            KlineInterval::D1 => "1d",
+            KlineInterval::D3 => "3d",
+            KlineInterval::W1 => "1w",
+            KlineInterval::Mo1 => "1M",
+        }
+    }
+
+    // Interval length in milliseconds, None for the calendar month interval
+    pub fn duration_ms(&self) -> Option<u64> {
+        const SECOND: u64 = 1_000;
+        const MINUTE: u64 = 60 * SECOND;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+        match self {
+            KlineInterval::S1 => Some(SECOND),
+            KlineInterval::M1 => Some(MINUTE),
+            KlineInterval::M3 => Some(3 * MINUTE),
+            KlineInterval::M5 => Some(5 * MINUTE),
+            KlineInterval::M15 => Some(15 * MINUTE),
+            KlineInterval::M30 => Some(30 * MINUTE),
+            KlineInterval::H1 => Some(HOUR),
+            KlineInterval::H2 => Some(2 * HOUR),
+            KlineInterval::H4 => Some(4 * HOUR),
+            KlineInterval::H6 => Some(6 * HOUR),
+            KlineInterval::H8 => Some(8 * HOUR),
+            KlineInterval::H12 => Some(12 * HOUR),
+            KlineInterval::D1 => Some(DAY),
+            KlineInterval::D3 => Some(3 * DAY),
+            KlineInterval::W1 => Some(7 * DAY),
+            KlineInterval::Mo1 => None,
+        }
+    }
+}
+
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KlineInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KlineInterval::ALL.iter()
+            .find(|interval| interval.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown kline interval: {s}"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Kline {
+    #[serde(rename = "t", alias = "start-time")]
+    pub start_time: u64,                 // Kline start time
+    #[serde(rename = "T", alias = "close-time")]
+    pub close_time: u64,                 // Kline close time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "i", alias = "interval")]
+    pub interval: KlineInterval,         // Interval
+    #[serde(rename = "f", alias = "first-trade-id")]
+    pub first_trade_id: i64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id")]
+    pub last_trade_id: i64,              // Last trade ID
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(rename = "c", alias = "close-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub close_price: f64,                // Close price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(rename = "v", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Base asset volume
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Number of trades
+    #[serde(rename = "x", alias = "is-closed")]
+    pub is_closed: bool,                 // Is this kline closed?
+    #[serde(rename = "q", alias = "quote-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_asset_volume: f64,         // Quote asset volume
+    #[serde(rename = "V", alias = "taker-buy-base-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_base_asset_volume: f64, // Taker buy base asset volume
+    #[serde(rename = "Q", alias = "taker-buy-quote-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_quote_asset_volume: f64, // Taker buy quote asset volume
+    #[serde(rename = "B", alias = "ignore")]
+    pub ignore: String,                  // Ignore
+}
+
+impl Kline {
+    // The final update of the interval. A kline past its close time is not final until Binance sends it
+    // with `x` set, trades of the interval can still be counted in after the boundary
+    pub fn is_final(&self) -> bool {
+        self.is_closed
+    }
+}
+
+#[cfg(any(feature = "spot", feature = "futures", feature = "options"))]
+#[derive(Deserialize)]
+struct EventTypeTag<'a> {
+    #[serde(rename = "e", borrow, default)]
+    event_type: Option<Cow<'a, str>>,
+}
+
+#[cfg(any(feature = "spot", feature = "futures", feature = "options"))]
+impl<'a> EventTypeTag<'a> {
+    fn from_json(json: &'a str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Every element of an array frame has the same event type. The first element is buffered to read it and
+// the rest deserialize straight into the type picked for it, so the frame is parsed once
+#[cfg(any(feature = "futures", feature = "options"))]
+trait ArrayEvent: Sized {
+    fn from_elements<'de, A: de::SeqAccess<'de>>(event_type: &str, first: serde_json::Value, rest: &mut A) -> Result<Option<Self>, A::Error>;
+}
+
+// None for an empty array or an event type `E` does not know
+#[cfg(any(feature = "futures", feature = "options"))]
+fn array_event_from_json<E: ArrayEvent>(json: &str) -> Result<Option<E>, serde_json::Error> {
+    struct ArrayVisitor<E>(std::marker::PhantomData<E>);
+
+    impl<'de, E: ArrayEvent> de::Visitor<'de> for ArrayVisitor<E> {
+        type Value = Option<E>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of events")
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Option<E>, A::Error> {
+            let Some(first) = seq.next_element::<serde_json::Value>()? else {
+                return Ok(None);
+            };
+            let event = match first.get("e").and_then(serde_json::Value::as_str).map(str::to_owned) {
+                Some(event_type) => E::from_elements(&event_type, first, &mut seq)?,
+                None => None,
+            };
+            while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+            Ok(event)
+        }
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let event = deserializer.deserialize_seq(ArrayVisitor(std::marker::PhantomData))?;
+    deserializer.end()?;
+    Ok(event)
+}
+
+#[cfg(any(feature = "futures", feature = "options"))]
+fn collect_elements<'de, T: de::DeserializeOwned, A: de::SeqAccess<'de>>(first: serde_json::Value, rest: &mut A) -> Result<Vec<T>, A::Error> {
+    let mut elements = vec![serde_json::from_value(first).map_err(de::Error::custom)?];
+    while let Some(element) = rest.next_element()? {
+        elements.push(element);
+    }
+    Ok(elements)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +1038,56 @@ mod tests {
         assert_eq!(request.params[1], "ethusdt@depth");
     }
 
+    #[test]
+    fn test_subscription_request_try_add_stream() {
+        let valid = [
+            ("btcusdt@trade", "btcusdt@trade"),
+            ("BTCUSDT@aggTrade", "btcusdt@aggTrade"),
+            ("btcusdt@kline_1m", "btcusdt@kline_1m"),
+            ("btcusdt@kline_1M", "btcusdt@kline_1M"),
+            ("btcusdt@ticker_4h", "btcusdt@ticker_4h"),
+            ("btcusdt@avgPrice", "btcusdt@avgPrice"),
+            ("btcusdt@depth", "btcusdt@depth"),
+            ("btcusdt@depth@100ms", "btcusdt@depth@100ms"),
+            ("btcusdt@depth20@1000ms", "btcusdt@depth20"),
+            ("!miniTicker@arr", "!miniTicker@arr"),
+            ("!ticker_1d@arr", "!ticker_1d@arr"),
+            ("btcdown@tokenNav", "BTCDOWN@tokenNav"),
+            ("BTCDOWN@nav_kline_5m", "BTCDOWN@nav_kline_5m"),
+        ];
+        let mut request = SubscriptionRequest::new(1);
+        for (stream, normalized) in valid {
+            assert_eq!(request.try_add_stream(stream), Ok(()), "{stream}");
+            assert_eq!(request.params.last().unwrap(), normalized);
+        }
+
+        let invalid = [
+            ("", StreamNameError::EmptyStream),
+            ("btcusdt", StreamNameError::MissingSeparator("btcusdt".to_string())),
+            ("btc_usdt@trade", StreamNameError::InvalidSymbol("btc_usdt".to_string())),
+            ("btcusdt@kline_60m", StreamNameError::InvalidKlineInterval("60m".to_string())),
+            ("btcdown@nav_kline_2m", StreamNameError::InvalidKlineInterval("2m".to_string())),
+            ("btcusdt@depth15", StreamNameError::InvalidDepthLevels(15)),
+            ("btcusdt@depth5@250ms", StreamNameError::InvalidDepthSpeed("250ms".to_string())),
+            ("btcusdt@ticker_2h", StreamNameError::InvalidRollingWindow("2h".to_string())),
+            ("btcusdt@trades", StreamNameError::UnknownStreamKind("trades".to_string())),
+            ("btcusdt@trade@100ms", StreamNameError::UnknownStreamKind("trade@100ms".to_string())),
+            ("!ticker", StreamNameError::InvalidAllMarketStream("!ticker".to_string())),
+            ("!bookTicker@arr", StreamNameError::InvalidAllMarketStream("!bookTicker@arr".to_string())),
+            ("btcusdt@forceOrder", StreamNameError::UnknownStreamKind("forceOrder".to_string())),
+            ("!forceOrder@arr", StreamNameError::InvalidAllMarketStream("!forceOrder@arr".to_string())),
+            ("!ticker_3d@arr", StreamNameError::InvalidRollingWindow("3d".to_string())),
+        ];
+        for (stream, error) in invalid {
+            assert_eq!(request.try_add_stream(stream), Err(error), "{stream}");
+        }
+        assert_eq!(request.params.len(), valid.len());
+
+        // Unchecked streams still go through add_stream
+        request.add_stream("btcusdt@kline_60m");
+        assert_eq!(request.params.last().unwrap(), "btcusdt@kline_60m");
+    }
+
     #[test]
     fn test_subscription_request_serialization() {
         let mut request = SubscriptionRequest::new(789);
@@ -140,6 +1100,65 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    fn test_subscription_request_builder() {
+        let request = SubscriptionRequest::builder()
+            .trade("BTCUSDT")
+            .kline("ethusdt", KlineInterval::M1)
+            .depth("btcusdt", 20, DepthSpeed::Ms100)
+            .diff_depth("bnbbtc", DepthSpeed::Ms1000)
+            .raw("custom@stream")
+            .id(42)
+            .build()
+            .unwrap();
+        let expected = r#"{"method":"SUBSCRIBE","params":["btcusdt@trade","ethusdt@kline_1m","btcusdt@depth20@100ms","bnbbtc@depth","custom@stream"],"id":42}"#;
+        assert_eq!(request.to_json().unwrap(), expected);
+
+        let request = SubscriptionRequest::builder().unsubscribe().book_ticker("BNBUSDT").build().unwrap();
+        assert_eq!((request.method.as_str(), request.params, request.id), ("UNSUBSCRIBE", vec!["bnbusdt@bookTicker".to_string()], 1));
+    }
+
+    #[test]
+    fn test_subscription_request_builder_errors() {
+        let error = SubscriptionRequest::builder().trade("btcusdt").agg_trade("btc-usdt").build().unwrap_err();
+        assert_eq!(error, StreamNameError::InvalidSymbol("btc-usdt".to_string()));
+        assert_eq!(error.to_string(), r#"invalid symbol "btc-usdt""#);
+        assert_eq!(SubscriptionRequest::builder().depth("btcusdt", 15, DepthSpeed::Ms100).build(), Err(StreamNameError::InvalidDepthLevels(15)));
+        assert_eq!(SubscriptionRequest::builder().raw("").build(), Err(StreamNameError::EmptyStream));
+    }
+
+    #[test]
+    fn test_scalar_value_variants() {
+        let values: Vec<ScalarValue> = serde_json::from_str(r#"[null, true, 42, -7, 1.5, "0.00100000", "false"]"#).unwrap();
+        assert_eq!(values, vec![
+            ScalarValue::Null,
+            ScalarValue::Bool(true),
+            ScalarValue::U64(42),
+            ScalarValue::I64(-7),
+            ScalarValue::F64(1.5),
+            ScalarValue::Str("0.00100000".to_string()),
+            ScalarValue::Str("false".to_string()),
+        ]);
+        assert!(values[0].is_null());
+        assert_eq!(values[1].as_bool(), Some(true));
+        assert_eq!(values[6].as_bool(), Some(false));
+        assert_eq!(values[2].as_bool(), None);
+        assert_eq!((values[2].as_u64(), values[2].as_i64(), values[2].as_f64()), (Some(42), Some(42), Some(42.0)));
+        assert_eq!((values[3].as_u64(), values[3].as_i64()), (None, Some(-7)));
+        assert_eq!(values[5].as_f64(), Some(0.001));
+        assert_eq!(values[5].as_str(), Some("0.00100000"));
+        assert_eq!(values[4].as_str(), None);
+        assert_eq!(serde_json::to_string(&values).unwrap(), r#"[null,true,42,-7,1.5,"0.00100000","false"]"#);
+    }
+
+    #[test]
+    fn test_property_response_deserialization() {
+        let response = PropertyResponse::from_json(r#"{"result":true,"id":5}"#).unwrap();
+        assert_eq!(response, PropertyResponse { result: ScalarValue::Bool(true), id: 5 });
+        let response = PropertyResponse::from_json(r#"{"result":null,"id":6}"#).unwrap();
+        assert!(response.result.is_null());
+    }
+
     #[test]
     fn test_subscription_request_deserialization() {
         let json = r#"{"method":"SUBSCRIBE","params":["btcusdt@ticker"],"id":100}"#;
@@ -172,6 +1191,21 @@ mod tests {
         assert_eq!(response.id, 300);
     }
 
+    #[test]
+    fn test_resubscribe_and_ack_matching() {
+        let request = SubscriptionRequest::builder().id(7).trade("btcusdt").book_ticker("ethusdt").build().unwrap();
+        let resubscribe = request.resubscribe(8);
+        assert_eq!(resubscribe.id, 8);
+        assert_eq!(resubscribe.method, "SUBSCRIBE");
+        assert_eq!(resubscribe.params[..], request.params[..]);
+
+        let ack = SubscriptionResponse::from_json(r#"{"result":null,"id":8}"#).unwrap();
+        assert!(ack.acknowledges(&resubscribe));
+        assert!(!ack.acknowledges(&request));
+        let listing = SubscriptionResponse::from_json(r#"{"result":["btcusdt@trade"],"id":8}"#).unwrap();
+        assert!(!listing.acknowledges(&resubscribe));
+    }
+
     #[test]
     fn test_subscription_response_with_null_method() {
         let response = SubscriptionResponse {
@@ -206,21 +1240,102 @@ mod tests {
         assert_eq!(response.id, 700);
     }
 
+    // Types moved into the per-market modules keep their pre-split paths
+    #[cfg(feature = "spot")]
     #[test]
-    fn test_trade_serialization() {
-        let expected = Trade {
-            event_type: "trade".to_string(),
-            event_time: 1759680390108723,
-            symbol: "ETHUSDT".to_string(),
-            trade_id: 2921785139,
-            price: 4532.56,
-            quantity: 0.0132,
-            trade_time: 1759680390108254,
-            is_buyer_market_maker: true,
-            ignore: true,
-        };
-        let json = r#"{"e":"trade","E":1759680390108723,"s":"ETHUSDT","t":2921785139,"p":"4532.56000000","q":"0.01320000","T":1759680390108254,"m":true,"M":true}"#;
-        let trade: Trade = serde_json::from_str(json).unwrap();
-        assert_eq!(expected, trade);
+    fn test_spot_reexports_at_old_paths() {
+        let _: fn(&str) -> Result<spot::Trade, serde_json::Error> = crate::websocket::Trade::from_json;
+        let _: fn(&str) -> Result<spot::PartialDepth, serde_json::Error> = crate::websocket::PartialDepth::from_json;
+        let _: fn(&str) -> Result<user_data::UserDataEvent, serde_json::Error> = crate::websocket::UserDataEvent::from_json;
+        let _: fn(&str) -> Result<user_data::ExecutionReport, serde_json::Error> = crate::websocket::ExecutionReport::from_json;
+
+        let json = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        assert_eq!(crate::websocket::BookTicker::from_json(json).unwrap(), spot::BookTicker::from_json(json).unwrap());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_futures_reexports_at_old_paths() {
+        let _: fn(&str) -> Result<futures::CompositeIndex, serde_json::Error> = crate::websocket::CompositeIndex::from_json;
+        let _: fn(&str) -> Result<futures::ContractInfo, serde_json::Error> = crate::websocket::ContractInfo::from_json;
+        let _: fn(&str) -> Result<user_data::OrderTradeUpdate, serde_json::Error> = crate::websocket::OrderTradeUpdate::from_json;
+        let _: fn(&str) -> Result<user_data::FuturesUserDataEvent, serde_json::Error> = crate::websocket::FuturesUserDataEvent::from_json;
+        let _: user_data::PositionSide = crate::websocket::PositionSide::Both;
+
+        let json = r#"{"e":"listenKeyExpired","E":1699596037418,"listenKey":"key"}"#;
+        assert_eq!(crate::websocket::ListenKeyExpired::from_json(json).unwrap(), user_data::ListenKeyExpired::from_json(json).unwrap());
+    }
+
+    #[test]
+    fn test_kline_interval_conversions() {
+        for interval in KlineInterval::ALL {
+            assert_eq!(interval.as_str().parse::<KlineInterval>(), Ok(interval));
+            assert_eq!(serde_json::to_string(&interval).unwrap(), format!("\"{interval}\""));
+        }
+        assert_eq!(KlineInterval::M15.duration_ms(), Some(900_000));
+        assert_eq!(KlineInterval::Mo1.duration_ms(), None);
+        assert!("60m".parse::<KlineInterval>().is_err());
+    }
+
+    #[test]
+    fn test_price_level_round_trip_and_removal() {
+        let levels: Vec<PriceLevel> = serde_json::from_str(r#"[["0.00260000","100.00000000"],["25.35190000","0.00000000"]]"#).unwrap();
+        assert_eq!(levels, vec![PriceLevel::new(0.0026, 100.0), PriceLevel::new(25.3519, 0.0)]);
+        assert!(!levels[0].is_removal());
+        assert!(levels[1].is_removal());
+
+        let json = serde_json::to_string(&levels).unwrap();
+        assert_eq!(json, r#"[["0.0026","100"],["25.3519","0"]]"#);
+        assert_eq!(serde_json::from_str::<Vec<PriceLevel>>(&json).unwrap(), levels);
+
+        let mut levels = vec![PriceLevel::new(1.0, 1.0), PriceLevel::new(3.0, 1.0), PriceLevel::new(2.0, 1.0)];
+        PriceLevel::sort_bids(&mut levels);
+        assert_eq!(levels.iter().map(|level| level.price).collect::<Vec<_>>(), vec![3.0, 2.0, 1.0]);
+        PriceLevel::sort_asks(&mut levels);
+        assert_eq!(levels.iter().map(|level| level.price).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0]);
     }
-}
\ No newline at end of file
+
+    #[cfg(any(feature = "spot", feature = "futures"))]
+    #[test]
+    fn test_optional_helpers_map_empty_string_to_none() {
+        #[derive(Deserialize, Debug)]
+        struct Optionals {
+            #[serde(default, deserialize_with = "de_option_string_to_f64")]
+            number: Option<f64>,
+            #[serde(default, deserialize_with = "de_option_string")]
+            string: Option<String>,
+        }
+
+        let parse = |json: &str| serde_json::from_str::<Optionals>(json).map(|o| (o.number, o.string));
+        assert_eq!(parse(r#"{"number":"","string":""}"#).unwrap(), (None, None));
+        assert_eq!(parse(r#"{"number":null,"string":null}"#).unwrap(), (None, None));
+        assert_eq!(parse("{}").unwrap(), (None, None));
+        assert_eq!(parse(r#"{"number":"0.5","string":"BNB"}"#).unwrap(), (Some(0.5), Some("BNB".to_string())));
+        assert_eq!(parse(r#"{"number":"0","string":" "}"#).unwrap(), (Some(0.0), Some(" ".to_string())));
+        assert!(parse(r#"{"number":"abc"}"#).is_err());
+        assert!(parse(r#"{"number":" "}"#).is_err());
+    }
+
+    #[test]
+    fn test_numeric_strings_accept_scientific_notation() {
+        #[derive(Deserialize)]
+        struct Numbers {
+            #[serde(deserialize_with = "de_string_to_f64")]
+            value: f64,
+            #[serde(default, deserialize_with = "de_option_string_to_f64")]
+            optional: Option<f64>,
+        }
+
+        let numbers: Numbers = serde_json::from_str(r#"{"value":"1e-8","optional":"-2.5E3"}"#).unwrap();
+        assert_eq!(numbers.value, 0.00000001);
+        assert_eq!(numbers.optional, Some(-2500.0));
+
+        let levels: Vec<PriceLevel> = serde_json::from_str(r#"[["1.2e-7","2.5E+3"],["4532.56E0","1E-8"]]"#).unwrap();
+        assert_eq!(levels, vec![PriceLevel::new(0.00000012, 2500.0), PriceLevel::new(4532.56, 0.00000001)]);
+
+        // Parsed exponents are the same f64 as the plain decimal, so fixed decimals print them exactly
+        let json = crate::format::with_price_format(PriceFormat::WIRE, || serde_json::to_string(&levels)).unwrap();
+        assert_eq!(json, r#"[["0.00000012","2500.00000000"],["4532.56000000","0.00000001"]]"#);
+        assert_eq!(PriceFormat::WIRE.display("1e-8".parse().unwrap()).to_string(), "0.00000001");
+    }
+}
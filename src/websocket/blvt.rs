@@ -0,0 +1,176 @@
+//! Binance leveraged token (BLVT) streams: NAV info and NAV klines. Token names are the leveraged
+//! tokens, e.g. BTCDOWN, not spot symbols.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use super::{de_string_to_f64, ser_f64_as_string, EventTypeTag, KlineInterval};
+
+// One futures position of the token basket
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BasketEntry {
+    #[serde(rename = "s", alias = "futures-symbol")]
+    pub futures_symbol: String,          // Futures symbol
+    #[serde(rename = "n", alias = "position")]
+    pub position: f64,                   // Position, negative for short
+}
+
+// <tokenName>@tokenNav, numbers are sent as JSON numbers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TokenNav {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "token-name")]
+    pub token_name: String,              // Token name
+    #[serde(rename = "m", alias = "token-issued")]
+    pub token_issued: f64,               // Tokens issued
+    #[serde(rename = "b", alias = "baskets")]
+    pub baskets: Vec<BasketEntry>,       // Basket composition
+    #[serde(rename = "n", alias = "nav")]
+    pub nav: f64,                        // NAV
+    #[serde(rename = "l", alias = "real-leverage")]
+    pub real_leverage: f64,              // Real leverage
+    #[serde(rename = "t", alias = "target-leverage")]
+    pub target_leverage: f64,            // Target leverage
+    #[serde(rename = "f", alias = "funding-ratio")]
+    pub funding_ratio: f64,              // Funding ratio
+}
+
+impl TokenNav {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Kline of the NAV. The spot kline fields are reused: `v` is the real leverage, `n` the number of NAV
+// updates and the volume fields are sent as zero
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NavKline {
+    #[serde(rename = "t", alias = "start-time")]
+    pub start_time: u64,                 // Kline start time
+    #[serde(rename = "T", alias = "close-time")]
+    pub close_time: u64,                 // Kline close time
+    #[serde(rename = "s", alias = "token-name")]
+    pub token_name: String,              // Token name
+    #[serde(rename = "i", alias = "interval")]
+    pub interval: KlineInterval,         // Interval
+    #[serde(rename = "f", alias = "first-update-time")]
+    pub first_update_time: u64,          // First NAV update time
+    #[serde(rename = "L", alias = "last-update-time")]
+    pub last_update_time: u64,           // Last NAV update time
+    #[serde(rename = "o", alias = "open-nav", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_nav: f64,                   // Open NAV
+    #[serde(rename = "c", alias = "close-nav", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub close_nav: f64,                  // Close NAV
+    #[serde(rename = "h", alias = "high-nav", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_nav: f64,                   // Highest NAV
+    #[serde(rename = "l", alias = "low-nav", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_nav: f64,                    // Lowest NAV
+    #[serde(rename = "v", alias = "real-leverage", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub real_leverage: f64,              // Real leverage
+    #[serde(rename = "n", alias = "number-of-updates")]
+    pub number_of_updates: u64,          // Number of NAV updates
+    #[serde(rename = "x", alias = "is-closed")]
+    pub is_closed: bool,                 // Is this kline closed?
+    #[serde(rename = "q", alias = "ignore-q")]
+    pub ignore_q: String,                // Ignore
+    #[serde(rename = "V", alias = "ignore-v")]
+    pub ignore_v: String,                // Ignore
+    #[serde(rename = "Q", alias = "ignore-big-q")]
+    pub ignore_big_q: String,            // Ignore
+    #[serde(rename = "B", alias = "ignore")]
+    pub ignore: String,                  // Ignore
+}
+
+// <tokenName>@nav_kline_<interval>
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NavKlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "token-name")]
+    pub token_name: String,              // Token name
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: NavKline,                 // NAV kline
+}
+
+impl NavKlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// NAV klines carry the spot `kline` event type, BLVT streams have to be parsed with this enum rather
+// than `SpotEvent`
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlvtEvent {
+    TokenNav(TokenNav),
+    NavKline(NavKlineEvent),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl BlvtEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, BlvtEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, BlvtEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("nav") => TokenNav::from_json(json).map(BlvtEvent::TokenNav),
+            Some("kline") => NavKlineEvent::from_json(json).map(BlvtEvent::NavKline),
+            _ => Ok(BlvtEvent::Unknown(json.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::blvt;
+    use crate::websocket::SubscriptionRequest;
+
+    #[test]
+    fn test_token_nav() {
+        let event = BlvtEvent::from_json(blvt::sample_token_nav_json()).unwrap();
+        let BlvtEvent::TokenNav(nav) = event else { panic!("expected a token NAV, got {event:?}") };
+        assert_eq!(nav.token_name, "BTCDOWN");
+        assert_eq!(nav.token_issued, 4750.16277646);
+        assert_eq!(nav.baskets, vec![
+            BasketEntry { futures_symbol: "BTCUSDT".to_string(), position: -9.67498043 },
+            BasketEntry { futures_symbol: "BTCUSD_PERP".to_string(), position: -0.50276319 },
+        ]);
+        assert_eq!((nav.nav, nav.real_leverage, nav.target_leverage), (14.6500129, 2.17028553, 2.0));
+        assert_eq!(nav.funding_ratio, 0.0000315);
+    }
+
+    #[test]
+    fn test_nav_kline() {
+        let event = BlvtEvent::parse(blvt::sample_nav_kline_json()).unwrap();
+        let BlvtEvent::NavKline(event) = event else { panic!("expected a NAV kline, got {event:?}") };
+        let kline = event.kline;
+        assert_eq!((kline.token_name.as_str(), kline.interval), ("BTCDOWN", KlineInterval::M1));
+        assert_eq!((kline.open_nav, kline.high_nav, kline.low_nav, kline.close_nav), (14.65977277, 14.66073432, 14.65944378, 14.66073432));
+        assert_eq!((kline.real_leverage, kline.number_of_updates, kline.is_closed), (2.17028553, 59, false));
+
+        let request = SubscriptionRequest::builder().token_nav("BTCDOWN").nav_kline("BTCDOWN", KlineInterval::M1).build().unwrap();
+        assert_eq!(&request.params[..], &["BTCDOWN@tokenNav".to_string(), "BTCDOWN@nav_kline_1m".to_string()][..]);
+    }
+}
@@ -0,0 +1,284 @@
+//! COIN-M (delivery) futures streams.
+//!
+//! COIN-M symbols are pair-based (`BTCUSD_PERP`, `BTCUSD_250627`) and volumes are denominated in
+//! contracts, with the base asset volume reported separately. USD-M payloads are served by the
+//! structs in [`crate::websocket::futures`].
+
+use serde::{Deserialize, Serialize};
+
+use super::{de_option_string_to_f64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, KlineInterval};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IndexPriceUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "i", alias = "pair")]
+    pub pair: String,                    // Pair
+    #[serde(rename = "p", alias = "index-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+}
+
+impl IndexPriceUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarkPriceUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "ps", alias = "pair", default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,            // Pair
+    #[serde(rename = "p", alias = "mark-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Mark price
+    #[serde(rename = "P", alias = "estimated-settle-price", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub estimated_settle_price: Option<f64>, // Estimated settle price
+    #[serde(rename = "i", alias = "index-price", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub index_price: Option<f64>,        // Index price
+    #[serde(rename = "r", alias = "funding-rate", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub funding_rate: Option<f64>,       // Funding rate (empty for delivery contracts)
+    #[serde(rename = "T", alias = "next-funding-time")]
+    pub next_funding_time: u64,          // Next funding time (0 for delivery contracts)
+}
+
+impl MarkPriceUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Kline {
+    #[serde(rename = "t", alias = "start-time")]
+    pub start_time: u64,                 // Kline start time
+    #[serde(rename = "T", alias = "close-time")]
+    pub close_time: u64,                 // Kline close time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "i", alias = "interval")]
+    pub interval: KlineInterval,         // Interval
+    #[serde(rename = "f", alias = "first-trade-id")]
+    pub first_trade_id: i64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id")]
+    pub last_trade_id: i64,              // Last trade ID
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(rename = "c", alias = "close-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub close_price: f64,                // Close price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(rename = "v", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Volume (in contracts)
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Number of trades
+    #[serde(rename = "x", alias = "is-closed")]
+    pub is_closed: bool,                 // Is this kline closed?
+    #[serde(rename = "q", alias = "base-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub base_asset_volume: f64,          // Base asset volume
+    #[serde(rename = "V", alias = "taker-buy-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_volume: f64,           // Taker buy volume (in contracts)
+    #[serde(rename = "Q", alias = "taker-buy-base-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_base_asset_volume: f64, // Taker buy base asset volume
+    #[serde(rename = "B", alias = "ignore")]
+    pub ignore: String,                  // Ignore
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol", default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,          // Symbol (kline streams)
+    #[serde(rename = "ps", alias = "pair", default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,            // Pair (continuous kline streams)
+    #[serde(rename = "ct", alias = "contract-type", default, skip_serializing_if = "Option::is_none")]
+    pub contract_type: Option<String>,   // Contract type (continuous kline streams)
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: Kline,                    // Kline
+}
+
+impl KlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Ticker {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "ps", alias = "pair")]
+    pub pair: String,                    // Pair
+    #[serde(rename = "p", alias = "price-change", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change: f64,               // Price change
+    #[serde(rename = "P", alias = "price-change-percent", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change_percent: f64,       // Price change percent
+    #[serde(rename = "w", alias = "weighted-average-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub weighted_average_price: f64,     // Weighted average price
+    #[serde(rename = "c", alias = "last-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_price: f64,                 // Last price
+    #[serde(rename = "Q", alias = "last-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_quantity: f64,              // Last quantity
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(rename = "v", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Total traded volume (in contracts)
+    #[serde(rename = "q", alias = "base-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub base_asset_volume: f64,          // Total traded base asset volume
+    #[serde(rename = "O", alias = "open-time")]
+    pub open_time: u64,                  // Statistics open time
+    #[serde(rename = "C", alias = "close-time")]
+    pub close_time: u64,                 // Statistics close time
+    #[serde(rename = "F", alias = "first-trade-id")]
+    pub first_trade_id: i64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id")]
+    pub last_trade_id: i64,              // Last trade ID
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Total number of trades
+}
+
+impl Ticker {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_price_update_deserialization() {
+        let json = r#"{"e":"indexPriceUpdate","E":1591261236000,"i":"BTCUSD","p":"9636.57860000"}"#;
+        let expected = IndexPriceUpdate {
+            event_type: "indexPriceUpdate".to_string(),
+            event_time: 1591261236000,
+            pair: "BTCUSD".to_string(),
+            index_price: 9636.5786,
+        };
+        let update = IndexPriceUpdate::from_json(json).unwrap();
+
+        assert_eq!(update, expected);
+        assert!(!update.pair.contains('_'));
+    }
+
+    #[test]
+    fn test_mark_price_update_perpetual_deserialization() {
+        let json = r#"{"e":"markPriceUpdate","E":1596095725000,"s":"BTCUSD_PERP","ps":"BTCUSD","p":"10934.62615417","P":"10962.17178236","i":"10933.62615417","r":"0.00010000","T":1596124800000}"#;
+        let expected = MarkPriceUpdate {
+            event_type: "markPriceUpdate".to_string(),
+            event_time: 1596095725000,
+            symbol: "BTCUSD_PERP".to_string(),
+            pair: Some("BTCUSD".to_string()),
+            mark_price: 10934.62615417,
+            estimated_settle_price: Some(10962.17178236),
+            index_price: Some(10933.62615417),
+            funding_rate: Some(0.0001),
+            next_funding_time: 1596124800000,
+        };
+        assert_eq!(MarkPriceUpdate::from_json(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_mark_price_update_delivery_contract_deserialization() {
+        let json = r#"{"e":"markPriceUpdate","E":1596095725000,"s":"BTCUSD_201225","p":"10934.62615417","P":"10962.17178236","r":"","T":0}"#;
+        let update = MarkPriceUpdate::from_json(json).unwrap();
+
+        assert_eq!(update.pair, None);
+        assert_eq!(update.index_price, None);
+        assert_eq!(update.funding_rate, None);
+        assert_eq!(update.next_funding_time, 0);
+    }
+
+    #[test]
+    fn test_kline_event_perpetual_deserialization() {
+        let json = r#"{"e":"kline","E":1591261542539,"s":"BTCUSD_PERP","k":{"t":1591261500000,"T":1591261559999,"s":"BTCUSD_PERP","i":"1m","f":606400,"L":606430,"o":"9638.9","c":"9639.8","h":"9639.8","l":"9638.6","v":"156","n":30,"x":false,"q":"1.61836886","V":"73","Q":"0.75731156","B":"0"}}"#;
+        let expected = KlineEvent {
+            event_type: "kline".to_string(),
+            event_time: 1591261542539,
+            symbol: Some("BTCUSD_PERP".to_string()),
+            pair: None,
+            contract_type: None,
+            kline: Kline {
+                start_time: 1591261500000,
+                close_time: 1591261559999,
+                symbol: "BTCUSD_PERP".to_string(),
+                interval: KlineInterval::M1,
+                first_trade_id: 606400,
+                last_trade_id: 606430,
+                open_price: 9638.9,
+                close_price: 9639.8,
+                high_price: 9639.8,
+                low_price: 9638.6,
+                volume: 156.0,
+                number_of_trades: 30,
+                is_closed: false,
+                base_asset_volume: 1.61836886,
+                taker_buy_volume: 73.0,
+                taker_buy_base_asset_volume: 0.75731156,
+                ignore: "0".to_string(),
+            },
+        };
+        assert_eq!(KlineEvent::from_json(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_ticker_deserialization() {
+        let json = r#"{"e":"24hrTicker","E":1591268262453,"s":"BTCUSD_200626","ps":"BTCUSD","p":"-43.4","P":"-0.452","w":"9548.5","c":"9556.9","Q":"1","o":"9600.3","h":"9623.6","l":"9483.3","v":"195215","q":"2044.47592602","O":1591181820000,"C":1591268262442,"F":512014,"L":615289,"n":103272}"#;
+        let ticker = Ticker::from_json(json).unwrap();
+
+        assert_eq!(ticker.symbol, "BTCUSD_200626");
+        assert_eq!(ticker.pair, "BTCUSD");
+        assert_eq!(ticker.price_change, -43.4);
+        assert_eq!(ticker.volume, 195215.0);
+        assert_eq!(ticker.base_asset_volume, 2044.47592602);
+        assert_eq!(ticker.number_of_trades, 103272);
+    }
+}
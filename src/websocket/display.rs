@@ -0,0 +1,550 @@
+//! One-line `Display` summaries of stream events for logging.
+//!
+//! Every line starts with an upper case tag naming the event, followed by space separated fields and
+//! ends with `@ <time>` where the event carries one. Prices and quantities use the shortest `f64` form.
+//! The formats are meant to be grepped, keep them stable:
+//!
+//! - `TRADE <symbol> <price> x <qty> (maker=buyer|seller) @ <trade time>`
+//! - `AGG_TRADE <symbol> <price> x <qty> #<agg id> [<first id>-<last id>] (maker=buyer|seller) @ <trade time>`
+//! - `BOOK_TICKER <symbol> <bid qty> @ <bid> / <ask qty> @ <ask>[ u=<update id>]`
+//! - `DEPTH <symbol> U=<first id> u=<final id> bids=<n> asks=<n> top_bid=<price> x <qty>|- top_ask=<price> x <qty>|- @ <event time>`
+//! - `KLINE <symbol> <interval> O=<open> H=<high> L=<low> C=<close> V=<volume> n=<trades> closed|open @ <start time>`
+//! - `SUBSCRIBE #<id> <stream>,<stream>` and `RESPONSE #<id> ok|<result>,<result>`
+//! - `EXECUTION_REPORT <symbol> <side> <type> <execution type> <status> <last qty> x <last price> filled=<cum qty>/<qty> order=<id> client=<client id> @ <transaction time>`
+//! - `ORDER_TRADE_UPDATE` with the same fields as `EXECUTION_REPORT`
+//! - `ACCOUNT_POSITION <asset>=<free>/<locked> ... @ <last update time>`
+//! - `BALANCE_UPDATE <asset> <signed delta> @ <clear time>`
+//! - `MARGIN_LEVEL <margin level> <status> @ <event time>`
+//! - `LIABILITY_CHANGE <asset> <type> principal=<qty> interest=<qty> tx=<transaction id> @ <event time>`
+//! - `ACCOUNT_UPDATE reason=<reason> balances=<n> positions=<n> @ <transaction time>`
+//! - `TRADE_LITE <symbol> <side> <last qty> x <last price> order=<id> client=<client id> @ <transaction time>`
+//! - `MARGIN_CALL positions=<n> @ <event time>`, `ACCOUNT_CONFIG_UPDATE <symbol>=<leverage>x|multi_assets=<bool> @ <transaction time>`
+//! - `STRATEGY_UPDATE` and `GRID_UPDATE <symbol> id=<strategy id> <status> @ <transaction time>`
+//! - `RISK_LEVEL <uni mmr> <status> equity=<equity> mm=<maintenance margin> @ <event time>`
+//! - `OPEN_ORDER_LOSS <asset>=<loss> ... @ <event time>`
+//! - `CONDITIONAL_ORDER <business unit> <symbol> <side> <strategy type> <status> id=<strategy id> client=<client id> @ <transaction time>`
+//! - `LIABILITY_CHANGE` on portfolio margin streams with ` total=<qty>` after the interest
+//! - `LISTEN_KEY_EXPIRED <listen key> @ <event time>`
+//! - `TOKEN_NAV <token> nav=<nav> leverage=<real leverage> issued=<tokens issued> @ <event time>`
+//! - `NAV_KLINE <token> <interval> O=<open> H=<high> L=<low> C=<close> n=<updates> closed|open @ <start time>`
+//! - `UNKNOWN <n> bytes` for payloads of unknown event types
+
+use std::fmt;
+
+use super::{Kline, ListenKeyExpired, SubscriptionRequest, SubscriptionResponse};
+
+cfg_spot! {
+    use super::blvt::{BlvtEvent, NavKlineEvent, TokenNav};
+    use super::{
+        AggTrade, BalanceUpdate, BookTicker, DepthUpdate, ExecutionReport, KlineEvent, LiabilityChange,
+        MarginLevelStatusChange, MarginUserDataEvent, OutboundAccountPosition, PriceLevel, Trade, UserDataEvent,
+    };
+}
+cfg_futures! {
+    use super::{
+        AccountConfigUpdate, AccountUpdate, ConditionalOrderTradeUpdate, FuturesUserDataEvent, GridUpdate, MarginCall,
+        OpenOrderLoss, OrderTradeUpdate, PortfolioLiabilityChange, PortfolioMarginEvent, RiskLevelChange, StrategyUpdate,
+        TradeLite,
+    };
+}
+
+#[cfg(feature = "spot")]
+fn maker(is_buyer_market_maker: bool) -> &'static str {
+    if is_buyer_market_maker { "buyer" } else { "seller" }
+}
+
+#[cfg(feature = "spot")]
+struct Top(Option<PriceLevel>);
+
+#[cfg(feature = "spot")]
+impl fmt::Display for Top {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(level) => write!(f, "{} x {}", level.price, level.quantity),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for Trade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TRADE {} {} x {} (maker={}) @ {}", self.symbol, self.price, self.quantity, maker(self.is_buyer_market_maker), self.trade_time)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for AggTrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AGG_TRADE {} {} x {} #{} [{}-{}] (maker={}) @ {}",
+            self.symbol,
+            self.price,
+            self.quantity,
+            self.aggregate_trade_id,
+            self.first_trade_id,
+            self.last_trade_id,
+            maker(self.is_buyer_market_maker),
+            self.trade_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for BookTicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BOOK_TICKER {} {} @ {} / {} @ {}",
+            self.symbol, self.best_bid_quantity, self.best_bid_price, self.best_ask_quantity, self.best_ask_price,
+        )?;
+        if let Some(update_id) = self.update_id {
+            write!(f, " u={update_id}")?;
+        }
+        Ok(())
+    }
+}
+
+// The top of changes is the highest changed bid and the lowest changed ask, removals included
+#[cfg(feature = "spot")]
+impl fmt::Display for DepthUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let top_bid = self.bids.iter().copied().min_by(PriceLevel::bid_order);
+        let top_ask = self.asks.iter().copied().min_by(PriceLevel::ask_order);
+        write!(
+            f,
+            "DEPTH {} U={} u={} bids={} asks={} top_bid={} top_ask={} @ {}",
+            self.symbol,
+            self.first_update_id,
+            self.final_update_id,
+            self.bids.len(),
+            self.asks.len(),
+            Top(top_bid),
+            Top(top_ask),
+            self.event_time,
+        )
+    }
+}
+
+impl fmt::Display for Kline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "KLINE {} {} O={} H={} L={} C={} V={} n={} {} @ {}",
+            self.symbol,
+            self.interval,
+            self.open_price,
+            self.high_price,
+            self.low_price,
+            self.close_price,
+            self.volume,
+            self.number_of_trades,
+            if self.is_closed { "closed" } else { "open" },
+            self.start_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for KlineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kline.fmt(f)
+    }
+}
+
+impl fmt::Display for SubscriptionRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} #{} {}", self.method, self.id, self.params.join(","))
+    }
+}
+
+impl fmt::Display for SubscriptionResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Some(result) => write!(f, "RESPONSE #{} {}", self.id, result.join(",")),
+            None => write!(f, "RESPONSE #{} ok", self.id),
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for ExecutionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "EXECUTION_REPORT {} {} {} {} {} {} x {} filled={}/{} order={} client={} @ {}",
+            self.symbol,
+            self.side.as_str(),
+            self.order_type,
+            self.execution_type.as_str(),
+            self.order_status.as_str(),
+            self.last_executed_quantity,
+            self.last_executed_price,
+            self.cumulative_filled_quantity,
+            self.order_quantity,
+            self.order_id,
+            self.client_order_id,
+            self.transaction_time,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for OrderTradeUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = &self.order;
+        write!(
+            f,
+            "ORDER_TRADE_UPDATE {} {} {} {} {} {} x {} filled={}/{} order={} client={} @ {}",
+            order.symbol,
+            order.side.as_str(),
+            order.order_type,
+            order.execution_type.as_str(),
+            order.order_status.as_str(),
+            order.last_filled_quantity,
+            order.last_filled_price,
+            order.filled_accumulated_quantity,
+            order.original_quantity,
+            order.order_id,
+            order.client_order_id,
+            self.transaction_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for OutboundAccountPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ACCOUNT_POSITION")?;
+        for balance in &self.balances {
+            write!(f, " {}={}/{}", balance.asset, balance.free, balance.locked)?;
+        }
+        write!(f, " @ {}", self.last_update_time)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for BalanceUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BALANCE_UPDATE {} {:+} @ {}", self.asset, self.balance_delta, self.clear_time)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for MarginLevelStatusChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MARGIN_LEVEL {} {} @ {}", self.margin_level, self.status.as_str(), self.event_time)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for LiabilityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LIABILITY_CHANGE {} {} principal={} interest={} tx={} @ {}",
+            self.asset,
+            self.liability_type,
+            self.principal,
+            self.interest,
+            self.transaction_id,
+            self.event_time,
+        )
+    }
+}
+
+impl fmt::Display for ListenKeyExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LISTEN_KEY_EXPIRED {} @ {}", self.listen_key, self.event_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for AccountUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = &self.update_data;
+        write!(
+            f,
+            "ACCOUNT_UPDATE reason={} balances={} positions={} @ {}",
+            data.reason,
+            data.balances.len(),
+            data.positions.len(),
+            self.transaction_time,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for TradeLite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TRADE_LITE {} {} {} x {} order={} client={} @ {}",
+            self.symbol,
+            self.side.as_str(),
+            self.last_filled_quantity,
+            self.last_filled_price,
+            self.order_id,
+            self.client_order_id,
+            self.transaction_time,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for MarginCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MARGIN_CALL positions={} @ {}", self.positions.len(), self.event_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for AccountConfigUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ACCOUNT_CONFIG_UPDATE")?;
+        if let Some(config) = &self.leverage_config {
+            write!(f, " {}={}x", config.symbol, config.leverage)?;
+        }
+        if let Some(config) = &self.multi_assets_config {
+            write!(f, " multi_assets={}", config.multi_assets_mode)?;
+        }
+        write!(f, " @ {}", self.transaction_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for StrategyUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let update = &self.strategy_update;
+        write!(f, "STRATEGY_UPDATE {} id={} {} @ {}", update.symbol, update.strategy_id, update.strategy_status, self.transaction_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for GridUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let update = &self.grid_update;
+        write!(f, "GRID_UPDATE {} id={} {} @ {}", update.symbol, update.strategy_id, update.strategy_status, self.transaction_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for RiskLevelChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RISK_LEVEL {} {} equity={} mm={} @ {}",
+            self.uni_mmr,
+            self.status.as_str(),
+            self.equity,
+            self.maintenance_margin,
+            self.event_time,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for OpenOrderLoss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OPEN_ORDER_LOSS")?;
+        for loss in &self.losses {
+            write!(f, " {}={}", loss.asset, loss.loss)?;
+        }
+        write!(f, " @ {}", self.event_time)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for ConditionalOrderTradeUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = &self.order;
+        write!(
+            f,
+            "CONDITIONAL_ORDER {} {} {} {} {} id={} client={} @ {}",
+            self.business_unit,
+            order.symbol,
+            order.side.as_str(),
+            order.strategy_type,
+            order.strategy_status,
+            order.strategy_id,
+            order.client_strategy_id,
+            self.transaction_time,
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for PortfolioLiabilityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LIABILITY_CHANGE {} {} principal={} interest={} total={} tx={} @ {}",
+            self.asset,
+            self.liability_type,
+            self.principal,
+            self.interest,
+            self.total_liability,
+            self.transaction_id,
+            self.event_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for TokenNav {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TOKEN_NAV {} nav={} leverage={} issued={} @ {}",
+            self.token_name, self.nav, self.real_leverage, self.token_issued, self.event_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for NavKlineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kline = &self.kline;
+        write!(
+            f,
+            "NAV_KLINE {} {} O={} H={} L={} C={} n={} {} @ {}",
+            kline.token_name,
+            kline.interval,
+            kline.open_nav,
+            kline.high_nav,
+            kline.low_nav,
+            kline.close_nav,
+            kline.number_of_updates,
+            if kline.is_closed { "closed" } else { "open" },
+            kline.start_time,
+        )
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for BlvtEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlvtEvent::TokenNav(event) => event.fmt(f),
+            BlvtEvent::NavKline(event) => event.fmt(f),
+            BlvtEvent::Unknown(json) => write!(f, "UNKNOWN {} bytes", json.len()),
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for UserDataEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserDataEvent::ExecutionReport(event) => event.fmt(f),
+            UserDataEvent::OutboundAccountPosition(event) => event.fmt(f),
+            UserDataEvent::BalanceUpdate(event) => event.fmt(f),
+            UserDataEvent::ListenKeyExpired(event) => event.fmt(f),
+            UserDataEvent::Unknown(json) => write!(f, "UNKNOWN {} bytes", json.len()),
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl fmt::Display for MarginUserDataEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarginUserDataEvent::ExecutionReport(event) => event.fmt(f),
+            MarginUserDataEvent::OutboundAccountPosition(event) => event.fmt(f),
+            MarginUserDataEvent::BalanceUpdate(event) => event.fmt(f),
+            MarginUserDataEvent::MarginLevelStatusChange(event) => event.fmt(f),
+            MarginUserDataEvent::LiabilityChange(event) => event.fmt(f),
+            MarginUserDataEvent::ListenKeyExpired(event) => event.fmt(f),
+            MarginUserDataEvent::Unknown(json) => write!(f, "UNKNOWN {} bytes", json.len()),
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for FuturesUserDataEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuturesUserDataEvent::MarginCall(event) => event.fmt(f),
+            FuturesUserDataEvent::AccountConfigUpdate(event) => event.fmt(f),
+            FuturesUserDataEvent::TradeLite(event) => event.fmt(f),
+            FuturesUserDataEvent::StrategyUpdate(event) => event.fmt(f),
+            FuturesUserDataEvent::GridUpdate(event) => event.fmt(f),
+            FuturesUserDataEvent::AccountUpdate(event) => event.fmt(f),
+            FuturesUserDataEvent::OrderTradeUpdate(event) => event.fmt(f),
+            FuturesUserDataEvent::ListenKeyExpired(event) => event.fmt(f),
+            FuturesUserDataEvent::Unknown(json) => write!(f, "UNKNOWN {} bytes", json.len()),
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl fmt::Display for PortfolioMarginEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortfolioMarginEvent::RiskLevelChange(event) => event.fmt(f),
+            PortfolioMarginEvent::OpenOrderLoss(event) => event.fmt(f),
+            PortfolioMarginEvent::ConditionalOrderTradeUpdate(event) => event.fmt(f),
+            PortfolioMarginEvent::LiabilityChange(event) => event.fmt(f),
+            PortfolioMarginEvent::ListenKeyExpired(event) => event.fmt(f),
+            PortfolioMarginEvent::Unknown(json) => write!(f, "UNKNOWN {} bytes", json.len()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "spot"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_trade_and_book_ticker() {
+        let trade = Trade::from_json(r#"{"e":"trade","E":1759680390108300,"s":"ETHUSDT","t":12345,"p":"4532.56000000","q":"0.01320000","T":1759680390108254,"m":true,"M":true}"#).unwrap();
+        assert_eq!(trade.to_string(), "TRADE ETHUSDT 4532.56 x 0.0132 (maker=buyer) @ 1759680390108254");
+
+        let ticker = BookTicker::from_json(r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#).unwrap();
+        assert_eq!(ticker.to_string(), "BOOK_TICKER BNBUSDT 31.21 @ 25.3519 / 40.66 @ 25.3652 u=400900217");
+    }
+
+    #[test]
+    fn test_display_depth_kline_and_user_data() {
+        let update = DepthUpdate::from_json(r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"],["0.0025","0"]],"a":[["0.0027","0"],["0.0026","100"]]}"#).unwrap();
+        assert_eq!(update.to_string(), "DEPTH BNBBTC U=157 u=160 bids=2 asks=2 top_bid=0.0025 x 0 top_ask=0.0026 x 100 @ 1672515782136");
+
+        let event = KlineEvent::from_json(r#"{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#).unwrap();
+        assert_eq!(event.to_string(), "KLINE BNBBTC 1m O=0.001 H=0.0025 L=0.0015 C=0.002 V=1000 n=100 open @ 1672515780000");
+
+        let event = UserDataEvent::from_json(r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"-100.00000000","T":1573200697068}"#).unwrap();
+        assert_eq!(event.to_string(), "BALANCE_UPDATE BTC -100 @ 1573200697068");
+
+        let event = MarginUserDataEvent::from_json(crate::testutil::margin::sample_liability_change_json()).unwrap();
+        assert_eq!(event.to_string(), "LIABILITY_CHANGE BTC BORROW principal=1.0345343 interest=0 tx=1352286576452864727 @ 1701949333880");
+        let event = MarginUserDataEvent::from_json(crate::testutil::margin::sample_margin_level_status_change_json()).unwrap();
+        assert_eq!(event.to_string(), "MARGIN_LEVEL 1.42 NORMAL @ 1701949763462");
+
+        let event = BlvtEvent::from_json(crate::testutil::blvt::sample_token_nav_json()).unwrap();
+        assert_eq!(event.to_string(), "TOKEN_NAV BTCDOWN nav=14.6500129 leverage=2.17028553 issued=4750.16277646 @ 1600246983025");
+        let event = BlvtEvent::from_json(crate::testutil::blvt::sample_nav_kline_json()).unwrap();
+        assert_eq!(event.to_string(), "NAV_KLINE BTCDOWN 1m O=14.65977277 H=14.66073432 L=14.65944378 C=14.66073432 n=59 open @ 1600247040000");
+
+        let mut request = SubscriptionRequest::new(1);
+        request.add_stream("btcusdt@trade");
+        request.add_stream("ethusdt@kline_1m");
+        assert_eq!(request.to_string(), "SUBSCRIBE #1 btcusdt@trade,ethusdt@kline_1m");
+        assert_eq!(SubscriptionResponse { result: None, id: 1 }.to_string(), "RESPONSE #1 ok");
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_display_portfolio_margin() {
+        use crate::testutil::portfolio;
+
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_risk_level_change_json()).unwrap();
+        assert_eq!(event.to_string(), "RISK_LEVEL 1.99999999 MARGIN_CALL equity=30.23416728 mm=15.11708371 @ 1587727187525");
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_open_order_loss_json()).unwrap();
+        assert_eq!(event.to_string(), "OPEN_ORDER_LOSS BUSD=-0.1232313 BNB=-12.1232313 @ 1678710578788");
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_conditional_order_trade_update_json()).unwrap();
+        assert_eq!(event.to_string(), "CONDITIONAL_ORDER UM BTCUSDT SELL TRAILING_STOP_MARKET NEW id=176057039 client=TEST @ 1669262908216");
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_liability_change_json()).unwrap();
+        assert_eq!(event.to_string(), "LIABILITY_CHANGE BTC BORROW principal=1.0345343 interest=0 total=1.03476851 tx=1352286576452864727 @ 1573200697110");
+    }
+}
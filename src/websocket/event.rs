@@ -0,0 +1,548 @@
+//! Accessors shared by every stream event, for code generic over event types.
+
+use super::ListenKeyExpired;
+
+cfg_spot! {
+    use std::ops::Deref;
+
+    use super::blvt;
+    use super::{
+        AggTrade, BalanceUpdate, BookTicker, DepthUpdate, ExecutionReport, KlineEvent, LiabilityChange, MarginLevelStatusChange,
+        MarginUserDataEvent, OutboundAccountPosition, SpotEvent, Trade, UserDataEvent,
+    };
+}
+cfg_futures! {
+    use super::{delivery, futures};
+    use super::{
+        AccountConfigUpdate, AccountUpdate, AssetIndex, CompositeIndex, ContractInfo, FuturesUserDataEvent, GridUpdate,
+        ConditionalOrderTradeUpdate, MarginCall, OpenOrderLoss, OrderTradeUpdate, PortfolioLiabilityChange,
+        PortfolioMarginEvent, RiskLevelChange, StrategyUpdate, TradeLite,
+    };
+}
+cfg_options! {
+    use super::options;
+}
+
+// Event type is the `e` field. Event time and symbol are None where the payload has no such field,
+// e.g. spot bookTicker has no event time and balance updates have no symbol
+pub trait BinanceEvent {
+    fn event_type(&self) -> &str;
+    fn event_time(&self) -> Option<u64>;
+    fn symbol(&self) -> Option<&str>;
+
+    // Exchange id ordering events of equal event time, e.g. the trade id or final update id
+    fn sequence_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+// For events with `event_type: String`, `event_time: u64` and a `String` symbol at `$($symbol).+`,
+// optionally sequenced by the `u64` field `$sequence`
+macro_rules! impl_binance_event {
+    ($event:ty, $($symbol:ident).+; sequence = $sequence:ident) => {
+        impl BinanceEvent for $event {
+            fn event_type(&self) -> &str {
+                &self.event_type
+            }
+
+            fn event_time(&self) -> Option<u64> {
+                Some(self.event_time)
+            }
+
+            fn symbol(&self) -> Option<&str> {
+                Some(&self.$($symbol).+)
+            }
+
+            fn sequence_id(&self) -> Option<u64> {
+                Some(self.$sequence)
+            }
+        }
+    };
+    ($event:ty, $($symbol:ident).+) => {
+        impl BinanceEvent for $event {
+            fn event_type(&self) -> &str {
+                &self.event_type
+            }
+
+            fn event_time(&self) -> Option<u64> {
+                Some(self.event_time)
+            }
+
+            fn symbol(&self) -> Option<&str> {
+                Some(&self.$($symbol).+)
+            }
+        }
+    };
+    ($event:ty) => {
+        impl BinanceEvent for $event {
+            fn event_type(&self) -> &str {
+                &self.event_type
+            }
+
+            fn event_time(&self) -> Option<u64> {
+                Some(self.event_time)
+            }
+
+            fn symbol(&self) -> Option<&str> {
+                None
+            }
+        }
+    };
+}
+
+impl_binance_event!(ListenKeyExpired);
+
+cfg_spot! {
+    impl_binance_event!(KlineEvent, symbol);
+    impl_binance_event!(ExecutionReport, symbol);
+    impl_binance_event!(OutboundAccountPosition);
+    impl_binance_event!(BalanceUpdate);
+    impl_binance_event!(MarginLevelStatusChange);
+    impl_binance_event!(LiabilityChange);
+    impl_binance_event!(blvt::TokenNav, token_name);
+    impl_binance_event!(blvt::NavKlineEvent, token_name);
+}
+
+cfg_futures! {
+    impl_binance_event!(CompositeIndex, symbol);
+    impl_binance_event!(ContractInfo, symbol);
+    impl_binance_event!(AssetIndex, symbol);
+    impl_binance_event!(MarginCall);
+    impl_binance_event!(TradeLite, symbol);
+    impl_binance_event!(StrategyUpdate, strategy_update.symbol);
+    impl_binance_event!(GridUpdate, grid_update.symbol);
+    impl_binance_event!(AccountUpdate);
+    impl_binance_event!(OrderTradeUpdate, order.symbol);
+    impl_binance_event!(RiskLevelChange);
+    impl_binance_event!(OpenOrderLoss);
+    impl_binance_event!(ConditionalOrderTradeUpdate, order.symbol);
+    impl_binance_event!(PortfolioLiabilityChange);
+    impl_binance_event!(futures::MarkPriceUpdate, symbol);
+    impl_binance_event!(futures::AggTrade, symbol; sequence = aggregate_trade_id);
+    impl_binance_event!(futures::KlineEvent, symbol);
+    impl_binance_event!(futures::ContinuousKlineEvent, pair);
+    impl_binance_event!(futures::BookTicker, symbol; sequence = update_id);
+    impl_binance_event!(futures::DepthUpdate, symbol; sequence = final_update_id);
+    impl_binance_event!(futures::ForceOrder, order.symbol);
+    impl_binance_event!(delivery::IndexPriceUpdate, pair);
+    impl_binance_event!(delivery::MarkPriceUpdate, symbol);
+    impl_binance_event!(delivery::Ticker, symbol);
+}
+
+cfg_options! {
+    impl_binance_event!(options::Trade, symbol);
+    impl_binance_event!(options::Ticker, symbol);
+    impl_binance_event!(options::IndexPrice, symbol);
+    impl_binance_event!(options::MarkPrice, symbol);
+    impl_binance_event!(options::KlineEvent, symbol);
+    impl_binance_event!(options::OpenInterest, symbol);
+    impl_binance_event!(options::OpenSymbolInfo, symbol);
+    impl_binance_event!(options::Depth, symbol);
+}
+
+// The spot market structs are generic over the symbol type, see `crate::symbol`
+#[cfg(feature = "spot")]
+impl<S: Deref<Target = str>> BinanceEvent for AggTrade<S> {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        Some(self.event_time)
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        Some(&self.symbol)
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        Some(self.aggregate_trade_id)
+    }
+}
+
+#[cfg(feature = "spot")]
+impl<S: Deref<Target = str>, L> BinanceEvent for DepthUpdate<S, L> {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        Some(self.event_time)
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        Some(&self.symbol)
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        Some(self.final_update_id)
+    }
+}
+
+// REST trades carry no event type or time
+#[cfg(feature = "spot")]
+impl<S: Deref<Target = str>> BinanceEvent for Trade<S> {
+    fn event_type(&self) -> &str {
+        self.event_type.as_deref().unwrap_or("trade")
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.event_time
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        Some(&self.symbol)
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        Some(self.trade_id)
+    }
+}
+
+// The spot stream has no `e` or `E`
+#[cfg(feature = "spot")]
+impl<S: Deref<Target = str>> BinanceEvent for BookTicker<S> {
+    fn event_type(&self) -> &str {
+        "bookTicker"
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        None
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        Some(&self.symbol)
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        self.update_id
+    }
+}
+
+// Only leverage changes name a symbol
+#[cfg(feature = "futures")]
+impl BinanceEvent for AccountConfigUpdate {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        Some(self.event_time)
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.leverage_config.as_ref().map(|config| config.symbol.as_str())
+    }
+}
+
+// Pair klines carry the pair instead of the symbol
+#[cfg(feature = "futures")]
+impl BinanceEvent for delivery::KlineEvent {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        Some(self.event_time)
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.symbol.as_deref().or(self.pair.as_deref())
+    }
+}
+
+#[cfg(any(feature = "spot", feature = "futures", feature = "options"))]
+const UNKNOWN_EVENT_TYPE: &str = "unknown";
+
+#[cfg(feature = "spot")]
+impl BinanceEvent for UserDataEvent {
+    fn event_type(&self) -> &str {
+        match self {
+            UserDataEvent::ExecutionReport(event) => event.event_type(),
+            UserDataEvent::OutboundAccountPosition(event) => event.event_type(),
+            UserDataEvent::BalanceUpdate(event) => event.event_type(),
+            UserDataEvent::ListenKeyExpired(event) => event.event_type(),
+            UserDataEvent::Unknown(_) => UNKNOWN_EVENT_TYPE,
+        }
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        match self {
+            UserDataEvent::ExecutionReport(event) => event.event_time(),
+            UserDataEvent::OutboundAccountPosition(event) => event.event_time(),
+            UserDataEvent::BalanceUpdate(event) => event.event_time(),
+            UserDataEvent::ListenKeyExpired(event) => event.event_time(),
+            UserDataEvent::Unknown(_) => None,
+        }
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        match self {
+            UserDataEvent::ExecutionReport(event) => event.symbol(),
+            UserDataEvent::OutboundAccountPosition(event) => event.symbol(),
+            UserDataEvent::BalanceUpdate(event) => event.symbol(),
+            UserDataEvent::ListenKeyExpired(event) => event.symbol(),
+            UserDataEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl MarginUserDataEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            MarginUserDataEvent::ExecutionReport(event) => Some(event),
+            MarginUserDataEvent::OutboundAccountPosition(event) => Some(event),
+            MarginUserDataEvent::BalanceUpdate(event) => Some(event),
+            MarginUserDataEvent::MarginLevelStatusChange(event) => Some(event),
+            MarginUserDataEvent::LiabilityChange(event) => Some(event),
+            MarginUserDataEvent::ListenKeyExpired(event) => Some(event),
+            MarginUserDataEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl BinanceEvent for MarginUserDataEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.as_event()?.symbol()
+    }
+}
+
+#[cfg(feature = "spot")]
+impl SpotEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            SpotEvent::Trade(event) => Some(event),
+            SpotEvent::AggTrade(event) => Some(event),
+            SpotEvent::Kline(event) => Some(event),
+            SpotEvent::DepthUpdate(event) => Some(event),
+            SpotEvent::UserData(event) => Some(event),
+            SpotEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl BinanceEvent for SpotEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.as_event()?.symbol()
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        self.as_event()?.sequence_id()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl FuturesUserDataEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            FuturesUserDataEvent::MarginCall(event) => Some(event),
+            FuturesUserDataEvent::AccountConfigUpdate(event) => Some(event),
+            FuturesUserDataEvent::TradeLite(event) => Some(event),
+            FuturesUserDataEvent::StrategyUpdate(event) => Some(event),
+            FuturesUserDataEvent::GridUpdate(event) => Some(event),
+            FuturesUserDataEvent::AccountUpdate(event) => Some(event),
+            FuturesUserDataEvent::OrderTradeUpdate(event) => Some(event),
+            FuturesUserDataEvent::ListenKeyExpired(event) => Some(event),
+            FuturesUserDataEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl BinanceEvent for FuturesUserDataEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.as_event()?.symbol()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl PortfolioMarginEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            PortfolioMarginEvent::RiskLevelChange(event) => Some(event),
+            PortfolioMarginEvent::OpenOrderLoss(event) => Some(event),
+            PortfolioMarginEvent::ConditionalOrderTradeUpdate(event) => Some(event),
+            PortfolioMarginEvent::LiabilityChange(event) => Some(event),
+            PortfolioMarginEvent::ListenKeyExpired(event) => Some(event),
+            PortfolioMarginEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl BinanceEvent for PortfolioMarginEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.as_event()?.symbol()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl futures::FuturesEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            futures::FuturesEvent::MarkPriceUpdate(event) => Some(event),
+            futures::FuturesEvent::MarkPriceUpdates(events) => events.first().map(|event| event as &dyn BinanceEvent),
+            futures::FuturesEvent::AggTrade(event) => Some(event),
+            futures::FuturesEvent::Kline(event) => Some(event),
+            futures::FuturesEvent::ContinuousKline(event) => Some(event),
+            futures::FuturesEvent::BookTicker(event) => Some(event),
+            futures::FuturesEvent::DepthUpdate(event) => Some(event),
+            futures::FuturesEvent::ForceOrder(event) => Some(event),
+            futures::FuturesEvent::CompositeIndex(event) => Some(event),
+            futures::FuturesEvent::ContractInfo(event) => Some(event),
+            futures::FuturesEvent::AssetIndex(event) => Some(event),
+            futures::FuturesEvent::AssetIndexes(events) => events.first().map(|event| event as &dyn BinanceEvent),
+            futures::FuturesEvent::IndexPriceUpdate(event) => Some(event),
+            futures::FuturesEvent::UserData(event) => Some(event),
+            futures::FuturesEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl BinanceEvent for futures::FuturesEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        match self {
+            futures::FuturesEvent::MarkPriceUpdates(_) | futures::FuturesEvent::AssetIndexes(_) => None,
+            _ => self.as_event()?.symbol(),
+        }
+    }
+
+    fn sequence_id(&self) -> Option<u64> {
+        self.as_event()?.sequence_id()
+    }
+}
+
+#[cfg(feature = "spot")]
+impl blvt::BlvtEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            blvt::BlvtEvent::TokenNav(event) => Some(event),
+            blvt::BlvtEvent::NavKline(event) => Some(event),
+            blvt::BlvtEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "spot")]
+impl BinanceEvent for blvt::BlvtEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        self.as_event()?.symbol()
+    }
+}
+
+// Array events report the type and time of their first element and no symbol
+#[cfg(feature = "options")]
+impl options::OptionsEvent {
+    fn as_event(&self) -> Option<&dyn BinanceEvent> {
+        match self {
+            options::OptionsEvent::Trade(event) => Some(event),
+            options::OptionsEvent::Ticker(event) => Some(event),
+            options::OptionsEvent::IndexPrice(event) => Some(event),
+            options::OptionsEvent::MarkPrice(events) => events.first().map(|event| event as &dyn BinanceEvent),
+            options::OptionsEvent::Kline(event) => Some(event),
+            options::OptionsEvent::OpenInterest(events) => events.first().map(|event| event as &dyn BinanceEvent),
+            options::OptionsEvent::OpenSymbolInfo(event) => Some(event),
+            options::OptionsEvent::Depth(event) => Some(event),
+            options::OptionsEvent::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "options")]
+impl BinanceEvent for options::OptionsEvent {
+    fn event_type(&self) -> &str {
+        self.as_event().map_or(UNKNOWN_EVENT_TYPE, |event| event.event_type())
+    }
+
+    fn event_time(&self) -> Option<u64> {
+        self.as_event()?.event_time()
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        match self {
+            options::OptionsEvent::MarkPrice(_) | options::OptionsEvent::OpenInterest(_) => None,
+            _ => self.as_event()?.symbol(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "spot", feature = "futures"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binance_event_through_trait_objects() {
+        let events: Vec<Box<dyn BinanceEvent>> = vec![
+            Box::new(Trade::from_json(r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#).unwrap()),
+            Box::new(BookTicker::from_json(r#"{"u":400900217,"s":"BNBUSDT","b":"25.35","B":"31.21","a":"25.36","A":"40.66"}"#).unwrap()),
+            Box::new(futures::MarkPriceUpdate::from_json(r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15","i":"11784.62","P":"11784.25","r":"0.00038167","T":1562306400000}"#).unwrap()),
+            Box::new(UserDataEvent::from_json(r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#).unwrap()),
+            Box::new(UserDataEvent::from_json(r#"{"e":"somethingNew","E":1}"#).unwrap()),
+            Box::new(futures::FuturesEvent::from_json(r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSDT","l":25}}"#).unwrap()),
+        ];
+        let summary: Vec<(&str, Option<u64>, Option<&str>)> = events
+            .iter()
+            .map(|event| (event.event_type(), event.event_time(), event.symbol()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("trade", Some(1672515782136), Some("BNBBTC")),
+            ("bookTicker", None, Some("BNBUSDT")),
+            ("markPriceUpdate", Some(1562305380000), Some("BTCUSDT")),
+            ("balanceUpdate", Some(1573200697110), None),
+            ("unknown", None, None),
+            ("ACCOUNT_CONFIG_UPDATE", Some(1611646737479), Some("BTCUSDT")),
+        ]);
+    }
+}
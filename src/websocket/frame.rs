@@ -0,0 +1,226 @@
+//! Cheap classification of websocket text frames before they are parsed.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameKind {
+    Ack { id: Option<u64> },             // `{"result":null,"id":N}`, a request that succeeded without a result
+    Response { id: Option<u64> },        // Request response with a non-null `result`, e.g. LIST_SUBSCRIPTIONS
+    Error { id: Option<u64> },           // Request response with an `error`, or a bare `{"code":...,"msg":...}`
+    Event,                               // Raw stream event with `e`, or an array stream such as `!ticker@arr`
+    CombinedEvent,                       // `{"stream":...,"data":...}` from a combined stream
+    Unknown,                             // Not a JSON object or array, or none of the above
+}
+
+#[derive(Default)]
+struct TopLevelKeys {
+    id: Option<Option<u64>>,
+    result_is_null: Option<bool>,
+    error: bool,
+    code: bool,
+    msg: bool,
+    event_type: bool,
+    stream: bool,
+    data: bool,
+}
+
+// Looks at the top-level keys only, nested values are skipped without being parsed. Ids that are not
+// unsigned integers are reported as None
+pub fn classify(frame: &str) -> FrameKind {
+    let mut scanner = Scanner { bytes: frame.as_bytes(), position: 0 };
+    match scanner.peek_non_whitespace() {
+        Some(b'{') => {}
+        Some(b'[') => return if scanner.skip_value().is_some() && scanner.at_end() { FrameKind::Event } else { FrameKind::Unknown },
+        _ => return FrameKind::Unknown,
+    }
+    let Some(keys) = scanner.top_level_keys() else {
+        return FrameKind::Unknown;
+    };
+    if keys.stream && keys.data {
+        FrameKind::CombinedEvent
+    } else if keys.event_type {
+        FrameKind::Event
+    } else if keys.error || (keys.code && keys.msg) {
+        FrameKind::Error { id: keys.id.flatten() }
+    } else if let (Some(id), Some(result_is_null)) = (keys.id, keys.result_is_null) {
+        if result_is_null { FrameKind::Ack { id } } else { FrameKind::Response { id } }
+    } else {
+        FrameKind::Unknown
+    }
+}
+
+// Stream name and raw `data` of a combined stream frame, sliced out of the frame without parsing the data.
+// None if the frame is not `{"stream":"...","data":...}` or the stream name contains escapes
+pub fn split_combined(frame: &str) -> Option<(&str, &str)> {
+    let mut scanner = Scanner { bytes: frame.as_bytes(), position: 0 };
+    let (mut stream, mut data) = (None, None);
+    scanner.members(|key, value| match key {
+        b"stream" => stream = Some(value),
+        b"data" => data = Some(value),
+        _ => {}
+    })?;
+    let stream = stream?.strip_prefix(b"\"").and_then(|stream| stream.strip_suffix(b"\""))?;
+    if stream.contains(&b'\\') {
+        return None;
+    }
+    Some((std::str::from_utf8(stream).ok()?, std::str::from_utf8(data?).ok()?))
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek_non_whitespace(&mut self) -> Option<u8> {
+        while self.bytes.get(self.position).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+        self.bytes.get(self.position).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Option<()> {
+        (self.peek_non_whitespace()? == expected).then(|| self.position += 1)
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.peek_non_whitespace().is_none()
+    }
+
+    fn top_level_keys(&mut self) -> Option<TopLevelKeys> {
+        let mut keys = TopLevelKeys::default();
+        self.members(|key, value| match key {
+            b"id" => keys.id = Some(std::str::from_utf8(value).ok().and_then(|id| id.parse().ok())),
+            b"result" => keys.result_is_null = Some(value == b"null"),
+            b"error" => keys.error = true,
+            b"code" => keys.code = true,
+            b"msg" => keys.msg = true,
+            b"e" => keys.event_type = true,
+            b"stream" => keys.stream = true,
+            b"data" => keys.data = true,
+            _ => {}
+        })?;
+        Some(keys)
+    }
+
+    // Calls `member` with the key and raw value of every top-level member of an object, None unless the
+    // whole frame is one well-formed object
+    fn members(&mut self, mut member: impl FnMut(&'a [u8], &'a [u8])) -> Option<()> {
+        self.expect(b'{')?;
+        if self.peek_non_whitespace()? == b'}' {
+            self.position += 1;
+            return self.at_end().then_some(());
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(b':')?;
+            self.peek_non_whitespace()?;
+            let value_start = self.position;
+            self.skip_value()?;
+            member(key, &self.bytes[value_start..self.position]);
+            match self.peek_non_whitespace()? {
+                b',' => self.position += 1,
+                b'}' => {
+                    self.position += 1;
+                    return self.at_end().then_some(());
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    // Contents of a string without its quotes, escapes left as they are
+    fn string(&mut self) -> Option<&'a [u8]> {
+        self.expect(b'"')?;
+        let start = self.position;
+        loop {
+            match *self.bytes.get(self.position)? {
+                b'\\' => self.position += 2,
+                b'"' => {
+                    self.position += 1;
+                    return Some(&self.bytes[start..self.position - 1]);
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    // Skips a value by matching brackets and strings, scalars are taken up to the next delimiter
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek_non_whitespace()? {
+            b'"' => self.string().map(|_| ()),
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                loop {
+                    match *self.bytes.get(self.position)? {
+                        b'"' => {
+                            self.string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.position += 1;
+                                return Some(());
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.position += 1;
+                }
+            }
+            _ => {
+                let start = self.position;
+                while self.bytes.get(self.position).is_some_and(|byte| !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace()) {
+                    self.position += 1;
+                }
+                (self.position > start).then_some(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_frames() {
+        assert_eq!(classify(r#"{"result":null,"id":1}"#), FrameKind::Ack { id: Some(1) });
+        assert_eq!(classify(" {\n  \"id\" : 7 ,\n  \"result\" : null\n} "), FrameKind::Ack { id: Some(7) });
+        assert_eq!(classify(r#"{"result":["btcusdt@trade"],"id":3}"#), FrameKind::Response { id: Some(3) });
+        assert_eq!(classify(r#"{"code":2,"msg":"Invalid request: property name must be a string"}"#), FrameKind::Error { id: None });
+        assert_eq!(classify(r#"{"error":{"code":2,"msg":"Invalid request: unknown variant \"}\""},"id":"abc"}"#), FrameKind::Error { id: None });
+        assert_eq!(classify(r#"{"id":4,"error":{"code":1,"msg":"Unknown property"}}"#), FrameKind::Error { id: Some(4) });
+
+        assert_eq!(classify(r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#), FrameKind::Event);
+        assert_eq!(classify(r#"[{"e":"24hrMiniTicker","E":1,"s":"BNBBTC"}]"#), FrameKind::Event);
+        // The kline's own `e` is nested and does not count
+        assert_eq!(
+            classify(r#"{"stream":"bnbbtc@kline_1m","data":{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1,"i":"1m","x":false}}}"#),
+            FrameKind::CombinedEvent,
+        );
+    }
+
+    #[test]
+    fn test_split_combined() {
+        let frame = r#"{"stream":"bnbbtc@trade","data":{"e":"trade","s":"BNBBTC","p":"0.001"}}"#;
+        assert_eq!(split_combined(frame), Some(("bnbbtc@trade", r#"{"e":"trade","s":"BNBBTC","p":"0.001"}"#)));
+        assert_eq!(split_combined(r#" { "data" : [1, 2] , "stream" : "!ticker@arr" } "#), Some(("!ticker@arr", "[1, 2]")));
+
+        assert_eq!(split_combined(r#"{"stream":"bnbbtc@trade"}"#), None);
+        assert_eq!(split_combined(r#"{"stream":"bnb\"btc","data":{}}"#), None);
+        assert_eq!(split_combined(r#"{"stream":7,"data":{}}"#), None);
+        assert_eq!(split_combined(r#"{"stream":"bnbbtc@trade","data":{}"#), None);
+    }
+
+    #[test]
+    fn test_classify_garbage() {
+        assert_eq!(classify(""), FrameKind::Unknown);
+        assert_eq!(classify("pong"), FrameKind::Unknown);
+        assert_eq!(classify(r#"{"result":null,"id":1"#), FrameKind::Unknown);
+        assert_eq!(classify(r#"{"result":null,"id":1} trailing"#), FrameKind::Unknown);
+        assert_eq!(classify(r#"{"e" "trade"}"#), FrameKind::Unknown);
+        assert_eq!(classify("{}"), FrameKind::Unknown);
+        assert_eq!(classify("[1, 2"), FrameKind::Unknown);
+    }
+}
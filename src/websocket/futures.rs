@@ -0,0 +1,794 @@
+//! USD-M futures market streams and the combined futures event dispatch.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use super::delivery::IndexPriceUpdate;
+use super::{
+    array_event_from_json, collect_elements, de_option_string_to_f64, de_string_to_f64, ser_f64_as_string,
+    ser_option_f64_as_string, ArrayEvent, EventTypeTag, FuturesUserDataEvent, KlineInterval, PriceLevel, Side,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarkPriceUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "p", alias = "mark-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Mark price
+    #[serde(rename = "i", alias = "index-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+    #[serde(rename = "P", alias = "estimated-settle-price", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub estimated_settle_price: Option<f64>, // Estimated settle price, only useful in the last hour before the settlement starts
+    #[serde(rename = "r", alias = "funding-rate", default, deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub funding_rate: Option<f64>,       // Funding rate
+    #[serde(rename = "T", alias = "next-funding-time")]
+    pub next_funding_time: u64,          // Next funding time
+}
+
+impl MarkPriceUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // !markPrice@arr
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AggTrade {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "a", alias = "aggregate-trade-id")]
+    pub aggregate_trade_id: u64,         // Aggregate trade ID
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "q", alias = "quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "nq", alias = "normal-quantity", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub normal_quantity: Option<f64>,    // Quantity without RPI orders
+    #[serde(rename = "f", alias = "first-trade-id")]
+    pub first_trade_id: u64,             // First trade ID
+    #[serde(rename = "l", alias = "last-trade-id")]
+    pub last_trade_id: u64,              // Last trade ID
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,                 // Trade time
+    #[serde(rename = "m", alias = "is-buyer-market-maker")]
+    pub is_buyer_market_maker: bool,     // Is the buyer the market maker?
+}
+
+impl AggTrade {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Kline {
+    #[serde(rename = "t", alias = "start-time")]
+    pub start_time: u64,                 // Kline start time
+    #[serde(rename = "T", alias = "close-time")]
+    pub close_time: u64,                 // Kline close time
+    #[serde(rename = "s", alias = "symbol", default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,          // Symbol (absent in continuous klines)
+    #[serde(rename = "i", alias = "interval")]
+    pub interval: KlineInterval,         // Interval
+    #[serde(rename = "f", alias = "first-trade-id")]
+    pub first_trade_id: i64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id")]
+    pub last_trade_id: i64,              // Last trade ID
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(rename = "c", alias = "close-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub close_price: f64,                // Close price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(rename = "v", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Base asset volume
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Number of trades
+    #[serde(rename = "x", alias = "is-closed")]
+    pub is_closed: bool,                 // Is this kline closed?
+    #[serde(rename = "q", alias = "quote-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_asset_volume: f64,         // Quote asset volume
+    #[serde(rename = "V", alias = "taker-buy-base-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_base_asset_volume: f64, // Taker buy base asset volume
+    #[serde(rename = "Q", alias = "taker-buy-quote-asset-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_quote_asset_volume: f64, // Taker buy quote asset volume
+    #[serde(rename = "B", alias = "ignore")]
+    pub ignore: String,                  // Ignore
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: Kline,                    // Kline
+}
+
+impl KlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContinuousKlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "ps", alias = "pair")]
+    pub pair: String,                    // Pair
+    #[serde(rename = "ct", alias = "contract-type")]
+    pub contract_type: String,           // Contract type
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: Kline,                    // Kline
+}
+
+impl ContinuousKlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BookTicker {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "u", alias = "update-id")]
+    pub update_id: u64,                  // Order book update ID
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "b", alias = "best-bid-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_bid_price: f64,             // Best bid price
+    #[serde(rename = "B", alias = "best-bid-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_bid_quantity: f64,          // Best bid quantity
+    #[serde(rename = "a", alias = "best-ask-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_ask_price: f64,             // Best ask price
+    #[serde(rename = "A", alias = "best-ask-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_ask_quantity: f64,          // Best ask quantity
+}
+
+impl BookTicker {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// `L` holds the levels, e.g. `InlineLevels` with the `smallvec` feature
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DepthUpdate<L = Vec<PriceLevel>> {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "U", alias = "first-update-id")]
+    pub first_update_id: u64,            // First update ID in event
+    #[serde(rename = "u", alias = "final-update-id")]
+    pub final_update_id: u64,            // Final update ID in event
+    #[serde(rename = "pu", alias = "previous-final-update-id")]
+    pub previous_final_update_id: u64,   // Final update ID in last stream (ie `u` in last stream)
+    #[serde(rename = "b", alias = "bids")]
+    pub bids: L,                         // Bids to be updated
+    #[serde(rename = "a", alias = "asks")]
+    pub asks: L,                         // Asks to be updated
+}
+
+impl DepthUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LiquidationOrder {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "S", alias = "side")]
+    pub side: Side,                      // Side
+    #[serde(rename = "o", alias = "order-type")]
+    pub order_type: String,              // Order type
+    #[serde(rename = "f", alias = "time-in-force")]
+    pub time_in_force: String,           // Time in force
+    #[serde(rename = "q", alias = "original-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_quantity: f64,          // Original quantity
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "ap", alias = "average-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub average_price: f64,              // Average price
+    #[serde(rename = "X", alias = "order-status")]
+    pub order_status: String,            // Order status
+    #[serde(rename = "l", alias = "last-filled-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_filled_quantity: f64,       // Order last filled quantity
+    #[serde(rename = "z", alias = "filled-accumulated-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub filled_accumulated_quantity: f64, // Order filled accumulated quantity
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,                 // Order trade time
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ForceOrder {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "o", alias = "order")]
+    pub order: LiquidationOrder,         // Liquidation order
+}
+
+impl ForceOrder {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CompositeIndexComponent {
+    #[serde(rename = "b", alias = "base-asset")]
+    pub base_asset: String,              // Base asset
+    #[serde(rename = "q", alias = "quote-asset")]
+    pub quote_asset: String,             // Quote asset
+    #[serde(rename = "w", alias = "weight-in-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub weight_in_quantity: f64,         // Weight in quantity
+    #[serde(rename = "W", alias = "weight-in-percentage", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub weight_in_percentage: f64,       // Weight in percentage
+    #[serde(rename = "i", alias = "index-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CompositeIndex {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "C", alias = "composition-asset")]
+    pub composition_asset: String,       // Composition asset
+    #[serde(rename = "c", alias = "composition")]
+    pub composition: Vec<CompositeIndexComponent>, // Composition
+}
+
+impl CompositeIndex {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContractStatus {
+    PendingTrading,
+    Trading,
+    PreDelivering,
+    Delivering,
+    Delivered,
+    PreSettle,
+    Settling,
+    Close,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContractBracket {
+    #[serde(rename = "bs", alias = "bracket")]
+    pub bracket: u32,                    // Notional bracket
+    #[serde(rename = "bnf", alias = "notional-floor")]
+    pub notional_floor: f64,             // Floor notional of this bracket
+    #[serde(rename = "bnc", alias = "notional-cap")]
+    pub notional_cap: f64,               // Cap notional of this bracket
+    #[serde(rename = "mmr", alias = "maintenance-margin-ratio")]
+    pub maintenance_margin_ratio: f64,   // Maintenance ratio for this bracket
+    #[serde(rename = "cf", alias = "auxiliary-number")]
+    pub auxiliary_number: f64,           // Auxiliary number for quick calculation
+    #[serde(rename = "mi", alias = "min-leverage")]
+    pub min_leverage: u32,               // Min leverage for this bracket
+    #[serde(rename = "ma", alias = "max-leverage")]
+    pub max_leverage: u32,               // Max leverage for this bracket
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContractInfo {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "ps", alias = "pair")]
+    pub pair: String,                    // Pair
+    #[serde(rename = "ct", alias = "contract-type")]
+    pub contract_type: String,           // Contract type
+    #[serde(rename = "dt", alias = "delivery-time")]
+    pub delivery_time: u64,              // Delivery date time
+    #[serde(rename = "ot", alias = "onboard-time")]
+    pub onboard_time: u64,               // Onboard date time
+    #[serde(rename = "cs", alias = "contract-status")]
+    pub contract_status: ContractStatus, // Contract status
+    #[serde(rename = "bks", alias = "brackets", default)]
+    pub brackets: Vec<ContractBracket>,  // Brackets (only pushed when bracket information changes)
+}
+
+impl ContractInfo {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AssetIndex {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Asset index symbol
+    #[serde(rename = "i", alias = "index-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+    #[serde(rename = "b", alias = "bid-buffer", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_buffer: f64,                 // Bid buffer
+    #[serde(rename = "a", alias = "ask-buffer", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_buffer: f64,                 // Ask buffer
+    #[serde(rename = "B", alias = "bid-rate", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bid_rate: f64,                   // Bid rate
+    #[serde(rename = "A", alias = "ask-rate", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub ask_rate: f64,                   // Ask rate
+    #[serde(rename = "q", alias = "auto-exchange-bid-buffer", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub auto_exchange_bid_buffer: f64,   // Auto exchange bid buffer
+    #[serde(rename = "g", alias = "auto-exchange-ask-buffer", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub auto_exchange_ask_buffer: f64,   // Auto exchange ask buffer
+    #[serde(rename = "Q", alias = "auto-exchange-bid-rate", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub auto_exchange_bid_rate: f64,     // Auto exchange bid rate
+    #[serde(rename = "G", alias = "auto-exchange-ask-rate", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub auto_exchange_ask_rate: f64,     // Auto exchange ask rate
+}
+
+impl AssetIndex {
+    // <assetSymbol>@assetIndex
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // !assetIndex@arr
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// USD-M futures streams spot does not have; symbols are lowercased when the name is built
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FuturesStreamName {
+    ForceOrder(String),                  // <symbol>@forceOrder, liquidations of one symbol
+    AllForceOrders,                      // !forceOrder@arr, liquidations of every symbol, each frame a single forceOrder object
+}
+
+impl fmt::Display for FuturesStreamName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuturesStreamName::ForceOrder(symbol) => write!(f, "{}@forceOrder", symbol.to_lowercase()),
+            FuturesStreamName::AllForceOrders => f.write_str("!forceOrder@arr"),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuturesEvent {
+    MarkPriceUpdate(MarkPriceUpdate),
+    MarkPriceUpdates(Vec<MarkPriceUpdate>), // !markPrice@arr
+    AggTrade(AggTrade),
+    Kline(KlineEvent),
+    ContinuousKline(ContinuousKlineEvent),
+    BookTicker(BookTicker),
+    DepthUpdate(DepthUpdate),
+    ForceOrder(ForceOrder),
+    CompositeIndex(CompositeIndex),
+    ContractInfo(ContractInfo),
+    AssetIndex(AssetIndex),
+    AssetIndexes(Vec<AssetIndex>),       // !assetIndex@arr
+    IndexPriceUpdate(IndexPriceUpdate), // COIN-M only
+    UserData(FuturesUserDataEvent),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl FuturesEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, FuturesEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, FuturesEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        if json.trim_start().starts_with('[') {
+            return Ok(array_event_from_json(json)?.unwrap_or_else(|| FuturesEvent::Unknown(json.to_string())));
+        }
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("markPriceUpdate") => MarkPriceUpdate::from_json(json).map(FuturesEvent::MarkPriceUpdate),
+            Some("aggTrade") => AggTrade::from_json(json).map(FuturesEvent::AggTrade),
+            Some("kline") => KlineEvent::from_json(json).map(FuturesEvent::Kline),
+            Some("continuous_kline") => ContinuousKlineEvent::from_json(json).map(FuturesEvent::ContinuousKline),
+            Some("bookTicker") => BookTicker::from_json(json).map(FuturesEvent::BookTicker),
+            Some("depthUpdate") => DepthUpdate::from_json(json).map(FuturesEvent::DepthUpdate),
+            Some("forceOrder") => ForceOrder::from_json(json).map(FuturesEvent::ForceOrder),
+            Some("compositeIndex") => CompositeIndex::from_json(json).map(FuturesEvent::CompositeIndex),
+            Some("contractInfo") => ContractInfo::from_json(json).map(FuturesEvent::ContractInfo),
+            Some("assetIndexUpdate") => AssetIndex::from_json(json).map(FuturesEvent::AssetIndex),
+            Some("indexPriceUpdate") => IndexPriceUpdate::from_json(json).map(FuturesEvent::IndexPriceUpdate),
+            event_type => match FuturesUserDataEvent::from_tagged(event_type, json)? {
+                FuturesUserDataEvent::Unknown(raw) => Ok(FuturesEvent::Unknown(raw)),
+                event => Ok(FuturesEvent::UserData(event)),
+            },
+        }
+    }
+}
+
+impl ArrayEvent for FuturesEvent {
+    fn from_elements<'de, A: serde::de::SeqAccess<'de>>(event_type: &str, first: serde_json::Value, rest: &mut A) -> Result<Option<Self>, A::Error> {
+        match event_type {
+            "markPriceUpdate" => collect_elements(first, rest).map(|events| Some(FuturesEvent::MarkPriceUpdates(events))),
+            "assetIndexUpdate" => collect_elements(first, rest).map(|events| Some(FuturesEvent::AssetIndexes(events))),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::make_combined;
+    use crate::websocket::frame::split_combined;
+
+    #[test]
+    fn test_mark_price_update_deserialization() {
+        let json = r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000}"#;
+        let expected = MarkPriceUpdate {
+            event_type: "markPriceUpdate".to_string(),
+            event_time: 1562305380000,
+            symbol: "BTCUSDT".to_string(),
+            mark_price: 11794.15,
+            index_price: 11784.62659091,
+            estimated_settle_price: Some(11784.25641265),
+            funding_rate: Some(0.00038167),
+            next_funding_time: 1562306400000,
+        };
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::MarkPriceUpdate(expected));
+    }
+
+    #[test]
+    fn test_mark_price_array_dispatch() {
+        let json = r#"[{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000},{"e":"markPriceUpdate","E":1562305380000,"s":"ETHUSDT","p":"2558.31000000","i":"2557.94","r":"0.0001","T":1562306400000}]"#;
+        let FuturesEvent::MarkPriceUpdates(updates) = FuturesEvent::from_json(json).unwrap() else { panic!("expected mark price updates") };
+        assert_eq!(updates, MarkPriceUpdate::from_json_array(json).unwrap());
+        assert_eq!(updates[1].estimated_settle_price, None);
+
+        let json = r#"[{"e":"assetIndexUpdate","E":1686749230000,"s":"ADAUSD","i":"0.27462452","b":"0.10000000","a":"0.10000000","B":"0.24716207","A":"0.30208698","q":"0.05000000","g":"0.05000000","Q":"0.26089330","G":"0.28835575"},{"e":"assetIndexUpdate","E":1686749230000,"s":"USDTUSD","i":"0.99987691","b":"0.00010000","a":"0.00010000","B":"0.99977692","A":"0.99997689","q":"0.00010000","g":"0.00010000","Q":"0.99977692","G":"0.99997689"}]"#;
+        let FuturesEvent::AssetIndexes(indices) = FuturesEvent::from_json(json).unwrap() else { panic!("expected asset indexes") };
+        assert_eq!(indices.len(), 2);
+        assert_eq!(indices, AssetIndex::from_json_array(json).unwrap());
+
+        let json = r#"[{"e":"compositeIndex","E":1602310596000}]"#;
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::Unknown(json.to_string()));
+        assert_eq!(FuturesEvent::from_json("[]").unwrap(), FuturesEvent::Unknown("[]".to_string()));
+        assert!(FuturesEvent::from_json(r#"[{"e":"markPriceUpdate","E":1}]"#).is_err());
+    }
+
+    #[test]
+    fn test_agg_trade_deserialization() {
+        let json = r#"{"e":"aggTrade","E":123456789,"s":"BTCUSDT","a":5933014,"p":"0.001","q":"100","nq":"100","f":100,"l":105,"T":123456785,"m":true}"#;
+        let expected = AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: 123456789,
+            symbol: "BTCUSDT".to_string(),
+            aggregate_trade_id: 5933014,
+            price: 0.001,
+            quantity: 100.0,
+            normal_quantity: Some(100.0),
+            first_trade_id: 100,
+            last_trade_id: 105,
+            trade_time: 123456785,
+            is_buyer_market_maker: true,
+        };
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::AggTrade(expected));
+    }
+
+    #[test]
+    fn test_kline_event_deserialization() {
+        let json = r#"{"e":"kline","E":1638747660000,"s":"BTCUSDT","k":{"t":1638747660000,"T":1638747719999,"s":"BTCUSDT","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#;
+        let event = FuturesEvent::from_json(json).unwrap();
+
+        let FuturesEvent::Kline(kline_event) = event else { panic!("expected kline, got {event:?}") };
+        assert_eq!(kline_event.symbol, "BTCUSDT");
+        assert_eq!(kline_event.kline.symbol.as_deref(), Some("BTCUSDT"));
+        assert_eq!(kline_event.kline.interval, KlineInterval::M1);
+        assert!(KlineEvent::from_json(&json.replace(r#""i":"1m""#, r#""i":"7m""#)).is_err());
+        assert_eq!(kline_event.kline.high_price, 0.0025);
+        assert_eq!(kline_event.kline.quote_asset_volume, 1.0);
+        assert!(!kline_event.kline.is_closed);
+    }
+
+    #[test]
+    fn test_continuous_kline_event_deserialization() {
+        let json = r#"{"e":"continuous_kline","E":1607443058651,"ps":"BTCUSDT","ct":"PERPETUAL","k":{"t":1607443020000,"T":1607443079999,"i":"1m","f":116467658886,"L":116468012423,"o":"18787.00","c":"18804.04","h":"18804.04","l":"18786.54","v":"197.664","n":543,"x":false,"q":"3715253.19494","V":"184.769","Q":"3472925.84746","B":"0"}}"#;
+        let event = FuturesEvent::from_json(json).unwrap();
+
+        let FuturesEvent::ContinuousKline(kline_event) = event else { panic!("expected continuous kline, got {event:?}") };
+        assert_eq!(kline_event.pair, "BTCUSDT");
+        assert_eq!(kline_event.contract_type, "PERPETUAL");
+        assert_eq!(kline_event.kline.symbol, None);
+        assert_eq!(kline_event.kline.close_price, 18804.04);
+        assert_eq!(kline_event.kline.number_of_trades, 543);
+    }
+
+    #[test]
+    fn test_book_ticker_deserialization() {
+        let json = r#"{"e":"bookTicker","u":400900217,"E":1568014460893,"T":1568014460891,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        let expected = BookTicker {
+            event_type: "bookTicker".to_string(),
+            update_id: 400900217,
+            event_time: 1568014460893,
+            transaction_time: 1568014460891,
+            symbol: "BNBUSDT".to_string(),
+            best_bid_price: 25.3519,
+            best_bid_quantity: 31.21,
+            best_ask_price: 25.3652,
+            best_ask_quantity: 40.66,
+        };
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::BookTicker(expected));
+    }
+
+    #[test]
+    fn test_depth_update_deserialization() {
+        let json = r#"{"e":"depthUpdate","E":123456789,"T":123456788,"s":"BTCUSDT","U":157,"u":160,"pu":149,"b":[["0.0024","10"]],"a":[["0.0026","100"]]}"#;
+        let expected = DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: 123456789,
+            transaction_time: 123456788,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 157,
+            final_update_id: 160,
+            previous_final_update_id: 149,
+            bids: vec![PriceLevel { price: 0.0024, quantity: 10.0 }],
+            asks: vec![PriceLevel { price: 0.0026, quantity: 100.0 }],
+        };
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::DepthUpdate(expected));
+    }
+
+    #[test]
+    fn test_force_order_deserialization() {
+        let json = r#"{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}"#;
+        let expected = ForceOrder {
+            event_type: "forceOrder".to_string(),
+            event_time: 1568014460893,
+            order: LiquidationOrder {
+                symbol: "BTCUSDT".to_string(),
+                side: Side::Sell,
+                order_type: "LIMIT".to_string(),
+                time_in_force: "IOC".to_string(),
+                original_quantity: 0.014,
+                price: 9910.0,
+                average_price: 9910.0,
+                order_status: "FILLED".to_string(),
+                last_filled_quantity: 0.014,
+                filled_accumulated_quantity: 0.014,
+                trade_time: 1568014460893,
+            },
+        };
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::ForceOrder(expected));
+    }
+
+    #[test]
+    fn test_force_order_on_all_market_stream() {
+        let json = r#"{"e":"forceOrder","E":1713934741520,"o":{"s":"1000PEPEUSDT","S":"BUY","o":"LIMIT","f":"IOC","q":"551696","p":"0.0072261","ap":"0.0071634","X":"FILLED","l":"551696","z":"551696","T":1713934741516}}"#;
+        let streams = [FuturesStreamName::ForceOrder("1000PEPEUSDT".to_string()), FuturesStreamName::AllForceOrders];
+        let events: Vec<_> = streams.iter().map(|stream| {
+            let frame = make_combined(&stream.to_string(), json);
+            let (_, data) = split_combined(&frame).unwrap();
+            FuturesEvent::from_json(data).unwrap()
+        }).collect();
+
+        let FuturesEvent::ForceOrder(event) = &events[0] else { panic!("expected a force order, got {:?}", events[0]) };
+        assert_eq!((event.order.symbol.as_str(), event.order.side), ("1000PEPEUSDT", Side::Buy));
+        assert_eq!(events[0], events[1]);
+        assert_eq!(streams.map(|stream| stream.to_string()), ["1000pepeusdt@forceOrder", "!forceOrder@arr"]);
+    }
+
+    #[test]
+    fn test_futures_event_routes_index_streams() {
+        let json = r#"{"e":"compositeIndex","E":1602310596000,"s":"DEFIUSDT","p":"554.41604065","C":"baseAsset","c":[{"b":"BAL","q":"USDT","w":"1.04884844","W":"0.01457800","i":"24.33521021"}]}"#;
+        assert!(matches!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::CompositeIndex(_)));
+
+        let json = r#"{"e":"contractInfo","E":1669356423908,"s":"IOTAUSDT","ps":"IOTAUSDT","ct":"PERPETUAL","dt":4133404800000,"ot":1569398400000,"cs":"TRADING","bks":[]}"#;
+        assert!(matches!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::ContractInfo(_)));
+
+        let json = r#"{"e":"assetIndexUpdate","E":1686749230000,"s":"ADAUSD","i":"0.27462452","b":"0.10000000","a":"0.10000000","B":"0.24716207","A":"0.30208698","q":"0.05000000","g":"0.05000000","Q":"0.26089330","G":"0.28835575"}"#;
+        assert!(matches!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::AssetIndex(_)));
+
+        let json = r#"{"e":"indexPriceUpdate","E":1591261236000,"i":"BTCUSD","p":"9636.57860000"}"#;
+        assert!(matches!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::IndexPriceUpdate(_)));
+    }
+
+    #[test]
+    fn test_futures_event_routes_user_data_and_unknown() {
+        let json = r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ai":{"j":true}}"#;
+        let event = FuturesEvent::from_json(json).unwrap();
+        assert!(matches!(event, FuturesEvent::UserData(FuturesUserDataEvent::AccountConfigUpdate(_))));
+
+        let json = r#"{"e":"somethingNew","E":1611646737479}"#;
+        assert_eq!(FuturesEvent::from_json(json).unwrap(), FuturesEvent::Unknown(json.to_string()));
+
+        assert!(FuturesEvent::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_composite_index_deserialization() {
+        let json = r#"{"e":"compositeIndex","E":1602310596000,"s":"DEFIUSDT","p":"554.41604065","C":"baseAsset","c":[{"b":"BAL","q":"USDT","w":"1.04884844","W":"0.01457800","i":"24.33521021"},{"b":"BAND","q":"USDT","w":"3.53782729","W":"0.03935200","i":"7.26420084"},{"b":"COMP","q":"USDT","w":"0.13479213","W":"0.06994300","i":"143.01750000"}]}"#;
+        let index = CompositeIndex::from_json(json).unwrap();
+
+        assert_eq!(index.event_type, "compositeIndex");
+        assert_eq!(index.event_time, 1602310596000);
+        assert_eq!(index.symbol, "DEFIUSDT");
+        assert_eq!(index.price, 554.41604065);
+        assert_eq!(index.composition_asset, "baseAsset");
+        assert_eq!(index.composition.len(), 3);
+        assert_eq!(index.composition[1], CompositeIndexComponent {
+            base_asset: "BAND".to_string(),
+            quote_asset: "USDT".to_string(),
+            weight_in_quantity: 3.53782729,
+            weight_in_percentage: 0.039352,
+            index_price: 7.26420084,
+        });
+        assert_eq!(index.composition[2].base_asset, "COMP");
+        assert_eq!(index.composition[2].index_price, 143.0175);
+    }
+
+    #[test]
+    fn test_contract_info_deserialization() {
+        let json = r#"{"e":"contractInfo","E":1669356423908,"s":"IOTAUSDT","ps":"IOTAUSDT","ct":"PERPETUAL","dt":4133404800000,"ot":1569398400000,"cs":"TRADING","bks":[{"bs":1,"bnf":0,"bnc":5000,"mmr":0.01,"cf":0,"mi":21,"ma":50},{"bs":2,"bnf":5000,"bnc":25000,"mmr":0.025,"cf":75,"mi":11,"ma":20}]}"#;
+        let info = ContractInfo::from_json(json).unwrap();
+
+        assert_eq!(info.event_type, "contractInfo");
+        assert_eq!(info.event_time, 1669356423908);
+        assert_eq!(info.symbol, "IOTAUSDT");
+        assert_eq!(info.pair, "IOTAUSDT");
+        assert_eq!(info.contract_type, "PERPETUAL");
+        assert_eq!(info.delivery_time, 4133404800000);
+        assert_eq!(info.onboard_time, 1569398400000);
+        assert_eq!(info.contract_status, ContractStatus::Trading);
+        assert_eq!(info.brackets, vec![
+            ContractBracket { bracket: 1, notional_floor: 0.0, notional_cap: 5000.0, maintenance_margin_ratio: 0.01, auxiliary_number: 0.0, min_leverage: 21, max_leverage: 50 },
+            ContractBracket { bracket: 2, notional_floor: 5000.0, notional_cap: 25000.0, maintenance_margin_ratio: 0.025, auxiliary_number: 75.0, min_leverage: 11, max_leverage: 20 },
+        ]);
+    }
+
+    #[test]
+    fn test_contract_info_status_without_brackets() {
+        let json = r#"{"e":"contractInfo","E":1669356423908,"s":"BTCUSDT_230331","ps":"BTCUSDT","ct":"CURRENT_QUARTER","dt":1680249600000,"ot":1672387200000,"cs":"SETTLING"}"#;
+        let info = ContractInfo::from_json(json).unwrap();
+
+        assert_eq!(info.contract_status, ContractStatus::Settling);
+        assert!(info.brackets.is_empty());
+
+        let json = json.replace("SETTLING", "SOME_NEW_STATUS");
+        assert_eq!(ContractInfo::from_json(&json).unwrap().contract_status, ContractStatus::Unknown);
+    }
+
+    #[test]
+    fn test_asset_index_array_deserialization() {
+        let json = r#"[{"e":"assetIndexUpdate","E":1686749230000,"s":"ADAUSD","i":"0.27462452","b":"0.10000000","a":"0.10000000","B":"0.24716207","A":"0.30208698","q":"0.05000000","g":"0.05000000","Q":"0.26089330","G":"0.28835575"},{"e":"assetIndexUpdate","E":1686749230000,"s":"USDTUSD","i":"0.99987691","b":"0.00010000","a":"0.00010000","B":"0.99977692","A":"0.99997689","q":"0.00010000","g":"0.00010000","Q":"0.99977692","G":"0.99997689"}]"#;
+        let indices = AssetIndex::from_json_array(json).unwrap();
+
+        assert_eq!(indices.len(), 2);
+        assert_eq!(indices[0], AssetIndex {
+            event_type: "assetIndexUpdate".to_string(),
+            event_time: 1686749230000,
+            symbol: "ADAUSD".to_string(),
+            index_price: 0.27462452,
+            bid_buffer: 0.1,
+            ask_buffer: 0.1,
+            bid_rate: 0.24716207,
+            ask_rate: 0.30208698,
+            auto_exchange_bid_buffer: 0.05,
+            auto_exchange_ask_buffer: 0.05,
+            auto_exchange_bid_rate: 0.2608933,
+            auto_exchange_ask_rate: 0.28835575,
+        });
+        assert_eq!(indices[1].symbol, "USDTUSD");
+        assert_eq!(indices[1].index_price, 0.99987691);
+    }
+
+    #[test]
+    fn test_asset_index_single_deserialization() {
+        let json = r#"{"e":"assetIndexUpdate","E":1686749230000,"s":"ADAUSD","i":"0.27462452","b":"0.10000000","a":"0.10000000","B":"0.24716207","A":"0.30208698","q":"0.05000000","g":"0.05000000","Q":"0.26089330","G":"0.28835575"}"#;
+        let index = AssetIndex::from_json(json).unwrap();
+
+        assert_eq!(index.symbol, "ADAUSD");
+        assert_eq!(index.auto_exchange_ask_rate, 0.28835575);
+    }
+}
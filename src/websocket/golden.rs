@@ -0,0 +1,161 @@
+//! Golden round-trip tests: every event type parsed from a captured payload in `testutil` and serialized
+//! back to the wire format.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::format::{with_price_format, PriceFormat};
+use crate::testutil;
+
+// Byte-for-byte comparison is only possible when the payload formats every number the same way
+enum Exact {
+    No,
+    With(PriceFormat),
+}
+
+fn assert_golden<T: DeserializeOwned + Serialize>(name: &str, fixture: &str, exact: Exact) {
+    let fixture = fixture.trim_end();
+    let parsed: T = serde_json::from_str(fixture).unwrap_or_else(|error| panic!("{name}: {error}"));
+
+    let wire: Value = serde_json::from_str(fixture).unwrap();
+    let serialized = serde_json::to_value(&parsed).unwrap();
+    assert_same(name, "$", &wire, &serialized);
+
+    let reparsed: T = serde_json::from_value(serialized).unwrap_or_else(|error| panic!("{name}: {error}"));
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), serde_json::to_value(&parsed).unwrap(), "{name}");
+
+    if let Exact::With(format) = exact {
+        let json = with_price_format(format, || serde_json::to_string(&parsed).unwrap());
+        assert_eq!(json, fixture, "{name}: serialization differs from the captured payload");
+    }
+}
+
+// Same keys, shape and JSON types. Only the formatting of numbers is normalized, so "0.00100000" matches
+// "0.001" and 1.0 matches 1, but a numeric string never matches a JSON number
+fn assert_same(name: &str, path: &str, wire: &Value, serialized: &Value) {
+    match (wire, serialized) {
+        (Value::Object(wire), Value::Object(serialized)) => {
+            let missing: Vec<&String> = wire.keys().filter(|key| !serialized.contains_key(*key)).collect();
+            let extra: Vec<&String> = serialized.keys().filter(|key| !wire.contains_key(*key)).collect();
+            assert!(missing.is_empty() && extra.is_empty(), "{name}: at {path} missing {missing:?}, extra {extra:?}");
+            for (key, value) in wire {
+                assert_same(name, &format!("{path}.{key}"), value, &serialized[key]);
+            }
+        }
+        (Value::Array(wire), Value::Array(serialized)) => {
+            assert_eq!(wire.len(), serialized.len(), "{name}: length of {path}");
+            for (index, (wire, serialized)) in wire.iter().zip(serialized).enumerate() {
+                assert_same(name, &format!("{path}[{index}]"), wire, serialized);
+            }
+        }
+        (Value::Number(wire), Value::Number(serialized)) if wire.as_f64() == serialized.as_f64() => {}
+        (Value::String(wire), Value::String(serialized)) if numeric(wire).is_some() && numeric(wire) == numeric(serialized) => {}
+        _ => assert_eq!(wire, serialized, "{name}: at {path}"),
+    }
+}
+
+fn numeric(string: &str) -> Option<f64> {
+    string.parse().ok()
+}
+
+macro_rules! golden {
+    ($test:ident, $type:ty, $sample:path, $exact:expr) => {
+        #[test]
+        fn $test() {
+            assert_golden::<$type>(stringify!($sample), $sample(), $exact);
+        }
+    };
+}
+
+#[cfg(feature = "spot")]
+mod spot {
+    use super::*;
+    use crate::websocket::{
+        AggTrade, BalanceUpdate, BookTicker, DepthUpdate, ExecutionReport, KlineEvent, ListenKeyExpired,
+        OutboundAccountPosition, PartialDepth, Trade,
+    };
+
+    golden!(test_golden_trade, Trade, testutil::sample_trade_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_agg_trade, AggTrade, testutil::sample_agg_trade_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_kline, KlineEvent, testutil::sample_kline_json, Exact::No);
+    golden!(test_golden_book_ticker, BookTicker, testutil::sample_book_ticker_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_depth_update, DepthUpdate, testutil::sample_depth_update_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_partial_depth, PartialDepth, testutil::sample_partial_depth_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_execution_report, ExecutionReport, testutil::sample_execution_report_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_outbound_account_position, OutboundAccountPosition, testutil::sample_outbound_account_position_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_balance_update, BalanceUpdate, testutil::sample_balance_update_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_listen_key_expired, ListenKeyExpired, testutil::sample_listen_key_expired_json, Exact::With(PriceFormat::Shortest));
+
+    #[test]
+    fn test_long_names_still_deserialize() {
+        let normalized = r#"{"event-type":"trade","event-time":1672515782136,"symbol":"BNBBTC","trade-id":12345,"price":"0.001","quantity":"100","trade-time":1672515782136,"is-buyer-market-maker":true,"ignore":true}"#;
+        let trade: Trade = serde_json::from_str(normalized).unwrap();
+        let wire = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","T":1672515782136,"m":true,"M":true}"#;
+        assert_eq!(trade, serde_json::from_str::<Trade>(wire).unwrap());
+        assert_eq!(serde_json::to_string(&trade).unwrap(), wire);
+    }
+}
+
+#[cfg(feature = "futures")]
+mod futures {
+    use super::*;
+    use crate::websocket::delivery;
+    use crate::websocket::futures::{
+        AggTrade, AssetIndex, BookTicker, CompositeIndex, ContinuousKlineEvent, ContractInfo, DepthUpdate, ForceOrder,
+        KlineEvent, MarkPriceUpdate,
+    };
+    use crate::websocket::{
+        AccountConfigUpdate, AccountUpdate, GridUpdate, ListenKeyExpired, MarginCall, OrderTradeUpdate, StrategyUpdate,
+        TradeLite,
+    };
+
+    golden!(test_golden_mark_price_update, MarkPriceUpdate, testutil::futures::sample_mark_price_update_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_agg_trade, AggTrade, testutil::futures::sample_agg_trade_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_kline, KlineEvent, testutil::futures::sample_kline_json, Exact::No);
+    golden!(test_golden_continuous_kline, ContinuousKlineEvent, testutil::futures::sample_continuous_kline_json, Exact::No);
+    golden!(test_golden_book_ticker, BookTicker, testutil::futures::sample_book_ticker_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_depth_update, DepthUpdate, testutil::futures::sample_depth_update_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_force_order, ForceOrder, testutil::futures::sample_force_order_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_composite_index, CompositeIndex, testutil::futures::sample_composite_index_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_contract_info, ContractInfo, testutil::futures::sample_contract_info_json, Exact::No);
+    golden!(test_golden_asset_index, AssetIndex, testutil::futures::sample_asset_index_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_margin_call, MarginCall, testutil::futures::sample_margin_call_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_account_config_update_leverage, AccountConfigUpdate, testutil::futures::sample_account_config_update_leverage_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_account_config_update_multi_assets, AccountConfigUpdate, testutil::futures::sample_account_config_update_multi_assets_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_trade_lite, TradeLite, testutil::futures::sample_trade_lite_json, Exact::No);
+    golden!(test_golden_strategy_update, StrategyUpdate, testutil::futures::sample_strategy_update_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_grid_update, GridUpdate, testutil::futures::sample_grid_update_json, Exact::No);
+    golden!(test_golden_account_update, AccountUpdate, testutil::futures::sample_account_update_json, Exact::No);
+    golden!(test_golden_order_trade_update, OrderTradeUpdate, testutil::futures::sample_order_trade_update_json, Exact::No);
+
+    golden!(test_golden_delivery_index_price_update, delivery::IndexPriceUpdate, testutil::delivery::sample_index_price_update_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_delivery_mark_price_update, delivery::MarkPriceUpdate, testutil::delivery::sample_mark_price_update_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_delivery_kline, delivery::KlineEvent, testutil::delivery::sample_kline_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_delivery_ticker, delivery::Ticker, testutil::delivery::sample_ticker_json, Exact::With(PriceFormat::Shortest));
+
+    // `ListenKeyExpired` is shared with spot, which sends the event time as a number, so the string the
+    // futures stream sends is not kept
+    #[test]
+    fn test_listen_key_expired_event_time_serialized_as_number() {
+        let event: ListenKeyExpired = serde_json::from_str(testutil::futures::sample_listen_key_expired_json()).unwrap();
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(serialized["E"], Value::from(event.event_time));
+        assert_eq!(serde_json::from_value::<ListenKeyExpired>(serialized).unwrap(), event);
+    }
+}
+
+#[cfg(feature = "options")]
+mod options {
+    use super::*;
+    use crate::websocket::options::{Depth, IndexPrice, KlineEvent, MarkPrice, OpenInterest, OpenSymbolInfo, Ticker, Trade};
+
+    golden!(test_golden_trade, Trade, testutil::options::sample_trade_json, Exact::No);
+    golden!(test_golden_ticker, Ticker, testutil::options::sample_ticker_json, Exact::No);
+    golden!(test_golden_index, IndexPrice, testutil::options::sample_index_json, Exact::With(PriceFormat::WIRE));
+    golden!(test_golden_mark_price, Vec<MarkPrice>, testutil::options::sample_mark_price_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_kline, KlineEvent, testutil::options::sample_kline_json, Exact::No);
+    golden!(test_golden_open_interest, Vec<OpenInterest>, testutil::options::sample_open_interest_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_option_pair, OpenSymbolInfo, testutil::options::sample_option_pair_json, Exact::With(PriceFormat::Shortest));
+    golden!(test_golden_depth, Depth, testutil::options::sample_depth_json, Exact::No);
+}
@@ -0,0 +1,233 @@
+//! Any spot websocket text frame parsed through one entry point, request responses and stream events alike.
+
+use serde::Deserialize;
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::rest::BinanceApiError;
+use super::frame::{classify, split_combined, FrameKind};
+use super::{BookTicker, PartialDepth, ScalarValue, SpotEvent, StreamName, UserDataEvent};
+
+// Response to a SUBSCRIBE, UNSUBSCRIBE, LIST_SUBSCRIPTIONS, GET_PROPERTY or SET_PROPERTY request. Ids
+// that are not unsigned integers are None
+#[derive(Clone, Debug, PartialEq)]
+pub enum ControlResponse {
+    Ack { id: Option<u64> },             // `{"result":null,"id":1}`, also the answer to SET_PROPERTY
+    List { id: Option<u64>, streams: Vec<String> }, // LIST_SUBSCRIPTIONS
+    Property { id: Option<u64>, value: ScalarValue }, // GET_PROPERTY, e.g. `{"result":true,"id":5}`
+    Error { id: Option<u64>, error: BinanceApiError }, // `{"error":{...},"id":1}` or a bare `{"code":...,"msg":...}`
+}
+
+impl ControlResponse {
+    pub fn id(&self) -> Option<u64> {
+        match self {
+            ControlResponse::Ack { id }
+            | ControlResponse::List { id, .. }
+            | ControlResponse::Property { id, .. }
+            | ControlResponse::Error { id, .. } => *id,
+        }
+    }
+}
+
+// Frame of a combined stream, `data` is never a control response or another envelope
+#[derive(Clone, Debug, PartialEq)]
+pub struct CombinedEnvelope {
+    pub stream: String,                  // Stream name, e.g. "bnbbtc@bookTicker"
+    pub data: Box<Message>,              // Event parsed with the stream name as a hint
+}
+
+// Frames are told apart by their top-level keys in one scan, see `frame::classify`, and then parsed once
+// by the matching parser. The rules, in order:
+// - `stream` and `data` make a combined envelope. Its data is a book ticker for `<symbol>@bookTicker`,
+//   a partial depth for `<symbol>@depth<levels>` and otherwise parsed like a raw frame
+// - `e` makes an event. User data event types are `UserData`, market events `Spot` (never holding
+//   `SpotEvent::UserData`), other event types and array streams such as `!ticker@arr` `Unknown`
+// - `error`, or `code` with `msg`, is a control error
+// - `result` with `id` is a control response: an ack if the result is null, which is also what
+//   SET_PROPERTY answers, a stream list if it is an array and a property value if it is a scalar
+// - Objects with none of these keys are tried as a book ticker, then as a partial depth, since raw spot
+//   `@bookTicker` and `@depth<levels>` frames have no `e`
+// Everything else, including responses with object results, is `Unknown` with the raw frame
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Control(ControlResponse),
+    Spot(SpotEvent),
+    UserData(UserDataEvent),
+    BookTicker(BookTicker),
+    PartialDepth(PartialDepth),
+    Combined(CombinedEnvelope),
+    Unknown(String),                     // Raw frame
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ResultValue {
+    Streams(Vec<String>),
+    Value(ScalarValue),
+}
+
+#[derive(Deserialize)]
+struct RawResponse {
+    result: ResultValue,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawError {
+    Wrapped { error: BinanceApiError },
+    Bare(BinanceApiError),
+}
+
+impl Message {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, Message::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, Message::from_json)
+    }
+
+    // Fails only when a frame recognized as an event does not parse as one
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let unknown = || Ok(Message::Unknown(json.to_string()));
+        match classify(json) {
+            FrameKind::CombinedEvent => match split_combined(json) {
+                Some((stream, data)) => Ok(Message::Combined(CombinedEnvelope {
+                    stream: stream.to_string(),
+                    data: Box::new(Message::from_stream_data(stream, data)?),
+                })),
+                None => unknown(),
+            },
+            FrameKind::Event => Message::from_event(json),
+            FrameKind::Ack { id } => Ok(Message::Control(ControlResponse::Ack { id })),
+            FrameKind::Response { id } => match serde_json::from_str::<RawResponse>(json) {
+                Ok(RawResponse { result: ResultValue::Streams(streams) }) => Ok(Message::Control(ControlResponse::List { id, streams })),
+                Ok(RawResponse { result: ResultValue::Value(value) }) => Ok(Message::Control(ControlResponse::Property { id, value })),
+                Err(_) => unknown(),
+            },
+            FrameKind::Error { id } => match serde_json::from_str::<RawError>(json) {
+                Ok(RawError::Wrapped { error } | RawError::Bare(error)) => Ok(Message::Control(ControlResponse::Error { id, error })),
+                Err(_) => unknown(),
+            },
+            FrameKind::Unknown if json.trim_start().starts_with('{') => {
+                if let Ok(ticker) = BookTicker::from_json(json) {
+                    Ok(Message::BookTicker(ticker))
+                } else if let Ok(depth) = PartialDepth::from_json(json) {
+                    Ok(Message::PartialDepth(depth))
+                } else {
+                    unknown()
+                }
+            }
+            FrameKind::Unknown => unknown(),
+        }
+    }
+
+    fn from_event(json: &str) -> Result<Self, serde_json::Error> {
+        if json.trim_start().starts_with('[') {
+            return Ok(Message::Unknown(json.to_string()));
+        }
+        Ok(match SpotEvent::from_json(json)? {
+            SpotEvent::UserData(event) => Message::UserData(event),
+            SpotEvent::Unknown(raw) => Message::Unknown(raw),
+            event => Message::Spot(event),
+        })
+    }
+
+    fn from_stream_data(stream: &str, data: &str) -> Result<Self, serde_json::Error> {
+        match stream.parse::<StreamName>() {
+            Ok(StreamName::BookTicker(_)) => BookTicker::from_json(data).map(Message::BookTicker),
+            Ok(StreamName::PartialDepth(..)) => PartialDepth::from_json(data).map(Message::PartialDepth),
+            _ => match classify(data) {
+                FrameKind::Event => Message::from_event(data),
+                _ => Ok(Message::Unknown(data.to_string())),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{
+        make_combined, sample_agg_trade_json, sample_balance_update_json, sample_book_ticker_json,
+        sample_depth_update_json, sample_execution_report_json, sample_kline_json, sample_listen_key_expired_json,
+        sample_partial_depth_json, sample_trade_json,
+    };
+    use crate::websocket::{KlineInterval, Trade};
+
+    #[test]
+    fn test_control_responses() {
+        assert_eq!(Message::from_json(r#"{"result":null,"id":1}"#).unwrap(), Message::Control(ControlResponse::Ack { id: Some(1) }));
+        assert_eq!(
+            Message::from_json(r#"{"result":["btcusdt@aggTrade","btcusdt@depth"],"id":3}"#).unwrap(),
+            Message::Control(ControlResponse::List { id: Some(3), streams: vec!["btcusdt@aggTrade".to_string(), "btcusdt@depth".to_string()] }),
+        );
+        assert_eq!(
+            Message::from_json(r#"{"result":true,"id":5}"#).unwrap(),
+            Message::Control(ControlResponse::Property { id: Some(5), value: ScalarValue::Bool(true) }),
+        );
+
+        let error = BinanceApiError { code: 2, msg: "Invalid request: unknown variant".to_string() };
+        match Message::from_json(r#"{"error":{"code":2,"msg":"Invalid request: unknown variant"},"id":7}"#).unwrap() {
+            Message::Control(control) => {
+                assert_eq!(control.id(), Some(7));
+                assert_eq!(control, ControlResponse::Error { id: Some(7), error: error.clone() });
+            }
+            other => panic!("expected a control error, got {:?}", other),
+        }
+        assert_eq!(
+            Message::from_json(r#"{"code":2,"msg":"Invalid request: unknown variant"}"#).unwrap(),
+            Message::Control(ControlResponse::Error { id: None, error }),
+        );
+    }
+
+    #[test]
+    fn test_spot_and_user_data_events() {
+        assert!(matches!(Message::from_json(sample_trade_json()).unwrap(), Message::Spot(SpotEvent::Trade(trade)) if trade.trade_id == 2921785139));
+        assert!(matches!(Message::from_json(sample_agg_trade_json()).unwrap(), Message::Spot(SpotEvent::AggTrade(_))));
+        assert!(matches!(Message::from_json(sample_kline_json()).unwrap(), Message::Spot(SpotEvent::Kline(kline)) if kline.kline.interval == KlineInterval::M1));
+        assert!(matches!(Message::from_json(sample_depth_update_json()).unwrap(), Message::Spot(SpotEvent::DepthUpdate(_))));
+
+        assert!(matches!(Message::from_json(sample_execution_report_json()).unwrap(), Message::UserData(UserDataEvent::ExecutionReport(_))));
+        assert!(matches!(Message::from_json(sample_balance_update_json()).unwrap(), Message::UserData(UserDataEvent::BalanceUpdate(_))));
+        assert!(matches!(Message::from_json(sample_listen_key_expired_json()).unwrap(), Message::UserData(UserDataEvent::ListenKeyExpired(_))));
+    }
+
+    #[test]
+    fn test_frames_without_event_type() {
+        assert_eq!(Message::from_json(sample_book_ticker_json()).unwrap(), Message::BookTicker(BookTicker::from_json(sample_book_ticker_json()).unwrap()));
+        assert_eq!(Message::from_json(sample_partial_depth_json()).unwrap(), Message::PartialDepth(PartialDepth::from_json(sample_partial_depth_json()).unwrap()));
+    }
+
+    #[test]
+    fn test_combined_envelopes() {
+        let frame = make_combined("ethusdt@trade", sample_trade_json());
+        match Message::from_json(&frame).unwrap() {
+            Message::Combined(envelope) => {
+                assert_eq!(envelope.stream, "ethusdt@trade");
+                assert_eq!(*envelope.data, Message::Spot(SpotEvent::Trade(Trade::from_json(sample_trade_json()).unwrap())));
+            }
+            other => panic!("expected a combined envelope, got {:?}", other),
+        }
+
+        let frame = make_combined("bnbusdt@bookTicker", sample_book_ticker_json());
+        assert!(matches!(Message::from_json(&frame).unwrap(), Message::Combined(envelope) if matches!(*envelope.data, Message::BookTicker(_))));
+        let frame = make_combined("bnbbtc@depth5@100ms", sample_partial_depth_json());
+        assert!(matches!(Message::from_json(&frame).unwrap(), Message::Combined(envelope) if matches!(*envelope.data, Message::PartialDepth(_))));
+        let frame = make_combined("!miniTicker@arr", r#"[{"e":"24hrMiniTicker","E":1,"s":"BNBBTC"}]"#);
+        assert!(matches!(Message::from_json(&frame).unwrap(), Message::Combined(envelope) if matches!(*envelope.data, Message::Unknown(_))));
+    }
+
+    #[test]
+    fn test_unknown_and_failing_frames() {
+        for frame in ["pong", "", r#"{"e":"newEvent","E":1}"#, r#"[{"e":"24hrTicker","E":1,"s":"BNBBTC"}]"#, r#"{"result":{"serverTime":1},"id":9}"#, r#"{"foo":1}"#] {
+            assert_eq!(Message::from_json(frame).unwrap(), Message::Unknown(frame.to_string()), "{frame}");
+        }
+
+        let error = Message::parse(r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":"12345"}"#).unwrap_err();
+        assert_eq!(error.event_type.as_deref(), Some("trade"));
+        let error = Message::parse(&make_combined("bnbusdt@bookTicker", r#"{"u":1}"#)).unwrap_err();
+        assert!(error.is_data());
+    }
+}
@@ -0,0 +1,779 @@
+//! European options (EAPI) streams.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use super::{
+    array_event_from_json, collect_elements, de_string_or_number_to_u64, de_string_to_f64, ser_f64_as_string, ser_u64_as_string,
+    ArrayEvent, EventTypeTag, KlineInterval, PriceLevel, Side,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+// Option symbol in the form <underlying>-<YYMMDD>-<strike>-<C|P>, e.g. ETH-240927-3000-C
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionSymbol {
+    pub underlying: String,              // Underlying base asset, e.g. ETH
+    pub expiry_date: String,             // Expiry date as YYMMDD
+    pub strike_price: f64,               // Strike price
+    pub kind: OptionKind,                // Call or put
+}
+
+impl OptionSymbol {
+    pub fn parse(symbol: &str) -> Option<Self> {
+        let mut parts = symbol.split('-');
+        let underlying = parts.next()?;
+        let expiry_date = parts.next()?;
+        let strike_price = parts.next()?.parse::<f64>().ok()?;
+        let kind = match parts.next()? {
+            "C" => OptionKind::Call,
+            "P" => OptionKind::Put,
+            _ => return None,
+        };
+        if parts.next().is_some() || underlying.is_empty()
+            || expiry_date.len() != 6 || !expiry_date.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(OptionSymbol {
+            underlying: underlying.to_string(),
+            expiry_date: expiry_date.to_string(),
+            strike_price,
+            kind,
+        })
+    }
+}
+
+// Options trades encode the taker side as "1" (buy) or "-1" (sell)
+fn de_side_from_sign<'de, D>(deserializer: D) -> Result<Side, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    match s.as_str() {
+        "1" => Ok(Side::Buy),
+        "-1" => Ok(Side::Sell),
+        _ => Err(de::Error::custom(format!("invalid option trade side: {s}"))),
+    }
+}
+
+fn ser_side_as_sign<S>(side: &Side, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match side {
+        Side::Buy => serializer.serialize_str("1"),
+        Side::Sell => serializer.serialize_str("-1"),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Trade {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "t", alias = "trade-id", deserialize_with = "de_string_or_number_to_u64", serialize_with = "ser_u64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub trade_id: u64,                   // Trade ID
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "q", alias = "quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "b", alias = "buy-order-id")]
+    pub buy_order_id: u64,               // Buy order ID
+    #[serde(rename = "a", alias = "sell-order-id")]
+    pub sell_order_id: u64,              // Sell order ID
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,                 // Trade time
+    #[serde(rename = "S", alias = "side", deserialize_with = "de_side_from_sign", serialize_with = "ser_side_as_sign")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::SignedSide"))]
+    pub side: Side,                      // Taker side
+    #[serde(rename = "X", alias = "trade-type", default, skip_serializing_if = "Option::is_none")]
+    pub trade_type: Option<String>,      // Trade type
+}
+
+impl Trade {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn option_symbol(&self) -> Option<OptionSymbol> {
+        OptionSymbol::parse(&self.symbol)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Ticker {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // 24-hour opening price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // Highest price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Lowest price
+    #[serde(rename = "c", alias = "last-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_price: f64,                 // Latest price
+    #[serde(rename = "V", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Trading volume (contracts)
+    #[serde(rename = "A", alias = "amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub amount: f64,                     // Trade amount (in quote asset)
+    #[serde(rename = "P", alias = "price-change-percent", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change_percent: f64,       // Price change percent
+    #[serde(rename = "p", alias = "price-change", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price_change: f64,               // Price change
+    #[serde(rename = "Q", alias = "last-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_quantity: f64,              // Volume of last completed trade (in contracts)
+    #[serde(rename = "F", alias = "first-trade-id", deserialize_with = "de_string_or_number_to_u64", serialize_with = "ser_u64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub first_trade_id: u64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id", deserialize_with = "de_string_or_number_to_u64", serialize_with = "ser_u64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub last_trade_id: u64,              // Last trade ID
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Number of trades
+    #[serde(rename = "bo", alias = "best-buy-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_buy_price: f64,             // Best buy price
+    #[serde(rename = "ao", alias = "best-sell-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_sell_price: f64,            // Best sell price
+    #[serde(rename = "bq", alias = "best-buy-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_buy_quantity: f64,          // Best buy quantity
+    #[serde(rename = "aq", alias = "best-sell-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_sell_quantity: f64,         // Best sell quantity
+    #[serde(rename = "b", alias = "buy-implied-volatility", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub buy_implied_volatility: f64,     // Buy implied volatility
+    #[serde(rename = "a", alias = "sell-implied-volatility", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub sell_implied_volatility: f64,    // Sell implied volatility
+    #[serde(rename = "d", alias = "delta", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub delta: f64,                      // Delta
+    #[serde(rename = "t", alias = "theta", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub theta: f64,                      // Theta
+    #[serde(rename = "g", alias = "gamma", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub gamma: f64,                      // Gamma
+    #[serde(rename = "v", alias = "vega", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub vega: f64,                       // Vega
+    #[serde(rename = "vo", alias = "implied-volatility", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub implied_volatility: f64,         // Implied volatility
+    #[serde(rename = "mp", alias = "mark-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Mark price
+    #[serde(rename = "hl", alias = "high-price-limit", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price_limit: f64,           // Buy maximum price
+    #[serde(rename = "ll", alias = "low-price-limit", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price_limit: f64,            // Sell minimum price
+    #[serde(rename = "eep", alias = "estimated-exercise-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub estimated_exercise_price: f64,   // Estimated strike price (only shown in the half hour before exercise)
+    #[serde(rename = "r", alias = "ignore-r", default, skip_serializing_if = "Option::is_none")]
+    pub ignore_r: Option<String>,        // Ignore
+}
+
+impl Ticker {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IndexPrice {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Underlying symbol, e.g. ETHUSDT
+    #[serde(rename = "p", alias = "index-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub index_price: f64,                // Index price
+}
+
+impl IndexPrice {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarkPrice {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "mp", alias = "mark-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                 // Option mark price
+}
+
+impl MarkPrice {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // <underlyingAsset>@markPrice delivers every strike of the underlying in one message
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Kline {
+    #[serde(rename = "t", alias = "start-time")]
+    pub start_time: u64,                 // Kline start time
+    #[serde(rename = "T", alias = "close-time")]
+    pub close_time: u64,                 // Kline close time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "i", alias = "interval")]
+    pub interval: KlineInterval,         // Candle period
+    #[serde(rename = "F", alias = "first-trade-id", deserialize_with = "de_string_or_number_to_u64", serialize_with = "ser_u64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub first_trade_id: u64,             // First trade ID
+    #[serde(rename = "L", alias = "last-trade-id", deserialize_with = "de_string_or_number_to_u64", serialize_with = "ser_u64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub last_trade_id: u64,              // Last trade ID
+    #[serde(rename = "o", alias = "open-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_price: f64,                 // Open price
+    #[serde(rename = "c", alias = "close-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub close_price: f64,                // Close price
+    #[serde(rename = "h", alias = "high-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub high_price: f64,                 // High price
+    #[serde(rename = "l", alias = "low-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub low_price: f64,                  // Low price
+    #[serde(rename = "v", alias = "volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub volume: f64,                     // Volume (in contracts)
+    #[serde(rename = "n", alias = "number-of-trades")]
+    pub number_of_trades: u64,           // Number of trades
+    #[serde(rename = "x", alias = "is-closed")]
+    pub is_closed: bool,                 // Is this kline closed?
+    #[serde(rename = "q", alias = "amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub amount: f64,                     // Trade amount (in quote asset)
+    #[serde(rename = "V", alias = "taker-buy-volume", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_volume: f64,           // Taker buy volume (in contracts)
+    #[serde(rename = "Q", alias = "taker-buy-amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub taker_buy_amount: f64,           // Taker buy amount (in quote asset)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: Kline,                    // Kline
+}
+
+impl KlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OpenInterest {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "o", alias = "open-interest", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_interest: f64,              // Open interest in contracts
+    #[serde(rename = "h", alias = "open-interest-usdt", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub open_interest_usdt: f64,         // Open interest in USDT
+}
+
+impl OpenInterest {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // <underlyingAsset>@openInterest@<expirationDate> delivers every strike of the expiration in one message
+    pub fn from_json_array(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OpenSymbolInfo {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "id")]
+    pub id: u64,                         // Option ID
+    #[serde(rename = "cid", alias = "contract-id")]
+    pub contract_id: u64,                // Contract ID
+    #[serde(rename = "u", alias = "underlying")]
+    pub underlying: String,              // Underlying index of the contract
+    #[serde(rename = "qa", alias = "quote-asset", default, skip_serializing_if = "Option::is_none")]
+    pub quote_asset: Option<String>,     // Quotation asset
+    #[serde(rename = "s", alias = "symbol", alias = "S")]
+    pub symbol: String,                  // Trading pair name
+    #[serde(rename = "unit")]
+    pub unit: u64,                       // Conversion ratio, the quantity of the underlying asset represented by a single contract
+    #[serde(rename = "mq", alias = "min-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub min_quantity: f64,               // Minimum trade volume of the underlying asset
+    #[serde(rename = "d", alias = "side")]
+    pub side: OptionKind,                // Type of option (CALL or PUT)
+    #[serde(rename = "sp", alias = "strike-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub strike_price: f64,               // Strike price
+    #[serde(rename = "ed", alias = "expiration-time", alias = "exp")]
+    pub expiration_time: u64,            // Expiration time
+}
+
+impl OpenSymbolInfo {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Depth {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Option symbol
+    #[serde(rename = "u", alias = "update-id")]
+    pub update_id: u64,                  // Update ID
+    #[serde(rename = "pu", alias = "previous-update-id")]
+    pub previous_update_id: u64,         // Same as update ID
+    #[serde(rename = "b", alias = "bids")]
+    pub bids: Vec<PriceLevel>,           // Buy order
+    #[serde(rename = "a", alias = "asks")]
+    pub asks: Vec<PriceLevel>,           // Sell order
+}
+
+impl Depth {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DepthLevels {
+    L10,
+    L20,
+    L50,
+    L100,
+}
+
+impl DepthLevels {
+    pub fn count(&self) -> u16 {
+        match self {
+            DepthLevels::L10 => 10,
+            DepthLevels::L20 => 20,
+            DepthLevels::L50 => 50,
+            DepthLevels::L100 => 100,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OptionsDepthSpeed {
+    Ms100,
+    Ms500,
+    Ms1000,
+}
+
+impl OptionsDepthSpeed {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OptionsDepthSpeed::Ms100 => "100ms",
+            OptionsDepthSpeed::Ms500 => "500ms",
+            OptionsDepthSpeed::Ms1000 => "1000ms",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OptionsStreamName {
+    Trade(String),                       // <symbol>@trade or <underlyingAsset>@trade
+    Ticker(String),                      // <symbol>@ticker
+    Index(String),                       // <underlying>@index, e.g. ETHUSDT@index
+    MarkPrice(String),                   // <underlyingAsset>@markPrice, e.g. ETH@markPrice
+    Kline(String, KlineInterval),        // <symbol>@kline_<interval>
+    OpenInterest(String, String),        // <underlyingAsset>@openInterest@<expirationDate>, date as YYMMDD
+    OptionPair,                          // option_pair, newly listed contracts
+    Depth(String, DepthLevels, Option<OptionsDepthSpeed>), // <symbol>@depth<levels>[@<speed>]
+}
+
+impl fmt::Display for OptionsStreamName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionsStreamName::Trade(symbol) => write!(f, "{symbol}@trade"),
+            OptionsStreamName::Ticker(symbol) => write!(f, "{symbol}@ticker"),
+            OptionsStreamName::Index(underlying) => write!(f, "{underlying}@index"),
+            OptionsStreamName::MarkPrice(underlying) => write!(f, "{underlying}@markPrice"),
+            OptionsStreamName::Kline(symbol, interval) => write!(f, "{symbol}@kline_{interval}"),
+            OptionsStreamName::OpenInterest(underlying, expiration_date) => write!(f, "{underlying}@openInterest@{expiration_date}"),
+            OptionsStreamName::OptionPair => f.write_str("option_pair"),
+            OptionsStreamName::Depth(symbol, levels, None) => write!(f, "{symbol}@depth{}", levels.count()),
+            OptionsStreamName::Depth(symbol, levels, Some(speed)) => write!(f, "{symbol}@depth{}@{}", levels.count(), speed.as_str()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionsEvent {
+    Trade(Trade),
+    Ticker(Ticker),
+    IndexPrice(IndexPrice),
+    MarkPrice(Vec<MarkPrice>),
+    Kline(KlineEvent),
+    OpenInterest(Vec<OpenInterest>),
+    OpenSymbolInfo(OpenSymbolInfo),
+    Depth(Depth),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl OptionsEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, OptionsEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, OptionsEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        if json.trim_start().starts_with('[') {
+            return Ok(array_event_from_json(json)?.unwrap_or_else(|| OptionsEvent::Unknown(json.to_string())));
+        }
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("trade") => Trade::from_json(json).map(OptionsEvent::Trade),
+            Some("24hrTicker") => Ticker::from_json(json).map(OptionsEvent::Ticker),
+            Some("index") => IndexPrice::from_json(json).map(OptionsEvent::IndexPrice),
+            Some("kline") => KlineEvent::from_json(json).map(OptionsEvent::Kline),
+            Some("OPTION_PAIR") => OpenSymbolInfo::from_json(json).map(OptionsEvent::OpenSymbolInfo),
+            Some("depth") => Depth::from_json(json).map(OptionsEvent::Depth),
+            _ => Ok(OptionsEvent::Unknown(json.to_string())),
+        }
+    }
+
+}
+
+impl ArrayEvent for OptionsEvent {
+    fn from_elements<'de, A: de::SeqAccess<'de>>(event_type: &str, first: serde_json::Value, rest: &mut A) -> Result<Option<Self>, A::Error> {
+        match event_type {
+            "markPrice" => collect_elements(first, rest).map(|events| Some(OptionsEvent::MarkPrice(events))),
+            "openInterest" => collect_elements(first, rest).map(|events| Some(OptionsEvent::OpenInterest(events))),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_deserialization() {
+        let json = r#"{"e":"trade","E":1591677941092,"s":"BTC-200630-9000-P","t":"2","p":"1000","q":"-0.1","b":4611781675939004417,"a":4611781675939004418,"T":1591677567872,"S":"-1","X":"TRADE"}"#;
+        let expected = Trade {
+            event_type: "trade".to_string(),
+            event_time: 1591677941092,
+            symbol: "BTC-200630-9000-P".to_string(),
+            trade_id: 2,
+            price: 1000.0,
+            quantity: -0.1,
+            buy_order_id: 4611781675939004417,
+            sell_order_id: 4611781675939004418,
+            trade_time: 1591677567872,
+            side: Side::Sell,
+            trade_type: Some("TRADE".to_string()),
+        };
+        let trade = Trade::from_json(json).unwrap();
+        assert_eq!(trade, expected);
+        assert_eq!(trade.option_symbol(), Some(OptionSymbol {
+            underlying: "BTC".to_string(),
+            expiry_date: "200630".to_string(),
+            strike_price: 9000.0,
+            kind: OptionKind::Put,
+        }));
+    }
+
+    #[test]
+    fn test_trade_side_mapping() {
+        let json = r#"{"e":"trade","E":1727420400123,"s":"ETH-240927-3000-C","t":20,"p":"12.5","q":"1.00","b":4647850284614262784,"a":4719907951263906816,"T":1727420400100,"S":"1"}"#;
+        let trade = Trade::from_json(json).unwrap();
+        assert_eq!(trade.side, Side::Buy);
+        assert_eq!(trade.trade_id, 20);
+        assert_eq!(trade.option_symbol().unwrap().kind, OptionKind::Call);
+
+        let json = json.replace(r#""S":"1""#, r#""S":"0""#);
+        assert!(Trade::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_option_symbol_parse_rejects_non_option_symbols() {
+        assert_eq!(OptionSymbol::parse("ETH"), None);
+        assert_eq!(OptionSymbol::parse("ETHUSDT"), None);
+        assert_eq!(OptionSymbol::parse("ETH-240927-3000-X"), None);
+        assert_eq!(OptionSymbol::parse("ETH-2409-3000-C"), None);
+    }
+
+    #[test]
+    fn test_ticker_deserialization_with_negative_greeks() {
+        let json = r#"{"e":"24hrTicker","E":1657706425200,"T":1657706425220,"s":"BTC-220930-18000-P","o":"2000","h":"2020","l":"2000","c":"2020","V":"1.42","A":"2841.9","P":"0.01","p":"20","Q":"0.01","F":"27","L":"48","n":22,"bo":"2012","ao":"2020","bq":"4.9","aq":"0.03","b":"0.1202","a":"0.1318","d":"-0.98911","t":"-0.16961","g":"0.00004","v":"2.66584","vo":"0.10001","mp":"2005.5","hl":"2023.5","ll":"1996.5","eep":"0","r":"0"}"#;
+        let ticker = Ticker::from_json(json).unwrap();
+
+        assert_eq!(ticker.symbol, "BTC-220930-18000-P");
+        assert_eq!(ticker.transaction_time, 1657706425220);
+        assert_eq!(ticker.volume, 1.42);
+        assert_eq!(ticker.amount, 2841.9);
+        assert_eq!(ticker.first_trade_id, 27);
+        assert_eq!(ticker.last_trade_id, 48);
+        assert_eq!(ticker.number_of_trades, 22);
+        assert_eq!(ticker.best_buy_price, 2012.0);
+        assert_eq!(ticker.best_sell_quantity, 0.03);
+        assert_eq!(ticker.buy_implied_volatility, 0.1202);
+        assert_eq!(ticker.sell_implied_volatility, 0.1318);
+        assert_eq!(ticker.delta, -0.98911);
+        assert_eq!(ticker.theta, -0.16961);
+        assert_eq!(ticker.gamma, 0.00004);
+        assert_eq!(ticker.vega, 2.66584);
+        assert_eq!(ticker.implied_volatility, 0.10001);
+        assert_eq!(ticker.mark_price, 2005.5);
+        assert_eq!(ticker.high_price_limit, 2023.5);
+        assert_eq!(ticker.low_price_limit, 1996.5);
+        assert_eq!(ticker.estimated_exercise_price, 0.0);
+    }
+
+    #[test]
+    fn test_index_price_deserialization() {
+        let json = r#"{"e":"index","E":1661415480351,"s":"ETHUSDT","p":"1707.89008607"}"#;
+        let expected = IndexPrice {
+            event_type: "index".to_string(),
+            event_time: 1661415480351,
+            symbol: "ETHUSDT".to_string(),
+            index_price: 1707.89008607,
+        };
+        assert_eq!(IndexPrice::from_json(json).unwrap(), expected);
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::IndexPrice(expected));
+    }
+
+    #[test]
+    fn test_options_event_dispatch() {
+        let json = r#"{"e":"trade","E":1591677941092,"s":"BTC-200630-9000-P","t":"2","p":"1000","q":"-0.1","b":4611781675939004417,"a":4611781675939004418,"T":1591677567872,"S":"-1","X":"TRADE"}"#;
+        assert!(matches!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::Trade(_)));
+
+        let json = r#"{"e":"somethingNew","E":1661415480351}"#;
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::Unknown(json.to_string()));
+    }
+
+    #[test]
+    fn test_stream_names() {
+        assert_eq!(OptionsStreamName::Trade("ETH-240927-3000-C".to_string()).to_string(), "ETH-240927-3000-C@trade");
+        assert_eq!(OptionsStreamName::Trade("ETH".to_string()).to_string(), "ETH@trade");
+        assert_eq!(OptionsStreamName::Ticker("ETH-240927-3000-C".to_string()).to_string(), "ETH-240927-3000-C@ticker");
+        assert_eq!(OptionsStreamName::Index("ETHUSDT".to_string()).to_string(), "ETHUSDT@index");
+    }
+
+    #[test]
+    fn test_mark_price_array_deserialization() {
+        let json = r#"[{"e":"markPrice","E":1663684594227,"s":"ETH-220930-1500-C","mp":"30.3"},{"e":"markPrice","E":1663684594228,"s":"ETH-220930-1500-P","mp":"22.1"},{"e":"markPrice","E":1663684594228,"s":"ETH-220930-1600-C","mp":"6.8"}]"#;
+        let mark_prices = MarkPrice::from_json_array(json).unwrap();
+
+        assert_eq!(mark_prices.len(), 3);
+        let symbols: Vec<&str> = mark_prices.iter().map(|m| m.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["ETH-220930-1500-C", "ETH-220930-1500-P", "ETH-220930-1600-C"]);
+        let prices: Vec<f64> = mark_prices.iter().map(|m| m.mark_price).collect();
+        assert_eq!(prices, vec![30.3, 22.1, 6.8]);
+
+        let OptionsEvent::MarkPrice(dispatched) = OptionsEvent::from_json(json).unwrap() else { panic!("expected mark prices") };
+        assert_eq!(dispatched, mark_prices);
+        assert!(OptionsEvent::from_json(r#"[{"e":"markPrice","E":1663684594227,"s":"ETH-220930-1500-C","mp":"30.3"},{"e":"markPrice","E":1663684594228}]"#).is_err());
+        assert_eq!(OptionsStreamName::MarkPrice("ETH".to_string()).to_string(), "ETH@markPrice");
+    }
+
+    #[test]
+    fn test_kline_event_open_candle() {
+        let json = r#"{"e":"kline","E":1638747660000,"s":"BTC-200630-9000-P","k":{"t":1638747660000,"T":1638747719999,"s":"BTC-200630-9000-P","i":"1m","F":0,"L":0,"o":"1000","c":"1000","h":"1000","l":"1000","v":"0","n":0,"x":false,"q":"0","V":"0","Q":"0"}}"#;
+        let expected = KlineEvent {
+            event_type: "kline".to_string(),
+            event_time: 1638747660000,
+            symbol: "BTC-200630-9000-P".to_string(),
+            kline: Kline {
+                start_time: 1638747660000,
+                close_time: 1638747719999,
+                symbol: "BTC-200630-9000-P".to_string(),
+                interval: KlineInterval::M1,
+                first_trade_id: 0,
+                last_trade_id: 0,
+                open_price: 1000.0,
+                close_price: 1000.0,
+                high_price: 1000.0,
+                low_price: 1000.0,
+                volume: 0.0,
+                number_of_trades: 0,
+                is_closed: false,
+                amount: 0.0,
+                taker_buy_volume: 0.0,
+                taker_buy_amount: 0.0,
+            },
+        };
+        assert_eq!(KlineEvent::from_json(json).unwrap(), expected);
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::Kline(expected));
+    }
+
+    #[test]
+    fn test_kline_event_closed_candle() {
+        let json = r#"{"e":"kline","E":1727421960012,"s":"ETH-240927-3000-C","k":{"t":1727421900000,"T":1727421959999,"s":"ETH-240927-3000-C","i":"1m","F":"121","L":"125","o":"12.4","c":"13.1","h":"13.2","l":"12.3","v":"5.5","n":5,"x":true,"q":"70.45","V":"3.5","Q":"45.2"}}"#;
+        let event = KlineEvent::from_json(json).unwrap();
+
+        assert!(event.kline.is_closed);
+        assert_eq!(event.kline.first_trade_id, 121);
+        assert_eq!(event.kline.last_trade_id, 125);
+        assert_eq!(event.kline.high_price, 13.2);
+        assert_eq!(event.kline.amount, 70.45);
+        assert_eq!(event.kline.taker_buy_volume, 3.5);
+        assert_eq!(OptionsStreamName::Kline("ETH-240927-3000-C".to_string(), KlineInterval::M1).to_string(), "ETH-240927-3000-C@kline_1m");
+    }
+
+    #[test]
+    fn test_open_interest_array_deserialization() {
+        let json = r#"[{"e":"openInterest","E":1668759300045,"s":"ETH-221125-2700-C","o":"97.94","h":"124776.6716"},{"e":"openInterest","E":1668759241000,"s":"ETH-221125-2700-P","o":"6.18","h":"7873.6592"}]"#;
+        let expected = vec![
+            OpenInterest {
+                event_type: "openInterest".to_string(),
+                event_time: 1668759300045,
+                symbol: "ETH-221125-2700-C".to_string(),
+                open_interest: 97.94,
+                open_interest_usdt: 124776.6716,
+            },
+            OpenInterest {
+                event_type: "openInterest".to_string(),
+                event_time: 1668759241000,
+                symbol: "ETH-221125-2700-P".to_string(),
+                open_interest: 6.18,
+                open_interest_usdt: 7873.6592,
+            },
+        ];
+        assert_eq!(OpenInterest::from_json_array(json).unwrap(), expected);
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::OpenInterest(expected));
+        let unknown = r#"[{"e":"openInterestUpdate","E":1668759300045}]"#;
+        assert_eq!(OptionsEvent::from_json(unknown).unwrap(), OptionsEvent::Unknown(unknown.to_string()));
+        assert!(OptionsEvent::from_json(r#"[{"e":"openInterest","E":1668759300045,"s":"ETH-221125-2700-C","o":"x","h":"1"}]"#).is_err());
+        assert_eq!(OptionsStreamName::OpenInterest("ETH".to_string(), "221125".to_string()).to_string(), "ETH@openInterest@221125");
+    }
+
+    #[test]
+    fn test_open_symbol_info_deserialization() {
+        let json = r#"{"e":"OPTION_PAIR","E":1668573571842,"id":652,"cid":2,"u":"BTCUSDT","qa":"USDT","s":"BTC-221116-21000-C","unit":1,"mq":"0.01","d":"CALL","sp":"21000","ed":1668585600000}"#;
+        let expected = OpenSymbolInfo {
+            event_type: "OPTION_PAIR".to_string(),
+            event_time: 1668573571842,
+            id: 652,
+            contract_id: 2,
+            underlying: "BTCUSDT".to_string(),
+            quote_asset: Some("USDT".to_string()),
+            symbol: "BTC-221116-21000-C".to_string(),
+            unit: 1,
+            min_quantity: 0.01,
+            side: OptionKind::Call,
+            strike_price: 21000.0,
+            expiration_time: 1668585600000,
+        };
+        assert_eq!(OpenSymbolInfo::from_json(json).unwrap(), expected);
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::OpenSymbolInfo(expected));
+        assert_eq!(OptionsStreamName::OptionPair.to_string(), "option_pair");
+    }
+
+    #[test]
+    fn test_depth10_deserialization_with_empty_asks() {
+        let json = r#"{"e":"depth","E":1591695934010,"T":1591695934000,"s":"BTC-200630-9000-P","u":162,"pu":162,"b":[["0.1000","0.9"],["0.0900","2.5"]],"a":[]}"#;
+        let expected = Depth {
+            event_type: "depth".to_string(),
+            event_time: 1591695934010,
+            transaction_time: 1591695934000,
+            symbol: "BTC-200630-9000-P".to_string(),
+            update_id: 162,
+            previous_update_id: 162,
+            bids: vec![PriceLevel { price: 0.1, quantity: 0.9 }, PriceLevel { price: 0.09, quantity: 2.5 }],
+            asks: vec![],
+        };
+        assert_eq!(Depth::from_json(json).unwrap(), expected);
+        assert_eq!(OptionsEvent::from_json(json).unwrap(), OptionsEvent::Depth(expected));
+    }
+
+    #[test]
+    fn test_depth_stream_names() {
+        let symbol = "BTC-200630-9000-P".to_string();
+        assert_eq!(OptionsStreamName::Depth(symbol.clone(), DepthLevels::L10, None).to_string(), "BTC-200630-9000-P@depth10");
+        assert_eq!(OptionsStreamName::Depth(symbol.clone(), DepthLevels::L20, Some(OptionsDepthSpeed::Ms100)).to_string(), "BTC-200630-9000-P@depth20@100ms");
+        assert_eq!(OptionsStreamName::Depth(symbol.clone(), DepthLevels::L50, Some(OptionsDepthSpeed::Ms500)).to_string(), "BTC-200630-9000-P@depth50@500ms");
+        assert_eq!(OptionsStreamName::Depth(symbol, DepthLevels::L100, Some(OptionsDepthSpeed::Ms1000)).to_string(), "BTC-200630-9000-P@depth100@1000ms");
+    }
+}
@@ -0,0 +1,199 @@
+//! Combined stream frames routed to a handler per stream, with the data decoded once into the type of
+//! the stream.
+
+use std::collections::HashMap;
+
+use super::frame::split_combined;
+use super::{AggTrade, BookTicker, DepthUpdate, KlineEvent, PartialDepth, StreamName, Trade};
+
+// Callbacks of a routed stream. Streams without a typed callback, e.g. tickers, are handed to `on_data`
+// with their raw `data`. Callbacks a handler does not override drop the event
+pub trait StreamHandler {
+    fn on_trade(&mut self, _stream: &StreamName, _trade: Trade) {}
+    fn on_agg_trade(&mut self, _stream: &StreamName, _trade: AggTrade) {}
+    fn on_kline(&mut self, _stream: &StreamName, _kline: KlineEvent) {}
+    fn on_depth(&mut self, _stream: &StreamName, _depth: DepthUpdate) {}
+    fn on_partial_depth(&mut self, _stream: &StreamName, _depth: PartialDepth) {}
+    fn on_book_ticker(&mut self, _stream: &StreamName, _ticker: BookTicker) {}
+    fn on_data(&mut self, _stream: &StreamName, _data: &str) {}
+}
+
+// Lets the caller keep the handler and look at it after routing
+impl<H: StreamHandler + ?Sized> StreamHandler for &mut H {
+    fn on_trade(&mut self, stream: &StreamName, trade: Trade) {
+        (**self).on_trade(stream, trade)
+    }
+
+    fn on_agg_trade(&mut self, stream: &StreamName, trade: AggTrade) {
+        (**self).on_agg_trade(stream, trade)
+    }
+
+    fn on_kline(&mut self, stream: &StreamName, kline: KlineEvent) {
+        (**self).on_kline(stream, kline)
+    }
+
+    fn on_depth(&mut self, stream: &StreamName, depth: DepthUpdate) {
+        (**self).on_depth(stream, depth)
+    }
+
+    fn on_partial_depth(&mut self, stream: &StreamName, depth: PartialDepth) {
+        (**self).on_partial_depth(stream, depth)
+    }
+
+    fn on_book_ticker(&mut self, stream: &StreamName, ticker: BookTicker) {
+        (**self).on_book_ticker(stream, ticker)
+    }
+
+    fn on_data(&mut self, stream: &StreamName, data: &str) {
+        (**self).on_data(stream, data)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Routed {
+    Handler,                             // Decoded and handed to the handler of the stream
+    Fallback,                            // Handed to the fallback, or dropped without one
+}
+
+type Fallback<'h> = Box<dyn FnMut(&str) + 'h>;
+
+struct Pattern<'h> {
+    matches: Box<dyn Fn(&StreamName) -> bool + 'h>,
+    handler: usize,
+}
+
+// Handlers registered for a stream name take precedence over patterns, patterns are tried in the order
+// they were added. Frames that are not combined stream frames, name a stream `StreamName` does not parse
+// or no handler was registered for go to the fallback with the raw frame
+#[derive(Default)]
+pub struct StreamRouter<'h> {
+    handlers: Vec<Box<dyn StreamHandler + 'h>>,
+    streams: HashMap<StreamName, usize>,
+    patterns: Vec<Pattern<'h>>,
+    fallback: Option<Fallback<'h>>,
+}
+
+impl<'h> StreamRouter<'h> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The name is normalized like `StreamName::from_str` does, so "BTCUSDT" matches "btcusdt@trade".
+    // A later handler for the same stream replaces the earlier one
+    pub fn on_stream(&mut self, stream: StreamName, handler: impl StreamHandler + 'h) -> &mut Self {
+        let stream = stream.to_stream().ok().and_then(|name| name.parse().ok()).unwrap_or(stream);
+        self.handlers.push(Box::new(handler));
+        self.streams.insert(stream, self.handlers.len() - 1);
+        self
+    }
+
+    // Every stream the predicate accepts, e.g. `|stream| matches!(stream, StreamName::Kline(..))`
+    pub fn on_matching(&mut self, matches: impl Fn(&StreamName) -> bool + 'h, handler: impl StreamHandler + 'h) -> &mut Self {
+        self.handlers.push(Box::new(handler));
+        self.patterns.push(Pattern { matches: Box::new(matches), handler: self.handlers.len() - 1 });
+        self
+    }
+
+    pub fn fallback(&mut self, fallback: impl FnMut(&str) + 'h) -> &mut Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    // Fails only when the data of a routed stream does not parse as the stream's type, the frame then
+    // reaches neither the handler nor the fallback
+    pub fn route(&mut self, frame: &str) -> Result<Routed, serde_json::Error> {
+        let Some((name, data)) = split_combined(frame) else { return Ok(self.route_fallback(frame)) };
+        let Ok(stream) = name.parse::<StreamName>() else { return Ok(self.route_fallback(frame)) };
+        let handler = self.streams.get(&stream).copied().or_else(|| {
+            self.patterns.iter().find(|pattern| (pattern.matches)(&stream)).map(|pattern| pattern.handler)
+        });
+        let Some(handler) = handler else { return Ok(self.route_fallback(frame)) };
+
+        let handler = &mut self.handlers[handler];
+        match stream {
+            StreamName::Trade(_) => handler.on_trade(&stream, Trade::from_json(data)?),
+            StreamName::AggTrade(_) => handler.on_agg_trade(&stream, AggTrade::from_json(data)?),
+            StreamName::Kline(..) => handler.on_kline(&stream, KlineEvent::from_json(data)?),
+            StreamName::DiffDepth(..) => handler.on_depth(&stream, DepthUpdate::from_json(data)?),
+            StreamName::PartialDepth(..) => handler.on_partial_depth(&stream, PartialDepth::from_json(data)?),
+            StreamName::BookTicker(_) => handler.on_book_ticker(&stream, BookTicker::from_json(data)?),
+            _ => handler.on_data(&stream, data),
+        }
+        Ok(Routed::Handler)
+    }
+
+    fn route_fallback(&mut self, frame: &str) -> Routed {
+        if let Some(fallback) = &mut self.fallback {
+            fallback(frame);
+        }
+        Routed::Fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{make_book_ticker, make_combined, make_depth_update, make_trade};
+    use crate::websocket::{DepthSpeed, KlineInterval};
+
+    #[derive(Default)]
+    struct Recorder {
+        trades: Vec<Trade>,
+        depth: Vec<DepthUpdate>,
+        other: Vec<String>,
+    }
+
+    impl StreamHandler for Recorder {
+        fn on_trade(&mut self, _stream: &StreamName, trade: Trade) {
+            self.trades.push(trade);
+        }
+
+        fn on_depth(&mut self, _stream: &StreamName, depth: DepthUpdate) {
+            self.depth.push(depth);
+        }
+
+        fn on_data(&mut self, stream: &StreamName, _data: &str) {
+            self.other.push(stream.to_stream().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_route_mixed_frames() {
+        let (mut trades, mut depth, mut klines) = (Recorder::default(), Recorder::default(), Recorder::default());
+        let mut unroutable = Vec::new();
+        let frames = [
+            make_combined("btcusdt@trade", &make_trade("BTCUSDT", 64000.5, 0.25, 1700000000000)),
+            make_combined("ethusdt@depth@100ms", &make_depth_update("ETHUSDT", 160, 162, &[(3001.5, 2.0)], &[(3002.0, 0.0)], 1700000000010)),
+            make_combined("bnbusdt@bookTicker", &make_book_ticker("BNBUSDT", 400900217, (25.35, 31.21), (25.36, 40.66))),
+            make_combined("btcusdt@trade", &make_trade("BTCUSDT", 64001.0, 0.5, 1700000000020)),
+            make_combined("btcusdt@somethingNew", r#"{"e":"somethingNew","E":1700000000030}"#),
+            make_trade("BTCUSDT", 64002.0, 0.1, 1700000000040),
+        ];
+        {
+            let mut router = StreamRouter::new();
+            router
+                .on_stream(StreamName::Trade("BTCUSDT".to_string()), &mut trades)
+                .on_stream(StreamName::DiffDepth("ethusdt".to_string(), DepthSpeed::Ms100), &mut depth)
+                .on_matching(|stream| matches!(stream, StreamName::Kline(..)), &mut klines)
+                .fallback(|frame| unroutable.push(frame.to_string()));
+            let routed: Vec<Routed> = frames.iter().map(|frame| router.route(frame).unwrap()).collect();
+            assert_eq!(routed, [Routed::Handler, Routed::Handler, Routed::Fallback, Routed::Handler, Routed::Fallback, Routed::Fallback]);
+        }
+
+        assert_eq!(trades.trades.iter().map(|trade| (trade.price, trade.quantity)).collect::<Vec<_>>(), [(64000.5, 0.25), (64001.0, 0.5)]);
+        assert_eq!((depth.trades.len(), depth.depth.len()), (0, 1));
+        assert_eq!((depth.depth[0].symbol.as_str(), depth.depth[0].final_update_id), ("ETHUSDT", 162));
+        assert_eq!(depth.depth[0].bids[0].price, 3001.5);
+        assert!(klines.trades.is_empty() && klines.other.is_empty());
+        assert_eq!(unroutable, [frames[2].clone(), frames[4].clone(), frames[5].clone()]);
+
+        // A pattern catches every stream it accepts, streams without a typed callback come as raw data
+        let mut router = StreamRouter::new();
+        router.on_matching(|stream| matches!(stream, StreamName::Kline(_, KlineInterval::M1) | StreamName::Ticker(_)), &mut klines);
+        assert_eq!(router.route(&make_combined("bnbusdt@ticker", r#"{"e":"24hrTicker"}"#)).unwrap(), Routed::Handler);
+        assert_eq!(router.route(&make_combined("btcusdt@trade", "{}")).unwrap(), Routed::Fallback);
+        assert!(router.route(&make_combined("ethbtc@kline_1m", "{}")).is_err());
+        drop(router);
+        assert_eq!(klines.other, ["bnbusdt@ticker"]);
+    }
+}
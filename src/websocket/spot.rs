@@ -0,0 +1,511 @@
+//! Spot market stream events: trades, klines, book tickers and depth.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::batch::{parse_many_with, ParseBatch};
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use super::{de_string_to_f64, ser_f64_as_string, EventTypeTag, Kline, PriceLevel, UserDataEvent};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Trade<S = String> {
+    #[serde(rename = "e", alias = "event-type", default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>, // Event type, absent for REST trades
+    #[serde(rename = "E", alias = "event-time", default, skip_serializing_if = "Option::is_none")]
+    pub event_time: Option<u64>,    // Event time, absent for REST trades
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: S,                  // Symbol
+    #[serde(rename = "t", alias = "trade-id")]
+    pub trade_id: u64,              // Trade ID
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                 // Price
+    #[serde(rename = "q", alias = "quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,              // Quantity
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,            // Trade time
+    #[serde(rename = "m", alias = "is-buyer-market-maker")]
+    pub is_buyer_market_maker: bool, // Is the buyer the market maker?
+    #[serde(rename = "M", alias = "ignore")]
+    pub ignore: bool,               // Ignore
+}
+
+impl Trade {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KlineEvent {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "k", alias = "kline")]
+    pub kline: Kline,                    // Kline
+}
+
+impl KlineEvent {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AggTrade<S = String> {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: S,                       // Symbol
+    #[serde(rename = "a", alias = "aggregate-trade-id")]
+    pub aggregate_trade_id: u64,         // Aggregate trade ID
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "q", alias = "quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "f", alias = "first-trade-id")]
+    pub first_trade_id: u64,             // First trade ID
+    #[serde(rename = "l", alias = "last-trade-id")]
+    pub last_trade_id: u64,              // Last trade ID
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,                 // Trade time
+    #[serde(rename = "m", alias = "is-buyer-market-maker")]
+    pub is_buyer_market_maker: bool,     // Is the buyer the market maker?
+    #[serde(rename = "M", alias = "ignore")]
+    pub ignore: bool,                    // Ignore
+}
+
+impl AggTrade {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BookTicker<S = String> {
+    #[serde(rename = "u", alias = "update-id", default, skip_serializing_if = "Option::is_none")]
+    pub update_id: Option<u64>,          // Order book update ID, absent for REST snapshots
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: S,                       // Symbol
+    #[serde(rename = "b", alias = "best-bid-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_bid_price: f64,             // Best bid price
+    #[serde(rename = "B", alias = "best-bid-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_bid_quantity: f64,          // Best bid quantity
+    #[serde(rename = "a", alias = "best-ask-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_ask_price: f64,             // Best ask price
+    #[serde(rename = "A", alias = "best-ask-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub best_ask_quantity: f64,          // Best ask quantity
+}
+
+impl BookTicker {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// `L` holds the levels, e.g. `InlineLevels` with the `smallvec` feature
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DepthUpdate<S = String, L = Vec<PriceLevel>> {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: S,                       // Symbol
+    #[serde(rename = "U", alias = "first-update-id")]
+    pub first_update_id: u64,            // First update ID in event
+    #[serde(rename = "u", alias = "final-update-id")]
+    pub final_update_id: u64,            // Final update ID in event
+    #[serde(rename = "b", alias = "bids")]
+    pub bids: L,                         // Bids to be updated
+    #[serde(rename = "a", alias = "asks")]
+    pub asks: L,                         // Asks to be updated
+}
+
+impl DepthUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, DepthUpdate::from_json)
+    }
+}
+
+// <symbol>@depth<levels> and <symbol>@depth<levels>@100ms
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PartialDepth {
+    #[serde(rename = "lastUpdateId", alias = "last-update-id")]
+    pub last_update_id: u64,             // Last update ID
+    pub bids: Vec<PriceLevel>,           // Top bids, best (highest) price first
+    pub asks: Vec<PriceLevel>,           // Top asks, best (lowest) price first
+}
+
+impl PartialDepth {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Partial depth with the level count in the type, for `<symbol>@depth<levels>` streams, see
+// `PartialDepth5`, `PartialDepth10` and `PartialDepth20`. More than N levels on a side is an error.
+// Fewer levels, e.g. a thin book, are padded with zero levels and `bid_count`/`ask_count` hold the
+// number received, `bids()` and `asks()` return only those
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartialDepthFixed<const N: usize> {
+    pub last_update_id: u64,             // Last update ID
+    pub bids: [PriceLevel; N],           // Top bids, best (highest) price first
+    pub asks: [PriceLevel; N],           // Top asks, best (lowest) price first
+    pub bid_count: usize,                // Bid levels received, at most N
+    pub ask_count: usize,                // Ask levels received, at most N
+}
+
+pub type PartialDepth5 = PartialDepthFixed<5>;
+
+pub type PartialDepth10 = PartialDepthFixed<10>;
+
+pub type PartialDepth20 = PartialDepthFixed<20>;
+
+const EMPTY_LEVEL: PriceLevel = PriceLevel { price: 0.0, quantity: 0.0 };
+
+impl<const N: usize> PartialDepthFixed<N> {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn bids(&self) -> &[PriceLevel] {
+        &self.bids[..self.bid_count]
+    }
+
+    pub fn asks(&self) -> &[PriceLevel] {
+        &self.asks[..self.ask_count]
+    }
+
+    // True when both sides carry all N levels
+    pub fn is_full(&self) -> bool {
+        self.bid_count == N && self.ask_count == N
+    }
+
+    pub fn to_partial_depth(&self) -> PartialDepth {
+        PartialDepth {
+            last_update_id: self.last_update_id,
+            bids: self.bids().to_vec(),
+            asks: self.asks().to_vec(),
+        }
+    }
+}
+
+struct FixedLevels<const N: usize>([PriceLevel; N], usize);
+
+impl<'de, const N: usize> Deserialize<'de> for FixedLevels<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FixedLevelsVisitor<const N: usize>;
+
+        impl<'de, const N: usize> de::Visitor<'de> for FixedLevelsVisitor<N> {
+            type Value = FixedLevels<N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "at most {N} price levels")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut levels = [EMPTY_LEVEL; N];
+                let mut count = 0;
+                while let Some(level) = seq.next_element::<PriceLevel>()? {
+                    if count == N {
+                        let mut received = N + 1;
+                        while seq.next_element::<de::IgnoredAny>()?.is_some() {
+                            received += 1;
+                        }
+                        return Err(de::Error::invalid_length(received, &self));
+                    }
+                    levels[count] = level;
+                    count += 1;
+                }
+                Ok(FixedLevels(levels, count))
+            }
+        }
+
+        deserializer.deserialize_seq(FixedLevelsVisitor::<N>)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for PartialDepthFixed<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawPartialDepthFixed<const N: usize> {
+            #[serde(rename = "lastUpdateId", alias = "last-update-id")]
+            last_update_id: u64,
+            bids: FixedLevels<N>,
+            asks: FixedLevels<N>,
+        }
+
+        let raw = RawPartialDepthFixed::<N>::deserialize(deserializer)?;
+        Ok(PartialDepthFixed {
+            last_update_id: raw.last_update_id,
+            bids: raw.bids.0,
+            asks: raw.asks.0,
+            bid_count: raw.bids.1,
+            ask_count: raw.asks.1,
+        })
+    }
+}
+
+// Serialized like `PartialDepth`, without the padding
+impl<const N: usize> Serialize for PartialDepthFixed<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("PartialDepth", 3)?;
+        state.serialize_field("lastUpdateId", &self.last_update_id)?;
+        state.serialize_field("bids", self.bids())?;
+        state.serialize_field("asks", self.asks())?;
+        state.end()
+    }
+}
+
+// Book ticker and partial depth frames carry no event type, they are returned as `Unknown` and need
+// parsing by type
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpotEvent {
+    Trade(Trade),
+    AggTrade(AggTrade),
+    Kline(KlineEvent),
+    DepthUpdate(DepthUpdate),
+    UserData(UserDataEvent),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl SpotEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, SpotEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, SpotEvent::from_json)
+    }
+
+    pub fn parse_many<'a>(frames: impl IntoIterator<Item = &'a str>) -> ParseBatch<Self> {
+        parse_many_with(frames, SpotEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("trade") => Trade::from_json(json).map(SpotEvent::Trade),
+            Some("aggTrade") => AggTrade::from_json(json).map(SpotEvent::AggTrade),
+            Some("kline") => KlineEvent::from_json(json).map(SpotEvent::Kline),
+            Some("depthUpdate") => DepthUpdate::from_json(json).map(SpotEvent::DepthUpdate),
+            _ => match UserDataEvent::from_json(json)? {
+                UserDataEvent::Unknown(raw) => Ok(SpotEvent::Unknown(raw)),
+                event => Ok(SpotEvent::UserData(event)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::KlineInterval;
+
+    #[test]
+    fn test_trade_serialization() {
+        let expected = Trade {
+            event_type: Some("trade".to_string()),
+            event_time: Some(1759680390108723),
+            symbol: "ETHUSDT".to_string(),
+            trade_id: 2921785139,
+            price: 4532.56,
+            quantity: 0.0132,
+            trade_time: 1759680390108254,
+            is_buyer_market_maker: true,
+            ignore: true,
+        };
+        let json = r#"{"e":"trade","E":1759680390108723,"s":"ETHUSDT","t":2921785139,"p":"4532.56000000","q":"0.01320000","T":1759680390108254,"m":true,"M":true}"#;
+        let trade: Trade = serde_json::from_str(json).unwrap();
+        assert_eq!(expected, trade);
+    }
+
+    #[test]
+    fn test_kline_event_deserialization() {
+        let json = r#"{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#;
+        let expected = KlineEvent {
+            event_type: "kline".to_string(),
+            event_time: 1672515782136,
+            symbol: "BNBBTC".to_string(),
+            kline: Kline {
+                start_time: 1672515780000,
+                close_time: 1672515839999,
+                symbol: "BNBBTC".to_string(),
+                interval: KlineInterval::M1,
+                first_trade_id: 100,
+                last_trade_id: 200,
+                open_price: 0.001,
+                close_price: 0.002,
+                high_price: 0.0025,
+                low_price: 0.0015,
+                volume: 1000.0,
+                number_of_trades: 100,
+                is_closed: false,
+                quote_asset_volume: 1.0,
+                taker_buy_base_asset_volume: 500.0,
+                taker_buy_quote_asset_volume: 0.5,
+                ignore: "123456".to_string(),
+            },
+        };
+        assert_eq!(KlineEvent::from_json(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_agg_trade_deserialization() {
+        let json = r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#;
+        let expected = AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: 1672515782136,
+            symbol: "BNBBTC".to_string(),
+            aggregate_trade_id: 12345,
+            price: 0.001,
+            quantity: 100.0,
+            first_trade_id: 100,
+            last_trade_id: 105,
+            trade_time: 1672515782136,
+            is_buyer_market_maker: true,
+            ignore: true,
+        };
+        assert_eq!(AggTrade::from_json(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_book_ticker_deserialization() {
+        let json = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        let expected = BookTicker {
+            update_id: Some(400900217),
+            symbol: "BNBUSDT".to_string(),
+            best_bid_price: 25.3519,
+            best_bid_quantity: 31.21,
+            best_ask_price: 25.3652,
+            best_ask_quantity: 40.66,
+        };
+        assert_eq!(BookTicker::from_json(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_depth_update_deserialization() {
+        let json = r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"]],"a":[["0.0026","100"],["0.0027","0"]]}"#;
+        let update = DepthUpdate::from_json(json).unwrap();
+
+        assert_eq!(update.symbol, "BNBBTC");
+        assert_eq!(update.first_update_id, 157);
+        assert_eq!(update.final_update_id, 160);
+        assert_eq!(update.bids[..], [PriceLevel { price: 0.0024, quantity: 10.0 }]);
+        assert_eq!(update.asks[1], PriceLevel { price: 0.0027, quantity: 0.0 });
+    }
+
+    #[test]
+    fn test_partial_depth_deserialization() {
+        let json = r#"{"lastUpdateId":160,"bids":[["0.0024","10"],["0.0023","5"]],"asks":[["0.0026","100"]]}"#;
+        let depth = PartialDepth::from_json(json).unwrap();
+
+        assert_eq!(depth.last_update_id, 160);
+        assert_eq!(depth.bids[..], [PriceLevel::new(0.0024, 10.0), PriceLevel::new(0.0023, 5.0)]);
+        assert_eq!(depth.asks[..], [PriceLevel::new(0.0026, 100.0)]);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_inline_levels_serialize_like_vec() {
+        use crate::websocket::{InlineLevels, LEVELS_INLINE};
+
+        let levels: Vec<String> = (0..LEVELS_INLINE * 2).map(|i| format!(r#"["{}.5","{i}"]"#, 100 + i)).collect();
+        let json = format!(r#"{{"e":"depthUpdate","E":1,"s":"BNBBTC","U":157,"u":160,"b":[{}],"a":[["0.0026","100"]]}}"#, levels.join(","));
+        let inline: DepthUpdate<String, InlineLevels> = serde_json::from_str(&json).unwrap();
+        let update = DepthUpdate::from_json(&json).unwrap();
+        assert!(inline.bids.spilled() && !inline.asks.spilled());
+        assert_eq!((inline.bids.len(), inline.bids[LEVELS_INLINE + 3]), (LEVELS_INLINE * 2, PriceLevel::new(119.5, 19.0)));
+        assert_eq!((&inline.bids[..], &inline.asks[..]), (&update.bids[..], &update.asks[..]));
+        assert_eq!(serde_json::to_string(&inline).unwrap(), serde_json::to_string(&update).unwrap());
+    }
+
+    fn depth_json(bids: usize, asks: usize) -> String {
+        let side = |count: usize, base: usize| {
+            (0..count).map(|i| format!(r#"["{}.0","{}"]"#, base + i, i + 1)).collect::<Vec<_>>().join(",")
+        };
+        format!(r#"{{"lastUpdateId":160,"bids":[{}],"asks":[{}]}}"#, side(bids, 100), side(asks, 200))
+    }
+
+    #[test]
+    fn test_partial_depth_fixed_exact_size() {
+        let depth = PartialDepth5::from_json(&depth_json(5, 5)).unwrap();
+        assert!(depth.is_full());
+        assert_eq!(depth.last_update_id, 160);
+        assert_eq!(depth.bids[4], PriceLevel::new(104.0, 5.0));
+        assert_eq!(depth.asks[0], PriceLevel::new(200.0, 1.0));
+        assert_eq!(depth.to_partial_depth(), PartialDepth::from_json(&depth_json(5, 5)).unwrap());
+        assert_eq!(serde_json::to_string(&depth).unwrap(), serde_json::to_string(&depth.to_partial_depth()).unwrap());
+
+        let depth = PartialDepth20::from_json(&depth_json(20, 20)).unwrap();
+        assert!(depth.is_full());
+        assert_eq!(depth.bids[19], PriceLevel::new(119.0, 20.0));
+        assert_eq!(depth.asks().len(), 20);
+    }
+
+    #[test]
+    fn test_partial_depth_fixed_short_is_padded() {
+        let depth = PartialDepth5::from_json(&depth_json(3, 0)).unwrap();
+        assert!(!depth.is_full());
+        assert_eq!((depth.bid_count, depth.ask_count), (3, 0));
+        assert_eq!(depth.bids().last(), Some(&PriceLevel::new(102.0, 3.0)));
+        assert_eq!(depth.bids[3], PriceLevel::new(0.0, 0.0));
+        assert!(depth.asks().is_empty());
+
+        let depth = PartialDepth20::from_json(&depth_json(20, 7)).unwrap();
+        assert_eq!((depth.bid_count, depth.ask_count), (20, 7));
+        assert_eq!(depth.to_partial_depth().asks.len(), 7);
+        assert_eq!(PartialDepth20::from_json(&serde_json::to_string(&depth).unwrap()).unwrap(), depth);
+    }
+
+    #[test]
+    fn test_partial_depth_fixed_oversized() {
+        let err = PartialDepth5::from_json(&depth_json(5, 6)).unwrap_err();
+        assert!(err.is_data());
+        assert!(err.to_string().contains("invalid length 6, expected at most 5 price levels"), "{err}");
+
+        let err = PartialDepth20::from_json(&depth_json(25, 20)).unwrap_err();
+        assert!(err.to_string().contains("invalid length 25, expected at most 20 price levels"), "{err}");
+        assert!(PartialDepth::from_json(&depth_json(25, 20)).is_ok());
+    }
+}
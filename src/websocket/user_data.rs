@@ -0,0 +1,93 @@
+//! User data stream events. Spot and margin events need the `spot` feature, USD-M futures and portfolio
+//! margin events the `futures` feature, listen key expiry and balances are shared.
+
+use serde::{Deserialize, Serialize};
+
+use super::{de_string_or_number_to_u64, de_string_to_f64, ser_f64_as_string};
+
+#[cfg(feature = "futures")]
+mod futures;
+#[cfg(feature = "spot")]
+mod margin;
+#[cfg(feature = "futures")]
+mod portfolio;
+#[cfg(feature = "spot")]
+mod spot;
+
+#[cfg(feature = "futures")]
+pub use self::futures::*;
+#[cfg(feature = "spot")]
+pub use self::margin::*;
+#[cfg(feature = "futures")]
+pub use self::portfolio::*;
+#[cfg(feature = "spot")]
+pub use self::spot::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccountBalance {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "f", alias = "free", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub free: f64,                       // Free
+    #[serde(rename = "l", alias = "locked", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub locked: f64,                     // Locked
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ListenKeyExpired {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time", deserialize_with = "de_string_or_number_to_u64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::IntegerOrString"))]
+    pub event_time: u64,                 // Event time (a string on the futures user data stream)
+    #[serde(rename = "listenKey", alias = "listen-key")]
+    pub listen_key: String,              // Expired listen key
+}
+
+impl ListenKeyExpired {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "spot")]
+    pub(super) const EXECUTION_REPORT_JSON: &str = r#"{"e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"mUvoqJxFIILMdfAW5iGSOW","S":"BUY","o":"LIMIT","f":"GTC","q":"1.00000000","p":"0.10264410","P":"0.00000000","F":"0.00000000","g":-1,"C":"","x":"NEW","X":"NEW","r":"NONE","i":4293153,"l":"0.00000000","z":"0.00000000","L":"0.00000000","n":"0","N":null,"T":1499405658657,"t":-1,"I":8641984,"w":true,"m":false,"M":false,"O":1499405658657,"Z":"0.00000000","Y":"0.00000000","Q":"0.00000000","W":1499405658657}"#;
+
+    #[cfg(feature = "futures")]
+    pub(super) const ORDER_TRADE_UPDATE_JSON: &str = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"TRAILING_STOP_MARKET","f":"GTC","q":"0.001","p":"0","ap":"0","sp":"7103.04","x":"NEW","X":"NEW","i":8886774,"l":"0","z":"0","L":"0","N":"USDT","n":"0","T":1568879465650,"t":0,"b":"0","a":"9.91","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"TRAILING_STOP_MARKET","ps":"LONG","cp":false,"AP":"7476.89","cr":"5.0","pP":false,"si":0,"ss":0,"rp":"0","V":"EXPIRE_TAKER","pm":"OPPONENT","gtd":0}}"#;
+
+    #[cfg(all(feature = "spot", feature = "futures"))]
+    mod spot_and_futures {
+        use super::super::*;
+        use super::{EXECUTION_REPORT_JSON, ORDER_TRADE_UPDATE_JSON};
+
+        #[test]
+        fn test_listen_key_expired_envelopes() {
+            let json = r#"{"e":"listenKeyExpired","E":"1736996475556","listenKey":"WsCMN0a4KHUPTQuX6IUnqEZfB1inxmv1qR4kbf1LuEjur5VdbzqvyxqG9TSjVVxv"}"#;
+            let event = FuturesUserDataEvent::from_json(json).unwrap();
+            let FuturesUserDataEvent::ListenKeyExpired(expired) = event else { panic!("expected listen key expired, got {event:?}") };
+            assert_eq!(expired.event_time, 1736996475556);
+
+            let json = r#"{"e":"listenKeyExpired","E":1699596037418,"listenKey":"OfYGbUzi3PraNagEkdKuFwUHn48brFsItTdsuiIXrucEvD0rhRXZ7I6URWfE8YE8"}"#;
+            let event = UserDataEvent::from_json(json).unwrap();
+            let UserDataEvent::ListenKeyExpired(expired) = event else { panic!("expected listen key expired, got {event:?}") };
+            assert_eq!(expired.event_time, 1699596037418);
+            assert_eq!(expired.listen_key, "OfYGbUzi3PraNagEkdKuFwUHn48brFsItTdsuiIXrucEvD0rhRXZ7I6URWfE8YE8");
+        }
+
+        #[test]
+        fn test_spot_and_futures_user_data_are_separate() {
+            assert!(matches!(UserDataEvent::from_json(EXECUTION_REPORT_JSON).unwrap(), UserDataEvent::ExecutionReport(_)));
+            assert_eq!(FuturesUserDataEvent::from_json(EXECUTION_REPORT_JSON).unwrap(), FuturesUserDataEvent::Unknown(EXECUTION_REPORT_JSON.to_string()));
+
+            assert!(matches!(FuturesUserDataEvent::from_json(ORDER_TRADE_UPDATE_JSON).unwrap(), FuturesUserDataEvent::OrderTradeUpdate(_)));
+            assert_eq!(UserDataEvent::from_json(ORDER_TRADE_UPDATE_JSON).unwrap(), UserDataEvent::Unknown(ORDER_TRADE_UPDATE_JSON.to_string()));
+        }
+    }
+}
@@ -0,0 +1,666 @@
+//! USD-M futures user data stream events.
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::websocket::{de_option_string, de_option_string_to_f64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, EventTypeTag, ExecutionType, OrderStatus, Side};
+use super::ListenKeyExpired;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PositionSide {
+    Both,
+    Long,
+    Short,
+}
+
+impl PositionSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PositionSide::Both => "BOTH",
+            PositionSide::Long => "LONG",
+            PositionSide::Short => "SHORT",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarginType {
+    #[serde(alias = "cross", alias = "CROSS", alias = "crossed")]
+    Crossed,
+    #[serde(alias = "isolated")]
+    Isolated,
+}
+
+// Account updates send the margin type in lowercase, margin calls in uppercase
+fn ser_margin_type_lowercase<S>(margin_type: &MarginType, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(match margin_type {
+        MarginType::Crossed => "cross",
+        MarginType::Isolated => "isolated",
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarginCallPosition {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                      // Symbol
+    #[serde(rename = "ps", alias = "position-side")]
+    pub position_side: PositionSide,         // Position side
+    #[serde(rename = "pa", alias = "position-amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_amount: f64,                // Position amount
+    #[serde(rename = "mt", alias = "margin-type")]
+    pub margin_type: MarginType,             // Margin type
+    #[serde(rename = "iw", alias = "isolated-wallet", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub isolated_wallet: Option<f64>,        // Isolated wallet (if isolated position)
+    #[serde(rename = "mp", alias = "mark-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub mark_price: f64,                     // Mark price
+    #[serde(rename = "up", alias = "unrealized-pnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unrealized_pnl: f64,                 // Unrealized PnL
+    #[serde(rename = "mm", alias = "maintenance-margin-required", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maintenance_margin_required: f64,    // Maintenance margin required
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarginCall {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,                  // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                     // Event time
+    #[serde(rename = "cw", alias = "cross-wallet-balance", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub cross_wallet_balance: Option<f64>,   // Cross wallet balance (only pushed with crossed position margin call)
+    #[serde(rename = "p", alias = "positions")]
+    pub positions: Vec<MarginCallPosition>,  // Positions which are at risk
+}
+
+impl MarginCall {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LeverageConfig {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "l", alias = "leverage")]
+    pub leverage: u32,                   // Leverage
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MultiAssetsConfig {
+    #[serde(rename = "j", alias = "multi-assets-mode")]
+    pub multi_assets_mode: bool,         // Multi-Assets mode
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountConfigChange<'a> {
+    Leverage(&'a LeverageConfig),
+    MultiAssetsMode(bool),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccountConfigUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,                          // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                             // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,                       // Transaction time
+    #[serde(rename = "ac", alias = "leverage-config", default, skip_serializing_if = "Option::is_none")]
+    pub leverage_config: Option<LeverageConfig>,     // Trade pair leverage change (only one of ac/ai is pushed)
+    #[serde(rename = "ai", alias = "multi-assets-config", default, skip_serializing_if = "Option::is_none")]
+    pub multi_assets_config: Option<MultiAssetsConfig>, // Multi-Assets mode change (only one of ac/ai is pushed)
+}
+
+impl AccountConfigUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn change(&self) -> Option<AccountConfigChange<'_>> {
+        match (&self.leverage_config, &self.multi_assets_config) {
+            (Some(leverage_config), _) => Some(AccountConfigChange::Leverage(leverage_config)),
+            (None, Some(multi_assets_config)) => Some(AccountConfigChange::MultiAssetsMode(multi_assets_config.multi_assets_mode)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TradeLite {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "q", alias = "original-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_quantity: f64,          // Original quantity
+    #[serde(rename = "p", alias = "original-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_price: f64,             // Original price
+    #[serde(rename = "m", alias = "is-maker")]
+    pub is_maker: bool,                  // Is this trade the maker side?
+    #[serde(rename = "c", alias = "client-order-id")]
+    pub client_order_id: String,         // Client order ID
+    #[serde(rename = "S", alias = "side")]
+    pub side: Side,                      // Side
+    #[serde(rename = "L", alias = "last-filled-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_filled_price: f64,          // Last filled price
+    #[serde(rename = "l", alias = "last-filled-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_filled_quantity: f64,       // Order last filled quantity
+    #[serde(rename = "t", alias = "trade-id")]
+    pub trade_id: u64,                   // Trade ID
+    #[serde(rename = "i", alias = "order-id")]
+    pub order_id: u64,                   // Order ID
+}
+
+impl TradeLite {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StrategyUpdateInfo {
+    #[serde(rename = "si", alias = "strategy-id")]
+    pub strategy_id: u64,                // Strategy ID
+    #[serde(rename = "st", alias = "strategy-type")]
+    pub strategy_type: String,           // Strategy type
+    #[serde(rename = "ss", alias = "strategy-status")]
+    pub strategy_status: String,         // Strategy status
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "ut", alias = "update-time")]
+    pub update_time: u64,                // Update time
+    #[serde(rename = "c", alias = "op-code", default, skip_serializing_if = "Option::is_none")]
+    pub op_code: Option<u32>,            // Operation code
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StrategyUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "su", alias = "strategy-update")]
+    pub strategy_update: StrategyUpdateInfo, // Strategy update
+}
+
+impl StrategyUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GridUpdateInfo {
+    #[serde(rename = "si", alias = "strategy-id")]
+    pub strategy_id: u64,                // Strategy ID of the grid
+    #[serde(rename = "st", alias = "strategy-type")]
+    pub strategy_type: String,           // Strategy type
+    #[serde(rename = "ss", alias = "strategy-status")]
+    pub strategy_status: String,         // Strategy status
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "r", alias = "realized-pnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub realized_pnl: f64,               // Realized PnL
+    #[serde(rename = "up", alias = "unmatched-average-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unmatched_average_price: f64,    // Unmatched average price
+    #[serde(rename = "uq", alias = "unmatched-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unmatched_quantity: f64,         // Unmatched quantity
+    #[serde(rename = "uf", alias = "unmatched-fee", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unmatched_fee: f64,              // Unmatched fee
+    #[serde(rename = "mp", alias = "matched-pnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub matched_pnl: f64,                // Matched PnL
+    #[serde(rename = "ut", alias = "update-time")]
+    pub update_time: u64,                // Update time
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GridUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "gu", alias = "grid-update")]
+    pub grid_update: GridUpdateInfo,     // Grid update
+}
+
+impl GridUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FuturesBalance {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "wb", alias = "wallet-balance", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub wallet_balance: f64,             // Wallet balance
+    #[serde(rename = "cw", alias = "cross-wallet-balance", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cross_wallet_balance: f64,       // Cross wallet balance
+    #[serde(rename = "bc", alias = "balance-change", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub balance_change: f64,             // Balance change except PnL and commission
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FuturesPosition {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "pa", alias = "position-amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub position_amount: f64,            // Position amount
+    #[serde(rename = "ep", alias = "entry-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub entry_price: f64,                // Entry price
+    #[serde(rename = "bep", alias = "breakeven-price", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub breakeven_price: Option<f64>,    // Breakeven price
+    #[serde(rename = "cr", alias = "accumulated-realized", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub accumulated_realized: f64,       // (Pre-fee) accumulated realized
+    #[serde(rename = "up", alias = "unrealized-pnl", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub unrealized_pnl: f64,             // Unrealized PnL
+    #[serde(rename = "mt", alias = "margin-type", serialize_with = "ser_margin_type_lowercase")]
+    pub margin_type: MarginType,         // Margin type, lowercase on the wire
+    #[serde(rename = "iw", alias = "isolated-wallet", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub isolated_wallet: f64,            // Isolated wallet (if isolated position)
+    #[serde(rename = "ps", alias = "position-side")]
+    pub position_side: PositionSide,     // Position side
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccountUpdateData {
+    #[serde(rename = "m", alias = "reason")]
+    pub reason: String,                  // Event reason type
+    #[serde(rename = "B", alias = "balances")]
+    pub balances: Vec<FuturesBalance>,   // Balances
+    #[serde(rename = "P", alias = "positions")]
+    pub positions: Vec<FuturesPosition>, // Positions
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccountUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "a", alias = "update-data")]
+    pub update_data: AccountUpdateData,  // Update data
+}
+
+impl AccountUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FuturesOrder {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "c", alias = "client-order-id")]
+    pub client_order_id: String,         // Client order ID
+    #[serde(rename = "S", alias = "side")]
+    pub side: Side,                      // Side
+    #[serde(rename = "o", alias = "order-type")]
+    pub order_type: String,              // Order type
+    #[serde(rename = "f", alias = "time-in-force")]
+    pub time_in_force: String,           // Time in force
+    #[serde(rename = "q", alias = "original-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_quantity: f64,          // Original quantity
+    #[serde(rename = "p", alias = "original-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub original_price: f64,             // Original price
+    #[serde(rename = "ap", alias = "average-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub average_price: f64,              // Average price
+    #[serde(rename = "sp", alias = "stop-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub stop_price: f64,                 // Stop price. Please ignore with TRAILING_STOP_MARKET order
+    #[serde(rename = "x", alias = "execution-type")]
+    pub execution_type: ExecutionType,   // Execution type
+    #[serde(rename = "X", alias = "order-status")]
+    pub order_status: OrderStatus,       // Order status
+    #[serde(rename = "i", alias = "order-id")]
+    pub order_id: u64,                   // Order ID
+    #[serde(rename = "l", alias = "last-filled-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_filled_quantity: f64,       // Order last filled quantity
+    #[serde(rename = "z", alias = "filled-accumulated-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub filled_accumulated_quantity: f64, // Order filled accumulated quantity
+    #[serde(rename = "L", alias = "last-filled-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_filled_price: f64,          // Last filled price
+    #[serde(rename = "N", alias = "commission-asset", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string")]
+    pub commission_asset: Option<String>, // Commission asset, will not push if no commission
+    #[serde(rename = "n", alias = "commission", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub commission: Option<f64>,         // Commission, will not push if no commission
+    #[serde(rename = "T", alias = "trade-time")]
+    pub trade_time: u64,                 // Order trade time
+    #[serde(rename = "t", alias = "trade-id")]
+    pub trade_id: i64,                   // Trade ID
+    #[serde(rename = "b", alias = "bids-notional", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub bids_notional: f64,              // Bids notional
+    #[serde(rename = "a", alias = "asks-notional", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub asks_notional: f64,              // Ask notional
+    #[serde(rename = "m", alias = "is-maker")]
+    pub is_maker: bool,                  // Is this trade the maker side?
+    #[serde(rename = "R", alias = "is-reduce-only")]
+    pub is_reduce_only: bool,            // Is this reduce only
+    #[serde(rename = "wt", alias = "stop-price-working-type")]
+    pub stop_price_working_type: String, // Stop price working type
+    #[serde(rename = "ot", alias = "original-order-type")]
+    pub original_order_type: String,     // Original order type
+    #[serde(rename = "ps", alias = "position-side")]
+    pub position_side: PositionSide,     // Position side
+    #[serde(rename = "cp", alias = "is-close-position")]
+    pub is_close_position: bool,         // If Close-All, pushed with conditional order
+    #[serde(rename = "AP", alias = "activation-price", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub activation_price: Option<f64>,   // Activation price, only pushed with TRAILING_STOP_MARKET order
+    #[serde(rename = "cr", alias = "callback-rate", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub callback_rate: Option<f64>,      // Callback rate, only pushed with TRAILING_STOP_MARKET order
+    #[serde(rename = "pP", alias = "price-protect", default)]
+    pub price_protect: bool,             // If price protection is turned on
+    #[serde(rename = "si", alias = "ignore-si", default, skip_serializing_if = "Option::is_none")]
+    pub ignore_si: Option<u64>,          // Ignore
+    #[serde(rename = "ss", alias = "ignore-ss", default, skip_serializing_if = "Option::is_none")]
+    pub ignore_ss: Option<u64>,          // Ignore
+    #[serde(rename = "rp", alias = "realized-profit", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub realized_profit: f64,            // Realized profit of the trade
+    #[serde(rename = "V", alias = "self-trade-prevention-mode", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string")]
+    pub self_trade_prevention_mode: Option<String>, // STP mode
+    #[serde(rename = "pm", alias = "price-match-mode", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string")]
+    pub price_match_mode: Option<String>, // Price match mode
+    #[serde(rename = "gtd", alias = "good-till-date", default, skip_serializing_if = "Option::is_none")]
+    pub good_till_date: Option<u64>,     // TIF GTD order auto cancel time
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "o", alias = "order")]
+    pub order: FuturesOrder,             // Order
+}
+
+impl OrderTradeUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuturesUserDataEvent {
+    MarginCall(MarginCall),
+    AccountConfigUpdate(AccountConfigUpdate),
+    TradeLite(TradeLite),
+    StrategyUpdate(StrategyUpdate),
+    GridUpdate(GridUpdate),
+    AccountUpdate(AccountUpdate),
+    OrderTradeUpdate(OrderTradeUpdate),
+    ListenKeyExpired(ListenKeyExpired),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl FuturesUserDataEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, FuturesUserDataEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, FuturesUserDataEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        FuturesUserDataEvent::from_tagged(EventTypeTag::from_json(json)?.event_type.as_deref(), json)
+    }
+
+    // For dispatchers that already read the event type of the payload
+    pub(crate) fn from_tagged(event_type: Option<&str>, json: &str) -> Result<Self, serde_json::Error> {
+        match event_type {
+            Some("MARGIN_CALL") => MarginCall::from_json(json).map(FuturesUserDataEvent::MarginCall),
+            Some("ACCOUNT_CONFIG_UPDATE") => AccountConfigUpdate::from_json(json).map(FuturesUserDataEvent::AccountConfigUpdate),
+            Some("TRADE_LITE") => TradeLite::from_json(json).map(FuturesUserDataEvent::TradeLite),
+            Some("STRATEGY_UPDATE") => StrategyUpdate::from_json(json).map(FuturesUserDataEvent::StrategyUpdate),
+            Some("GRID_UPDATE") => GridUpdate::from_json(json).map(FuturesUserDataEvent::GridUpdate),
+            Some("ACCOUNT_UPDATE") => AccountUpdate::from_json(json).map(FuturesUserDataEvent::AccountUpdate),
+            Some("ORDER_TRADE_UPDATE") => OrderTradeUpdate::from_json(json).map(FuturesUserDataEvent::OrderTradeUpdate),
+            Some("listenKeyExpired") => ListenKeyExpired::from_json(json).map(FuturesUserDataEvent::ListenKeyExpired),
+            _ => Ok(FuturesUserDataEvent::Unknown(json.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::user_data::tests::ORDER_TRADE_UPDATE_JSON;
+
+    #[test]
+    fn test_margin_call_isolated_deserialization() {
+        let json = r#"{"e":"MARGIN_CALL","E":1587727187525,"p":[{"s":"ETHUSDT","ps":"LONG","pa":"1.327","mt":"ISOLATED","iw":"0.85012354","mp":"187.17127","up":"-1.166074","mm":"1.614445"}]}"#;
+        let margin_call = MarginCall::from_json(json).unwrap();
+
+        assert_eq!(margin_call.event_type, "MARGIN_CALL");
+        assert_eq!(margin_call.event_time, 1587727187525);
+        assert_eq!(margin_call.cross_wallet_balance, None);
+        assert_eq!(margin_call.positions, vec![MarginCallPosition {
+            symbol: "ETHUSDT".to_string(),
+            position_side: PositionSide::Long,
+            position_amount: 1.327,
+            margin_type: MarginType::Isolated,
+            isolated_wallet: Some(0.85012354),
+            mark_price: 187.17127,
+            unrealized_pnl: -1.166074,
+            maintenance_margin_required: 1.614445,
+        }]);
+    }
+
+    #[test]
+    fn test_margin_call_crossed_deserialization() {
+        let json = r#"{"e":"MARGIN_CALL","E":1587727187525,"cw":"3.16812045","p":[{"s":"ETHUSDT","ps":"BOTH","pa":"-1.327","mt":"CROSSED","mp":"187.17127","up":"-1.166074","mm":"1.614445"}]}"#;
+        let margin_call = MarginCall::from_json(json).unwrap();
+
+        assert_eq!(margin_call.cross_wallet_balance, Some(3.16812045));
+        assert_eq!(margin_call.positions[0].margin_type, MarginType::Crossed);
+        assert_eq!(margin_call.positions[0].position_side, PositionSide::Both);
+        assert_eq!(margin_call.positions[0].isolated_wallet, None);
+    }
+
+    #[test]
+    fn test_account_config_update_leverage() {
+        let json = r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSDT","l":25}}"#;
+        let update = AccountConfigUpdate::from_json(json).unwrap();
+
+        assert_eq!(update.event_type, "ACCOUNT_CONFIG_UPDATE");
+        assert_eq!(update.event_time, 1611646737479);
+        assert_eq!(update.transaction_time, 1611646737476);
+        assert_eq!(update.multi_assets_config, None);
+        let expected = LeverageConfig { symbol: "BTCUSDT".to_string(), leverage: 25 };
+        assert_eq!(update.change(), Some(AccountConfigChange::Leverage(&expected)));
+    }
+
+    #[test]
+    fn test_account_config_update_multi_assets_mode() {
+        let json = r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ai":{"j":true}}"#;
+        let update = AccountConfigUpdate::from_json(json).unwrap();
+
+        assert_eq!(update.leverage_config, None);
+        assert_eq!(update.change(), Some(AccountConfigChange::MultiAssetsMode(true)));
+    }
+
+    #[test]
+    fn test_trade_lite_deserialization() {
+        let json = r#"{"e":"TRADE_LITE","E":1721895408092,"T":1721895408214,"s":"BTCUSDT","q":"0.001","p":"0","m":false,"c":"z8hcUoOsqEdKMeKPSABslD","S":"BUY","L":"64089.20","l":"0.040","t":109100866,"i":8886774}"#;
+        let expected = TradeLite {
+            event_type: "TRADE_LITE".to_string(),
+            event_time: 1721895408092,
+            transaction_time: 1721895408214,
+            symbol: "BTCUSDT".to_string(),
+            original_quantity: 0.001,
+            original_price: 0.0,
+            is_maker: false,
+            client_order_id: "z8hcUoOsqEdKMeKPSABslD".to_string(),
+            side: Side::Buy,
+            last_filled_price: 64089.2,
+            last_filled_quantity: 0.04,
+            trade_id: 109100866,
+            order_id: 8886774,
+        };
+        assert_eq!(TradeLite::from_json(json).unwrap(), expected);
+        assert_eq!(FuturesUserDataEvent::from_json(json).unwrap(), FuturesUserDataEvent::TradeLite(expected));
+    }
+
+    #[test]
+    fn test_futures_user_data_event_unknown() {
+        let json = r#"{"e":"SOMETHING_NEW","E":1721895408092}"#;
+        let event = FuturesUserDataEvent::from_json(json).unwrap();
+        assert_eq!(event, FuturesUserDataEvent::Unknown(json.to_string()));
+    }
+
+    #[test]
+    fn test_strategy_update_deserialization() {
+        let json = r#"{"e":"STRATEGY_UPDATE","T":1669262908216,"E":1669262908218,"su":{"si":176054594,"st":"GRID","ss":"NEW","s":"BTCUSDT","ut":1669262908216,"c":8007}}"#;
+        let expected = StrategyUpdate {
+            event_type: "STRATEGY_UPDATE".to_string(),
+            event_time: 1669262908218,
+            transaction_time: 1669262908216,
+            strategy_update: StrategyUpdateInfo {
+                strategy_id: 176054594,
+                strategy_type: "GRID".to_string(),
+                strategy_status: "NEW".to_string(),
+                symbol: "BTCUSDT".to_string(),
+                update_time: 1669262908216,
+                op_code: Some(8007),
+            },
+        };
+        assert_eq!(StrategyUpdate::from_json(json).unwrap(), expected);
+        assert_eq!(FuturesUserDataEvent::from_json(json).unwrap(), FuturesUserDataEvent::StrategyUpdate(expected));
+    }
+
+    #[test]
+    fn test_grid_update_deserialization() {
+        let json = r#"{"e":"GRID_UPDATE","T":1669262908216,"E":1669262908218,"gu":{"si":176057039,"st":"GRID","ss":"WORKING","s":"BTCUSDT","r":"-0.00300716","up":"16720","uq":"0.001","uf":"0.00300716","mp":"0.0","ut":1669262908197}}"#;
+        let expected = GridUpdate {
+            event_type: "GRID_UPDATE".to_string(),
+            event_time: 1669262908218,
+            transaction_time: 1669262908216,
+            grid_update: GridUpdateInfo {
+                strategy_id: 176057039,
+                strategy_type: "GRID".to_string(),
+                strategy_status: "WORKING".to_string(),
+                symbol: "BTCUSDT".to_string(),
+                realized_pnl: -0.00300716,
+                unmatched_average_price: 16720.0,
+                unmatched_quantity: 0.001,
+                unmatched_fee: 0.00300716,
+                matched_pnl: 0.0,
+                update_time: 1669262908197,
+            },
+        };
+        assert_eq!(GridUpdate::from_json(json).unwrap(), expected);
+        assert_eq!(FuturesUserDataEvent::from_json(json).unwrap(), FuturesUserDataEvent::GridUpdate(expected));
+    }
+
+    #[test]
+    fn test_order_trade_update_deserialization() {
+        let update = OrderTradeUpdate::from_json(ORDER_TRADE_UPDATE_JSON).unwrap();
+
+        assert_eq!(update.event_time, 1568879465651);
+        assert_eq!(update.order.symbol, "BTCUSDT");
+        assert_eq!(update.order.side, Side::Sell);
+        assert_eq!(update.order.stop_price, 7103.04);
+        assert_eq!(update.order.position_side, PositionSide::Long);
+        assert_eq!(update.order.activation_price, Some(7476.89));
+        assert_eq!(update.order.callback_rate, Some(5.0));
+        assert_eq!(update.order.commission_asset.as_deref(), Some("USDT"));
+        assert_eq!(update.order.self_trade_prevention_mode.as_deref(), Some("EXPIRE_TAKER"));
+    }
+
+    #[test]
+    fn test_account_update_deserialization() {
+        let json = r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"122624.12345678","cw":"100.12345678","bc":"50.12345678"}],"P":[{"s":"BTCUSDT","pa":"-20","ep":"6563.66500","bep":"6563.6","cr":"0","up":"2850.21200","mt":"isolated","iw":"13200.70726908","ps":"SHORT"}]}}"#;
+        let event = FuturesUserDataEvent::from_json(json).unwrap();
+
+        let FuturesUserDataEvent::AccountUpdate(update) = event else { panic!("expected account update, got {event:?}") };
+        assert_eq!(update.update_data.reason, "ORDER");
+        assert_eq!(update.update_data.balances[0].wallet_balance, 122624.12345678);
+        assert_eq!(update.update_data.positions[0], FuturesPosition {
+            symbol: "BTCUSDT".to_string(),
+            position_amount: -20.0,
+            entry_price: 6563.665,
+            breakeven_price: Some(6563.6),
+            accumulated_realized: 0.0,
+            unrealized_pnl: 2850.212,
+            margin_type: MarginType::Isolated,
+            isolated_wallet: 13200.70726908,
+            position_side: PositionSide::Short,
+        });
+    }
+}
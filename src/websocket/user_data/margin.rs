@@ -0,0 +1,163 @@
+//! Cross and isolated margin user data stream events. Orders, balances and account positions arrive in the
+//! spot shapes, the margin level and liability events are margin only.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::websocket::{de_string_to_f64, ser_f64_as_string, EventTypeTag};
+use super::{BalanceUpdate, ExecutionReport, ListenKeyExpired, OutboundAccountPosition};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarginLevelStatus {
+    Excessive,
+    Normal,
+    MarginCall,
+    PreLiquidation,
+    ForceLiquidation,
+    #[serde(other)]
+    Unknown,
+}
+
+impl MarginLevelStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarginLevelStatus::Excessive => "EXCESSIVE",
+            MarginLevelStatus::Normal => "NORMAL",
+            MarginLevelStatus::MarginCall => "MARGIN_CALL",
+            MarginLevelStatus::PreLiquidation => "PRE_LIQUIDATION",
+            MarginLevelStatus::ForceLiquidation => "FORCE_LIQUIDATION",
+            MarginLevelStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+// Sent when the margin level crosses into another status
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MarginLevelStatusChange {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "l", alias = "margin-level", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub margin_level: f64,               // Margin level
+    #[serde(rename = "s", alias = "status")]
+    pub status: MarginLevelStatus,       // Margin level status
+}
+
+impl MarginLevelStatusChange {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Sent when a borrow changes the liability of an asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LiabilityChange {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "t", alias = "liability-type")]
+    pub liability_type: String,          // Liability type, e.g. "BORROW"
+    #[serde(rename = "T", alias = "transaction-id")]
+    pub transaction_id: u64,             // Transaction ID
+    #[serde(rename = "p", alias = "principal", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub principal: f64,                  // Principal quantity
+    #[serde(rename = "i", alias = "interest", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub interest: f64,                   // Interest quantity
+}
+
+impl LiabilityChange {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Events of a cross margin stream or of the per symbol stream of an isolated margin account, see
+// `rest::IsolatedMarginListenKey`
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MarginUserDataEvent {
+    ExecutionReport(ExecutionReport),
+    OutboundAccountPosition(OutboundAccountPosition),
+    BalanceUpdate(BalanceUpdate),
+    MarginLevelStatusChange(MarginLevelStatusChange),
+    LiabilityChange(LiabilityChange),
+    ListenKeyExpired(ListenKeyExpired),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl MarginUserDataEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, MarginUserDataEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, MarginUserDataEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("executionReport") => ExecutionReport::from_json(json).map(MarginUserDataEvent::ExecutionReport),
+            Some("outboundAccountPosition") => OutboundAccountPosition::from_json(json).map(MarginUserDataEvent::OutboundAccountPosition),
+            Some("balanceUpdate") => BalanceUpdate::from_json(json).map(MarginUserDataEvent::BalanceUpdate),
+            Some("MARGIN_LEVEL_STATUS_CHANGE") => MarginLevelStatusChange::from_json(json).map(MarginUserDataEvent::MarginLevelStatusChange),
+            Some("USER_LIABILITY_CHANGE") => LiabilityChange::from_json(json).map(MarginUserDataEvent::LiabilityChange),
+            Some("listenKeyExpired") => ListenKeyExpired::from_json(json).map(MarginUserDataEvent::ListenKeyExpired),
+            _ => Ok(MarginUserDataEvent::Unknown(json.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest::IsolatedMarginListenKey;
+    use crate::testutil::{margin, rest, sample_execution_report_json};
+    use crate::websocket::UserDataEvent;
+
+    #[test]
+    fn test_isolated_margin_listen_key() {
+        let key = IsolatedMarginListenKey::from_json(rest::sample_listen_key_json(), "BNBUSDT").unwrap();
+        assert_eq!(key.symbol, "BNBUSDT");
+        assert_eq!(key.listen_key, "T3ee22BIYuWqmvne0HNq2A2WsFlEtLhvWCtItw6ffhhdmjifQ2tRbuKkTHhr");
+
+        let json = r#"{"symbol":"ETHUSDT","listenKey":"T3ee22BIYuWqmvne0HNq2A2WsFlEtLhvWCtItw6ffhhdmjifQ2tRbuKkTHhr"}"#;
+        assert_eq!(IsolatedMarginListenKey::from_json(json, "BNBUSDT").unwrap().symbol, "ETHUSDT");
+    }
+
+    #[test]
+    fn test_execution_report_on_margin_stream() {
+        let event = MarginUserDataEvent::from_json(sample_execution_report_json()).unwrap();
+        let UserDataEvent::ExecutionReport(spot) = UserDataEvent::from_json(sample_execution_report_json()).unwrap() else { panic!("expected an execution report") };
+        assert_eq!(event, MarginUserDataEvent::ExecutionReport(spot));
+    }
+
+    #[test]
+    fn test_margin_only_events() {
+        let event = MarginUserDataEvent::from_json(margin::sample_margin_level_status_change_json()).unwrap();
+        let MarginUserDataEvent::MarginLevelStatusChange(change) = event else { panic!("expected a margin level change, got {event:?}") };
+        assert_eq!((change.margin_level, change.status), (1.42, MarginLevelStatus::Normal));
+
+        let event = MarginUserDataEvent::parse(margin::sample_liability_change_json()).unwrap();
+        let MarginUserDataEvent::LiabilityChange(change) = event else { panic!("expected a liability change, got {event:?}") };
+        assert_eq!(change.asset, "BTC");
+        assert_eq!(change.transaction_id, 1352286576452864727);
+        assert_eq!((change.principal, change.interest), (1.0345343, 0.0));
+
+        let unknown = r#"{"e":"MARGIN_LEVEL_STATUS_CHANGE","E":1701949763462,"l":"0.98","s":"NEW_STATUS"}"#;
+        assert!(matches!(MarginUserDataEvent::from_json(unknown), Ok(MarginUserDataEvent::MarginLevelStatusChange(change)) if change.status == MarginLevelStatus::Unknown));
+        assert_eq!(UserDataEvent::from_json(margin::sample_liability_change_json()).unwrap(), UserDataEvent::Unknown(margin::sample_liability_change_json().to_string()));
+    }
+}
@@ -0,0 +1,290 @@
+//! Portfolio margin user data stream events, on the stream of a `/papi` listen key.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::websocket::{de_option_string_to_f64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, EventTypeTag, Side};
+use super::{ListenKeyExpired, PositionSide};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskLevelStatus {
+    Normal,
+    MarginCall,
+    SupplyMargin,
+    ReduceOnly,
+    ActiveLiquidation,
+    ForceLiquidation,
+    Bankrupted,
+    #[serde(other)]
+    Unknown,
+}
+
+impl RiskLevelStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskLevelStatus::Normal => "NORMAL",
+            RiskLevelStatus::MarginCall => "MARGIN_CALL",
+            RiskLevelStatus::SupplyMargin => "SUPPLY_MARGIN",
+            RiskLevelStatus::ReduceOnly => "REDUCE_ONLY",
+            RiskLevelStatus::ActiveLiquidation => "ACTIVE_LIQUIDATION",
+            RiskLevelStatus::ForceLiquidation => "FORCE_LIQUIDATION",
+            RiskLevelStatus::Bankrupted => "BANKRUPTED",
+            RiskLevelStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+// Sent when the unified maintenance margin ratio moves the account into another risk status
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RiskLevelChange {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "u", alias = "uni-mmr", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub uni_mmr: f64,                    // Unified maintenance margin ratio
+    #[serde(rename = "s", alias = "status")]
+    pub status: RiskLevelStatus,         // Risk status
+    #[serde(rename = "eq", alias = "equity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub equity: f64,                     // Account equity in USD
+    #[serde(rename = "ae", alias = "actual-equity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub actual_equity: f64,              // Account equity without collateral rates in USD
+    #[serde(rename = "m", alias = "maintenance-margin", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub maintenance_margin: f64,         // Total maintenance margin in USD
+}
+
+impl RiskLevelChange {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrderLoss {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "o", alias = "loss", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub loss: f64,                       // Open order loss, negative
+}
+
+// Sent when the loss of open orders changes, with every asset that has one
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OpenOrderLoss {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "O", alias = "losses")]
+    pub losses: Vec<OrderLoss>,          // Losses by asset
+}
+
+impl OpenOrderLoss {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Strategy order, the conditional order that places a regular order once triggered
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConditionalOrder {
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "c", alias = "client-strategy-id")]
+    pub client_strategy_id: String,      // Client strategy ID
+    #[serde(rename = "si", alias = "strategy-id")]
+    pub strategy_id: u64,                // Strategy ID
+    #[serde(rename = "S", alias = "side")]
+    pub side: Side,                      // Side
+    #[serde(rename = "st", alias = "strategy-type")]
+    pub strategy_type: String,           // Strategy type, e.g. "STOP", "TAKE_PROFIT" or "TRAILING_STOP_MARKET"
+    #[serde(rename = "f", alias = "time-in-force")]
+    pub time_in_force: String,           // Time in force
+    #[serde(rename = "q", alias = "quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quantity: f64,                   // Quantity
+    #[serde(rename = "p", alias = "price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub price: f64,                      // Price
+    #[serde(rename = "sp", alias = "stop-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub stop_price: f64,                 // Stop price
+    #[serde(rename = "os", alias = "strategy-status")]
+    pub strategy_status: String,         // Strategy status, e.g. "NEW", "CANCELED", "EXPIRED" or "TRIGGERED"
+    #[serde(rename = "T", alias = "order-book-time")]
+    pub order_book_time: u64,            // Order book time
+    #[serde(rename = "ut", alias = "update-time")]
+    pub update_time: u64,                // Update time
+    #[serde(rename = "R", alias = "is-reduce-only")]
+    pub is_reduce_only: bool,            // Is this reduce only
+    #[serde(rename = "wt", alias = "stop-price-working-type")]
+    pub stop_price_working_type: String, // Stop price working type
+    #[serde(rename = "ps", alias = "position-side")]
+    pub position_side: PositionSide,     // Position side
+    #[serde(rename = "cp", alias = "is-close-position")]
+    pub is_close_position: bool,         // If Close-All
+    #[serde(rename = "AP", alias = "activation-price", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub activation_price: Option<f64>,   // Activation price, only pushed with TRAILING_STOP_MARKET orders
+    #[serde(rename = "cr", alias = "callback-rate", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub callback_rate: Option<f64>,      // Callback rate, only pushed with TRAILING_STOP_MARKET orders
+    #[serde(rename = "i", alias = "order-id", default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<u64>,           // ID of the order placed when triggered
+    #[serde(rename = "V", alias = "self-trade-prevention-mode", default, skip_serializing_if = "Option::is_none")]
+    pub self_trade_prevention_mode: Option<String>, // Self-trade prevention mode
+    #[serde(rename = "gtd", alias = "good-till-date", default, skip_serializing_if = "Option::is_none")]
+    pub good_till_date: Option<u64>,     // Auto cancel time of GTD orders, 0 otherwise
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConditionalOrderTradeUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "fs", alias = "business-unit")]
+    pub business_unit: String,           // "UM" for USD-M or "CM" for COIN-M futures
+    #[serde(rename = "so", alias = "strategy-order")]
+    pub order: ConditionalOrder,         // Strategy order
+}
+
+impl ConditionalOrderTradeUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// Sent when a borrow or repayment changes the margin liability of an asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PortfolioLiabilityChange {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "t", alias = "liability-type")]
+    pub liability_type: String,          // Liability type, e.g. "BORROW"
+    #[serde(rename = "tx", alias = "transaction-id")]
+    pub transaction_id: u64,             // Transaction ID
+    #[serde(rename = "p", alias = "principal", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub principal: f64,                  // Principal
+    #[serde(rename = "i", alias = "interest", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub interest: f64,                   // Interest
+    #[serde(rename = "l", alias = "total-liability", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub total_liability: f64,            // Total liability
+}
+
+impl PortfolioLiabilityChange {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// The product changes often, event types added later are returned as `Unknown`
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortfolioMarginEvent {
+    RiskLevelChange(RiskLevelChange),
+    OpenOrderLoss(OpenOrderLoss),
+    ConditionalOrderTradeUpdate(ConditionalOrderTradeUpdate),
+    LiabilityChange(PortfolioLiabilityChange),
+    ListenKeyExpired(ListenKeyExpired),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl PortfolioMarginEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, PortfolioMarginEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, PortfolioMarginEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("riskLevelChange") => RiskLevelChange::from_json(json).map(PortfolioMarginEvent::RiskLevelChange),
+            Some("openOrderLoss") => OpenOrderLoss::from_json(json).map(PortfolioMarginEvent::OpenOrderLoss),
+            Some("CONDITIONAL_ORDER_TRADE_UPDATE") => ConditionalOrderTradeUpdate::from_json(json).map(PortfolioMarginEvent::ConditionalOrderTradeUpdate),
+            Some("liabilityChange") => PortfolioLiabilityChange::from_json(json).map(PortfolioMarginEvent::LiabilityChange),
+            Some("listenKeyExpired") => ListenKeyExpired::from_json(json).map(PortfolioMarginEvent::ListenKeyExpired),
+            _ => Ok(PortfolioMarginEvent::Unknown(json.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::portfolio;
+
+    #[test]
+    fn test_risk_level_change() {
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_risk_level_change_json()).unwrap();
+        let expected = RiskLevelChange {
+            event_type: "riskLevelChange".to_string(),
+            event_time: 1587727187525,
+            uni_mmr: 1.99999999,
+            status: RiskLevelStatus::MarginCall,
+            equity: 30.23416728,
+            actual_equity: 30.23416728,
+            maintenance_margin: 15.11708371,
+        };
+        assert_eq!(event, PortfolioMarginEvent::RiskLevelChange(expected));
+
+        let json = portfolio::sample_risk_level_change_json().replace("MARGIN_CALL", "SOMETHING_NEW");
+        assert!(matches!(PortfolioMarginEvent::from_json(&json), Ok(PortfolioMarginEvent::RiskLevelChange(change)) if change.status == RiskLevelStatus::Unknown));
+    }
+
+    #[test]
+    fn test_conditional_order_trade_update() {
+        let event = PortfolioMarginEvent::parse(portfolio::sample_conditional_order_trade_update_json()).unwrap();
+        let PortfolioMarginEvent::ConditionalOrderTradeUpdate(update) = event else { panic!("expected a conditional order update, got {event:?}") };
+        assert_eq!((update.transaction_time, update.business_unit.as_str()), (1669262908216, "UM"));
+
+        let order = &update.order;
+        assert_eq!((order.symbol.as_str(), order.strategy_id, order.side), ("BTCUSDT", 176057039, Side::Sell));
+        assert_eq!((order.strategy_type.as_str(), order.strategy_status.as_str()), ("TRAILING_STOP_MARKET", "NEW"));
+        assert_eq!((order.stop_price, order.activation_price, order.callback_rate), (7103.04, Some(7476.89), Some(5.0)));
+        assert_eq!((order.position_side, order.order_id, order.good_till_date), (PositionSide::Long, Some(8886774), Some(0)));
+
+        // Before it triggers a strategy has no order ID
+        let json = r#"{"e":"CONDITIONAL_ORDER_TRADE_UPDATE","T":1669262908216,"E":1669262908218,"fs":"CM","so":{"s":"BTCUSD_PERP","c":"TEST","si":176057040,"S":"BUY","st":"STOP","f":"GTC","q":"1","p":"30000","sp":"29900","os":"NEW","T":1568879465650,"ut":1669262908197,"R":false,"wt":"CONTRACT_PRICE","ps":"BOTH","cp":false}}"#;
+        let update = ConditionalOrderTradeUpdate::from_json(json).unwrap();
+        assert_eq!((update.order.order_id, update.order.activation_price), (None, None));
+    }
+
+    #[test]
+    fn test_open_order_loss_liability_and_unknown() {
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_open_order_loss_json()).unwrap();
+        let PortfolioMarginEvent::OpenOrderLoss(loss) = event else { panic!("expected open order loss, got {event:?}") };
+        assert_eq!(loss.losses.iter().map(|loss| (loss.asset.as_str(), loss.loss)).collect::<Vec<_>>(), vec![("BUSD", -0.1232313), ("BNB", -12.1232313)]);
+
+        let event = PortfolioMarginEvent::from_json(portfolio::sample_liability_change_json()).unwrap();
+        assert!(matches!(event, PortfolioMarginEvent::LiabilityChange(change) if change.transaction_id == 1352286576452864727 && change.total_liability == 1.03476851));
+
+        let json = r#"{"e":"newPortfolioEvent","E":1678710578788}"#;
+        assert_eq!(PortfolioMarginEvent::from_json(json).unwrap(), PortfolioMarginEvent::Unknown(json.to_string()));
+    }
+}
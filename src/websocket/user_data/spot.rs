@@ -0,0 +1,243 @@
+//! Spot user data stream events.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{parse_with, CodecError};
+use crate::limits::{parse_with_limited, LimitedParseError, ParseLimits};
+use crate::rest::SelfTradePreventionMode;
+use crate::websocket::{de_option_string, de_option_string_to_f64, de_string_to_f64, ser_f64_as_string, ser_option_f64_as_string, EventTypeTag, ExecutionType, OrderStatus, Side};
+use super::{AccountBalance, ListenKeyExpired};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ExecutionReport {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "s", alias = "symbol")]
+    pub symbol: String,                  // Symbol
+    #[serde(rename = "c", alias = "client-order-id")]
+    pub client_order_id: String,         // Client order ID
+    #[serde(rename = "S", alias = "side")]
+    pub side: Side,                      // Side
+    #[serde(rename = "o", alias = "order-type")]
+    pub order_type: String,              // Order type
+    #[serde(rename = "f", alias = "time-in-force")]
+    pub time_in_force: String,           // Time in force
+    #[serde(rename = "q", alias = "order-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub order_quantity: f64,             // Order quantity
+    #[serde(rename = "p", alias = "order-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub order_price: f64,                // Order price
+    #[serde(rename = "P", alias = "stop-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub stop_price: f64,                 // Stop price
+    #[serde(rename = "F", alias = "iceberg-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub iceberg_quantity: f64,           // Iceberg quantity
+    #[serde(rename = "g", alias = "order-list-id")]
+    pub order_list_id: i64,              // OrderListId
+    #[serde(rename = "C", alias = "original-client-order-id")]
+    pub original_client_order_id: String, // Original client order ID; This is the ID of the order being canceled
+    #[serde(rename = "x", alias = "execution-type")]
+    pub execution_type: ExecutionType,   // Current execution type
+    #[serde(rename = "X", alias = "order-status")]
+    pub order_status: OrderStatus,       // Current order status
+    #[serde(rename = "r", alias = "reject-reason")]
+    pub reject_reason: String,           // Order reject reason; will be NONE if not rejected
+    #[serde(rename = "i", alias = "order-id")]
+    pub order_id: u64,                   // Order ID
+    #[serde(rename = "l", alias = "last-executed-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_executed_quantity: f64,     // Last executed quantity
+    #[serde(rename = "z", alias = "cumulative-filled-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cumulative_filled_quantity: f64, // Cumulative filled quantity
+    #[serde(rename = "L", alias = "last-executed-price", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_executed_price: f64,        // Last executed price
+    #[serde(rename = "n", alias = "commission-amount", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub commission_amount: f64,          // Commission amount
+    #[serde(rename = "N", alias = "commission-asset", default, deserialize_with = "de_option_string")]
+    pub commission_asset: Option<String>, // Commission asset, null or empty until filled
+    #[serde(rename = "T", alias = "transaction-time")]
+    pub transaction_time: u64,           // Transaction time
+    #[serde(rename = "t", alias = "trade-id")]
+    pub trade_id: i64,                   // Trade ID
+    #[serde(rename = "I", alias = "ignore-i")]
+    pub ignore_i: u64,                   // Ignore
+    #[serde(rename = "w", alias = "is-on-book")]
+    pub is_on_book: bool,                // Is the order on the book?
+    #[serde(rename = "m", alias = "is-maker")]
+    pub is_maker: bool,                  // Is this trade the maker side?
+    #[serde(rename = "M", alias = "ignore-m")]
+    pub ignore_m: bool,                  // Ignore
+    #[serde(rename = "O", alias = "order-creation-time")]
+    pub order_creation_time: u64,        // Order creation time
+    #[serde(rename = "Z", alias = "cumulative-quote-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub cumulative_quote_quantity: f64,  // Cumulative quote asset transacted quantity
+    #[serde(rename = "Y", alias = "last-quote-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub last_quote_quantity: f64,        // Last quote asset transacted quantity (i.e. lastPrice * lastQty)
+    #[serde(rename = "Q", alias = "quote-order-quantity", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub quote_order_quantity: f64,       // Quote Order Quantity
+    #[serde(rename = "W", alias = "working-time", default, skip_serializing_if = "Option::is_none")]
+    pub working_time: Option<u64>,       // Working Time; This is only visible if the order has been placed on the book
+    #[serde(rename = "V", alias = "self-trade-prevention-mode", default, skip_serializing_if = "Option::is_none")]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>, // STP mode
+    #[serde(rename = "u", alias = "trade-group-id", default, skip_serializing_if = "Option::is_none")]
+    pub trade_group_id: Option<i64>,     // Trade group ID, only on prevented matches
+    #[serde(rename = "v", alias = "prevented-match-id", default, skip_serializing_if = "Option::is_none")]
+    pub prevented_match_id: Option<u64>, // Prevented match ID, only on prevented matches
+    #[serde(rename = "U", alias = "counter-order-id", default, skip_serializing_if = "Option::is_none")]
+    pub counter_order_id: Option<u64>,   // Order ID of the counterparty, only on prevented matches
+    #[serde(rename = "A", alias = "prevented-quantity", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub prevented_quantity: Option<f64>, // Quantity prevented from matching so far
+    #[serde(rename = "B", alias = "last-prevented-quantity", default, skip_serializing_if = "Option::is_none", deserialize_with = "de_option_string_to_f64", serialize_with = "ser_option_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::OptionalNumericString"))]
+    pub last_prevented_quantity: Option<f64>, // Quantity of the last prevented match
+}
+
+impl ExecutionReport {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OutboundAccountPosition {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "u", alias = "last-update-time")]
+    pub last_update_time: u64,           // Time of last account update
+    #[serde(rename = "B", alias = "balances")]
+    pub balances: Vec<AccountBalance>,   // Balances array
+}
+
+impl OutboundAccountPosition {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BalanceUpdate {
+    #[serde(rename = "e", alias = "event-type")]
+    pub event_type: String,              // Event type
+    #[serde(rename = "E", alias = "event-time")]
+    pub event_time: u64,                 // Event time
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: String,                   // Asset
+    #[serde(rename = "d", alias = "balance-delta", deserialize_with = "de_string_to_f64", serialize_with = "ser_f64_as_string")]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::schema::NumericString"))]
+    pub balance_delta: f64,              // Balance delta
+    #[serde(rename = "T", alias = "clear-time")]
+    pub clear_time: u64,                 // Clear time
+}
+
+impl BalanceUpdate {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum UserDataEvent {
+    ExecutionReport(ExecutionReport),
+    OutboundAccountPosition(OutboundAccountPosition),
+    BalanceUpdate(BalanceUpdate),
+    ListenKeyExpired(ListenKeyExpired),
+    Unknown(String),                     // Raw payload of an event type not known to this crate
+}
+
+impl UserDataEvent {
+    pub fn parse(json: &str) -> Result<Self, CodecError> {
+        parse_with(json, UserDataEvent::from_json)
+    }
+
+    pub fn from_json_limited(json: &str, limits: &ParseLimits) -> Result<Self, LimitedParseError> {
+        parse_with_limited(json, limits, UserDataEvent::from_json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let tag = EventTypeTag::from_json(json)?;
+        match tag.event_type.as_deref() {
+            Some("executionReport") => ExecutionReport::from_json(json).map(UserDataEvent::ExecutionReport),
+            Some("outboundAccountPosition") => OutboundAccountPosition::from_json(json).map(UserDataEvent::OutboundAccountPosition),
+            Some("balanceUpdate") => BalanceUpdate::from_json(json).map(UserDataEvent::BalanceUpdate),
+            Some("listenKeyExpired") => ListenKeyExpired::from_json(json).map(UserDataEvent::ListenKeyExpired),
+            _ => Ok(UserDataEvent::Unknown(json.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::user_data::tests::EXECUTION_REPORT_JSON;
+
+    #[test]
+    fn test_execution_report_deserialization() {
+        let report = ExecutionReport::from_json(EXECUTION_REPORT_JSON).unwrap();
+
+        assert_eq!(report.symbol, "ETHBTC");
+        assert_eq!(report.side, Side::Buy);
+        assert_eq!(report.order_price, 0.1026441);
+        assert_eq!(report.order_list_id, -1);
+        assert_eq!(report.execution_type, ExecutionType::New);
+        assert_eq!(report.order_status, OrderStatus::New);
+        assert_eq!(report.commission_asset, None);
+        assert_eq!(report.trade_id, -1);
+        assert_eq!(report.working_time, Some(1499405658657));
+    }
+
+    #[test]
+    fn test_execution_report_self_trade_prevention() {
+        let report = ExecutionReport::from_json(crate::testutil::sample_execution_report_stp_json()).unwrap();
+        assert_eq!((report.execution_type, report.order_status), (ExecutionType::TradePrevention, OrderStatus::Expired));
+        assert_eq!(report.self_trade_prevention_mode, Some(SelfTradePreventionMode::ExpireTaker));
+        assert_eq!((report.trade_group_id, report.prevented_match_id, report.counter_order_id), (Some(1), Some(3), Some(4293152)));
+        assert_eq!((report.prevented_quantity, report.last_prevented_quantity), (Some(1.0), Some(1.0)));
+
+        let newer = crate::testutil::sample_execution_report_stp_json().replace(r#""V":"EXPIRE_TAKER""#, r#""V":"TRANSFER""#);
+        assert_eq!(ExecutionReport::from_json(&newer).unwrap().self_trade_prevention_mode, Some(SelfTradePreventionMode::Unknown));
+
+        let report = ExecutionReport::from_json(EXECUTION_REPORT_JSON).unwrap();
+        assert_eq!((report.self_trade_prevention_mode, report.prevented_match_id, report.prevented_quantity), (None, None, None));
+    }
+
+    #[test]
+    fn test_execution_report_empty_commission_asset() {
+        let empty = EXECUTION_REPORT_JSON.replace(r#""N":null"#, r#""N":"""#);
+        assert_eq!(ExecutionReport::from_json(&empty).unwrap().commission_asset, None);
+        let missing = EXECUTION_REPORT_JSON.replace(r#""N":null,"#, "");
+        assert_eq!(ExecutionReport::from_json(&missing).unwrap().commission_asset, None);
+        let filled = EXECUTION_REPORT_JSON.replace(r#""N":null"#, r#""N":"BNB""#);
+        assert_eq!(ExecutionReport::from_json(&filled).unwrap().commission_asset.as_deref(), Some("BNB"));
+    }
+
+    #[test]
+    fn test_spot_account_events_deserialization() {
+        let json = r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"ETH","f":"10000.000000","l":"0.000000"}]}"#;
+        let event = UserDataEvent::from_json(json).unwrap();
+        let UserDataEvent::OutboundAccountPosition(position) = event else { panic!("expected account position, got {event:?}") };
+        assert_eq!(position.balances, vec![AccountBalance { asset: "ETH".to_string(), free: 10000.0, locked: 0.0 }]);
+
+        let json = r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#;
+        let event = UserDataEvent::from_json(json).unwrap();
+        let UserDataEvent::BalanceUpdate(update) = event else { panic!("expected balance update, got {event:?}") };
+        assert_eq!(update.balance_delta, 100.0);
+        assert_eq!(update.clear_time, 1573200697068);
+    }
+}
@@ -0,0 +1,235 @@
+//! WebSocket API request and response envelopes. Params of the methods come from the REST request types,
+//! see their `to_params`, and results parse into the REST response models, so both APIs share one model.
+
+#[cfg(feature = "futures")]
+pub mod futures;
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::rest::{BinanceApiError, RateLimit, RateLimitTracker};
+
+// Order count rate limits of the account, the result is a `Vec<RateLimit>` with a count per window.
+// Signed, the params are only apiKey, timestamp and signature
+pub const ACCOUNT_RATE_LIMITS_ORDERS: &str = "account.rateLimits.orders";
+
+// Exchange trading rules and symbol information, params from `rest::ExchangeInfoRequest::to_params` and
+// the result a `rest::ExchangeInfo`
+pub const EXCHANGE_INFO: &str = "exchangeInfo";
+
+// Trading day ticker statistics, params from `rest::TradingDayTickerRequest::to_params` and the result
+// a `rest::OneOrMany<rest::TradingDayTicker>`
+pub const TICKER_TRADING_DAY: &str = "ticker.tradingDay";
+
+// Request id, echoed back in the response. Binance accepts strings, integers and null
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum WsApiId {
+    Number(u64),
+    String(String),
+}
+
+impl fmt::Display for WsApiId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WsApiId::Number(id) => write!(f, "{}", id),
+            WsApiId::String(id) => f.write_str(id),
+        }
+    }
+}
+
+impl From<u64> for WsApiId {
+    fn from(id: u64) -> Self {
+        WsApiId::Number(id)
+    }
+}
+
+impl From<&str> for WsApiId {
+    fn from(id: &str) -> Self {
+        WsApiId::String(id.to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WsApiRequest {
+    pub id: WsApiId,                     // Request id
+    pub method: String,                  // Method, e.g. "order.place"
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub params: serde_json::Map<String, serde_json::Value>, // Params, omitted when empty
+}
+
+impl WsApiRequest {
+    pub fn new(id: impl Into<WsApiId>, method: &str) -> Self {
+        WsApiRequest { id: id.into(), method: method.to_string(), params: serde_json::Map::new() }
+    }
+
+    // Params of a `to_params` object, other values are ignored
+    pub fn params(mut self, params: serde_json::Value) -> Self {
+        if let serde_json::Value::Object(params) = params {
+            self.params.extend(params);
+        }
+        self
+    }
+
+    // Adds the apiKey and timestamp params of a signed request, sign `signature_payload` next
+    pub fn authenticate(mut self, api_key: &str, timestamp: u64) -> Self {
+        self.params.insert("apiKey".to_string(), api_key.into());
+        self.params.insert("timestamp".to_string(), timestamp.into());
+        self
+    }
+
+    pub fn signature(mut self, signature: &str) -> Self {
+        self.params.insert("signature".to_string(), signature.into());
+        self
+    }
+
+    // Params but the signature as `name=value` sorted by name and joined by `&`, the string HMAC or
+    // Ed25519 signatures are computed over
+    pub fn signature_payload(&self) -> String {
+        let mut params: Vec<_> = self.params.iter().filter(|(name, _)| *name != "signature").collect();
+        params.sort_by_key(|(name, _)| *name);
+        let mut payload = String::with_capacity(128);
+        for (name, value) in params {
+            if !payload.is_empty() {
+                payload.push('&');
+            }
+            payload.push_str(name);
+            payload.push('=');
+            match value {
+                serde_json::Value::String(value) => payload.push_str(value),
+                value => payload.push_str(&value.to_string()),
+            }
+        }
+        payload
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+// Response to a request. Errors carry `error` in place of `result`; both carry the rate limits the
+// request counted against, with usage counts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WsApiResponse<T> {
+    pub id: Option<WsApiId>,             // Id of the request, None for errors on unparsable requests
+    pub status: u16,                     // HTTP-like status code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,               // Result of a successful request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BinanceApiError>,  // Error of a failed request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<RateLimit>,     // Rate limits with their current usage
+}
+
+impl<T: DeserializeOwned> WsApiResponse<T> {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl<T> WsApiResponse<T> {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status) && self.error.is_none()
+    }
+
+    // The result, or the error. A response with neither is reported as error code 0
+    pub fn into_result(self) -> Result<T, BinanceApiError> {
+        match (self.result, self.error) {
+            (_, Some(error)) => Err(error),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(BinanceApiError { code: 0, msg: format!("status {} without result", self.status) }),
+        }
+    }
+}
+
+impl RateLimitTracker {
+    // Usage counts of any response, whatever its result
+    pub fn update_from_ws_api<T>(&mut self, response: &WsApiResponse<T>) {
+        self.update_from_rate_limits(&response.rate_limits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest::{ExchangeInfo, ExchangeInfoRequest, OrderRequestError, RateLimitInterval, RateLimitType, RateLimitWindow};
+    use crate::testutil::ws_api;
+
+    #[test]
+    fn test_signed_request() {
+        let request = WsApiRequest::new("d3783d8d-f8d1-4d2c-b8a0-b7596af5a664", ACCOUNT_RATE_LIMITS_ORDERS)
+            .authenticate("vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A", 1660801839480);
+        assert_eq!(request.signature_payload(), "apiKey=vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A&timestamp=1660801839480");
+
+        let request = request.signature("76289424d6e288f4dc47d167ac824e859dabf78736f4348abbbac848d719eb94");
+        assert_eq!(request.signature_payload().matches('&').count(), 1);
+        assert_eq!(
+            request.to_json().unwrap(),
+            r#"{"id":"d3783d8d-f8d1-4d2c-b8a0-b7596af5a664","method":"account.rateLimits.orders","params":{"apiKey":"vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A","signature":"76289424d6e288f4dc47d167ac824e859dabf78736f4348abbbac848d719eb94","timestamp":1660801839480}}"#
+        );
+        assert_eq!(WsApiRequest::new(7, "ping").to_json().unwrap(), r#"{"id":7,"method":"ping"}"#);
+    }
+
+    #[test]
+    fn test_account_rate_limits_orders() {
+        let response = WsApiResponse::<Vec<RateLimit>>::from_json(ws_api::sample_account_rate_limits_orders_json()).unwrap();
+        assert!(response.is_success());
+        assert_eq!(response.id, Some(WsApiId::from("d3783d8d-f8d1-4d2c-b8a0-b7596af5a664")));
+
+        let mut tracker = RateLimitTracker::new();
+        tracker.update_from_ws_api(&response);
+        let order_limits = response.into_result().unwrap();
+        assert!(order_limits.iter().all(|limit| limit.rate_limit_type == RateLimitType::Orders));
+        tracker.update_from_rate_limits(&order_limits);
+
+        let ten_seconds = RateLimitWindow::new(RateLimitInterval::Second, 10);
+        let day = RateLimitWindow::new(RateLimitInterval::Day, 1);
+        let minute = RateLimitWindow::new(RateLimitInterval::Minute, 1);
+        assert_eq!(tracker.remaining(RateLimitType::Orders, ten_seconds), Some(48));
+        assert_eq!(tracker.remaining(RateLimitType::Orders, day), Some(159998));
+        assert_eq!(tracker.remaining(RateLimitType::RequestWeight, minute), Some(5960));
+
+        // After a burst of orders the next query reports the new counts
+        let burst = ws_api::sample_account_rate_limits_orders_json().replace(r#""limit":50,"count":2"#, r#""limit":50,"count":50"#);
+        tracker.update_from_rate_limits(&WsApiResponse::<Vec<RateLimit>>::from_json(&burst).unwrap().into_result().unwrap());
+        assert_eq!(tracker.remaining(RateLimitType::Orders, ten_seconds), Some(0));
+    }
+
+    #[test]
+    fn test_exchange_info() {
+        let params = ExchangeInfoRequest::new().permissions(&["SPOT", "MARGIN"]).to_params().unwrap();
+        let request = WsApiRequest::new("5494febb-d167-46a2-996d-70533eb4d976", EXCHANGE_INFO).params(params);
+        assert_eq!(
+            request.to_json().unwrap(),
+            r#"{"id":"5494febb-d167-46a2-996d-70533eb4d976","method":"exchangeInfo","params":{"permissions":["SPOT","MARGIN"]}}"#
+        );
+        assert_eq!(
+            ExchangeInfoRequest::new().symbol("BNBBTC").symbols(&["BTCUSDT"]).to_params(),
+            Err(OrderRequestError::MutuallyExclusive("symbol", "symbols"))
+        );
+
+        let response = WsApiResponse::<ExchangeInfo>::from_json(ws_api::sample_exchange_info_json()).unwrap();
+        let info = response.into_result().unwrap();
+        assert_eq!(info.rate_limits.len(), 4);
+        let symbol = info.symbol("BNBBTC").unwrap();
+        assert_eq!(symbol.permission_sets, vec![vec!["SPOT".to_string(), "MARGIN".to_string()]]);
+        assert_eq!((symbol.tick_size(), symbol.step_size()), (Some(0.000001), Some(0.001)));
+        assert_eq!(info.price_format("BNBBTC").unwrap().display(0.0123).to_string(), "0.012300");
+    }
+
+    #[test]
+    fn test_error_response() {
+        let json = r#"{"id":"5a1f9e2b","status":400,"error":{"code":-1102,"msg":"Mandatory parameter 'timestamp' was not sent."},"rateLimits":[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":6000,"count":41}]}"#;
+        let response = WsApiResponse::<Vec<RateLimit>>::from_json(json).unwrap();
+        assert!(!response.is_success());
+        assert_eq!(response.rate_limits.len(), 1);
+        assert_eq!(response.into_result().unwrap_err().code, -1102);
+    }
+}
@@ -0,0 +1,80 @@
+//! USD-M futures WebSocket API methods. Requests and responses use the envelopes of the parent module,
+//! params come from the futures REST request types and results parse into the futures REST models.
+
+// Endpoint of the USD-M futures WebSocket API, separate from the spot one
+pub const BASE_URL: &str = "wss://ws-fapi.binance.com/ws-fapi/v1";
+
+// Places an order, params from `rest::futures::FuturesNewOrderRequest::to_params` and the result a
+// `rest::futures::FuturesOrderResult`. Signed
+pub const ORDER_PLACE: &str = "order.place";
+
+// Modifies the price and quantity of a LIMIT order, params from
+// `rest::futures::FuturesModifyOrderRequest::to_params` and the result a `rest::futures::FuturesOrderResult`. Signed
+pub const ORDER_MODIFY: &str = "order.modify";
+
+// Cancels an order, params from `rest::futures::FuturesCancelOrderRequest::to_params` and the result a
+// `rest::futures::FuturesOrderResult`. Signed
+pub const ORDER_CANCEL: &str = "order.cancel";
+
+// Positions of the account, an optional `symbol` param and the result a `Vec<rest::futures::PositionRisk>`.
+// Signed
+pub const ACCOUNT_POSITION: &str = "account.position";
+
+// Best bid and ask, an optional `symbol` param and the result a `rest::OneOrMany<rest::futures::FuturesBookTicker>`
+pub const TICKER_BOOK: &str = "ticker.book";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::PriceFormat;
+    use crate::rest::futures::{FuturesBookTicker, FuturesNewOrderRequest, FuturesOrderResult, FuturesOrderType, FuturesTimeInForce, WorkingType};
+    use crate::rest::{OneOrMany, OrderRequestError};
+    use crate::testutil::ws_api;
+    use crate::websocket::{OrderStatus, PositionSide, Side};
+    use crate::ws_api::{WsApiRequest, WsApiResponse};
+
+    #[test]
+    fn test_order_place_short_limit() {
+        let order = FuturesNewOrderRequest::new("BTCUSDT", Side::Sell, FuturesOrderType::Limit)
+            .position_side(PositionSide::Short)
+            .time_in_force(FuturesTimeInForce::Gtc)
+            .quantity(0.1)
+            .price(43187.0);
+        let params = order.to_params_with(PriceFormat::Fixed(2), PriceFormat::Fixed(3)).unwrap();
+        let request = WsApiRequest::new("60fa9b6a-5e86-4f3e-9b1b-2d1c6c4a7f10", ORDER_PLACE)
+            .params(params)
+            .authenticate("HMOchcfii9ZRZnhjp2XjGXhsOBd6msAhKz9joQaWwZ7arcJTlD2hGPHQj1lGdTjR", 1702555533821);
+        assert_eq!(
+            request.signature_payload(),
+            "apiKey=HMOchcfii9ZRZnhjp2XjGXhsOBd6msAhKz9joQaWwZ7arcJTlD2hGPHQj1lGdTjR&positionSide=SHORT&price=43187.00&quantity=0.100&side=SELL&symbol=BTCUSDT&timeInForce=GTC&timestamp=1702555533821&type=LIMIT"
+        );
+        assert_eq!(
+            request.to_json().unwrap(),
+            r#"{"id":"60fa9b6a-5e86-4f3e-9b1b-2d1c6c4a7f10","method":"order.place","params":{"apiKey":"HMOchcfii9ZRZnhjp2XjGXhsOBd6msAhKz9joQaWwZ7arcJTlD2hGPHQj1lGdTjR","positionSide":"SHORT","price":"43187.00","quantity":"0.100","side":"SELL","symbol":"BTCUSDT","timeInForce":"GTC","timestamp":1702555533821,"type":"LIMIT"}}"#
+        );
+
+        // Hedge mode closes a short with a BUY on the SHORT side, reduceOnly is one-way mode only
+        assert_eq!(
+            order.reduce_only(true).to_params(),
+            Err(OrderRequestError::MutuallyExclusive("reduceOnly", "positionSide"))
+        );
+    }
+
+    #[test]
+    fn test_order_modify_response() {
+        let response = WsApiResponse::<FuturesOrderResult>::from_json(ws_api::sample_futures_order_modify_json()).unwrap();
+        assert!(response.is_success());
+        assert_eq!(response.rate_limits[0].limit, 2400);
+
+        let order = response.into_result().unwrap();
+        assert_eq!((order.order_id, order.status, order.side, order.position_side), (328971409, OrderStatus::New, Side::Buy, PositionSide::Long));
+        assert_eq!((order.price, order.original_quantity, order.executed_quantity), (43769.1, 0.002, 0.0));
+        assert_eq!((order.order_type, order.orig_type, order.time_in_force), (FuturesOrderType::Limit, FuturesOrderType::Limit, FuturesTimeInForce::Gtc));
+        assert_eq!((order.working_type, order.price_protect, order.reduce_only), (WorkingType::ContractPrice, false, false));
+        assert_eq!((order.activation_price, order.callback_rate), (None, None));
+        assert_eq!(order.update_time, 1703918436425);
+
+        let tickers = FuturesBookTicker::from_json(crate::testutil::rest::futures::sample_book_ticker_json()).unwrap();
+        assert!(matches!(tickers, OneOrMany::One(ref ticker) if ticker.ask_price == 4.000002));
+    }
+}
@@ -0,0 +1,70 @@
+//! Oversized messages are rejected by `parse_limited` before the parser allocates. Kept apart from the
+//! unit tests because the counting allocator replaces the global allocator of the whole test binary.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use s9_binance_codec::limits::{parse_limited, Limit, LimitedParseError, ParseLimits};
+
+// Counts live and peak heap bytes per thread, so parallel tests do not disturb each other
+struct CountingAllocator;
+
+thread_local! {
+    static LIVE: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = LIVE.try_with(|live| {
+            live.set(live.get() + layout.size());
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = LIVE.try_with(|live| live.set(live.get().saturating_sub(layout.size())));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Peak heap growth while running `f`
+fn peak_allocation<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let baseline = LIVE.with(Cell::get);
+    PEAK.with(|peak| peak.set(baseline));
+    let result = f();
+    (result, PEAK.with(Cell::get) - baseline)
+}
+
+fn depth_update(levels: usize) -> String {
+    let mut json = String::from(r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":["#);
+    for index in 0..levels {
+        json.push_str(if index == 0 { r#"["0.0024","10"]"# } else { r#",["0.0024","10"]"# });
+    }
+    json.push_str(r#"],"a":[]}"#);
+    json
+}
+
+#[test]
+fn test_oversized_depth_rejected_without_allocating() {
+    let json = depth_update(1_000_000);
+    let (result, peak) = peak_allocation(|| parse_limited::<serde_json::Value>(&json, &ParseLimits::default()));
+
+    match result {
+        Err(LimitedParseError::LimitExceeded(error)) => {
+            assert_eq!(error.limit, Limit::ArrayElements);
+            assert_eq!(error.max, 100_000);
+            assert_eq!(&json[error.offset..error.offset + 2], "[\"");
+        }
+        other => panic!("expected the array limit to trip, got {:?}", other),
+    }
+    assert!(peak < 1024, "rejection allocated {} bytes", peak);
+
+    let (result, peak) = peak_allocation(|| parse_limited::<serde_json::Value>(&depth_update(1_000), &ParseLimits::default()));
+    assert!(result.is_ok());
+    assert!(peak > 1024);
+}